@@ -389,7 +389,7 @@ fn route(request_line: &str, scenario: &str) -> String {
         ("GET", "/settlements") => json_response(
             200,
             r#"{
-  "settlements": [
+  "items": [
     {
       "id": "a1b2c3d4-e5f6-7890-abcd-ef1234567890",
       "asset_code": "USD",
@@ -413,8 +413,11 @@ fn route(request_line: &str, scenario: &str) -> String {
       "updated_at": "2026-06-30T05:30:00Z"
     }
   ],
-  "next_cursor": null,
-  "has_more": false
+  "total": 2,
+  "limit": 10,
+  "offset": 0,
+  "has_more": false,
+  "next_offset": null
 }"#,
         ),
 
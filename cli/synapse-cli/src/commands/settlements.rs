@@ -200,8 +200,10 @@ mod tests {
             .with_header("content-type", "application/json")
             .with_body(
                 serde_json::json!({
-                    "settlements": [sample_settlement(id)],
-                    "next_cursor": null,
+                    "items": [sample_settlement(id)],
+                    "total": 1,
+                    "limit": 10,
+                    "offset": 0,
                     "has_more": false
                 })
                 .to_string(),
@@ -225,7 +227,7 @@ mod tests {
             .mock("GET", "/settlements")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"settlements":[],"next_cursor":null,"has_more":false}"#)
+            .with_body(r#"{"items":[],"total":0,"limit":10,"offset":0,"has_more":false}"#)
             .create_async()
             .await;
 
@@ -245,8 +247,10 @@ mod tests {
             .with_header("content-type", "application/json")
             .with_body(
                 serde_json::json!({
-                    "settlements": [sample_settlement(id)],
-                    "next_cursor": null,
+                    "items": [sample_settlement(id)],
+                    "total": 1,
+                    "limit": 10,
+                    "offset": 0,
                     "has_more": false
                 })
                 .to_string(),
@@ -257,7 +261,7 @@ mod tests {
         let client = ApiClient::new(&server.url(), "test-key");
         let resp: SettlementList = client.get("/settlements").await.unwrap();
         let json = serde_json::to_string_pretty(&resp).unwrap();
-        assert!(json.contains("\"settlements\""));
+        assert!(json.contains("\"items\""));
         assert!(json.contains(id));
         assert!(json.contains("USD"));
     }
@@ -276,9 +280,12 @@ mod tests {
             .with_header("content-type", "application/json")
             .with_body(
                 serde_json::json!({
-                    "settlements": [sample_settlement(id)],
-                    "next_cursor": "next-page-token",
-                    "has_more": true
+                    "items": [sample_settlement(id)],
+                    "total": 6,
+                    "limit": 5,
+                    "offset": 0,
+                    "has_more": true,
+                    "next_cursor": "next-page-token"
                 })
                 .to_string(),
             )
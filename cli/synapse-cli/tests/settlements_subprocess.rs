@@ -105,15 +105,15 @@ fn settlements_list_json_output_happy_path() {
         serde_json::from_str(&stdout).expect("stdout should be valid JSON");
 
     assert!(
-        parsed["settlements"].is_array(),
-        "JSON should have 'settlements' array\nparsed: {parsed}"
+        parsed["items"].is_array(),
+        "JSON should have 'items' array\nparsed: {parsed}"
     );
     assert!(
         parsed["has_more"].is_boolean(),
         "JSON should have 'has_more' boolean"
     );
 
-    let settlements = parsed["settlements"].as_array().unwrap();
+    let settlements = parsed["items"].as_array().unwrap();
     assert!(
         !settlements.is_empty(),
         "settlements array should be non-empty"
@@ -132,7 +132,7 @@ fn settlements_list_json_output_happy_path() {
     assert!(first["status"].is_string(), "settlement should have status");
 }
 
-/// JSON output: `settlements` array contains required top-level keys.
+/// JSON output: the page envelope contains required top-level keys.
 #[test]
 fn settlements_list_json_contains_required_keys() {
     let server = MockServer::spawn("happy");
@@ -146,7 +146,7 @@ fn settlements_list_json_contains_required_keys() {
     assert!(output.status.success());
     let stdout = String::from_utf8(output.stdout).expect("valid utf-8");
 
-    for key in &["\"settlements\"", "\"has_more\"", "\"next_cursor\""] {
+    for key in &["\"items\"", "\"total\"", "\"has_more\"", "\"next_cursor\""] {
         assert!(
             stdout.contains(key),
             "JSON output should contain key {key}\nstdout: {stdout}"
@@ -0,0 +1,21 @@
+-- Tracks asynchronous transaction exports. Large exports time out when served
+-- inline by GET /export, so POST /export/jobs records a job here, a background
+-- worker writes the artifact to disk, and clients poll for a download link.
+CREATE TABLE IF NOT EXISTS export_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    format VARCHAR(10) NOT NULL,
+    filters JSONB NOT NULL DEFAULT '{}'::jsonb,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    artifact_path TEXT,
+    row_count BIGINT,
+    error_message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    CONSTRAINT chk_export_jobs_status
+        CHECK (status IN ('pending', 'running', 'completed', 'failed')),
+    CONSTRAINT chk_export_jobs_format
+        CHECK (format IN ('csv', 'json'))
+);
+
+CREATE INDEX idx_export_jobs_status_created_at ON export_jobs(status, created_at);
@@ -0,0 +1,4 @@
+-- Rollback: Remove processing retry tracking from transactions table
+ALTER TABLE transactions DROP COLUMN IF EXISTS next_attempt_at;
+ALTER TABLE transactions DROP COLUMN IF EXISTS last_processing_error;
+ALTER TABLE transactions DROP COLUMN IF EXISTS processing_attempts;
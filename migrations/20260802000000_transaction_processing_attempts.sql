@@ -0,0 +1,5 @@
+-- Track processing retries per transaction so permanent failures escalate to
+-- the DLQ instead of being retried forever.
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS processing_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS last_processing_error TEXT DEFAULT NULL;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS next_attempt_at TIMESTAMPTZ DEFAULT NULL;
@@ -0,0 +1,2 @@
+-- Rollback: Remove processor claim lease from transactions table
+ALTER TABLE transactions DROP COLUMN IF EXISTS claimed_until;
@@ -0,0 +1,3 @@
+-- Lease taken by a processor worker when it claims a batch of pending
+-- transactions; other workers skip rows whose lease has not yet expired.
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS claimed_until TIMESTAMPTZ DEFAULT NULL;
@@ -0,0 +1,2 @@
+-- Rollback: Remove stored raw webhook payloads
+DROP TABLE IF EXISTS webhook_payloads;
@@ -0,0 +1,11 @@
+-- Raw inbound webhook bodies and headers, captured at ingest so admin replays
+-- re-deliver the original bytes instead of rebuilding them from `transactions`.
+CREATE TABLE IF NOT EXISTS webhook_payloads (
+    transaction_id UUID PRIMARY KEY,
+    source VARCHAR(64) NOT NULL,
+    headers JSONB NOT NULL DEFAULT '{}'::jsonb,
+    body BYTEA NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_payloads_received_at ON webhook_payloads(received_at);
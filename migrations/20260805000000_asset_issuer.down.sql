@@ -0,0 +1,4 @@
+-- Rollback: Remove asset issuers from transactions and settlements
+DROP INDEX IF EXISTS idx_transactions_asset_code_issuer;
+ALTER TABLE settlements DROP COLUMN IF EXISTS asset_issuer;
+ALTER TABLE transactions DROP COLUMN IF EXISTS asset_issuer;
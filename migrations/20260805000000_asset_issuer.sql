@@ -0,0 +1,9 @@
+-- Identify assets by (asset_code, asset_issuer) so two assets sharing a code
+-- settle and reconcile separately. Native XLM is stored as ('XLM', NULL).
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS asset_issuer VARCHAR(56) DEFAULT NULL;
+ALTER TABLE settlements ADD COLUMN IF NOT EXISTS asset_issuer VARCHAR(56) DEFAULT NULL;
+
+UPDATE transactions SET asset_code = 'XLM', asset_issuer = NULL WHERE asset_code = 'native';
+
+CREATE INDEX IF NOT EXISTS idx_transactions_asset_code_issuer
+    ON transactions (asset_code, asset_issuer);
@@ -0,0 +1,2 @@
+-- Rollback: Remove optimistic locking version from settlements
+ALTER TABLE settlements DROP COLUMN IF EXISTS version;
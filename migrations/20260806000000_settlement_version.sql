@@ -0,0 +1,3 @@
+-- Optimistic locking for settlements: every update must name the version it
+-- read and bumps it, so concurrent writers cannot silently overwrite each other.
+ALTER TABLE settlements ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
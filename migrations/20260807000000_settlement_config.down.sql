@@ -0,0 +1,3 @@
+-- Rollback: Remove per-asset settlement configuration
+ALTER TABLE settlements DROP COLUMN IF EXISTS target_account;
+DROP TABLE IF EXISTS settlement_config;
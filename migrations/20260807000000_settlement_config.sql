@@ -0,0 +1,16 @@
+-- Per-asset settlement settings. Assets without a row, and NULL columns,
+-- fall back to the service-wide defaults.
+CREATE TABLE IF NOT EXISTS settlement_config (
+    asset_code TEXT PRIMARY KEY,
+    -- Smallest unsettled total (per issuer) worth settling.
+    min_amount NUMERIC CHECK (min_amount >= 0),
+    min_tx_count INTEGER CHECK (min_tx_count >= 1),
+    schedule VARCHAR(20) NOT NULL DEFAULT 'hourly'
+        CHECK (schedule IN ('hourly', 'daily', 'weekly')),
+    -- Stellar account settlements of this asset pay out to.
+    target_account TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE settlements ADD COLUMN IF NOT EXISTS target_account TEXT;
@@ -0,0 +1,2 @@
+-- Rollback: Remove the callback payment matching feature flag
+DELETE FROM feature_flags WHERE name = 'callback_payment_matching';
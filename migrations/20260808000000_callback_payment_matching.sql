@@ -0,0 +1,4 @@
+-- Feature flag for matching callbacks to on-chain payments by memo at ingest
+INSERT INTO feature_flags (name, enabled, description) VALUES
+    ('callback_payment_matching', false, 'Match callbacks to Horizon payments by account and memo on ingest')
+ON CONFLICT (name) DO NOTHING;
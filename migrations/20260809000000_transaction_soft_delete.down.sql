@@ -0,0 +1,3 @@
+-- Rollback: Remove transaction soft-delete
+DROP INDEX IF EXISTS idx_transactions_deleted_at;
+ALTER TABLE transactions DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,6 @@
+-- Retire transactions without deleting them, so the audit trail stays intact.
+-- Rows with deleted_at set are excluded from default reads.
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ DEFAULT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_deleted_at
+    ON transactions (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,2 @@
+-- Rollback: Remove per-tenant CORS origins
+ALTER TABLE tenants DROP COLUMN IF EXISTS cors_allowed_origins;
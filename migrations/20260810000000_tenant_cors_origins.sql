@@ -0,0 +1,4 @@
+-- Browser origins allowed for requests that resolve to the tenant. When set,
+-- they replace CORS_ALLOWED_ORIGINS for that tenant's requests; an empty list
+-- keeps the global allowlist.
+ALTER TABLE tenants ADD COLUMN IF NOT EXISTS cors_allowed_origins TEXT[] NOT NULL DEFAULT '{}';
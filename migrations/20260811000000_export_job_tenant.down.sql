@@ -0,0 +1,2 @@
+-- Rollback: Remove export job tenant scoping
+ALTER TABLE export_jobs DROP COLUMN IF EXISTS tenant_id;
@@ -0,0 +1,3 @@
+-- Tenant that requested an export job. The worker only exports that tenant's
+-- transactions; NULL keeps the unscoped (admin) export.
+ALTER TABLE export_jobs ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES tenants(tenant_id);
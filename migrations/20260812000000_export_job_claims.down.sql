@@ -0,0 +1,2 @@
+-- Rollback: Remove export job leases
+ALTER TABLE export_jobs DROP COLUMN IF EXISTS claimed_until;
@@ -0,0 +1,4 @@
+-- Lease taken by the export worker when it claims a job. A job still
+-- 'running' after its lease expired belongs to a worker that died and is
+-- handed out again.
+ALTER TABLE export_jobs ADD COLUMN IF NOT EXISTS claimed_until TIMESTAMPTZ DEFAULT NULL;
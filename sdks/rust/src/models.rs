@@ -79,12 +79,23 @@ pub struct Settlement {
     pub reviewed_at: Option<DateTime<Utc>>,
 }
 
-/// Paginated list of settlements.
+/// Paginated list of settlements, from the server's shared page envelope.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SettlementList {
+    #[serde(rename = "items")]
     pub settlements: Vec<Settlement>,
+    /// Settlements matching the filters across all pages.
+    #[serde(default)]
+    pub total: i64,
+    #[serde(default)]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
     pub next_cursor: Option<String>,
     pub has_more: bool,
+    /// `offset` of the next page; `None` on the last page.
+    #[serde(default)]
+    pub next_offset: Option<i64>,
 }
 
 /// Query parameters for [`Settlements::list`].
@@ -443,11 +454,23 @@ pub struct FailedWebhookInfo {
     pub retry_count: i32,
 }
 
-/// Response from `GET /admin/webhooks/failed`.
+/// Response from `GET /admin/webhooks/failed`, in the server's shared page
+/// envelope.
 #[derive(Debug, Clone, Deserialize)]
 pub struct FailedWebhooksResponse {
-    pub total: i64,
+    #[serde(rename = "items")]
     pub webhooks: Vec<FailedWebhookInfo>,
+    pub total: i64,
+    #[serde(default)]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    /// Whether more failed webhooks follow this page.
+    #[serde(default)]
+    pub has_more: bool,
+    /// `offset` of the next page; `None` on the last page.
+    #[serde(default)]
+    pub next_offset: Option<i64>,
 }
 
 /// Result of a single replay attempt (individual or within a batch).
@@ -253,8 +253,11 @@ mod tests {
             .and(path("/admin/webhooks/failed"))
             .and(header("X-Admin-Key", "admin-test-key"))
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [failed_webhook_json(txid)],
                 "total": 1,
-                "webhooks": [failed_webhook_json(txid)],
+                "limit": 50,
+                "offset": 0,
+                "has_more": false,
             })))
             .mount(&server)
             .await;
@@ -281,8 +284,11 @@ mod tests {
             .and(path("/admin/webhooks/failed"))
             .and(header("X-Admin-Key", "admin-test-key"))
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [],
                 "total": 0,
-                "webhooks": [],
+                "limit": 50,
+                "offset": 0,
+                "has_more": false,
             })))
             .mount(&server)
             .await;
@@ -306,8 +312,11 @@ mod tests {
             .and(path("/admin/webhooks/failed"))
             .and(header("X-Admin-Key", "admin-test-key"))
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [],
                 "total": 0,
-                "webhooks": [],
+                "limit": 50,
+                "offset": 0,
+                "has_more": false,
             })))
             .mount(&server)
             .await;
@@ -333,8 +342,11 @@ mod tests {
             .and(path("/admin/webhooks/failed"))
             .and(header("X-Admin-Key", "admin-test-key"))
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [],
                 "total": 0,
-                "webhooks": [],
+                "limit": 50,
+                "offset": 0,
+                "has_more": false,
             })))
             .mount(&server)
             .await;
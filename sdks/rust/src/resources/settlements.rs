@@ -211,9 +211,12 @@ mod tests {
             .and(header("X-API-Key", "test-key"))
             .and(query_param("limit", "10"))
             .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                "settlements": [settlement_body(id)],
-                "next_cursor": "next-page-token",
-                "has_more": true
+                "items": [settlement_body(id)],
+                "total": 12,
+                "limit": 10,
+                "offset": 0,
+                "has_more": true,
+                "next_cursor": "next-page-token"
             })))
             .mount(&server)
             .await;
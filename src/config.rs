@@ -5,8 +5,9 @@ use ipnet::IpNet;
 use std::env;
 
 /// Active environment profile
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum AppEnv {
+    #[default]
     Development,
     Staging,
     Production,
@@ -43,41 +44,58 @@ fn load_env_profile(app_env: &AppEnv) {
     dotenvy::from_filename(&profile_file).ok();
 }
 
+/// Env vars each profile fills in when they are not already set.
+fn profile_defaults(app_env: &AppEnv) -> &'static [(&'static str, &'static str)] {
+    match app_env {
+        // Verbose logging, relaxed limits, longer timeouts
+        AppEnv::Development => &[
+            ("LOG_FORMAT", "text"),
+            ("RUST_LOG", "debug"),
+            ("DEFAULT_RATE_LIMIT", "10000"),
+            ("WHITELIST_RATE_LIMIT", "100000"),
+            ("DB_TIMEOUT_READ_SECS", "30"),
+            ("DB_TIMEOUT_WRITE_SECS", "60"),
+            ("DB_STATEMENT_TIMEOUT_MS", "60000"),
+        ],
+        AppEnv::Staging => &[
+            ("LOG_FORMAT", "json"),
+            ("RUST_LOG", "info"),
+            ("DEFAULT_RATE_LIMIT", "500"),
+            ("WHITELIST_RATE_LIMIT", "5000"),
+            ("DB_TIMEOUT_READ_SECS", "10"),
+            ("DB_TIMEOUT_WRITE_SECS", "20"),
+            ("DB_STATEMENT_TIMEOUT_MS", "30000"),
+        ],
+        // JSON logging, strict rate limits, short timeouts
+        AppEnv::Production => &[
+            ("LOG_FORMAT", "json"),
+            ("RUST_LOG", "warn"),
+            ("DEFAULT_RATE_LIMIT", "100"),
+            ("WHITELIST_RATE_LIMIT", "1000"),
+            ("DB_TIMEOUT_READ_SECS", "5"),
+            ("DB_TIMEOUT_WRITE_SECS", "10"),
+            ("DB_STATEMENT_TIMEOUT_MS", "30000"),
+        ],
+    }
+}
+
 /// Apply profile defaults for any env vars not already set
 fn apply_profile_defaults(app_env: &AppEnv) {
-    match app_env {
-        AppEnv::Development => {
-            // Verbose logging, relaxed limits, longer timeouts
-            set_default("LOG_FORMAT", "text");
-            set_default("RUST_LOG", "debug");
-            set_default("DEFAULT_RATE_LIMIT", "10000");
-            set_default("WHITELIST_RATE_LIMIT", "100000");
-            set_default("DB_TIMEOUT_READ_SECS", "30");
-            set_default("DB_TIMEOUT_WRITE_SECS", "60");
-            set_default("DB_STATEMENT_TIMEOUT_MS", "60000");
-        }
-        AppEnv::Staging => {
-            set_default("LOG_FORMAT", "json");
-            set_default("RUST_LOG", "info");
-            set_default("DEFAULT_RATE_LIMIT", "500");
-            set_default("WHITELIST_RATE_LIMIT", "5000");
-            set_default("DB_TIMEOUT_READ_SECS", "10");
-            set_default("DB_TIMEOUT_WRITE_SECS", "20");
-            set_default("DB_STATEMENT_TIMEOUT_MS", "30000");
-        }
-        AppEnv::Production => {
-            // JSON logging, strict rate limits, short timeouts
-            set_default("LOG_FORMAT", "json");
-            set_default("RUST_LOG", "warn");
-            set_default("DEFAULT_RATE_LIMIT", "100");
-            set_default("WHITELIST_RATE_LIMIT", "1000");
-            set_default("DB_TIMEOUT_READ_SECS", "5");
-            set_default("DB_TIMEOUT_WRITE_SECS", "10");
-            set_default("DB_STATEMENT_TIMEOUT_MS", "30000");
-        }
+    for (key, value) in profile_defaults(app_env) {
+        set_default(key, value);
     }
 }
 
+/// Profile-defaulted env vars whose effective value differs from the
+/// profile's default, i.e. were overridden by the environment or a `.env` file.
+fn profile_overrides(app_env: &AppEnv) -> Vec<String> {
+    profile_defaults(app_env)
+        .iter()
+        .filter(|(key, default)| env::var(key).is_ok_and(|value| value != *default))
+        .map(|(key, _)| key.to_string())
+        .collect()
+}
+
 /// Set an env var only if it is not already set
 fn set_default(key: &str, value: &str) {
     if env::var(key).is_err() {
@@ -121,11 +139,40 @@ impl Default for DbTimeoutConfig {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub app_env: AppEnv,
+    /// Profile-defaulted env vars set to something other than the profile's
+    /// default, reported by `GET /admin/config`.
+    pub profile_overrides: Vec<String>,
     pub server_port: u16,
     pub database_url: String,
     pub database_replica_url: Option<String>,
     pub stellar_horizon_url: String,
+    /// Horizon URLs tried in order when `stellar_horizon_url` is unreachable
+    /// (`STELLAR_HORIZON_FALLBACK_URLS`, comma-separated).
+    pub stellar_horizon_fallback_urls: Vec<String>,
+    /// Network `STELLAR_HORIZON_URL` must serve (`STELLAR_NETWORK`, default
+    /// `testnet`); checked against Horizon's passphrase at startup.
+    pub stellar_network: crate::stellar::StellarNetwork,
+    /// Records requested per Horizon payments page when reconciling
+    /// (`HORIZON_PAGE_LIMIT`, 1–200).
+    pub horizon_page_limit: u32,
+    /// Latency above which a reachable dependency is reported as `degraded`
+    /// by `/health` (`HEALTH_LATENCY_THRESHOLD_MS`).
+    pub health_latency_threshold_ms: u64,
+    /// Whether keyed callbacks proceed or are rejected when the idempotency
+    /// store is unavailable (`IDEMPOTENCY_FAILURE_MODE`, default `fail_closed`).
+    pub idempotency_failure_mode: crate::middleware::idempotency::IdempotencyFailureMode,
+    /// Optional dependencies (`redis`, `horizon`) that must be reachable for
+    /// `/ready` to report ready (`READINESS_REQUIRED_DEPENDENCIES`, comma
+    /// separated). The database always is.
+    pub readiness_required_dependencies: Vec<&'static str>,
+    /// Flag values served while the feature flag table can't be read
+    /// (`FEATURE_FLAG_DEFAULTS`, e.g. `new_asset_support=true,beta=false`).
+    pub feature_flag_defaults: std::collections::HashMap<String, bool>,
     pub anchor_webhook_secret: String,
+    /// HS256 key for the WebSocket, profiling and config bearer tokens
+    /// (`JWT_SECRET`). Unset rejects every token; a set value shorter than
+    /// `MIN_JWT_SECRET_LEN` bytes fails startup.
+    pub jwt_secret: Option<String>,
     pub redis_url: String,
     pub default_rate_limit: u32,
     pub whitelist_rate_limit: u32,
@@ -134,12 +181,51 @@ pub struct Config {
     pub allowed_ips: AllowedIps,
     pub backup_dir: String,
     pub backup_encryption_key: Option<String>,
+    /// `/health` reports backups as degraded once the newest is older than
+    /// this (`BACKUP_MAX_AGE_HOURS`).
+    pub backup_max_age_hours: u64,
+    /// Directory the SQL migrations are read from (`MIGRATIONS_DIR`).
+    pub migrations_dir: String,
     pub db_timeouts: DbTimeoutConfig,
     pub otlp_endpoint: Option<String>,
     // CORS
     pub cors_allowed_origins: Vec<String>,
+    /// Extra key patterns redacted from logged request bodies, on top of the
+    /// built-in sensitive fields. See `utils::sanitize::SensitiveKeys`.
+    pub log_sensitive_keys: Vec<String>,
     // Back-pressure
     pub max_pending_queue: u64,
+    /// Webhook and callback requests processed at once; further requests get
+    /// `503` with `Retry-After` until a slot frees up.
+    pub webhook_max_concurrency: usize,
+    /// Hours stored flamegraphs are kept (`PROFILING_RETENTION_HOURS`).
+    pub profiling_retention_hours: u64,
+    /// Minimum seconds between profiling session starts
+    /// (`PROFILING_MIN_START_INTERVAL_SECS`); `0` disables the limit.
+    pub profiling_min_start_interval_secs: u64,
+    /// Highest CPU sample rate a profiling session may request
+    /// (`PROFILING_MAX_SAMPLE_RATE_HZ`).
+    pub profiling_max_sample_rate_hz: u32,
+    /// Largest `transaction_ids` list a batch webhook replay accepts
+    /// (`WEBHOOK_REPLAY_MAX_BATCH`).
+    pub webhook_replay_max_batch: usize,
+    /// Replays run at once within one batch (`WEBHOOK_REPLAY_CONCURRENCY`).
+    pub webhook_replay_concurrency: usize,
+    /// Largest POST body accepted before responding 413.
+    pub max_body_bytes: usize,
+    /// Seconds a request may take before it is answered with `504`
+    /// (`REQUEST_TIMEOUT_SECS`); `0` disables the limit. Exports are exempt.
+    pub request_timeout_secs: u64,
+    /// Days a search covers when it names no date range
+    /// (`SEARCH_DEFAULT_WINDOW_DAYS`); `0` searches everything by default.
+    pub search_default_window_days: u32,
+    /// Directory the export job worker writes artifacts to (`EXPORT_JOBS_DIR`).
+    pub export_jobs_dir: String,
+    /// Status updates buffered for WebSocket and GraphQL subscribers. A
+    /// subscriber more than this many updates behind skips ahead and the
+    /// skipped updates are counted in `broadcast_messages_lagged_total`;
+    /// larger values tolerate slower clients at the cost of memory.
+    pub broadcast_capacity: usize,
     // DB pool sizing
     pub db_min_connections: u32,
     pub db_max_connections: u32,
@@ -151,15 +237,33 @@ pub struct Config {
     pub processor_workers: usize,
     pub processor_batch_size: u32,
     pub processor_poll_interval_ms: u64,
+    /// Transactions processed concurrently within one claimed batch.
+    pub processor_concurrency: usize,
     // Adaptive batch sizing
     pub processor_min_batch: u32,
     pub processor_max_batch: u32,
     pub processor_scaling_factor: f64,
+    /// Failed attempts before a transaction is moved to the DLQ
+    /// (`PROCESSOR_MAX_ATTEMPTS`).
+    pub processor_max_attempts: u32,
+    /// Delay before the first retry of a failed transaction, doubled after
+    /// every further failure (`PROCESSOR_RETRY_BACKOFF_MS`).
+    pub processor_retry_backoff_ms: u64,
+    /// Upper bound on the retry delay (`PROCESSOR_RETRY_MAX_BACKOFF_MS`).
+    pub processor_retry_max_backoff_ms: u64,
     // Slow query logging
     pub slow_query_threshold_ms: u64,
     // Settlement batch limits
     pub settlement_max_batch_size: usize,
     pub settlement_min_tx_count: usize,
+    /// Cron expression (with seconds) for the scheduled settlement run.
+    pub settlement_schedule: String,
+    // Partition maintenance
+    pub partition_strategy: crate::db::partition::PartitionStrategy,
+    pub partition_months_ahead: u32,
+    pub partition_days_ahead: u32,
+    pub partition_retention_months: i64,
+    pub partition_drop_after_archive: bool,
 }
 
 pub mod assets;
@@ -175,6 +279,7 @@ impl Config {
 
         // Apply profile defaults for any unset vars
         apply_profile_defaults(&app_env);
+        let profile_overrides = profile_overrides(&app_env);
 
         tracing::info!("Active environment profile: {}", app_env.as_str());
 
@@ -204,15 +309,136 @@ impl Config {
             )
         };
 
+        let partition_months_ahead: u32 = env::var("PARTITION_MONTHS_AHEAD")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()?;
+        let partition_days_ahead: u32 = env::var("PARTITION_DAYS_AHEAD")
+            .unwrap_or_else(|_| "7".to_string())
+            .parse()?;
+        let partition_retention_months: i64 = env::var("PARTITION_RETENTION_MONTHS")
+            .unwrap_or_else(|_| "12".to_string())
+            .parse()?;
+        validate_partition_settings(
+            partition_months_ahead,
+            partition_days_ahead,
+            partition_retention_months,
+        )?;
+        let broadcast_capacity: usize = env::var("BROADCAST_CAPACITY")
+            .unwrap_or_else(|_| "100".to_string())
+            .parse()?;
+        if broadcast_capacity == 0 {
+            anyhow::bail!("BROADCAST_CAPACITY must be greater than 0");
+        }
+        let webhook_max_concurrency: usize = env::var("WEBHOOK_MAX_CONCURRENCY")
+            .map(|v| v.parse())
+            .unwrap_or(Ok(
+                crate::middleware::concurrency::DEFAULT_WEBHOOK_MAX_CONCURRENCY,
+            ))?;
+        if webhook_max_concurrency == 0 {
+            anyhow::bail!("WEBHOOK_MAX_CONCURRENCY must be greater than 0");
+        }
+        let profiling_retention_hours: u64 = env::var("PROFILING_RETENTION_HOURS")
+            .map(|v| v.parse())
+            .unwrap_or(Ok(
+                crate::handlers::profiling::DEFAULT_RETENTION.as_secs() / 3600
+            ))?;
+        if profiling_retention_hours == 0 {
+            anyhow::bail!("PROFILING_RETENTION_HOURS must be greater than 0");
+        }
+        let profiling_min_start_interval_secs: u64 = env::var("PROFILING_MIN_START_INTERVAL_SECS")
+            .map(|v| v.parse())
+            .unwrap_or(Ok(
+                crate::handlers::profiling::DEFAULT_MIN_START_INTERVAL.as_secs()
+            ))?;
+        let profiling_max_sample_rate_hz: u32 = env::var("PROFILING_MAX_SAMPLE_RATE_HZ")
+            .map(|v| v.parse())
+            .unwrap_or(Ok(crate::handlers::profiling::DEFAULT_MAX_SAMPLE_RATE_HZ))?;
+        if profiling_max_sample_rate_hz == 0 {
+            anyhow::bail!("PROFILING_MAX_SAMPLE_RATE_HZ must be greater than 0");
+        }
+        let webhook_replay_max_batch: usize = env::var("WEBHOOK_REPLAY_MAX_BATCH")
+            .unwrap_or_else(|_| "500".to_string())
+            .parse()?;
+        if webhook_replay_max_batch == 0 {
+            anyhow::bail!("WEBHOOK_REPLAY_MAX_BATCH must be greater than 0");
+        }
+        let webhook_replay_concurrency: usize = env::var("WEBHOOK_REPLAY_CONCURRENCY")
+            .unwrap_or_else(|_| "8".to_string())
+            .parse()?;
+        if webhook_replay_concurrency == 0 {
+            anyhow::bail!("WEBHOOK_REPLAY_CONCURRENCY must be greater than 0");
+        }
+        let horizon_page_limit: u32 = env::var("HORIZON_PAGE_LIMIT")
+            .unwrap_or_else(|_| crate::stellar::client::MAX_PAGE_LIMIT.to_string())
+            .parse()?;
+        if !(1..=crate::stellar::client::MAX_PAGE_LIMIT).contains(&horizon_page_limit) {
+            anyhow::bail!(
+                "HORIZON_PAGE_LIMIT must be between 1 and {}",
+                crate::stellar::client::MAX_PAGE_LIMIT
+            );
+        }
+        let health_latency_threshold_ms: u64 = env::var("HEALTH_LATENCY_THRESHOLD_MS")
+            .unwrap_or_else(|_| crate::health::DEFAULT_LATENCY_THRESHOLD_MS.to_string())
+            .parse()?;
+        if health_latency_threshold_ms == 0 {
+            anyhow::bail!("HEALTH_LATENCY_THRESHOLD_MS must be greater than 0");
+        }
+
+        let backup_max_age_hours: u64 = env::var("BACKUP_MAX_AGE_HOURS")
+            .unwrap_or_else(|_| crate::health::DEFAULT_BACKUP_MAX_AGE_HOURS.to_string())
+            .parse()?;
+        if backup_max_age_hours == 0 {
+            anyhow::bail!("BACKUP_MAX_AGE_HOURS must be greater than 0");
+        }
+
+        let processor_max_attempts: u32 = env::var("PROCESSOR_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()?;
+        let processor_retry_backoff_ms: u64 = env::var("PROCESSOR_RETRY_BACKOFF_MS")
+            .unwrap_or_else(|_| "1000".to_string())
+            .parse()?;
+        let processor_retry_max_backoff_ms: u64 = env::var("PROCESSOR_RETRY_MAX_BACKOFF_MS")
+            .unwrap_or_else(|_| "300000".to_string())
+            .parse()?;
+        validate_retry_settings(
+            processor_max_attempts,
+            processor_retry_backoff_ms,
+            processor_retry_max_backoff_ms,
+        )?;
+
         Ok(Config {
             app_env,
+            profile_overrides,
             server_port: env::var("SERVER_PORT")
                 .unwrap_or_else(|_| "3000".to_string())
                 .parse()?,
             database_url,
             database_replica_url: env::var("DATABASE_REPLICA_URL").ok(),
             stellar_horizon_url: env::var("STELLAR_HORIZON_URL")?,
+            stellar_horizon_fallback_urls: env::var("STELLAR_HORIZON_FALLBACK_URLS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
+            stellar_network: crate::stellar::StellarNetwork::parse(
+                &env::var("STELLAR_NETWORK").unwrap_or_else(|_| "testnet".to_string()),
+                env::var("STELLAR_NETWORK_PASSPHRASE").ok().as_deref(),
+            )?,
+            horizon_page_limit,
+            health_latency_threshold_ms,
+            idempotency_failure_mode: parse_idempotency_failure_mode(
+                &env::var("IDEMPOTENCY_FAILURE_MODE").unwrap_or_else(|_| "fail_closed".to_string()),
+            )?,
+            readiness_required_dependencies: parse_readiness_dependencies(
+                &env::var("READINESS_REQUIRED_DEPENDENCIES").unwrap_or_default(),
+            )?,
+            feature_flag_defaults: parse_feature_flag_defaults(
+                &env::var("FEATURE_FLAG_DEFAULTS").unwrap_or_default(),
+            )?,
             anchor_webhook_secret,
+            jwt_secret: parse_jwt_secret(env::var("JWT_SECRET").ok())?,
             redis_url: env::var("REDIS_URL")
                 .unwrap_or_else(|_| "redis://localhost:6379".to_string()),
             default_rate_limit: env::var("DEFAULT_RATE_LIMIT")
@@ -226,6 +452,9 @@ impl Config {
             allowed_ips,
             backup_dir: env::var("BACKUP_DIR").unwrap_or_else(|_| "./backups".to_string()),
             backup_encryption_key: env::var("BACKUP_ENCRYPTION_KEY").ok(),
+            backup_max_age_hours,
+            migrations_dir: env::var("MIGRATIONS_DIR")
+                .unwrap_or_else(|_| "./migrations".to_string()),
             db_timeouts: DbTimeoutConfig {
                 read_query_secs: env::var("DB_TIMEOUT_READ_SECS")
                     .unwrap_or_else(|_| "5".to_string())
@@ -248,9 +477,37 @@ impl Config {
                 .filter(|s| !s.is_empty())
                 .map(String::from)
                 .collect(),
+            log_sensitive_keys: env::var("LOG_SENSITIVE_KEYS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect(),
             max_pending_queue: env::var("MAX_PENDING_QUEUE")
                 .unwrap_or_else(|_| "10000".to_string())
                 .parse()?,
+            webhook_max_concurrency,
+            profiling_retention_hours,
+            profiling_min_start_interval_secs,
+            profiling_max_sample_rate_hz,
+            webhook_replay_max_batch,
+            webhook_replay_concurrency,
+            max_body_bytes: env::var("MAX_BODY_BYTES")
+                .unwrap_or_else(|_| "1048576".to_string())
+                .parse()?,
+            request_timeout_secs: env::var("REQUEST_TIMEOUT_SECS")
+                .unwrap_or_else(|_| {
+                    crate::middleware::timeout::DEFAULT_REQUEST_TIMEOUT_SECS.to_string()
+                })
+                .parse()?,
+            search_default_window_days: env::var("SEARCH_DEFAULT_WINDOW_DAYS")
+                .unwrap_or_else(|_| crate::handlers::search::DEFAULT_SEARCH_WINDOW_DAYS.to_string())
+                .parse()?,
+            export_jobs_dir: env::var("EXPORT_JOBS_DIR").unwrap_or_else(|_| {
+                crate::services::export_jobs::DEFAULT_EXPORT_JOBS_DIR.to_string()
+            }),
+            broadcast_capacity,
             db_min_connections: env::var("DB_MIN_CONNECTIONS")
                 .unwrap_or_else(|_| "5".to_string())
                 .parse()?,
@@ -275,6 +532,9 @@ impl Config {
             processor_poll_interval_ms: env::var("PROCESSOR_POLL_INTERVAL_MS")
                 .unwrap_or_else(|_| "1000".to_string())
                 .parse()?,
+            processor_concurrency: env::var("PROCESSOR_CONCURRENCY")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse()?,
             processor_min_batch: env::var("PROCESSOR_MIN_BATCH")
                 .unwrap_or_else(|_| "10".to_string())
                 .parse()?,
@@ -284,6 +544,9 @@ impl Config {
             processor_scaling_factor: env::var("PROCESSOR_SCALING_FACTOR")
                 .unwrap_or_else(|_| "0.5".to_string())
                 .parse()?,
+            processor_max_attempts,
+            processor_retry_backoff_ms,
+            processor_retry_max_backoff_ms,
             slow_query_threshold_ms: env::var("SLOW_QUERY_THRESHOLD_MS")
                 .unwrap_or_else(|_| "500".to_string())
                 .parse()?,
@@ -293,8 +556,280 @@ impl Config {
             settlement_min_tx_count: env::var("SETTLEMENT_MIN_TX_COUNT")
                 .unwrap_or_else(|_| "1".to_string())
                 .parse()?,
+            settlement_schedule: parse_cron_schedule(
+                "SETTLEMENT_SCHEDULE",
+                &env::var("SETTLEMENT_SCHEDULE").unwrap_or_else(|_| "0 0 * * * *".to_string()),
+            )?,
+            partition_strategy: parse_partition_strategy(
+                &env::var("PARTITION_STRATEGY").unwrap_or_else(|_| "monthly".to_string()),
+            )?,
+            partition_months_ahead,
+            partition_days_ahead,
+            partition_retention_months,
+            partition_drop_after_archive: env::var("PARTITION_DROP_AFTER_ARCHIVE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()?,
         })
     }
+
+    /// Horizon base URLs in failover order: `STELLAR_HORIZON_URL`, then each
+    /// of `STELLAR_HORIZON_FALLBACK_URLS`.
+    pub fn stellar_horizon_urls(&self) -> Vec<String> {
+        std::iter::once(&self.stellar_horizon_url)
+            .chain(&self.stellar_horizon_fallback_urls)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Effective configuration as reported by `GET /admin/config`: the active
+/// profile, the profile defaults that were overridden, and every value with
+/// credentials redacted.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ConfigInfo {
+    pub profile: String,
+    pub overrides: Vec<String>,
+    pub values: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Config {
+    /// Profiling manager honouring the configured retention, start rate
+    /// limit and sample rate ceiling.
+    pub fn profiling_manager(&self) -> crate::handlers::profiling::ProfilingManager {
+        crate::handlers::profiling::ProfilingManager::new()
+            .with_retention(std::time::Duration::from_secs(
+                self.profiling_retention_hours * 60 * 60,
+            ))
+            .with_min_start_interval(std::time::Duration::from_secs(
+                self.profiling_min_start_interval_secs,
+            ))
+            .with_max_sample_rate(self.profiling_max_sample_rate_hz)
+    }
+
+    /// Ceiling and parallelism for batch webhook replays.
+    pub fn batch_replay_limits(&self) -> crate::handlers::admin::webhook_replay::BatchReplayLimits {
+        crate::handlers::admin::webhook_replay::BatchReplayLimits {
+            max_batch_size: self.webhook_replay_max_batch,
+            concurrency: self.webhook_replay_concurrency,
+        }
+    }
+
+    /// Retry policy the transaction processor applies to failed transactions.
+    pub fn retry_policy(&self) -> crate::services::transaction_processor::RetryPolicy {
+        crate::services::transaction_processor::RetryPolicy {
+            max_attempts: self.processor_max_attempts,
+            base_backoff: std::time::Duration::from_millis(self.processor_retry_backoff_ms),
+            max_backoff: std::time::Duration::from_millis(self.processor_retry_max_backoff_ms),
+        }
+    }
+
+    /// Snapshot of this configuration that is safe to show operators. URLs
+    /// keep their host but lose any password; secrets are masked entirely.
+    pub fn info(&self) -> ConfigInfo {
+        use crate::cli::{redact_secret, redact_url};
+        use serde_json::json;
+
+        let allowed_ips = match &self.allowed_ips {
+            AllowedIps::Any => "*".to_string(),
+            AllowedIps::Cidrs(cidrs) => cidrs
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        let values = [
+            ("server_port", json!(self.server_port)),
+            ("database_url", json!(redact_url(&self.database_url))),
+            (
+                "database_replica_url",
+                json!(self.database_replica_url.as_deref().map(redact_url)),
+            ),
+            (
+                "stellar_horizon_url",
+                json!(redact_url(&self.stellar_horizon_url)),
+            ),
+            (
+                "stellar_horizon_fallback_urls",
+                json!(self
+                    .stellar_horizon_fallback_urls
+                    .iter()
+                    .map(|url| redact_url(url))
+                    .collect::<Vec<_>>()),
+            ),
+            ("stellar_network", json!(self.stellar_network.as_str())),
+            ("horizon_page_limit", json!(self.horizon_page_limit)),
+            (
+                "health_latency_threshold_ms",
+                json!(self.health_latency_threshold_ms),
+            ),
+            (
+                "idempotency_failure_mode",
+                json!(self.idempotency_failure_mode.as_str()),
+            ),
+            (
+                "readiness_required_dependencies",
+                json!(self.readiness_required_dependencies),
+            ),
+            ("feature_flag_defaults", json!(self.feature_flag_defaults)),
+            (
+                "anchor_webhook_secret",
+                json!(redact_secret(&self.anchor_webhook_secret)),
+            ),
+            (
+                "jwt_secret",
+                json!(self.jwt_secret.as_deref().map(redact_secret)),
+            ),
+            ("redis_url", json!(redact_url(&self.redis_url))),
+            ("default_rate_limit", json!(self.default_rate_limit)),
+            ("whitelist_rate_limit", json!(self.whitelist_rate_limit)),
+            ("whitelisted_ips", json!(self.whitelisted_ips)),
+            (
+                "log_format",
+                json!(format!("{:?}", self.log_format).to_lowercase()),
+            ),
+            ("allowed_ips", json!(allowed_ips)),
+            ("backup_dir", json!(self.backup_dir)),
+            (
+                "backup_encryption_key",
+                json!(self.backup_encryption_key.as_deref().map(redact_secret)),
+            ),
+            ("backup_max_age_hours", json!(self.backup_max_age_hours)),
+            ("migrations_dir", json!(self.migrations_dir)),
+            (
+                "db_timeout_read_secs",
+                json!(self.db_timeouts.read_query_secs),
+            ),
+            (
+                "db_timeout_write_secs",
+                json!(self.db_timeouts.write_query_secs),
+            ),
+            (
+                "db_timeout_admin_secs",
+                json!(self.db_timeouts.admin_query_secs),
+            ),
+            (
+                "otlp_endpoint",
+                json!(self.otlp_endpoint.as_deref().map(redact_url)),
+            ),
+            ("cors_allowed_origins", json!(self.cors_allowed_origins)),
+            ("log_sensitive_keys", json!(self.log_sensitive_keys)),
+            ("max_pending_queue", json!(self.max_pending_queue)),
+            (
+                "webhook_max_concurrency",
+                json!(self.webhook_max_concurrency),
+            ),
+            (
+                "profiling_retention_hours",
+                json!(self.profiling_retention_hours),
+            ),
+            (
+                "profiling_min_start_interval_secs",
+                json!(self.profiling_min_start_interval_secs),
+            ),
+            (
+                "profiling_max_sample_rate_hz",
+                json!(self.profiling_max_sample_rate_hz),
+            ),
+            (
+                "webhook_replay_max_batch",
+                json!(self.webhook_replay_max_batch),
+            ),
+            (
+                "webhook_replay_concurrency",
+                json!(self.webhook_replay_concurrency),
+            ),
+            ("max_body_bytes", json!(self.max_body_bytes)),
+            ("request_timeout_secs", json!(self.request_timeout_secs)),
+            (
+                "search_default_window_days",
+                json!(self.search_default_window_days),
+            ),
+            ("export_jobs_dir", json!(self.export_jobs_dir)),
+            ("broadcast_capacity", json!(self.broadcast_capacity)),
+            ("db_min_connections", json!(self.db_min_connections)),
+            ("db_max_connections", json!(self.db_max_connections)),
+            (
+                "db_statement_timeout_ms",
+                json!(self.db_statement_timeout_ms),
+            ),
+            ("db_idle_timeout_secs", json!(self.db_idle_timeout_secs)),
+            (
+                "db_long_running_statement_timeout_ms",
+                json!(self.db_long_running_statement_timeout_ms),
+            ),
+            ("processor_workers", json!(self.processor_workers)),
+            ("processor_batch_size", json!(self.processor_batch_size)),
+            (
+                "processor_poll_interval_ms",
+                json!(self.processor_poll_interval_ms),
+            ),
+            ("processor_concurrency", json!(self.processor_concurrency)),
+            ("processor_min_batch", json!(self.processor_min_batch)),
+            ("processor_max_batch", json!(self.processor_max_batch)),
+            (
+                "processor_scaling_factor",
+                json!(self.processor_scaling_factor),
+            ),
+            ("processor_max_attempts", json!(self.processor_max_attempts)),
+            (
+                "processor_retry_backoff_ms",
+                json!(self.processor_retry_backoff_ms),
+            ),
+            (
+                "processor_retry_max_backoff_ms",
+                json!(self.processor_retry_max_backoff_ms),
+            ),
+            (
+                "slow_query_threshold_ms",
+                json!(self.slow_query_threshold_ms),
+            ),
+            (
+                "settlement_max_batch_size",
+                json!(self.settlement_max_batch_size),
+            ),
+            (
+                "settlement_min_tx_count",
+                json!(self.settlement_min_tx_count),
+            ),
+            ("settlement_schedule", json!(self.settlement_schedule)),
+            (
+                "partition_strategy",
+                json!(format!("{:?}", self.partition_strategy).to_lowercase()),
+            ),
+            ("partition_months_ahead", json!(self.partition_months_ahead)),
+            ("partition_days_ahead", json!(self.partition_days_ahead)),
+            (
+                "partition_retention_months",
+                json!(self.partition_retention_months),
+            ),
+            (
+                "partition_drop_after_archive",
+                json!(self.partition_drop_after_archive),
+            ),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+
+        ConfigInfo {
+            profile: self.app_env.as_str().to_string(),
+            overrides: self.profile_overrides.clone(),
+            values,
+        }
+    }
+}
+
+/// Rejects a `JWT_SECRET` that is set but empty or too short to be a safe
+/// HS256 key, so a misconfigured secret stops startup instead of weakening
+/// token checks.
+pub fn parse_jwt_secret(raw: Option<String>) -> anyhow::Result<Option<String>> {
+    let min = crate::middleware::auth::MIN_JWT_SECRET_LEN;
+    match raw {
+        Some(secret) if secret.len() < min => {
+            anyhow::bail!("JWT_SECRET must be at least {min} bytes")
+        }
+        secret => Ok(secret),
+    }
 }
 
 fn parse_allowed_ips(raw: &str) -> anyhow::Result<AllowedIps> {
@@ -324,3 +859,98 @@ fn parse_log_format(raw: &str) -> anyhow::Result<LogFormat> {
         _ => anyhow::bail!("LOG_FORMAT must be 'text' or 'json'"),
     }
 }
+
+fn parse_idempotency_failure_mode(
+    raw: &str,
+) -> anyhow::Result<crate::middleware::idempotency::IdempotencyFailureMode> {
+    raw.parse().map_err(|_| {
+        anyhow::anyhow!("IDEMPOTENCY_FAILURE_MODE must be 'fail_open' or 'fail_closed'")
+    })
+}
+
+fn parse_readiness_dependencies(raw: &str) -> anyhow::Result<Vec<&'static str>> {
+    use crate::readiness::subsystem;
+    let mut dependencies = Vec::new();
+    for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        match name.to_ascii_lowercase().as_str() {
+            // Always required.
+            "database" => {}
+            "redis" => dependencies.push(subsystem::REDIS),
+            "horizon" => dependencies.push(subsystem::HORIZON),
+            other => anyhow::bail!(
+                "READINESS_REQUIRED_DEPENDENCIES: unknown dependency '{other}' \
+                 (expected 'database', 'redis' or 'horizon')"
+            ),
+        }
+    }
+    Ok(dependencies)
+}
+
+fn parse_feature_flag_defaults(
+    raw: &str,
+) -> anyhow::Result<std::collections::HashMap<String, bool>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|entry| {
+            let (name, value) = entry.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!("FEATURE_FLAG_DEFAULTS: expected 'name=true|false', got '{entry}'")
+            })?;
+            let enabled = value.trim().parse::<bool>().map_err(|_| {
+                anyhow::anyhow!("FEATURE_FLAG_DEFAULTS: '{entry}' must be 'true' or 'false'")
+            })?;
+            Ok((name.trim().to_string(), enabled))
+        })
+        .collect()
+}
+
+fn parse_cron_schedule(var: &str, raw: &str) -> anyhow::Result<String> {
+    let raw = raw.trim();
+    raw.parse::<cron::Schedule>()
+        .map_err(|e| anyhow::anyhow!("{var} is not a valid cron expression: {e}"))?;
+    Ok(raw.to_string())
+}
+
+fn parse_partition_strategy(raw: &str) -> anyhow::Result<crate::db::partition::PartitionStrategy> {
+    use crate::db::partition::PartitionStrategy;
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "monthly" => Ok(PartitionStrategy::Monthly),
+        "daily" => Ok(PartitionStrategy::Daily),
+        _ => anyhow::bail!("PARTITION_STRATEGY must be 'monthly' or 'daily'"),
+    }
+}
+
+/// Reject partition settings that would leave the table without a current
+/// partition or archive everything on the next maintenance run.
+pub fn validate_partition_settings(
+    months_ahead: u32,
+    days_ahead: u32,
+    retention_months: i64,
+) -> anyhow::Result<()> {
+    if months_ahead < 1 {
+        anyhow::bail!("PARTITION_MONTHS_AHEAD must be at least 1");
+    }
+    if days_ahead < 1 {
+        anyhow::bail!("PARTITION_DAYS_AHEAD must be at least 1");
+    }
+    if retention_months <= 0 {
+        anyhow::bail!("PARTITION_RETENTION_MONTHS must be greater than 0");
+    }
+    Ok(())
+}
+
+/// Reject retry settings under which a failing transaction would never reach
+/// the DLQ or would back off past its own cap.
+pub fn validate_retry_settings(
+    max_attempts: u32,
+    backoff_ms: u64,
+    max_backoff_ms: u64,
+) -> anyhow::Result<()> {
+    if max_attempts == 0 {
+        anyhow::bail!("PROCESSOR_MAX_ATTEMPTS must be greater than 0");
+    }
+    if max_backoff_ms < backoff_ms {
+        anyhow::bail!("PROCESSOR_RETRY_MAX_BACKOFF_MS must be at least PROCESSOR_RETRY_BACKOFF_MS");
+    }
+    Ok(())
+}
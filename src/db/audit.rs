@@ -71,6 +71,42 @@ impl AuditLog {
         Ok(())
     }
 
+    /// Insert several entries with a single statement.
+    pub async fn log_many(
+        tx: &mut SqlxTransaction<'_, Postgres>,
+        entries: &[AuditLog],
+    ) -> sqlx::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let entity_ids: Vec<Uuid> = entries.iter().map(|e| e.entity_id).collect();
+        let entity_types: Vec<&str> = entries.iter().map(|e| e.entity_type.as_str()).collect();
+        let actions: Vec<&str> = entries.iter().map(|e| e.action.as_str()).collect();
+        let old_vals: Vec<Option<JsonValue>> = entries.iter().map(|e| e.old_val.clone()).collect();
+        let new_vals: Vec<Option<JsonValue>> = entries.iter().map(|e| e.new_val.clone()).collect();
+        let actors: Vec<&str> = entries.iter().map(|e| e.actor.as_str()).collect();
+        let timestamps: Vec<DateTime<Utc>> = entries.iter().map(|e| e.timestamp).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (entity_id, entity_type, action, old_val, new_val, actor, timestamp)
+            SELECT * FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::jsonb[], $5::jsonb[], $6::text[], $7::timestamptz[])
+            "#,
+        )
+        .bind(entity_ids)
+        .bind(entity_types)
+        .bind(actions)
+        .bind(old_vals)
+        .bind(new_vals)
+        .bind(actors)
+        .bind(timestamps)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
     /// Log a status change
     pub async fn log_status_change(
         tx: &mut SqlxTransaction<'_, Postgres>,
@@ -2,6 +2,21 @@ use chrono::{Datelike, NaiveDate, TimeZone, Utc};
 use sqlx::postgres::PgPool;
 use sqlx::Row;
 
+/// Name of the monthly partition covering `year`-`month`, e.g. `transactions_y2025m02`.
+pub fn monthly_partition_name(year: i32, month: u32) -> String {
+    format!("transactions_y{year}m{month:02}")
+}
+
+/// Name of the daily partition covering `date`, e.g. `transactions_y2025m02d03`.
+pub fn daily_partition_name(date: NaiveDate) -> String {
+    format!(
+        "transactions_y{}m{:02}d{:02}",
+        date.year(),
+        date.month(),
+        date.day()
+    )
+}
+
 pub async fn create_month_partition(
     pool: &PgPool,
     year: i32,
@@ -14,9 +29,7 @@ pub async fn create_month_partition(
     }
 
     let start = NaiveDate::from_ymd_opt(year, month, 1)
-        .ok_or_else(|| sqlx::Error::Protocol("Invalid date".into()))?
-        .and_hms_opt(0, 0, 0)
-        .ok_or_else(|| sqlx::Error::Protocol("Invalid time".into()))?;
+        .ok_or_else(|| sqlx::Error::Protocol("Invalid date".into()))?;
     // compute next month
     let (ny, nm) = if month == 12 {
         (year + 1, 1)
@@ -24,13 +37,68 @@ pub async fn create_month_partition(
         (year, month + 1)
     };
     let end = NaiveDate::from_ymd_opt(ny, nm, 1)
-        .ok_or_else(|| sqlx::Error::Protocol("Invalid date".into()))?
+        .ok_or_else(|| sqlx::Error::Protocol("Invalid date".into()))?;
+
+    create_range_partition(pool, &monthly_partition_name(year, month), start, end).await
+}
+
+/// Create the single-day partition for `date`.
+///
+/// Days already covered by an existing (e.g. monthly) partition are skipped,
+/// since Postgres rejects overlapping partition bounds. This lets a table
+/// switch from monthly to daily partitioning without manual cleanup.
+pub async fn create_day_partition(pool: &PgPool, date: NaiveDate) -> Result<(), sqlx::Error> {
+    let end = date
+        .succ_opt()
+        .ok_or_else(|| sqlx::Error::Protocol("Invalid date".into()))?;
+    let part_name = daily_partition_name(date);
+
+    let start_ts = to_utc_midnight(date)?;
+    let end_ts = to_utc_midnight(end)?;
+    let covered: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM pg_inherits i
+            JOIN pg_class c ON i.inhrelid = c.oid
+            JOIN pg_class p ON i.inhparent = p.oid
+            WHERE p.relname = 'transactions'
+              AND c.relname <> $3
+              AND substring(pg_get_expr(c.relpartbound, c.oid) FROM 'FROM \(''([^'']+)''\)')::timestamptz < $2
+              AND substring(pg_get_expr(c.relpartbound, c.oid) FROM 'TO \(''([^'']+)''\)')::timestamptz > $1
+        )
+        "#,
+    )
+    .bind(start_ts)
+    .bind(end_ts)
+    .bind(&part_name)
+    .fetch_one(pool)
+    .await?;
+
+    if covered {
+        tracing::debug!(partition = %part_name, "day already covered by an existing partition");
+        return Ok(());
+    }
+
+    create_range_partition(pool, &part_name, date, end).await
+}
+
+fn to_utc_midnight(date: NaiveDate) -> Result<chrono::DateTime<Utc>, sqlx::Error> {
+    let midnight = date
         .and_hms_opt(0, 0, 0)
         .ok_or_else(|| sqlx::Error::Protocol("Invalid time".into()))?;
+    Ok(Utc.from_utc_datetime(&midnight))
+}
 
-    let part_name = format!("transactions_y{year}m{month:02}");
-    let start_ts = Utc.from_utc_datetime(&start).to_rfc3339();
-    let end_ts = Utc.from_utc_datetime(&end).to_rfc3339();
+/// Create `part_name` covering `[start, end)` along with its per-partition indexes.
+async fn create_range_partition(
+    pool: &PgPool,
+    part_name: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    let start_ts = to_utc_midnight(start)?.to_rfc3339();
+    let end_ts = to_utc_midnight(end)?.to_rfc3339();
 
     let create_sql = format!(
         "CREATE TABLE IF NOT EXISTS \"{part_name}\" PARTITION OF transactions FOR VALUES FROM ('{start_ts}') TO ('{end_ts}')"
@@ -49,10 +117,12 @@ pub async fn create_month_partition(
 }
 
 /// Detach partitions older than `retention_months` and move them to `archive` schema.
+///
+/// Returns the names of the partitions archived by this call.
 pub async fn detach_and_archive_old_partitions(
     pool: &PgPool,
     retention_months: i64,
-) -> Result<(), sqlx::Error> {
+) -> Result<Vec<String>, sqlx::Error> {
     // compute cutoff year-month
     let now = Utc::now();
     let cutoff = now - chrono::Duration::days(30 * retention_months);
@@ -67,11 +137,12 @@ pub async fn detach_and_archive_old_partitions(
         .execute(pool)
         .await?;
 
+    let mut archived = Vec::new();
     for row in rows {
         let child: String = row.get("child");
-        // expect names like transactions_y2025m02
-        if let Some((y, m)) = parse_partition_name(&child) {
-            let part_date = Utc.with_ymd_and_hms(y, m, 1, 0, 0, 0).single().unwrap();
+        // expect names like transactions_y2025m02 or transactions_y2025m02d03
+        if let Some(part_start) = parse_partition_name(&child) {
+            let part_date = Utc.from_utc_datetime(&part_start.and_hms_opt(0, 0, 0).unwrap());
             if part_date < cutoff {
                 // detach
                 let detach_sql = format!("ALTER TABLE transactions DETACH PARTITION \"{child}\"");
@@ -79,27 +150,24 @@ pub async fn detach_and_archive_old_partitions(
                 // move to archive schema
                 let set_schema = format!("ALTER TABLE \"{child}\" SET SCHEMA archive");
                 sqlx::query(&set_schema).execute(pool).await?;
+                archived.push(child);
             }
         }
     }
 
-    Ok(())
+    Ok(archived)
 }
 
-fn parse_partition_name(name: &str) -> Option<(i32, u32)> {
-    // Very small parser for expected pattern transactions_yYYYYmMM
-    if !name.starts_with("transactions_y") {
-        return None;
-    }
-    let rest = &name[14..]; // after transactions_y
-    if let Some(idx) = rest.find('m') {
-        let y = &rest[..idx];
-        let m = &rest[idx + 1..];
-        if let (Ok(yy), Ok(mm)) = (y.parse::<i32>(), m.parse::<u32>()) {
-            return Some((yy, mm));
-        }
-    }
-    None
+/// Parse the start date out of a partition name: `transactions_yYYYYmMM`
+/// (first of the month) or `transactions_yYYYYmMMdDD` (that day).
+pub fn parse_partition_name(name: &str) -> Option<NaiveDate> {
+    let rest = name.strip_prefix("transactions_y")?;
+    let (y, rest) = rest.split_once('m')?;
+    let (m, d) = match rest.split_once('d') {
+        Some((m, d)) => (m, d.parse::<u32>().ok()?),
+        None => (rest, 1),
+    };
+    NaiveDate::from_ymd_opt(y.parse().ok()?, m.parse().ok()?, d)
 }
 
 /// Convenience: create partitions for the next `months_ahead` months (including current month).
@@ -119,3 +187,18 @@ pub async fn ensure_future_partitions(pool: &PgPool, months_ahead: u32) -> Resul
     }
     Ok(())
 }
+
+/// Convenience: create daily partitions for the next `days_ahead` days (including today).
+pub async fn ensure_future_daily_partitions(
+    pool: &PgPool,
+    days_ahead: u32,
+) -> Result<(), sqlx::Error> {
+    let mut date = Utc::now().date_naive();
+    for _ in 0..days_ahead {
+        create_day_partition(pool, date).await?;
+        date = date
+            .succ_opt()
+            .ok_or_else(|| sqlx::Error::Protocol("Invalid date".into()))?;
+    }
+    Ok(())
+}
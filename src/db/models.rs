@@ -16,6 +16,9 @@ pub enum TransactionStatus {
     Completed,
     #[serde(rename = "failed")]
     Failed,
+    /// Retries exhausted; parked in the dead letter queue.
+    #[serde(rename = "dlq")]
+    Dlq,
 }
 
 impl std::fmt::Display for TransactionStatus {
@@ -25,6 +28,7 @@ impl std::fmt::Display for TransactionStatus {
             TransactionStatus::Processing => write!(f, "processing"),
             TransactionStatus::Completed => write!(f, "completed"),
             TransactionStatus::Failed => write!(f, "failed"),
+            TransactionStatus::Dlq => write!(f, "dlq"),
         }
     }
 }
@@ -38,11 +42,28 @@ impl FromStr for TransactionStatus {
             "processing" => Ok(TransactionStatus::Processing),
             "completed" => Ok(TransactionStatus::Completed),
             "failed" => Ok(TransactionStatus::Failed),
+            "dlq" => Ok(TransactionStatus::Dlq),
             _ => Err(format!("Invalid transaction status: {}", s)),
         }
     }
 }
 
+/// Asset code for native lumens. Native XLM has no issuer.
+pub const NATIVE_ASSET_CODE: &str = "XLM";
+
+/// Canonical `(asset_code, asset_issuer)` for an asset.
+///
+/// Horizon's `"native"` and an issuer-less `"XLM"` both become
+/// `("XLM", None)`; an empty issuer is treated as absent. An `"XLM"` code
+/// with an issuer is a distinct issued asset and is left alone.
+pub fn normalize_asset(asset_code: &str, asset_issuer: Option<&str>) -> (String, Option<String>) {
+    let issuer = asset_issuer.map(str::trim).filter(|i| !i.is_empty());
+    if asset_code == "native" || (asset_code == NATIVE_ASSET_CODE && issuer.is_none()) {
+        return (NATIVE_ASSET_CODE.to_string(), None);
+    }
+    (asset_code.to_string(), issuer.map(String::from))
+}
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct Transaction {
@@ -61,6 +82,21 @@ pub struct Transaction {
     pub memo_type: Option<String>,
     pub metadata: Option<serde_json::Value>,
     pub trace_id: Option<String>,
+    /// Issuing account of `asset_code`; `None` for native XLM and for rows
+    /// recorded before issuers were tracked.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub asset_issuer: Option<String>,
+    /// When the transaction was soft-deleted; such rows are hidden from
+    /// default reads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Tenant whose API key delivered the transaction; `None` for rows
+    /// ingested without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub tenant_id: Option<Uuid>,
 }
 
 #[async_graphql::Object]
@@ -77,6 +113,9 @@ impl Transaction {
     async fn asset_code(&self) -> &str {
         &self.asset_code
     }
+    async fn asset_issuer(&self) -> Option<&str> {
+        self.asset_issuer.as_deref()
+    }
     async fn status(&self) -> &str {
         &self.status
     }
@@ -104,6 +143,16 @@ impl Transaction {
     async fn memo_type(&self) -> Option<&str> {
         self.memo_type.as_deref()
     }
+    /// Creation and audited changes, oldest first.
+    async fn events(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::Result<Vec<crate::db::queries::TransactionEvent>> {
+        let state = ctx.data::<crate::AppState>()?;
+        crate::db::queries::get_transaction_events(&state.db, self.id)
+            .await
+            .map_err(|e| crate::graphql::error::app_error(e.into()))
+    }
 }
 
 impl Transaction {
@@ -123,7 +172,8 @@ impl Transaction {
             id: Uuid::new_v4(),
             stellar_account,
             amount,
-            asset_code,
+            // Canonical case so `usd` and `USD` settle together.
+            asset_code: asset_code.trim().to_ascii_uppercase(),
             status: TransactionStatus::Pending.to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
@@ -135,6 +185,9 @@ impl Transaction {
             memo_type,
             metadata,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         }
     }
 
@@ -142,6 +195,20 @@ impl Transaction {
         self.trace_id = trace_id;
         self
     }
+
+    /// Records the tenant that delivered the transaction.
+    pub fn with_tenant_id(mut self, tenant_id: Option<Uuid>) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
+
+    /// Sets the asset issuer, normalizing native XLM via [`normalize_asset`].
+    pub fn with_asset_issuer(mut self, asset_issuer: Option<String>) -> Self {
+        let (asset_code, asset_issuer) = normalize_asset(&self.asset_code, asset_issuer.as_deref());
+        self.asset_code = asset_code;
+        self.asset_issuer = asset_issuer;
+        self
+    }
 }
 
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
@@ -159,6 +226,39 @@ pub struct Settlement {
     pub original_total_amount: Option<BigDecimal>,
     pub reviewed_by: Option<String>,
     pub reviewed_at: Option<DateTime<Utc>>,
+    /// Issuer shared by every transaction in this settlement; `None` for
+    /// native XLM.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub asset_issuer: Option<String>,
+    /// Incremented by every update; writers must present the version they
+    /// read or the update is rejected as a conflict.
+    #[serde(default = "default_settlement_version")]
+    pub version: i32,
+    /// Account this settlement pays out to, from the asset's
+    /// [`SettlementConfig`] at settlement time.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub target_account: Option<String>,
+}
+
+/// Per-asset row of `settlement_config`. `None` fields fall back to the
+/// settlement service's defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct SettlementConfig {
+    pub asset_code: String,
+    /// Smallest unsettled total, per issuer, worth settling.
+    pub min_amount: Option<BigDecimal>,
+    pub min_tx_count: Option<i32>,
+    /// `hourly`, `daily` or `weekly`.
+    pub schedule: String,
+    pub target_account: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_settlement_version() -> i32 {
+    1
 }
 
 #[async_graphql::Object]
@@ -169,6 +269,9 @@ impl Settlement {
     async fn asset_code(&self) -> &str {
         &self.asset_code
     }
+    async fn asset_issuer(&self) -> Option<&str> {
+        self.asset_issuer.as_deref()
+    }
     async fn total_amount(&self) -> String {
         self.total_amount.to_string()
     }
@@ -190,6 +293,9 @@ impl Settlement {
     async fn updated_at(&self) -> DateTime<Utc> {
         self.updated_at
     }
+    async fn version(&self) -> i32 {
+        self.version
+    }
 }
 
 #[derive(Debug, FromRow, Serialize, Deserialize)]
@@ -207,6 +313,29 @@ pub struct TransactionDlq {
     pub moved_to_dlq_at: DateTime<Utc>,
     pub last_retry_at: Option<DateTime<Utc>>,
 }
+
+/// Raw inbound webhook captured at ingest, before it is normalized into a
+/// [`Transaction`]. Written once alongside the transaction it created.
+#[derive(Debug, Clone)]
+pub struct NewWebhookPayload {
+    /// Ingest route that received the payload, e.g. `callback`.
+    pub source: String,
+    /// Request headers as a JSON object, with credentials stripped.
+    pub headers: serde_json::Value,
+    /// Request body exactly as received.
+    pub body: Vec<u8>,
+}
+
+/// A row of `webhook_payloads`.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct StoredWebhookPayload {
+    pub transaction_id: Uuid,
+    pub source: String,
+    pub headers: serde_json::Value,
+    pub body: Vec<u8>,
+    pub received_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
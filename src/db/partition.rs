@@ -1,25 +1,132 @@
+use crate::db::cron;
+use crate::services::backup::BackupService;
 use crate::services::query_cache::QueryCache;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use sqlx::PgPool;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// Schema that detached partitions are moved into by [`cron::detach_and_archive_old_partitions`].
+const ARCHIVE_SCHEMA: &str = "archive";
+
+/// Dumps an archived partition before it is dropped. Kept as a trait so the
+/// detach → backup → drop sequence can be tested without `pg_dump`.
+#[async_trait]
+pub trait PartitionBackup: Send + Sync {
+    /// Back up `table` (schema-qualified) and return the backup filename.
+    async fn backup_partition(&self, table: &str) -> anyhow::Result<String>;
+}
+
+#[async_trait]
+impl PartitionBackup for BackupService {
+    async fn backup_partition(&self, table: &str) -> anyhow::Result<String> {
+        Ok(self.backup_table(table).await?.filename)
+    }
+}
+
+/// Granularity of `transactions` partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionStrategy {
+    /// One partition per month, named `transactions_yYYYYmMM`.
+    Monthly,
+    /// One partition per day, named `transactions_yYYYYmMMdDD`. For high-volume deployments.
+    Daily,
+}
+
+/// How far ahead partitions are pre-created and how long they are kept attached.
+#[derive(Debug, Clone)]
+pub struct PartitionConfig {
+    pub strategy: PartitionStrategy,
+    /// Number of monthly partitions to ensure exist, starting with the current month. Default: 3
+    pub months_ahead: u32,
+    /// Number of daily partitions to ensure exist, starting with today, under
+    /// [`PartitionStrategy::Daily`]. Default: 7
+    pub days_ahead: u32,
+    /// Partitions older than this many months are detached and archived. Default: 12
+    pub retention_months: i64,
+    /// Back up and drop partitions once archived instead of keeping them as
+    /// orphan tables. Requires a backup to be attached; partitions whose
+    /// backup fails stay archived and are retried on the next run. Default: false
+    pub drop_after_archive: bool,
+}
+
+impl Default for PartitionConfig {
+    fn default() -> Self {
+        Self {
+            strategy: PartitionStrategy::Monthly,
+            months_ahead: 3,
+            days_ahead: 7,
+            retention_months: 12,
+            drop_after_archive: false,
+        }
+    }
+}
+
+impl PartitionConfig {
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            strategy: config.partition_strategy,
+            months_ahead: config.partition_months_ahead,
+            days_ahead: config.partition_days_ahead,
+            retention_months: config.partition_retention_months,
+            drop_after_archive: config.partition_drop_after_archive,
+        }
+    }
+}
+
+/// A partition of `transactions` as reported by [`PartitionManager::list_partitions`].
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct PartitionInfo {
+    pub name: String,
+    /// Inclusive lower bound of the partition range; `None` for a DEFAULT partition.
+    pub range_start: Option<DateTime<Utc>>,
+    /// Exclusive upper bound of the partition range; `None` for a DEFAULT partition.
+    pub range_end: Option<DateTime<Utc>>,
+    /// Planner row estimate (`pg_class.reltuples`); 0 until the partition is analyzed.
+    pub row_estimate: i64,
+    /// Table + index + TOAST size in bytes (`pg_total_relation_size`).
+    pub total_bytes: i64,
+}
 
 /// Partition manager that runs maintenance tasks periodically
 pub struct PartitionManager {
     pool: PgPool,
     interval: Duration,
     cache: Option<QueryCache>,
+    config: PartitionConfig,
+    backup: Option<Arc<dyn PartitionBackup>>,
 }
 
 impl PartitionManager {
     pub fn new(pool: PgPool, interval_hours: u64, cache: Option<QueryCache>) -> Self {
+        Self::with_config(pool, interval_hours, cache, PartitionConfig::default())
+    }
+
+    pub fn with_config(
+        pool: PgPool,
+        interval_hours: u64,
+        cache: Option<QueryCache>,
+        config: PartitionConfig,
+    ) -> Self {
         Self {
             pool,
             interval: Duration::from_secs(interval_hours * 3600),
             cache,
+            config,
+            backup: None,
         }
     }
 
+    /// Attach the backup used to dump archived partitions before they are dropped.
+    pub fn with_backup(mut self, backup: Arc<dyn PartitionBackup>) -> Self {
+        self.backup = Some(backup);
+        self
+    }
+
     /// Start the partition maintenance background task
     pub fn start(self) {
         tokio::spawn(async move {
@@ -28,7 +135,7 @@ impl PartitionManager {
 
             loop {
                 interval.tick().await;
-                let result = self.maintain_partitions().await;
+                let result = self.run_maintenance().await;
 
                 if let Err(e) = result {
                     error!("Partition maintenance failed: {}", e);
@@ -39,55 +146,164 @@ impl PartitionManager {
         });
     }
 
-    /// Run partition maintenance (create new partitions, detach old ones)
-    async fn maintain_partitions(&self) -> Result<(), sqlx::Error> {
-        sqlx::query("SELECT maintain_partitions()")
-            .execute(&self.pool)
-            .await?;
+    /// Run partition maintenance: ensure `months_ahead` (or, for daily
+    /// partitioning, `days_ahead`) partitions exist and archive those older
+    /// than `retention_months`. With `drop_after_archive` set, every partition
+    /// in the archive schema is backed up and then dropped, including ones
+    /// left there by an earlier run whose backup failed or that had no backup
+    /// configured.
+    pub async fn run_maintenance(&self) -> anyhow::Result<()> {
+        match self.config.strategy {
+            PartitionStrategy::Monthly => {
+                cron::ensure_future_partitions(&self.pool, self.config.months_ahead).await?
+            }
+            PartitionStrategy::Daily => {
+                cron::ensure_future_daily_partitions(&self.pool, self.config.days_ahead).await?
+            }
+        }
+        cron::detach_and_archive_old_partitions(&self.pool, self.config.retention_months).await?;
+
+        if !self.config.drop_after_archive {
+            return Ok(());
+        }
+
+        let archived = self.archived_partitions().await?;
+        if archived.is_empty() {
+            return Ok(());
+        }
+
+        let Some(backup) = &self.backup else {
+            warn!(
+                count = archived.len(),
+                "drop_after_archive is enabled but no backup is configured; keeping archived partitions"
+            );
+            return Ok(());
+        };
+
+        // A failed backup leaves its partition in the archive schema for the
+        // next run to retry; the remaining partitions are still processed.
+        let mut failed = 0;
+        for partition in archived {
+            let table = format!("{ARCHIVE_SCHEMA}.{partition}");
+            let filename = match backup.backup_partition(&table).await {
+                Ok(filename) => filename,
+                Err(e) => {
+                    error!(partition = %table, error = %e, "archived partition backup failed, keeping it");
+                    failed += 1;
+                    continue;
+                }
+            };
+            info!(partition = %table, backup = %filename, "archived partition backed up, dropping");
+
+            let drop_sql = format!("DROP TABLE \"{ARCHIVE_SCHEMA}\".\"{partition}\"");
+            sqlx::query(&drop_sql).execute(&self.pool).await?;
+        }
+
+        if failed > 0 {
+            anyhow::bail!("{failed} archived partition(s) could not be backed up");
+        }
         Ok(())
     }
 
+    /// Names of the `transactions_*` tables in the archive schema, oldest first.
+    async fn archived_partitions(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT tablename::text FROM pg_tables \
+             WHERE schemaname = $1 AND tablename LIKE 'transactions\\_%' \
+             ORDER BY tablename",
+        )
+        .bind(ARCHIVE_SCHEMA)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// List the partitions currently attached to `transactions`, oldest first.
+    ///
+    /// Range bounds are parsed from the partition bound expression rather than
+    /// the partition name, so manually created partitions are reported too.
+    pub async fn list_partitions(pool: &PgPool) -> Result<Vec<PartitionInfo>, sqlx::Error> {
+        sqlx::query_as::<_, PartitionInfo>(
+            r#"
+            SELECT
+                c.relname::text AS name,
+                substring(pg_get_expr(c.relpartbound, c.oid) FROM 'FROM \(''([^'']+)''\)')::timestamptz
+                    AS range_start,
+                substring(pg_get_expr(c.relpartbound, c.oid) FROM 'TO \(''([^'']+)''\)')::timestamptz
+                    AS range_end,
+                GREATEST(c.reltuples, 0)::bigint AS row_estimate,
+                pg_total_relation_size(c.oid) AS total_bytes
+            FROM pg_inherits i
+            JOIN pg_class c ON i.inhrelid = c.oid
+            JOIN pg_class p ON i.inhparent = p.oid
+            WHERE p.relname = 'transactions'
+            ORDER BY range_start NULLS LAST, name
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Manually trigger partition creation.
     ///
     /// Returns `true` if a new partition was created, `false` if it already existed.
     /// Triggers cache warming when a new partition is created.
+    ///
+    /// Monthly partitioning creates the partition for next month + 1; daily
+    /// partitioning creates the partition `days_ahead` days from today.
     pub async fn create_partition(&self) -> Result<bool, sqlx::Error> {
-        // Determine the name of the partition that would be created for next month + 1.
-        let partition_name: String = sqlx::query_scalar(
-            "SELECT 'transactions_y' || TO_CHAR(DATE_TRUNC('month', NOW() + INTERVAL '2 months'), 'YYYY') \
-             || 'm' || TO_CHAR(DATE_TRUNC('month', NOW() + INTERVAL '2 months'), 'MM')",
-        )
-        .fetch_one(&self.pool)
-        .await?;
-
-        let already_exists: bool =
-            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM pg_class WHERE relname = $1)")
-                .bind(&partition_name)
+        let (partition_name, already_exists) = match self.config.strategy {
+            PartitionStrategy::Monthly => {
+                // Determine the name of the partition that would be created for next month + 1.
+                let partition_name: String = sqlx::query_scalar(
+                    "SELECT 'transactions_y' || TO_CHAR(DATE_TRUNC('month', NOW() + INTERVAL '2 months'), 'YYYY') \
+                     || 'm' || TO_CHAR(DATE_TRUNC('month', NOW() + INTERVAL '2 months'), 'MM')",
+                )
                 .fetch_one(&self.pool)
                 .await?;
+                let already_exists = self.relation_exists(&partition_name).await?;
 
-        sqlx::query("SELECT create_monthly_partition()")
-            .execute(&self.pool)
-            .await?;
+                sqlx::query("SELECT create_monthly_partition()")
+                    .execute(&self.pool)
+                    .await?;
+                (partition_name, already_exists)
+            }
+            PartitionStrategy::Daily => {
+                let date = Utc::now().date_naive()
+                    + chrono::Duration::days(i64::from(self.config.days_ahead));
+                let partition_name = cron::daily_partition_name(date);
+                let already_exists = self.relation_exists(&partition_name).await?;
+
+                cron::create_day_partition(&self.pool, date).await?;
+                (partition_name, already_exists)
+            }
+        };
 
         let created = !already_exists;
         if created {
             info!(partition = %partition_name, "new partition created, warming cache");
             if let Some(cache) = &self.cache {
-                if let Err(e) = cache
-                    .warm_cache(
-                        &self.pool,
-                        &crate::services::query_cache::CacheConfig::default(),
-                    )
-                    .await
-                {
+                let config = crate::services::query_cache::CacheConfig::default();
+                if let Err(e) = cache.warm_cache(&self.pool, &config).await {
                     error!("cache warming after partition creation failed: {}", e);
                 }
+                if let Err(e) = cache.warm_settlement_aggregates(&self.pool, &config).await {
+                    error!(
+                        "settlement aggregate warming after partition creation failed: {}",
+                        e
+                    );
+                }
             }
         }
         Ok(created)
     }
 
+    async fn relation_exists(&self, name: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM pg_class WHERE relname = $1)")
+            .bind(name)
+            .fetch_one(&self.pool)
+            .await
+    }
+
     /// Manually trigger old partition detachment
     pub async fn detach_old_partitions(&self, retention_months: i32) -> Result<(), sqlx::Error> {
         sqlx::query("SELECT detach_old_partitions($1)")
@@ -113,6 +329,8 @@ mod tests {
         let manager = PartitionManager::new(pool, 24, None);
 
         assert_eq!(manager.interval, Duration::from_secs(24 * 3600));
+        assert_eq!(manager.config.months_ahead, 3);
+        assert_eq!(manager.config.retention_months, 12);
     }
 
     /// Cache is warm after partition creation; no extra warming if partition already exists.
@@ -31,9 +31,13 @@
 //! - Sensitive data (passwords, tokens) never logged; only query structure logged
 
 use crate::db::audit::{AuditLog, ENTITY_TRANSACTION};
-use crate::db::models::{Settlement, Transaction};
+use crate::db::models::{
+    NewWebhookPayload, Settlement, SettlementConfig, StoredWebhookPayload, Transaction,
+    TransactionStatus,
+};
+use crate::error::AppError;
 use crate::tenant::TenantConfig;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::types::BigDecimal;
@@ -172,7 +176,7 @@ pub async fn lookup_api_key(pool: &PgPool, api_key: &str) -> Result<bool> {
 /// callers must not log or persist them in audit records.
 pub async fn get_all_tenant_configs(pool: &PgPool) -> Result<Vec<TenantConfig>> {
     let configs = sqlx::query_as::<_, TenantConfig>(
-        "SELECT tenant_id, name, webhook_secret, stellar_account, rate_limit_per_minute, is_active FROM tenants WHERE is_active = true",
+        "SELECT tenant_id, name, webhook_secret, stellar_account, rate_limit_per_minute, is_active, cors_allowed_origins FROM tenants WHERE is_active = true",
     )
     .fetch_all(pool)
     .await?;
@@ -411,6 +415,26 @@ pub async fn with_tenant<T>(
 /// `is_new = false` means a prior delivery already owns the `anchor_transaction_id`
 /// and the returned transaction is that existing row - no new row was written.
 pub async fn insert_transaction(pool: &PgPool, tx: &Transaction) -> Result<(Transaction, bool)> {
+    insert_transaction_inner(pool, tx, None).await
+}
+
+/// Like [`insert_transaction`], but also stores the raw inbound `payload` in
+/// `webhook_payloads` within the same SQL transaction. The payload is only
+/// written when this delivery created the row, so a duplicate delivery never
+/// overwrites the body that was originally received.
+pub async fn insert_transaction_with_payload(
+    pool: &PgPool,
+    tx: &Transaction,
+    payload: &NewWebhookPayload,
+) -> Result<(Transaction, bool)> {
+    insert_transaction_inner(pool, tx, Some(payload)).await
+}
+
+async fn insert_transaction_inner(
+    pool: &PgPool,
+    tx: &Transaction,
+    payload: Option<&NewWebhookPayload>,
+) -> Result<(Transaction, bool)> {
     with_timeout(
         QueryTier::Write,
         "INSERT INTO transactions ... RETURNING *",
@@ -420,12 +444,15 @@ pub async fn insert_transaction(pool: &PgPool, tx: &Transaction) -> Result<(Tran
             let (result, is_new) = persist_transaction(&mut db_tx, tx).await?;
             if is_new {
                 audit_transaction_creation(&mut db_tx, &result).await?;
+                if let Some(payload) = payload {
+                    persist_webhook_payload(&mut db_tx, result.id, payload).await?;
+                }
             }
 
             db_tx.commit().await?;
 
             if is_new {
-                invalidate_transaction_caches(&result.asset_code).await;
+                invalidate_transaction_caches(&[&result.asset_code]).await;
             }
 
             Ok((result, is_new))
@@ -499,8 +526,8 @@ async fn persist_transaction(
         INSERT INTO transactions (
             id, stellar_account, amount, asset_code, status,
             created_at, updated_at, anchor_transaction_id, callback_type, callback_status,
-            settlement_id, memo, memo_type, metadata
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            settlement_id, memo, memo_type, metadata, asset_issuer, tenant_id
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
         -- Partitioned by created_at; only (id, created_at) is a valid conflict target.
         ON CONFLICT (id, created_at) DO NOTHING
         RETURNING *
@@ -520,6 +547,8 @@ async fn persist_transaction(
     .bind(&tx.memo)
     .bind(&tx.memo_type)
     .bind(&tx.metadata)
+    .bind(&tx.asset_issuer)
+    .bind(tx.tenant_id)
     .fetch_optional(&mut **db_tx)
     .await?;
 
@@ -539,6 +568,47 @@ async fn persist_transaction(
     }
 }
 
+async fn persist_webhook_payload(
+    db_tx: &mut SqlxTransaction<'_, Postgres>,
+    transaction_id: Uuid,
+    payload: &NewWebhookPayload,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO webhook_payloads (transaction_id, source, headers, body)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (transaction_id) DO NOTHING
+        "#,
+    )
+    .bind(transaction_id)
+    .bind(&payload.source)
+    .bind(&payload.headers)
+    .bind(&payload.body)
+    .execute(&mut **db_tx)
+    .await?;
+    Ok(())
+}
+
+/// Returns the raw payload stored when `transaction_id` was ingested, or
+/// `None` for transactions created before payloads were captured or through
+/// a path that does not record them.
+pub async fn get_webhook_payload(
+    pool: &PgPool,
+    transaction_id: Uuid,
+) -> Result<Option<StoredWebhookPayload>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM webhook_payloads WHERE transaction_id = $1",
+        sqlx::query_as::<_, StoredWebhookPayload>(
+            "SELECT transaction_id, source, headers, body, received_at \
+             FROM webhook_payloads WHERE transaction_id = $1",
+        )
+        .bind(transaction_id)
+        .fetch_optional(pool),
+    )
+    .await
+}
+
 async fn audit_transaction_creation(
     db_tx: &mut SqlxTransaction<'_, Postgres>,
     result: &Transaction,
@@ -564,17 +634,83 @@ async fn audit_transaction_creation(
     .await
 }
 
+/// Fetches transaction `id`; a soft-deleted one is reported as `RowNotFound`.
 pub async fn get_transaction(pool: &PgPool, id: Uuid) -> Result<Transaction> {
     with_timeout(
         QueryTier::Read,
-        "SELECT * FROM transactions WHERE id = $1",
-        sqlx::query_as::<_, Transaction>("SELECT * FROM transactions WHERE id = $1")
-            .bind(id)
-            .fetch_one(pool),
+        "SELECT * FROM transactions WHERE id = $1 AND deleted_at IS NULL",
+        sqlx::query_as::<_, Transaction>(
+            "SELECT * FROM transactions WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_one(pool),
+    )
+    .await
+}
+
+/// Like [`get_transaction`], or [`get_transaction_for_tenant`] when
+/// `tenant_id` is given, but also returns a soft-deleted transaction.
+pub async fn get_transaction_including_deleted(
+    pool: &PgPool,
+    tenant_id: Option<Uuid>,
+    id: Uuid,
+) -> Result<Transaction> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM transactions WHERE id = $1 [including deleted]",
+        sqlx::query_as::<_, Transaction>(
+            "SELECT * FROM transactions WHERE id = $1 AND ($2::uuid IS NULL OR tenant_id = $2)",
+        )
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_one(pool),
     )
     .await
 }
 
+/// Soft-deletes transaction `id`: sets `deleted_at` and records a `deleted`
+/// audit entry holding the row as it was, in one database transaction.
+/// Returns `None` when the transaction does not exist or is already deleted.
+pub async fn soft_delete_transaction(
+    pool: &PgPool,
+    id: Uuid,
+    actor: &str,
+) -> Result<Option<Transaction>> {
+    let mut db_tx = pool.begin().await?;
+
+    let deleted = sqlx::query_as::<_, Transaction>(
+        "UPDATE transactions SET deleted_at = NOW(), updated_at = NOW() \
+         WHERE id = $1 AND deleted_at IS NULL RETURNING *",
+    )
+    .bind(id)
+    .fetch_optional(&mut *db_tx)
+    .await?;
+
+    if let Some(tx) = &deleted {
+        AuditLog::log_deletion(
+            &mut db_tx,
+            tx.id,
+            ENTITY_TRANSACTION,
+            json!({
+                "stellar_account": tx.stellar_account,
+                "amount": tx.amount.to_string(),
+                "asset_code": tx.asset_code,
+                "status": tx.status,
+                "anchor_transaction_id": tx.anchor_transaction_id,
+                "deleted_at": tx.deleted_at,
+            }),
+            actor,
+        )
+        .await?;
+    }
+
+    db_tx.commit().await?;
+    if let Some(tx) = &deleted {
+        invalidate_transaction_caches(&[&tx.asset_code]).await;
+    }
+    Ok(deleted)
+}
+
 pub async fn list_transactions(
     pool: &PgPool,
     limit: i64,
@@ -588,7 +724,7 @@ pub async fn list_transactions(
             if let Some((ts, id)) = cursor {
                 if !backward {
                     let q = sqlx::query_as::<_, Transaction>(
-                        "SELECT * FROM transactions WHERE (created_at, id) < ($1, $2) ORDER BY created_at DESC, id DESC LIMIT $3",
+                        "SELECT * FROM transactions WHERE deleted_at IS NULL AND (created_at, id) < ($1, $2) ORDER BY created_at DESC, id DESC LIMIT $3",
                     )
                     .bind(ts)
                     .bind(id)
@@ -598,7 +734,7 @@ pub async fn list_transactions(
                     Ok(q)
                 } else {
                     let mut rows = sqlx::query_as::<_, Transaction>(
-                        "SELECT * FROM transactions WHERE (created_at, id) > ($1, $2) ORDER BY created_at ASC, id ASC LIMIT $3",
+                        "SELECT * FROM transactions WHERE deleted_at IS NULL AND (created_at, id) > ($1, $2) ORDER BY created_at ASC, id ASC LIMIT $3",
                     )
                     .bind(ts)
                     .bind(id)
@@ -610,7 +746,7 @@ pub async fn list_transactions(
                 }
             } else if !backward {
                 let q = sqlx::query_as::<_, Transaction>(
-                    "SELECT * FROM transactions ORDER BY created_at DESC, id DESC LIMIT $1",
+                    "SELECT * FROM transactions WHERE deleted_at IS NULL ORDER BY created_at DESC, id DESC LIMIT $1",
                 )
                 .bind(limit)
                 .fetch_all(pool)
@@ -618,7 +754,7 @@ pub async fn list_transactions(
                 Ok(q)
             } else {
                 let mut rows = sqlx::query_as::<_, Transaction>(
-                    "SELECT * FROM transactions ORDER BY created_at ASC, id ASC LIMIT $1",
+                    "SELECT * FROM transactions WHERE deleted_at IS NULL ORDER BY created_at ASC, id ASC LIMIT $1",
                 )
                 .bind(limit)
                 .fetch_all(pool)
@@ -638,14 +774,82 @@ pub async fn list_transactions_filtered(
     backward: bool,
     from_date: Option<DateTime<Utc>>,
     to_date: Option<DateTime<Utc>>,
+) -> Result<Vec<Transaction>> {
+    list_transactions_scoped(pool, None, limit, cursor, backward, from_date, to_date).await
+}
+
+/// Fetches transaction `id` only if it belongs to `tenant_id`. A transaction
+/// of another tenant (or of none) is reported as `RowNotFound`, exactly like
+/// a missing one, so callers cannot probe for other tenants' ids.
+pub async fn get_transaction_for_tenant(
+    pool: &PgPool,
+    tenant_id: Uuid,
+    id: Uuid,
+) -> Result<Transaction> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM transactions WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL",
+        sqlx::query_as::<_, Transaction>(
+            "SELECT * FROM transactions WHERE id = $1 AND tenant_id = $2 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_one(pool),
+    )
+    .await
+}
+
+/// [`list_transactions_filtered`] restricted to the transactions of
+/// `tenant_id`. The nil tenant, which tenantless connections are scoped to,
+/// selects the transactions that belong to no tenant.
+pub async fn list_transactions_for_tenant(
+    pool: &PgPool,
+    tenant_id: Uuid,
+    limit: i64,
+    cursor: Option<(DateTime<Utc>, Uuid)>,
+    backward: bool,
+    from_date: Option<DateTime<Utc>>,
+    to_date: Option<DateTime<Utc>>,
+) -> Result<Vec<Transaction>> {
+    list_transactions_scoped(
+        pool,
+        Some(tenant_id),
+        limit,
+        cursor,
+        backward,
+        from_date,
+        to_date,
+    )
+    .await
+}
+
+async fn list_transactions_scoped(
+    pool: &PgPool,
+    tenant_id: Option<Uuid>,
+    limit: i64,
+    cursor: Option<(DateTime<Utc>, Uuid)>,
+    backward: bool,
+    from_date: Option<DateTime<Utc>>,
+    to_date: Option<DateTime<Utc>>,
 ) -> Result<Vec<Transaction>> {
     with_timeout(
         QueryTier::Read,
         "SELECT * FROM transactions [filtered cursor-paginated]",
         async {
-            let mut conditions: Vec<String> = Vec::new();
+            let mut conditions: Vec<String> = vec!["deleted_at IS NULL".to_string()];
             let mut bind_idx = 1i32;
 
+            match tenant_id {
+                Some(tenant_id) if tenant_id.is_nil() => {
+                    conditions.push("tenant_id IS NULL".to_string());
+                }
+                Some(_) => {
+                    conditions.push(format!("tenant_id = ${}", bind_idx));
+                    bind_idx += 1;
+                }
+                None => {}
+            }
+
             if cursor.is_some() {
                 if !backward {
                     conditions.push(format!(
@@ -672,11 +876,7 @@ pub async fn list_transactions_filtered(
                 bind_idx += 1;
             }
 
-            let where_clause = if conditions.is_empty() {
-                String::new()
-            } else {
-                format!("WHERE {}", conditions.join(" AND "))
-            };
+            let where_clause = format!("WHERE {}", conditions.join(" AND "));
 
             let order = if !backward {
                 "ORDER BY created_at DESC, id DESC"
@@ -691,6 +891,9 @@ pub async fn list_transactions_filtered(
 
             let mut q = sqlx::query_as::<_, Transaction>(&sql);
 
+            if let Some(tenant_id) = tenant_id.filter(|id| !id.is_nil()) {
+                q = q.bind(tenant_id);
+            }
             if let Some((ts, id)) = cursor {
                 q = q.bind(ts).bind(id);
             }
@@ -712,6 +915,8 @@ pub async fn list_transactions_filtered(
     .await
 }
 
+/// Locks the completed, unsettled transactions in `asset_code` up to
+/// `end_time`, across every issuer of that code, ordered by issuer.
 pub async fn get_unsettled_transactions(
     executor: &mut SqlxTransaction<'_, Postgres>,
     asset_code: &str,
@@ -719,15 +924,17 @@ pub async fn get_unsettled_transactions(
 ) -> Result<Vec<Transaction>> {
     with_timeout(
         QueryTier::Read,
-        "SELECT * FROM transactions WHERE status = 'completed' AND settlement_id IS NULL FOR UPDATE",
+        "SELECT * FROM transactions WHERE status = 'completed' AND settlement_id IS NULL FOR UPDATE SKIP LOCKED",
         sqlx::query_as::<_, Transaction>(
             r#"
         SELECT * FROM transactions
         WHERE status = 'completed'
         AND settlement_id IS NULL
+        AND deleted_at IS NULL
         AND asset_code = $1
         AND updated_at <= $2
-        FOR UPDATE
+        ORDER BY asset_issuer NULLS FIRST, created_at
+        FOR UPDATE SKIP LOCKED
         "#,
         )
         .bind(asset_code)
@@ -737,38 +944,43 @@ pub async fn get_unsettled_transactions(
     .await
 }
 
+/// Assigns `settlement_id` to those of `tx_ids` that are still unsettled and
+/// returns their ids; rows settled in the meantime are left alone.
 pub async fn update_transactions_settlement(
     executor: &mut SqlxTransaction<'_, Postgres>,
     tx_ids: &[Uuid],
     settlement_id: Uuid,
-) -> Result<()> {
+) -> Result<Vec<Uuid>> {
     with_timeout(
         QueryTier::Write,
-        "UPDATE transactions SET settlement_id = $1 WHERE id = ANY($2)",
+        "UPDATE transactions SET settlement_id = $1 WHERE id = ANY($2) AND settlement_id IS NULL",
         async {
-            sqlx::query(
-                "UPDATE transactions SET settlement_id = $1, updated_at = NOW() WHERE id = ANY($2)",
+            let updated: Vec<Uuid> = sqlx::query_scalar(
+                "UPDATE transactions SET settlement_id = $1, updated_at = NOW() \
+                 WHERE id = ANY($2) AND settlement_id IS NULL RETURNING id",
             )
             .bind(settlement_id)
             .bind(tx_ids)
-            .execute(&mut **executor)
+            .fetch_all(&mut **executor)
             .await?;
 
             // Audit log: record settlement_id update for each transaction
-            for tx_id in tx_ids {
-                AuditLog::log_field_update(
-                    executor,
-                    *tx_id,
-                    ENTITY_TRANSACTION,
-                    "settlement_id",
-                    json!(null),
-                    json!(settlement_id.to_string()),
-                    "system",
-                )
-                .await?;
-            }
+            let entries: Vec<AuditLog> = updated
+                .iter()
+                .map(|tx_id| {
+                    AuditLog::new(
+                        *tx_id,
+                        ENTITY_TRANSACTION,
+                        "settlement_id_update",
+                        Some(json!({ "settlement_id": null })),
+                        Some(json!({ "settlement_id": settlement_id.to_string() })),
+                        "system",
+                    )
+                })
+                .collect();
+            AuditLog::log_many(executor, &entries).await?;
 
-            Ok(())
+            Ok(updated)
         },
     )
     .await
@@ -778,22 +990,326 @@ pub async fn update_transactions_settlement(
 // Cache invalidation helper
 // ---------------------------------------------------------------------------
 
-async fn invalidate_transaction_caches(asset_code: &str) {
-    if let Ok(redis_url) = std::env::var("REDIS_URL") {
-        if let Ok(cache) = crate::services::QueryCache::new(&redis_url).await {
-            let _ = cache.invalidate("query:status_counts").await;
-            let _ = cache.invalidate("query:daily_totals:*").await;
-            let _ = cache.invalidate("query:asset_stats").await;
-            let _ = cache
-                .invalidate_exact(&format!("query:asset_total:{}", asset_code))
-                .await;
-        }
+static INVALIDATION_CACHE: std::sync::RwLock<Option<crate::services::QueryCache>> =
+    std::sync::RwLock::new(None);
+
+/// Registers the application's [`crate::services::QueryCache`] as the target
+/// of transaction cache invalidation. Invalidating through the same instance
+/// the handlers read from also clears its in-memory layer; without a
+/// registered cache a fresh connection to `REDIS_URL` is used, which only
+/// clears Redis.
+pub fn register_invalidation_cache(cache: crate::services::QueryCache) {
+    *INVALIDATION_CACHE
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = Some(cache);
+}
+
+async fn invalidation_cache() -> Option<crate::services::QueryCache> {
+    let registered = INVALIDATION_CACHE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if registered.is_some() {
+        return registered;
+    }
+    let redis_url = std::env::var("REDIS_URL").ok()?;
+    crate::services::QueryCache::new(&redis_url).await.ok()
+}
+
+/// Drops every aggregate that counts transactions (status counts, daily
+/// totals, asset stats) plus the per-asset total of each of `asset_codes`.
+async fn invalidate_transaction_caches<S: AsRef<str>>(asset_codes: &[S]) {
+    let Some(cache) = invalidation_cache().await else {
+        return;
+    };
+    let _ = cache.invalidate("query:status_counts").await;
+    let _ = cache.invalidate("query:daily_totals:*").await;
+    let _ = cache.invalidate("query:asset_stats").await;
+    for asset_code in asset_codes {
+        let _ = cache
+            .invalidate_exact(&format!("query:asset_total:{}", asset_code.as_ref()))
+            .await;
     }
 }
 
 /// Public cache invalidation function for use by other modules
 pub async fn invalidate_caches_for_asset(asset_code: &str) {
-    invalidate_transaction_caches(asset_code).await;
+    invalidate_transaction_caches(&[asset_code]).await;
+}
+
+/// Drops the settlement aggregates (daily totals and per-asset counts).
+/// Call after a settlement is created or its status or total changes.
+pub async fn invalidate_settlement_caches() {
+    let Some(cache) = invalidation_cache().await else {
+        return;
+    };
+    let _ = cache.invalidate("query:settlement_daily_totals:*").await;
+    let _ = cache.invalidate("query:settlement_asset_counts").await;
+}
+
+/// Moves transaction `id` to `status`, then invalidates the aggregate caches
+/// and the transaction's asset total. Returns `None` when no row matched.
+///
+/// The move must be allowed by the transaction state machine
+/// ([`can_transition`](crate::validation::state_machine::can_transition));
+/// otherwise `AppError::BadRequest` is returned and nothing changes. The
+/// current row is locked while it is checked, so concurrent updates cannot
+/// slip an illegal transition in between. Operator overrides go through
+/// [`force_transaction_status`] instead.
+///
+/// The change is recorded as a `status_update` audit entry by `system`, so
+/// it shows up in [`get_transaction_events`].
+///
+/// Status changes should go through this helper (or call
+/// [`invalidate_caches_for_asset`] after committing) so cached aggregates
+/// never outlive the data they summarize.
+pub async fn update_transaction_status(
+    pool: &PgPool,
+    id: Uuid,
+    status: &str,
+) -> std::result::Result<Option<Transaction>, AppError> {
+    use crate::validation::state_machine::can_transition;
+
+    let to: TransactionStatus = status.parse().map_err(AppError::BadRequest)?;
+
+    let mut db_tx = pool.begin().await?;
+    let current: Option<String> =
+        sqlx::query_scalar("SELECT status FROM transactions WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *db_tx)
+            .await?;
+    let Some(current) = current else {
+        return Ok(None);
+    };
+
+    let from: TransactionStatus = current.parse().map_err(AppError::BadRequest)?;
+    if !can_transition(from, to) {
+        return Err(AppError::BadRequest(format!(
+            "Cannot transition transaction {id} from '{from}' to '{to}'"
+        )));
+    }
+
+    let updated = with_timeout(
+        QueryTier::Write,
+        "UPDATE transactions SET status = $2 ... RETURNING *",
+        sqlx::query_as::<_, Transaction>(
+            "UPDATE transactions SET status = $2, updated_at = NOW() WHERE id = $1 RETURNING *",
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_optional(&mut *db_tx),
+    )
+    .await?;
+    if updated.is_some() {
+        AuditLog::log_status_change(
+            &mut db_tx,
+            id,
+            ENTITY_TRANSACTION,
+            &current,
+            status,
+            "system",
+        )
+        .await?;
+    }
+    db_tx.commit().await?;
+
+    if let Some(tx) = &updated {
+        invalidate_caches_for_asset(&tx.asset_code).await;
+    }
+    Ok(updated)
+}
+
+/// Re-applies the fields of `payload`, decoded from the webhook that created
+/// transaction `id`, and moves the row back to `pending` so the processor
+/// picks it up again. The status change is checked against the state machine
+/// and audited like [`update_transaction_status`]. Returns `None` when no row
+/// matched.
+pub async fn reapply_webhook_payload(
+    pool: &PgPool,
+    id: Uuid,
+    payload: &Transaction,
+) -> std::result::Result<Option<Transaction>, AppError> {
+    use crate::validation::state_machine::can_transition;
+
+    let mut db_tx = pool.begin().await?;
+    let current: Option<(String, String)> = sqlx::query_as(
+        "SELECT status, asset_code FROM transactions WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *db_tx)
+    .await?;
+    let Some((current, previous_asset)) = current else {
+        return Ok(None);
+    };
+
+    let from: TransactionStatus = current.parse().map_err(AppError::BadRequest)?;
+    if !can_transition(from, TransactionStatus::Pending) {
+        return Err(AppError::BadRequest(format!(
+            "Cannot transition transaction {id} from '{from}' to 'pending'"
+        )));
+    }
+
+    let updated = with_timeout(
+        QueryTier::Write,
+        "UPDATE transactions SET <payload fields>, status = 'pending' ... RETURNING *",
+        sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions
+            SET stellar_account = $2, amount = $3, asset_code = $4, asset_issuer = $5,
+                anchor_transaction_id = $6, callback_type = $7, callback_status = $8,
+                memo = $9, memo_type = $10, metadata = $11,
+                status = 'pending', updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(&payload.stellar_account)
+        .bind(&payload.amount)
+        .bind(&payload.asset_code)
+        .bind(&payload.asset_issuer)
+        .bind(&payload.anchor_transaction_id)
+        .bind(&payload.callback_type)
+        .bind(&payload.callback_status)
+        .bind(&payload.memo)
+        .bind(&payload.memo_type)
+        .bind(&payload.metadata)
+        .fetch_optional(&mut *db_tx),
+    )
+    .await?;
+    if updated.is_some() {
+        AuditLog::log_status_change(
+            &mut db_tx,
+            id,
+            ENTITY_TRANSACTION,
+            &current,
+            "pending",
+            "system",
+        )
+        .await?;
+    }
+    db_tx.commit().await?;
+
+    if let Some(tx) = &updated {
+        invalidate_transaction_caches(&[&previous_asset, &tx.asset_code]).await;
+    }
+    Ok(updated)
+}
+
+/// Sets transaction `id` to `status` without consulting the state machine,
+/// recording the override in the audit log under `actor`. Returns `None`
+/// when no row matched.
+///
+/// Reserved for explicit operator actions such as force-complete; everything
+/// else should use [`update_transaction_status`].
+pub async fn force_transaction_status(
+    pool: &PgPool,
+    id: Uuid,
+    status: &str,
+    actor: &str,
+) -> Result<Option<Transaction>> {
+    let mut db_tx = pool.begin().await?;
+    let old_status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM transactions WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *db_tx)
+            .await?;
+    let Some(old_status) = old_status else {
+        return Ok(None);
+    };
+
+    let updated = override_status(
+        &mut db_tx,
+        id,
+        &old_status,
+        status,
+        actor,
+        json!({ "status": status }),
+    )
+    .await?;
+    db_tx.commit().await?;
+
+    invalidate_caches_for_asset(&updated.asset_code).await;
+    Ok(Some(updated))
+}
+
+/// Outcome of [`force_transaction_status_with_reason`].
+#[derive(Debug)]
+pub enum StatusOverride {
+    Applied(Box<Transaction>),
+    /// The transaction already had the requested status; nothing was written.
+    Unchanged,
+    NotFound,
+}
+
+/// Like [`force_transaction_status`], but records `reason` in the audit entry
+/// and leaves transactions that already have `status` untouched.
+pub async fn force_transaction_status_with_reason(
+    pool: &PgPool,
+    id: Uuid,
+    status: &str,
+    actor: &str,
+    reason: &str,
+) -> Result<StatusOverride> {
+    let mut db_tx = pool.begin().await?;
+    let old_status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM transactions WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *db_tx)
+            .await?;
+    let Some(old_status) = old_status else {
+        return Ok(StatusOverride::NotFound);
+    };
+    if old_status == status {
+        return Ok(StatusOverride::Unchanged);
+    }
+
+    let updated = override_status(
+        &mut db_tx,
+        id,
+        &old_status,
+        status,
+        actor,
+        json!({ "status": status, "reason": reason }),
+    )
+    .await?;
+    db_tx.commit().await?;
+
+    invalidate_caches_for_asset(&updated.asset_code).await;
+    Ok(StatusOverride::Applied(Box::new(updated)))
+}
+
+async fn override_status(
+    db_tx: &mut SqlxTransaction<'_, Postgres>,
+    id: Uuid,
+    old_status: &str,
+    status: &str,
+    actor: &str,
+    new_val: serde_json::Value,
+) -> Result<Transaction> {
+    let updated = with_timeout(
+        QueryTier::Write,
+        "UPDATE transactions SET status = $2 ... RETURNING *",
+        sqlx::query_as::<_, Transaction>(
+            "UPDATE transactions SET status = $2, updated_at = NOW() WHERE id = $1 RETURNING *",
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(&mut **db_tx),
+    )
+    .await?;
+
+    AuditLog::log(
+        db_tx,
+        id,
+        ENTITY_TRANSACTION,
+        "status_override",
+        Some(json!({ "status": old_status })),
+        Some(new_val),
+        actor,
+    )
+    .await?;
+    Ok(updated)
 }
 
 // ---------------------------------------------------------------------------
@@ -810,8 +1326,9 @@ pub async fn insert_settlement(
         sqlx::query_as::<_, Settlement>(
             r#"
         INSERT INTO settlements (
-            id, asset_code, total_amount, tx_count, period_start, period_end, status, created_at, updated_at
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            id, asset_code, total_amount, tx_count, period_start, period_end, status, created_at, updated_at,
+            asset_issuer, target_account
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
         RETURNING *
         "#,
         )
@@ -824,11 +1341,91 @@ pub async fn insert_settlement(
         .bind(&settlement.status)
         .bind(settlement.created_at)
         .bind(settlement.updated_at)
+        .bind(&settlement.asset_issuer)
+        .bind(&settlement.target_account)
         .fetch_one(&mut **executor),
     )
     .await
 }
 
+pub async fn get_settlement_config(
+    pool: &PgPool,
+    asset_code: &str,
+) -> Result<Option<SettlementConfig>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM settlement_config WHERE asset_code = $1",
+        sqlx::query_as::<_, SettlementConfig>(
+            "SELECT * FROM settlement_config WHERE asset_code = $1",
+        )
+        .bind(asset_code)
+        .fetch_optional(pool),
+    )
+    .await
+}
+
+pub async fn list_settlement_configs(pool: &PgPool) -> Result<Vec<SettlementConfig>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM settlement_config ORDER BY asset_code",
+        sqlx::query_as::<_, SettlementConfig>(
+            "SELECT * FROM settlement_config ORDER BY asset_code",
+        )
+        .fetch_all(pool),
+    )
+    .await
+}
+
+/// Creates or replaces the settlement settings of `asset_code`.
+pub async fn upsert_settlement_config(
+    pool: &PgPool,
+    asset_code: &str,
+    min_amount: Option<&BigDecimal>,
+    min_tx_count: Option<i32>,
+    schedule: &str,
+    target_account: Option<&str>,
+) -> Result<SettlementConfig> {
+    with_timeout(
+        QueryTier::Write,
+        "INSERT INTO settlement_config ... ON CONFLICT (asset_code) DO UPDATE",
+        sqlx::query_as::<_, SettlementConfig>(
+            r#"
+            INSERT INTO settlement_config (asset_code, min_amount, min_tx_count, schedule, target_account)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (asset_code) DO UPDATE SET
+                min_amount = EXCLUDED.min_amount,
+                min_tx_count = EXCLUDED.min_tx_count,
+                schedule = EXCLUDED.schedule,
+                target_account = EXCLUDED.target_account,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(asset_code)
+        .bind(min_amount)
+        .bind(min_tx_count)
+        .bind(schedule)
+        .bind(target_account)
+        .fetch_one(pool),
+    )
+    .await
+}
+
+/// When `asset_code` was last settled, if ever.
+pub async fn get_last_settlement_at(
+    pool: &PgPool,
+    asset_code: &str,
+) -> Result<Option<DateTime<Utc>>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT MAX(created_at) FROM settlements WHERE asset_code = $1",
+        sqlx::query_scalar("SELECT MAX(created_at) FROM settlements WHERE asset_code = $1")
+            .bind(asset_code)
+            .fetch_one(pool),
+    )
+    .await
+}
+
 pub async fn get_settlement(pool: &PgPool, id: Uuid) -> Result<Settlement> {
     with_timeout(
         QueryTier::Read,
@@ -840,61 +1437,148 @@ pub async fn get_settlement(pool: &PgPool, id: Uuid) -> Result<Settlement> {
     .await
 }
 
-pub async fn list_settlements(pool: &PgPool, limit: i64, offset: i64) -> Result<Vec<Settlement>> {
+pub async fn list_settlements(pool: &PgPool, limit: i64, offset: i64) -> Result<Vec<Settlement>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM settlements ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        sqlx::query_as::<_, Settlement>(
+            "SELECT * FROM settlements ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool),
+    )
+    .await
+}
+
+/// Optional filters for settlement listing. Date bounds apply to
+/// `created_at` and are inclusive.
+#[derive(Debug, Default, Clone)]
+pub struct SettlementFilter {
+    pub asset_code: Option<String>,
+    pub status: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl SettlementFilter {
+    fn push_where(&self, qb: &mut sqlx::QueryBuilder<'_, Postgres>) {
+        qb.push(" WHERE TRUE");
+        if let Some(asset_code) = &self.asset_code {
+            qb.push(" AND asset_code = ");
+            qb.push_bind(asset_code.clone());
+        }
+        if let Some(status) = &self.status {
+            qb.push(" AND status = ");
+            qb.push_bind(status.clone());
+        }
+        if let Some(from) = self.from {
+            qb.push(" AND created_at >= ");
+            qb.push_bind(from);
+        }
+        if let Some(to) = self.to {
+            qb.push(" AND created_at <= ");
+            qb.push_bind(to);
+        }
+    }
+}
+
+/// Cursor-based settlement listing used by the settlements handler.
+///
+/// Forward pages walk `(created_at, id)` newest first; backward pages walk
+/// towards newer rows and are returned in the same newest-first order.
+pub async fn list_settlements_cursor(
+    pool: &PgPool,
+    filter: &SettlementFilter,
+    limit: i64,
+    offset: i64,
+    cursor: Option<(DateTime<Utc>, Uuid)>,
+    backward: bool,
+) -> Result<Vec<Settlement>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT * FROM settlements [filtered, cursor-paginated]",
+        async {
+            let mut qb = sqlx::QueryBuilder::new("SELECT * FROM settlements");
+            filter.push_where(&mut qb);
+            if let Some((ts, id)) = cursor {
+                qb.push(if backward {
+                    " AND (created_at, id) > ("
+                } else {
+                    " AND (created_at, id) < ("
+                });
+                qb.push_bind(ts);
+                qb.push(", ");
+                qb.push_bind(id);
+                qb.push(")");
+            }
+            qb.push(if backward {
+                " ORDER BY created_at ASC, id ASC LIMIT "
+            } else {
+                " ORDER BY created_at DESC, id DESC LIMIT "
+            });
+            qb.push_bind(limit);
+            qb.push(" OFFSET ");
+            qb.push_bind(offset);
+
+            let mut rows = qb.build_query_as::<Settlement>().fetch_all(pool).await?;
+            if backward {
+                rows.reverse();
+            }
+            Ok(rows)
+        },
+    )
+    .await
+}
+
+/// Number of settlements matching `filter`, ignoring pagination.
+pub async fn count_settlements(pool: &PgPool, filter: &SettlementFilter) -> Result<i64> {
     with_timeout(
         QueryTier::Read,
-        "SELECT * FROM settlements ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-        sqlx::query_as::<_, Settlement>(
-            "SELECT * FROM settlements ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool),
+        "SELECT COUNT(*) FROM settlements [filtered]",
+        async {
+            let mut qb = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM settlements");
+            filter.push_where(&mut qb);
+            qb.build_query_scalar().fetch_one(pool).await
+        },
     )
     .await
 }
 
-/// Cursor-based settlement listing used by the settlements handler.
-pub async fn list_settlements_cursor(
+/// Cursor-paginated member transactions of a settlement, newest first.
+pub async fn list_settlement_transactions_cursor(
     pool: &PgPool,
+    settlement_id: Uuid,
     limit: i64,
     cursor: Option<(DateTime<Utc>, Uuid)>,
-    backward: bool,
-) -> Result<Vec<Settlement>> {
+) -> Result<Vec<Transaction>> {
     with_timeout(
         QueryTier::Read,
-        "SELECT * FROM settlements [cursor-paginated]",
+        "SELECT * FROM transactions WHERE settlement_id = $1 [cursor-paginated]",
         async {
-            if let Some((ts, id)) = cursor {
-                if !backward {
-                    sqlx::query_as::<_, Settlement>(
-                        "SELECT * FROM settlements WHERE (created_at, id) < ($1, $2) ORDER BY created_at DESC, id DESC LIMIT $3",
+            match cursor {
+                Some((ts, id)) => {
+                    sqlx::query_as::<_, Transaction>(
+                        "SELECT * FROM transactions WHERE settlement_id = $1 AND (created_at, id) < ($2, $3) \
+                         ORDER BY created_at DESC, id DESC LIMIT $4",
                     )
-                    .bind(ts).bind(id).bind(limit)
-                    .fetch_all(pool).await
-                } else {
-                    let mut rows = sqlx::query_as::<_, Settlement>(
-                        "SELECT * FROM settlements WHERE (created_at, id) > ($1, $2) ORDER BY created_at ASC, id ASC LIMIT $3",
+                    .bind(settlement_id)
+                    .bind(ts)
+                    .bind(id)
+                    .bind(limit)
+                    .fetch_all(pool)
+                    .await
+                }
+                None => {
+                    sqlx::query_as::<_, Transaction>(
+                        "SELECT * FROM transactions WHERE settlement_id = $1 \
+                         ORDER BY created_at DESC, id DESC LIMIT $2",
                     )
-                    .bind(ts).bind(id).bind(limit)
-                    .fetch_all(pool).await?;
-                    rows.reverse();
-                    Ok(rows)
+                    .bind(settlement_id)
+                    .bind(limit)
+                    .fetch_all(pool)
+                    .await
                 }
-            } else if !backward {
-                sqlx::query_as::<_, Settlement>(
-                    "SELECT * FROM settlements ORDER BY created_at DESC, id DESC LIMIT $1",
-                )
-                .bind(limit)
-                .fetch_all(pool).await
-            } else {
-                let mut rows = sqlx::query_as::<_, Settlement>(
-                    "SELECT * FROM settlements ORDER BY created_at ASC, id ASC LIMIT $1",
-                )
-                .bind(limit)
-                .fetch_all(pool).await?;
-                rows.reverse();
-                Ok(rows)
             }
         },
     )
@@ -902,10 +1586,14 @@ pub async fn list_settlements_cursor(
 }
 
 /// Update settlement status with reason; returns the updated settlement.
+///
+/// The update only applies while the row is still at `expected_version` and
+/// bumps the version. A concurrent writer that got there first leaves this
+/// call with `RowNotFound`, which the service layer reports as a conflict.
 pub async fn update_settlement_status(
     pool: &PgPool,
     id: Uuid,
-    expected_from_status: &str,
+    expected_version: i32,
     new_status: &str,
     reason: Option<&str>,
     new_total: Option<&sqlx::types::BigDecimal>,
@@ -913,20 +1601,12 @@ pub async fn update_settlement_status(
 ) -> Result<Settlement> {
     let mut db_tx = pool.begin().await?;
 
-    let current =
-        sqlx::query_as::<_, Settlement>("SELECT * FROM settlements WHERE id = $1 FOR UPDATE")
-            .bind(id)
-            .fetch_optional(&mut *db_tx)
-            .await?
-            .ok_or(sqlx::Error::RowNotFound)?;
-
-    // Validate transition against the locked row (not the pre-lock read).
-    // This catches concurrent modifications: if the status changed or no-op transition is detected,
-    // mark as stale by returning RowNotFound (which will be converted to StaleTransition in service layer).
-    if current.status != expected_from_status || current.status == new_status {
-        db_tx.rollback().await?;
-        return Err(sqlx::Error::RowNotFound);
-    }
+    let current = sqlx::query_as::<_, Settlement>("SELECT * FROM settlements WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *db_tx)
+        .await?
+        .filter(|s| s.version == expected_version)
+        .ok_or(sqlx::Error::RowNotFound)?;
 
     // Preserve original amount on first adjustment
     let original_total = if current.original_total_amount.is_none() && new_total.is_some() {
@@ -944,8 +1624,9 @@ pub async fn update_settlement_status(
             original_total_amount = COALESCE($4, original_total_amount),
             reviewed_by = $5,
             reviewed_at = NOW(),
-            updated_at = NOW()
-        WHERE id = $6 AND status = $7
+            updated_at = NOW(),
+            version = version + 1
+        WHERE id = $6 AND version = $7
         RETURNING *
         "#,
     )
@@ -955,18 +1636,35 @@ pub async fn update_settlement_status(
     .bind(original_total)
     .bind(actor)
     .bind(id)
-    .bind(expected_from_status)
+    .bind(expected_version)
     .fetch_optional(&mut *db_tx)
     .await?
     .ok_or(sqlx::Error::RowNotFound)?;
 
     // If voided, release transactions back to unsettled
     if new_status == "voided" {
-        sqlx::query(
-            "UPDATE transactions SET settlement_id = NULL, updated_at = NOW() WHERE settlement_id = $1",
+        let released: Vec<Uuid> = sqlx::query_scalar(
+            "UPDATE transactions SET settlement_id = NULL, updated_at = NOW() WHERE settlement_id = $1 RETURNING id",
         )
         .bind(id)
-        .execute(&mut *db_tx)
+        .fetch_all(&mut *db_tx)
+        .await?;
+
+        crate::db::audit::AuditLog::log(
+            &mut db_tx,
+            id,
+            crate::db::audit::ENTITY_SETTLEMENT,
+            "settlement_reversed",
+            None,
+            Some(serde_json::json!({
+                "asset_code": updated.asset_code,
+                "total_amount": updated.total_amount.to_string(),
+                "tx_count": released.len(),
+                "transaction_ids": released,
+                "reason": reason,
+            })),
+            actor,
+        )
         .await?;
     }
 
@@ -1009,18 +1707,74 @@ pub async fn get_unique_assets_to_settle(pool: &PgPool) -> Result<Vec<String>> {
 // Transaction Search
 // ---------------------------------------------------------------------------
 
+/// Ordering of search results; always descending with `id` as tie-breaker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    #[default]
+    CreatedAt,
+    Amount,
+}
+
+impl SearchSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SearchSort::CreatedAt => "created_at",
+            SearchSort::Amount => "amount",
+        }
+    }
+}
+
+/// Keyset position after the last row of a search page, keyed by the
+/// column the results are sorted on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchCursor {
+    CreatedAt(DateTime<Utc>, Uuid),
+    Amount(BigDecimal, Uuid),
+}
+
+impl SearchCursor {
+    /// The position of `tx` under `sort`.
+    pub fn after(sort: SearchSort, tx: &Transaction) -> Self {
+        match sort {
+            SearchSort::CreatedAt => SearchCursor::CreatedAt(tx.created_at, tx.id),
+            SearchSort::Amount => SearchCursor::Amount(tx.amount.clone(), tx.id),
+        }
+    }
+
+    pub fn sort(&self) -> SearchSort {
+        match self {
+            SearchCursor::CreatedAt(..) => SearchSort::CreatedAt,
+            SearchCursor::Amount(..) => SearchSort::Amount,
+        }
+    }
+}
+
+/// Transaction filters shared by search and export, parsed from
+/// `handlers::search::TransactionFilterParams`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionFilters {
+    /// Matches any of these; empty matches every status.
+    pub statuses: Vec<String>,
+    pub asset_code: Option<String>,
+    pub stellar_account: Option<String>,
+    pub min_amount: Option<BigDecimal>,
+    pub max_amount: Option<BigDecimal>,
+    pub from: Option<DateTime<Utc>>,
+    /// Inclusive.
+    pub to: Option<DateTime<Utc>>,
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn search_transactions(
     pool: &PgPool,
-    status: Option<&str>,
-    asset_code: Option<&str>,
-    min_amount: Option<&BigDecimal>,
-    max_amount: Option<&BigDecimal>,
-    from_date: Option<DateTime<Utc>>,
-    to_date: Option<DateTime<Utc>>,
-    stellar_account: Option<&str>,
+    tenant_id: Option<Uuid>,
+    filters: &TransactionFilters,
+    metadata_contains: Option<&serde_json::Value>,
+    sort: SearchSort,
     limit: i64,
-    cursor: Option<(DateTime<Utc>, Uuid)>,
+    cursor: Option<&SearchCursor>,
+    include_deleted: bool,
 ) -> Result<(i64, Vec<Transaction>)> {
     with_timeout(
         QueryTier::Read,
@@ -1030,49 +1784,87 @@ pub async fn search_transactions(
             let mut conditions = Vec::new();
             let mut param_count = 1;
 
-            if status.is_some() {
-                conditions.push(format!("status = ${}", param_count));
-                param_count += 1;
+            if !include_deleted {
+                conditions.push("deleted_at IS NULL".to_string());
             }
 
-            if asset_code.is_some() {
-                conditions.push(format!("asset_code = ${}", param_count));
+            if tenant_id.is_some() {
+                conditions.push(format!("tenant_id = ${}", param_count));
                 param_count += 1;
             }
 
-            if min_amount.is_some() {
-                conditions.push(format!("amount >= ${}", param_count));
+            if !filters.statuses.is_empty() {
+                conditions.push(format!("status = ANY(${})", param_count));
                 param_count += 1;
             }
 
-            if max_amount.is_some() {
-                conditions.push(format!("amount <= ${}", param_count));
+            if filters.asset_code.is_some() {
+                conditions.push(format!("asset_code = ${}", param_count));
                 param_count += 1;
             }
 
-            if from_date.is_some() {
+            // Amounts are compared as NUMERIC against BigDecimal binds so money
+            // never round-trips through floating point; both bounds are inclusive.
+            match (filters.min_amount.is_some(), filters.max_amount.is_some()) {
+                (true, true) => {
+                    conditions.push(format!(
+                        "amount::numeric BETWEEN ${}::numeric AND ${}::numeric",
+                        param_count,
+                        param_count + 1
+                    ));
+                    param_count += 2;
+                }
+                (true, false) => {
+                    conditions.push(format!("amount::numeric >= ${}::numeric", param_count));
+                    param_count += 1;
+                }
+                (false, true) => {
+                    conditions.push(format!("amount::numeric <= ${}::numeric", param_count));
+                    param_count += 1;
+                }
+                (false, false) => {}
+            }
+
+            if filters.from.is_some() {
                 conditions.push(format!("created_at >= ${}", param_count));
                 param_count += 1;
             }
 
-            if to_date.is_some() {
+            if filters.to.is_some() {
                 conditions.push(format!("created_at <= ${}", param_count));
                 param_count += 1;
             }
 
-            if stellar_account.is_some() {
+            if filters.stellar_account.is_some() {
                 conditions.push(format!("stellar_account = ${}", param_count));
                 param_count += 1;
             }
 
+            // Served by idx_transactions_metadata_gin (jsonb_path_ops).
+            if metadata_contains.is_some() {
+                conditions.push(format!("metadata @> ${}", param_count));
+                param_count += 1;
+            }
+
             // Add cursor condition
-            if cursor.is_some() {
-                conditions.push(format!(
-                    "(created_at, id) < (${}, ${})",
-                    param_count,
-                    param_count + 1
-                ));
-                param_count += 2;
+            match cursor {
+                Some(SearchCursor::CreatedAt(..)) => {
+                    conditions.push(format!(
+                        "(created_at, id) < (${}, ${})",
+                        param_count,
+                        param_count + 1
+                    ));
+                    param_count += 2;
+                }
+                Some(SearchCursor::Amount(..)) => {
+                    conditions.push(format!(
+                        "(amount::numeric, id) < (${}::numeric, ${})",
+                        param_count,
+                        param_count + 1
+                    ));
+                    param_count += 2;
+                }
+                None => {}
             }
 
             let where_clause = if conditions.is_empty() {
@@ -1088,40 +1880,57 @@ pub async fn search_transactions(
             );
 
             // Build data query with pagination.
-            // ORDER BY is aligned with idx_transactions_status_asset_created
-            // (status, asset_code, created_at DESC) so the planner can use an
-            // index scan instead of a sequential scan + sort.
+            // The created_at ORDER BY is aligned with
+            // idx_transactions_status_asset_created (status, asset_code,
+            // created_at DESC) so the planner can use an index scan instead of
+            // a sequential scan + sort.
+            let order_by = match sort {
+                SearchSort::CreatedAt => "created_at DESC, id DESC",
+                SearchSort::Amount => "amount::numeric DESC, id DESC",
+            };
             let data_query = format!(
-                "SELECT * FROM transactions {} ORDER BY created_at DESC, id DESC LIMIT ${}",
-                where_clause, param_count
+                "SELECT * FROM transactions {} ORDER BY {} LIMIT ${}",
+                where_clause, order_by, param_count
             );
 
             // Execute count query
             let mut count_query_builder = sqlx::query(&count_query);
 
-            if let Some(s) = status {
-                count_query_builder = count_query_builder.bind(s);
+            if let Some(tenant_id) = tenant_id {
+                count_query_builder = count_query_builder.bind(tenant_id);
             }
-            if let Some(a) = asset_code {
+            if !filters.statuses.is_empty() {
+                count_query_builder = count_query_builder.bind(&filters.statuses);
+            }
+            if let Some(a) = &filters.asset_code {
                 count_query_builder = count_query_builder.bind(a);
             }
-            if let Some(min) = min_amount {
+            if let Some(min) = &filters.min_amount {
                 count_query_builder = count_query_builder.bind(min);
             }
-            if let Some(max) = max_amount {
+            if let Some(max) = &filters.max_amount {
                 count_query_builder = count_query_builder.bind(max);
             }
-            if let Some(from) = from_date {
+            if let Some(from) = filters.from {
                 count_query_builder = count_query_builder.bind(from);
             }
-            if let Some(to) = to_date {
+            if let Some(to) = filters.to {
                 count_query_builder = count_query_builder.bind(to);
             }
-            if let Some(acc) = stellar_account {
+            if let Some(acc) = &filters.stellar_account {
                 count_query_builder = count_query_builder.bind(acc);
             }
-            if let Some((ts, id)) = cursor {
-                count_query_builder = count_query_builder.bind(ts).bind(id);
+            if let Some(metadata) = metadata_contains {
+                count_query_builder = count_query_builder.bind(metadata);
+            }
+            match cursor {
+                Some(SearchCursor::CreatedAt(ts, id)) => {
+                    count_query_builder = count_query_builder.bind(ts).bind(id);
+                }
+                Some(SearchCursor::Amount(amount, id)) => {
+                    count_query_builder = count_query_builder.bind(amount).bind(id);
+                }
+                None => {}
             }
 
             let count_row = count_query_builder.fetch_one(pool).await?;
@@ -1130,29 +1939,41 @@ pub async fn search_transactions(
             // Execute data query
             let mut data_query_builder = sqlx::query_as::<_, Transaction>(&data_query);
 
-            if let Some(s) = status {
-                data_query_builder = data_query_builder.bind(s);
+            if let Some(tenant_id) = tenant_id {
+                data_query_builder = data_query_builder.bind(tenant_id);
+            }
+            if !filters.statuses.is_empty() {
+                data_query_builder = data_query_builder.bind(&filters.statuses);
             }
-            if let Some(a) = asset_code {
+            if let Some(a) = &filters.asset_code {
                 data_query_builder = data_query_builder.bind(a);
             }
-            if let Some(min) = min_amount {
+            if let Some(min) = &filters.min_amount {
                 data_query_builder = data_query_builder.bind(min);
             }
-            if let Some(max) = max_amount {
+            if let Some(max) = &filters.max_amount {
                 data_query_builder = data_query_builder.bind(max);
             }
-            if let Some(from) = from_date {
+            if let Some(from) = filters.from {
                 data_query_builder = data_query_builder.bind(from);
             }
-            if let Some(to) = to_date {
+            if let Some(to) = filters.to {
                 data_query_builder = data_query_builder.bind(to);
             }
-            if let Some(acc) = stellar_account {
+            if let Some(acc) = &filters.stellar_account {
                 data_query_builder = data_query_builder.bind(acc);
             }
-            if let Some((ts, id)) = cursor {
-                data_query_builder = data_query_builder.bind(ts).bind(id);
+            if let Some(metadata) = metadata_contains {
+                data_query_builder = data_query_builder.bind(metadata);
+            }
+            match cursor {
+                Some(SearchCursor::CreatedAt(ts, id)) => {
+                    data_query_builder = data_query_builder.bind(ts).bind(id);
+                }
+                Some(SearchCursor::Amount(amount, id)) => {
+                    data_query_builder = data_query_builder.bind(amount).bind(id);
+                }
+                None => {}
             }
             data_query_builder = data_query_builder.bind(limit);
 
@@ -1232,6 +2053,8 @@ pub struct AuditSearchParams<'a> {
     pub from_date: Option<DateTime<Utc>>,
     pub to_date: Option<DateTime<Utc>>,
     pub entity_type: Option<&'a str>,
+    /// Restricts the search to a single entity's history.
+    pub entity_id: Option<Uuid>,
     pub limit: i64,
     /// Cursor: (timestamp, id) of the last seen row for keyset pagination.
     pub cursor: Option<(DateTime<Utc>, Uuid)>,
@@ -1284,6 +2107,10 @@ pub async fn search_audit_logs(
                 conditions.push(format!("entity_type = ${p}"));
                 p += 1;
             }
+            if params.entity_id.is_some() {
+                conditions.push(format!("entity_id = ${p}"));
+                p += 1;
+            }
             if params.cursor.is_some() {
                 conditions.push(format!("(timestamp, id) < (${p}, ${})", p + 1));
                 p += 2;
@@ -1314,6 +2141,9 @@ pub async fn search_audit_logs(
                     if let Some(v) = params.entity_type {
                         q = q.bind(v);
                     }
+                    if let Some(v) = params.entity_id {
+                        q = q.bind(v);
+                    }
                     if let Some((ts, id)) = params.cursor {
                         q = q.bind(ts).bind(id);
                     }
@@ -1359,6 +2189,9 @@ pub async fn search_audit_logs(
             if let Some(v) = params.entity_type {
                 count_q = count_q.bind(v);
             }
+            if let Some(v) = params.entity_id {
+                count_q = count_q.bind(v);
+            }
 
             let total: i64 = count_q.fetch_one(pool).await?.try_get(0)?;
 
@@ -1395,6 +2228,56 @@ pub async fn search_audit_logs(
 
 // --- Audit Log Queries ---
 
+/// One entry of a transaction's timeline.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TransactionEvent {
+    /// Audit action, e.g. `created`, `status_update` or `status_override`.
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub timestamp: DateTime<Utc>,
+    /// `None` for the creation event of a transaction inserted without an
+    /// audit entry.
+    pub actor: Option<String>,
+    /// `{"old": ..., "new": ...}` from the audit entry, omitting absent sides.
+    pub detail: Option<serde_json::Value>,
+}
+
+/// Timeline of transaction `id`, oldest first: its audit entries, led by a
+/// `created` event taken from the row itself when creation was not audited.
+/// Empty when the transaction does not exist.
+pub async fn get_transaction_events(pool: &PgPool, id: Uuid) -> Result<Vec<TransactionEvent>> {
+    with_timeout(
+        QueryTier::Read,
+        "SELECT transaction created_at UNION audit_logs ORDER BY timestamp",
+        sqlx::query_as::<_, TransactionEvent>(
+            r#"
+            SELECT event_type, timestamp, actor, detail
+            FROM (
+                SELECT 'created' AS event_type, created_at AS timestamp,
+                       NULL::text AS actor, NULL::jsonb AS detail, 0 AS ord, NULL::uuid AS id
+                FROM transactions
+                WHERE id = $1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM audit_logs
+                      WHERE entity_id = $1 AND entity_type = $2 AND action = 'created'
+                  )
+                UNION ALL
+                SELECT action, timestamp, actor,
+                       NULLIF(jsonb_strip_nulls(jsonb_build_object('old', old_val, 'new', new_val)), '{}'),
+                       CASE WHEN action = 'created' THEN 0 ELSE 1 END, id
+                FROM audit_logs
+                WHERE entity_id = $1 AND entity_type = $2
+            ) events
+            ORDER BY timestamp, ord, id
+            "#,
+        )
+        .bind(id)
+        .bind(ENTITY_TRANSACTION)
+        .fetch_all(pool),
+    )
+    .await
+}
+
 /// Retrieve audit logs for a specific entity using cursor-based pagination on (timestamp, id).
 pub async fn get_audit_logs(
     pool: &PgPool,
@@ -1464,6 +2347,63 @@ pub async fn get_audit_logs(
 
 // --- Bulk Status Update ---
 
+/// Sets every transaction in `ids` to `new_status` with one `UPDATE` and
+/// records one `status_update` audit entry per changed row in a single
+/// insert, both inside `executor`. Returns the number of rows updated.
+///
+/// Transitions are not checked against the state machine, and caches are
+/// left to the caller to invalidate once the transaction commits.
+pub async fn bulk_update_status(
+    executor: &mut SqlxTransaction<'_, Postgres>,
+    ids: &[Uuid],
+    new_status: &str,
+    reason: Option<&str>,
+    actor: &str,
+) -> Result<u64> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let rows: Vec<(Uuid, String)> = with_timeout(
+        QueryTier::Write,
+        "UPDATE transactions SET status = $2 WHERE id = ANY($1) RETURNING old status",
+        sqlx::query_as(
+            r#"
+            UPDATE transactions t
+            SET status = $2, updated_at = NOW()
+            FROM (SELECT id, status FROM transactions WHERE id = ANY($1) FOR UPDATE) old
+            WHERE t.id = old.id
+            RETURNING t.id, old.status
+            "#,
+        )
+        .bind(ids)
+        .bind(new_status)
+        .fetch_all(&mut **executor),
+    )
+    .await?;
+
+    let mut new_val = json!({ "status": new_status });
+    if let Some(r) = reason {
+        new_val["reason"] = json!(r);
+    }
+    let entries: Vec<AuditLog> = rows
+        .iter()
+        .map(|(id, old_status)| {
+            AuditLog::new(
+                *id,
+                ENTITY_TRANSACTION,
+                "status_update",
+                Some(json!({ "status": old_status })),
+                Some(new_val.clone()),
+                actor,
+            )
+        })
+        .collect();
+    AuditLog::log_many(executor, &entries).await?;
+
+    Ok(rows.len() as u64)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BulkUpdateError {
     pub transaction_id: Uuid,
@@ -1478,8 +2418,7 @@ pub struct BulkUpdateResult {
 }
 
 /// Bulk update transaction statuses, validating each transition individually.
-/// Uses a single UPDATE ... WHERE id = ANY($1) for the valid subset, then
-/// audit-logs each successful update within the same transaction.
+/// The valid subset is written with [`bulk_update_status`].
 pub async fn bulk_update_transaction_status(
     pool: &PgPool,
     transaction_ids: &[Uuid],
@@ -1490,19 +2429,26 @@ pub async fn bulk_update_transaction_status(
     use crate::validation::state_machine::validate_status_transition;
 
     // Fetch current statuses for all requested IDs in one query
-    let rows = sqlx::query("SELECT id, status FROM transactions WHERE id = ANY($1)")
+    let rows = sqlx::query("SELECT id, status, asset_code FROM transactions WHERE id = ANY($1)")
         .bind(transaction_ids)
         .fetch_all(pool)
         .await?;
 
-    let current: std::collections::HashMap<Uuid, String> = rows
+    let current: std::collections::HashMap<Uuid, (String, String)> = rows
         .into_iter()
-        .map(|r| (r.get::<Uuid, _>("id"), r.get::<String, _>("status")))
+        .map(|r| {
+            (
+                r.get::<Uuid, _>("id"),
+                (
+                    r.get::<String, _>("status"),
+                    r.get::<String, _>("asset_code"),
+                ),
+            )
+        })
         .collect();
 
     let mut valid_ids: Vec<Uuid> = Vec::new();
-    let mut old_statuses: std::collections::HashMap<Uuid, String> =
-        std::collections::HashMap::new();
+    let mut asset_codes: Vec<String> = Vec::new();
     let mut errors: Vec<BulkUpdateError> = Vec::new();
 
     for &id in transaction_ids {
@@ -1511,10 +2457,10 @@ pub async fn bulk_update_transaction_status(
                 transaction_id: id,
                 error: "transaction not found".to_string(),
             }),
-            Some(from) => match validate_status_transition(from, new_status) {
+            Some((from, asset_code)) => match validate_status_transition(from, new_status) {
                 Ok(_) => {
-                    old_statuses.insert(id, from.clone());
                     valid_ids.push(id);
+                    asset_codes.push(asset_code.clone());
                 }
                 Err(e) => errors.push(BulkUpdateError {
                     transaction_id: id,
@@ -1533,36 +2479,13 @@ pub async fn bulk_update_transaction_status(
     }
 
     let mut db_tx = pool.begin().await?;
-
-    sqlx::query("UPDATE transactions SET status = $1, updated_at = NOW() WHERE id = ANY($2)")
-        .bind(new_status)
-        .bind(&valid_ids)
-        .execute(&mut *db_tx)
-        .await?;
-
-    for &id in &valid_ids {
-        let old_status = old_statuses
-            .get(&id)
-            .map(|s| s.as_str())
-            .unwrap_or("unknown");
-        let mut new_val = serde_json::json!({ "status": new_status });
-        if let Some(r) = reason {
-            new_val["reason"] = serde_json::json!(r);
-        }
-        AuditLog::log(
-            &mut db_tx,
-            id,
-            ENTITY_TRANSACTION,
-            "status_update",
-            Some(serde_json::json!({ "status": old_status })),
-            Some(new_val),
-            actor,
-        )
-        .await?;
-    }
-
+    bulk_update_status(&mut db_tx, &valid_ids, new_status, reason, actor).await?;
     db_tx.commit().await?;
 
+    asset_codes.sort();
+    asset_codes.dedup();
+    invalidate_transaction_caches(&asset_codes).await;
+
     let updated = valid_ids.len();
     Ok(BulkUpdateResult {
         updated,
@@ -1586,6 +2509,15 @@ pub struct DailyTotal {
     pub tx_count: i64,
 }
 
+/// One UTC day of [`daily_totals_by_asset`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetDailyTotal {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub count: i64,
+    pub total_amount: BigDecimal,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetStats {
     pub asset_code: String,
@@ -1594,11 +2526,26 @@ pub struct AssetStats {
     pub avg_amount: BigDecimal,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementDailyTotal {
+    pub date: String,
+    pub total_amount: BigDecimal,
+    pub settlement_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementAssetCount {
+    pub asset_code: String,
+    pub settlement_count: i64,
+    pub total_amount: BigDecimal,
+}
+
 pub async fn get_status_counts(pool: &PgPool) -> Result<Vec<StatusCount>> {
     let rows = sqlx::query(
         r#"
         SELECT status, COUNT(*) as count
         FROM transactions
+        WHERE deleted_at IS NULL
         GROUP BY status
         ORDER BY status
         "#,
@@ -1626,6 +2573,7 @@ pub async fn get_daily_totals(pool: &PgPool, days: i32) -> Result<Vec<DailyTotal
         FROM transactions
         WHERE created_at >= $1
           AND created_at < $2
+          AND deleted_at IS NULL
         GROUP BY DATE(created_at)
         ORDER BY DATE(created_at) DESC
         "#;
@@ -1662,6 +2610,66 @@ pub async fn get_daily_totals(pool: &PgPool, days: i32) -> Result<Vec<DailyTotal
         .collect())
 }
 
+/// Daily transaction count and volume of `asset_code` for each UTC day from
+/// `from` to `to`, both inclusive, oldest first. Days without transactions
+/// are reported with zero totals; soft-deleted transactions are excluded.
+pub async fn daily_totals_by_asset(
+    pool: &PgPool,
+    asset_code: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<AssetDailyTotal>> {
+    // Bounding created_at (rather than only the truncated day) lets the
+    // planner prune the monthly partitions outside the range.
+    let start = from.and_time(NaiveTime::MIN).and_utc();
+    let end = (to + chrono::Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    let sql = r#"
+        WITH daily AS (
+            SELECT date_trunc('day', created_at AT TIME ZONE 'UTC')::date AS day,
+                   COUNT(*) AS count,
+                   SUM(amount) AS total_amount
+            FROM transactions
+            WHERE asset_code = $1
+              AND deleted_at IS NULL
+              AND created_at >= $2
+              AND created_at < $3
+            GROUP BY 1
+        )
+        SELECT series.day::date::text AS date,
+               COALESCE(daily.count, 0) AS count,
+               COALESCE(daily.total_amount, 0) AS total_amount
+        FROM generate_series($4::date, $5::date, interval '1 day') AS series(day)
+        LEFT JOIN daily ON daily.day = series.day::date
+        ORDER BY series.day
+        "#;
+
+    with_timeout(
+        QueryTier::Read,
+        "daily_totals_by_asset [date_trunc group-by]",
+        async {
+            let rows = sqlx::query(sql)
+                .bind(asset_code)
+                .bind(start)
+                .bind(end)
+                .bind(from)
+                .bind(to)
+                .fetch_all(pool)
+                .await?;
+            Ok(rows
+                .into_iter()
+                .map(|row| AssetDailyTotal {
+                    date: row.get("date"),
+                    count: row.get("count"),
+                    total_amount: row.get("total_amount"),
+                })
+                .collect())
+        },
+    )
+    .await
+}
+
 pub async fn get_asset_stats(pool: &PgPool) -> Result<Vec<AssetStats>> {
     let rows = sqlx::query(
         r#"
@@ -1671,6 +2679,7 @@ pub async fn get_asset_stats(pool: &PgPool) -> Result<Vec<AssetStats>> {
             COUNT(*) as tx_count,
             AVG(amount) as avg_amount
         FROM transactions
+        WHERE deleted_at IS NULL
         GROUP BY asset_code
         ORDER BY total_amount DESC
         "#,
@@ -1689,6 +2698,68 @@ pub async fn get_asset_stats(pool: &PgPool) -> Result<Vec<AssetStats>> {
         .collect())
 }
 
+/// Settled amount and settlement count per day over the last `days` days,
+/// newest first.
+pub async fn get_settlement_daily_totals(
+    pool: &PgPool,
+    days: i32,
+) -> Result<Vec<SettlementDailyTotal>> {
+    let end = Utc::now();
+    let start = end - chrono::Duration::days(days.into());
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            DATE(created_at)::text as date,
+            SUM(total_amount) as total_amount,
+            COUNT(*) as settlement_count
+        FROM settlements
+        WHERE created_at >= $1
+          AND created_at < $2
+        GROUP BY DATE(created_at)
+        ORDER BY DATE(created_at) DESC
+        "#,
+    )
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SettlementDailyTotal {
+            date: row.get("date"),
+            total_amount: row.get("total_amount"),
+            settlement_count: row.get("settlement_count"),
+        })
+        .collect())
+}
+
+/// Settlement count and settled amount per asset.
+pub async fn get_settlement_asset_counts(pool: &PgPool) -> Result<Vec<SettlementAssetCount>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            asset_code,
+            COUNT(*) as settlement_count,
+            SUM(total_amount) as total_amount
+        FROM settlements
+        GROUP BY asset_code
+        ORDER BY asset_code
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SettlementAssetCount {
+            asset_code: row.get("asset_code"),
+            settlement_count: row.get("settlement_count"),
+            total_amount: row.get("total_amount"),
+        })
+        .collect())
+}
+
 // --- Idempotency Fallback Queries ---
 //
 // Webhook handlers and replay flows can use these helpers to avoid processing
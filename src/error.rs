@@ -1,5 +1,5 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -31,6 +31,8 @@ pub mod codes {
     );
     pub const NOT_FOUND_001: (&str, u16, &str) = ("ERR_NOT_FOUND_001", 404, "Resource not found");
     pub const INTERNAL_001: (&str, u16, &str) = ("ERR_INTERNAL_001", 500, "Internal server error");
+    pub const INTERNAL_002: (&str, u16, &str) =
+        ("ERR_INTERNAL_002", 500, "Unexpected internal error");
     pub const BAD_REQUEST_001: (&str, u16, &str) = (
         "ERR_BAD_REQUEST_001",
         400,
@@ -41,11 +43,21 @@ pub mod codes {
         401,
         "Unauthorized - authentication required",
     );
+    pub const CONFLICT_001: (&str, u16, &str) = (
+        "ERR_CONFLICT_001",
+        409,
+        "Conflict - resource was modified concurrently",
+    );
 
     // Authentication specific errors
     pub const AUTH_001: (&str, u16, &str) =
         ("ERR_AUTH_001", 401, "Invalid authentication credentials");
     pub const AUTH_002: (&str, u16, &str) = ("ERR_AUTH_002", 403, "Insufficient permissions");
+    pub const AUTH_003: (&str, u16, &str) =
+        ("ERR_AUTH_003", 401, "Invalid API key or tenant header");
+
+    // Tenant specific errors
+    pub const TENANT_001: (&str, u16, &str) = ("ERR_TENANT_001", 404, "Tenant not found");
 
     // Transaction specific errors
     pub const TRANSACTION_001: (&str, u16, &str) =
@@ -89,11 +101,22 @@ pub mod codes {
     pub const RATE_LIMIT_001: (&str, u16, &str) =
         ("ERR_RATE_LIMIT_001", 429, "Rate limit exceeded");
 
+    // Request timeouts
+    pub const TIMEOUT_001: (&str, u16, &str) = ("ERR_TIMEOUT_001", 504, "Request timed out");
+
+    // Load shedding
+    pub const UNAVAILABLE_001: (&str, u16, &str) = (
+        "ERR_UNAVAILABLE_001",
+        503,
+        "Service busy, retry after the Retry-After delay",
+    );
+
     // Redis errors
     pub const REDIS_001: (&str, u16, &str) = ("ERR_REDIS_001", 500, "Redis operation failed");
 }
 
-/// Get all error codes as a vector for catalog generation
+/// Get all error codes as a vector for catalog generation.
+/// Every `AppError` variant maps to exactly one entry.
 pub fn get_all_error_codes() -> Vec<ErrorCode> {
     vec![
         ErrorCode {
@@ -121,6 +144,11 @@ pub fn get_all_error_codes() -> Vec<ErrorCode> {
             http_status: codes::INTERNAL_001.1,
             description: codes::INTERNAL_001.2,
         },
+        ErrorCode {
+            code: codes::INTERNAL_002.0,
+            http_status: codes::INTERNAL_002.1,
+            description: codes::INTERNAL_002.2,
+        },
         ErrorCode {
             code: codes::BAD_REQUEST_001.0,
             http_status: codes::BAD_REQUEST_001.1,
@@ -131,6 +159,11 @@ pub fn get_all_error_codes() -> Vec<ErrorCode> {
             http_status: codes::UNAUTHORIZED_001.1,
             description: codes::UNAUTHORIZED_001.2,
         },
+        ErrorCode {
+            code: codes::CONFLICT_001.0,
+            http_status: codes::CONFLICT_001.1,
+            description: codes::CONFLICT_001.2,
+        },
         ErrorCode {
             code: codes::AUTH_001.0,
             http_status: codes::AUTH_001.1,
@@ -141,6 +174,16 @@ pub fn get_all_error_codes() -> Vec<ErrorCode> {
             http_status: codes::AUTH_002.1,
             description: codes::AUTH_002.2,
         },
+        ErrorCode {
+            code: codes::AUTH_003.0,
+            http_status: codes::AUTH_003.1,
+            description: codes::AUTH_003.2,
+        },
+        ErrorCode {
+            code: codes::TENANT_001.0,
+            http_status: codes::TENANT_001.1,
+            description: codes::TENANT_001.2,
+        },
         ErrorCode {
             code: codes::TRANSACTION_001.0,
             http_status: codes::TRANSACTION_001.1,
@@ -196,6 +239,16 @@ pub fn get_all_error_codes() -> Vec<ErrorCode> {
             http_status: codes::RATE_LIMIT_001.1,
             description: codes::RATE_LIMIT_001.2,
         },
+        ErrorCode {
+            code: codes::TIMEOUT_001.0,
+            http_status: codes::TIMEOUT_001.1,
+            description: codes::TIMEOUT_001.2,
+        },
+        ErrorCode {
+            code: codes::UNAVAILABLE_001.0,
+            http_status: codes::UNAVAILABLE_001.1,
+            description: codes::UNAVAILABLE_001.2,
+        },
         ErrorCode {
             code: codes::REDIS_001.0,
             http_status: codes::REDIS_001.1,
@@ -227,6 +280,9 @@ pub enum AppError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Tenant not found")]
     TenantNotFound,
 
@@ -267,6 +323,12 @@ pub enum AppError {
     #[error("Rate limit exceeded")]
     RateLimitExceeded,
 
+    #[error("Request timed out after {0:?}")]
+    RequestTimeout(std::time::Duration),
+
+    #[error("Service busy, retry later")]
+    ServiceBusy,
+
     #[error("Authentication failed: {0}")]
     AuthenticationFailed(String),
 
@@ -282,7 +344,7 @@ pub enum AppError {
 
 impl AppError {
     /// Get the HTTP status code for this error
-    fn status_code(&self) -> StatusCode {
+    pub(crate) fn status_code(&self) -> StatusCode {
         match self {
             AppError::Database(_) | AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Validation(_) => StatusCode::BAD_REQUEST,
@@ -290,6 +352,7 @@ impl AppError {
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
             AppError::TenantNotFound => StatusCode::NOT_FOUND,
             AppError::InvalidApiKey => StatusCode::UNAUTHORIZED,
             AppError::InvalidTransactionAmount(_) => StatusCode::BAD_REQUEST,
@@ -303,6 +366,8 @@ impl AppError {
             AppError::InvalidSettlementAmount(_) => StatusCode::BAD_REQUEST,
             AppError::SettlementAlreadyExists(_) => StatusCode::CONFLICT,
             AppError::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
+            AppError::RequestTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            AppError::ServiceBusy => StatusCode::SERVICE_UNAVAILABLE,
             AppError::AuthenticationFailed(_) => StatusCode::UNAUTHORIZED,
             AppError::InsufficientPermissions(_) => StatusCode::FORBIDDEN,
             AppError::Redis(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -313,35 +378,50 @@ impl AppError {
     /// Get the stable error code for this error
     /// These codes are stable and should never be renamed or reused
     pub fn code(&self) -> &'static str {
+        self.catalog_entry().0
+    }
+
+    /// Short, human-readable summary of the error type (the problem `title`)
+    pub fn title(&self) -> &'static str {
+        self.catalog_entry().2
+    }
+
+    fn catalog_entry(&self) -> (&'static str, u16, &'static str) {
         match self {
-            AppError::Database(_) => codes::DATABASE_001.0,
-            AppError::DatabaseError(_) => codes::DATABASE_002.0,
-            AppError::Validation(_) => codes::VALIDATION_001.0,
-            AppError::NotFound(_) => codes::NOT_FOUND_001.0,
-            AppError::Internal(_) => codes::INTERNAL_001.0,
-            AppError::BadRequest(_) => codes::BAD_REQUEST_001.0,
-            AppError::Unauthorized(_) => codes::UNAUTHORIZED_001.0,
-            AppError::TenantNotFound => codes::NOT_FOUND_001.0,
-            AppError::InvalidApiKey => codes::UNAUTHORIZED_001.0,
-            AppError::InvalidTransactionAmount(_) => codes::TRANSACTION_001.0,
-            AppError::AmountBelowMinimum(_) => codes::TRANSACTION_002.0,
-            AppError::InvalidStellarAddress(_) => codes::TRANSACTION_003.0,
-            AppError::TransactionAlreadyProcessed(_) => codes::TRANSACTION_004.0,
-            AppError::InvalidStatusTransition(_) => codes::TRANSACTION_005.0,
-            AppError::StaleTransition => codes::SETTLEMENT_003.0,
-            AppError::InvalidWebhookSignature => codes::WEBHOOK_001.0,
-            AppError::MalformedWebhookPayload(_) => codes::WEBHOOK_002.0,
-            AppError::InvalidSettlementAmount(_) => codes::SETTLEMENT_001.0,
-            AppError::SettlementAlreadyExists(_) => codes::SETTLEMENT_002.0,
-            AppError::RateLimitExceeded => codes::RATE_LIMIT_001.0,
-            AppError::AuthenticationFailed(_) => codes::AUTH_001.0,
-            AppError::InsufficientPermissions(_) => codes::AUTH_002.0,
-            AppError::Redis(_) => codes::REDIS_001.0,
-            AppError::Anyhow(_) => codes::INTERNAL_001.0,
+            AppError::Database(_) => codes::DATABASE_001,
+            AppError::DatabaseError(_) => codes::DATABASE_002,
+            AppError::Validation(_) => codes::VALIDATION_001,
+            AppError::NotFound(_) => codes::NOT_FOUND_001,
+            AppError::Internal(_) => codes::INTERNAL_001,
+            AppError::BadRequest(_) => codes::BAD_REQUEST_001,
+            AppError::Unauthorized(_) => codes::UNAUTHORIZED_001,
+            AppError::Conflict(_) => codes::CONFLICT_001,
+            AppError::TenantNotFound => codes::TENANT_001,
+            AppError::InvalidApiKey => codes::AUTH_003,
+            AppError::InvalidTransactionAmount(_) => codes::TRANSACTION_001,
+            AppError::AmountBelowMinimum(_) => codes::TRANSACTION_002,
+            AppError::InvalidStellarAddress(_) => codes::TRANSACTION_003,
+            AppError::TransactionAlreadyProcessed(_) => codes::TRANSACTION_004,
+            AppError::InvalidStatusTransition(_) => codes::TRANSACTION_005,
+            AppError::StaleTransition => codes::SETTLEMENT_003,
+            AppError::InvalidWebhookSignature => codes::WEBHOOK_001,
+            AppError::MalformedWebhookPayload(_) => codes::WEBHOOK_002,
+            AppError::InvalidSettlementAmount(_) => codes::SETTLEMENT_001,
+            AppError::SettlementAlreadyExists(_) => codes::SETTLEMENT_002,
+            AppError::RateLimitExceeded => codes::RATE_LIMIT_001,
+            AppError::RequestTimeout(_) => codes::TIMEOUT_001,
+            AppError::ServiceBusy => codes::UNAVAILABLE_001,
+            AppError::AuthenticationFailed(_) => codes::AUTH_001,
+            AppError::InsufficientPermissions(_) => codes::AUTH_002,
+            AppError::Redis(_) => codes::REDIS_001,
+            AppError::Anyhow(_) => codes::INTERNAL_002,
         }
     }
 }
 
+/// Media type for RFC 7807 problem details.
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
 /// Extension type to carry request ID through the request lifecycle.
 #[derive(Clone, Debug)]
 pub struct RequestId(pub String);
@@ -373,16 +453,25 @@ impl IntoResponse for AppError {
             _ => self.to_string(),
         };
 
+        // RFC 7807 members first; `error`, `timestamp` and `docs_url` are kept
+        // as extension members for existing clients.
         let body = serde_json::json!({
-            "error": self.to_string(),
-            "code": code,
+            "type": docs_url,
+            "title": self.title(),
             "status": status.as_u16(),
-            "timestamp": timestamp,
             "detail": detail,
+            "code": code,
+            "error": self.to_string(),
+            "timestamp": timestamp,
             "docs_url": docs_url,
         });
 
-        (status, Json(body)).into_response()
+        (
+            status,
+            [(header::CONTENT_TYPE, PROBLEM_JSON_CONTENT_TYPE)],
+            Json(body),
+        )
+            .into_response()
     }
 }
 
@@ -466,6 +555,45 @@ mod tests {
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    async fn problem_body(response: Response) -> serde_json::Value {
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_not_found_renders_problem_json() {
+        let response = AppError::NotFound("Transaction abc".to_string()).into_response();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+        let body = problem_body(response).await;
+        assert_eq!(body["type"], "/errors#ERR_NOT_FOUND_001");
+        assert_eq!(body["title"], "Resource not found");
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["detail"], "Not found: Transaction abc");
+        assert_eq!(body["code"], "ERR_NOT_FOUND_001");
+    }
+
+    #[tokio::test]
+    async fn test_bad_request_renders_problem_json() {
+        let response = AppError::BadRequest("Invalid cursor".to_string()).into_response();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+        let body = problem_body(response).await;
+        assert_eq!(body["type"], "/errors#ERR_BAD_REQUEST_001");
+        assert_eq!(body["title"], "Bad request - invalid parameters");
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["detail"], "Bad request: Invalid cursor");
+        assert_eq!(body["code"], "ERR_BAD_REQUEST_001");
+    }
+
     #[test]
     fn test_error_codes() {
         // Test that all error types return correct codes
@@ -536,6 +664,11 @@ mod tests {
             codes::SETTLEMENT_002.0
         );
         assert_eq!(AppError::RateLimitExceeded.code(), codes::RATE_LIMIT_001.0);
+        assert_eq!(
+            AppError::RequestTimeout(std::time::Duration::from_secs(30)).code(),
+            codes::TIMEOUT_001.0
+        );
+        assert_eq!(AppError::ServiceBusy.code(), codes::UNAVAILABLE_001.0);
         assert_eq!(
             AppError::AuthenticationFailed("test".to_string()).code(),
             codes::AUTH_001.0
@@ -546,6 +679,98 @@ mod tests {
         );
     }
 
+    /// One instance of every variant. The exhaustive match fails to compile
+    /// when a variant is added without extending this list.
+    fn all_variants() -> Vec<AppError> {
+        let s = || "test".to_string();
+        let variants = vec![
+            AppError::Database(sqlx::Error::RowNotFound),
+            AppError::DatabaseError(s()),
+            AppError::Validation(s()),
+            AppError::NotFound(s()),
+            AppError::Internal(s()),
+            AppError::BadRequest(s()),
+            AppError::Unauthorized(s()),
+            AppError::Conflict(s()),
+            AppError::TenantNotFound,
+            AppError::InvalidApiKey,
+            AppError::InvalidTransactionAmount(s()),
+            AppError::AmountBelowMinimum(s()),
+            AppError::InvalidStellarAddress(s()),
+            AppError::TransactionAlreadyProcessed(s()),
+            AppError::InvalidStatusTransition(s()),
+            AppError::StaleTransition,
+            AppError::InvalidWebhookSignature,
+            AppError::MalformedWebhookPayload(s()),
+            AppError::InvalidSettlementAmount(s()),
+            AppError::SettlementAlreadyExists(s()),
+            AppError::RateLimitExceeded,
+            AppError::RequestTimeout(std::time::Duration::from_secs(30)),
+            AppError::ServiceBusy,
+            AppError::AuthenticationFailed(s()),
+            AppError::InsufficientPermissions(s()),
+            AppError::Redis(redis::RedisError::from((redis::ErrorKind::IoError, "test"))),
+            AppError::Anyhow(anyhow::anyhow!("test")),
+        ];
+        for variant in &variants {
+            match variant {
+                AppError::Database(_)
+                | AppError::DatabaseError(_)
+                | AppError::Validation(_)
+                | AppError::NotFound(_)
+                | AppError::Internal(_)
+                | AppError::BadRequest(_)
+                | AppError::Unauthorized(_)
+                | AppError::Conflict(_)
+                | AppError::TenantNotFound
+                | AppError::InvalidApiKey
+                | AppError::InvalidTransactionAmount(_)
+                | AppError::AmountBelowMinimum(_)
+                | AppError::InvalidStellarAddress(_)
+                | AppError::TransactionAlreadyProcessed(_)
+                | AppError::InvalidStatusTransition(_)
+                | AppError::StaleTransition
+                | AppError::InvalidWebhookSignature
+                | AppError::MalformedWebhookPayload(_)
+                | AppError::InvalidSettlementAmount(_)
+                | AppError::SettlementAlreadyExists(_)
+                | AppError::RateLimitExceeded
+                | AppError::RequestTimeout(_)
+                | AppError::ServiceBusy
+                | AppError::AuthenticationFailed(_)
+                | AppError::InsufficientPermissions(_)
+                | AppError::Redis(_)
+                | AppError::Anyhow(_) => {}
+            }
+        }
+        variants
+    }
+
+    #[test]
+    fn test_every_variant_in_catalog_exactly_once() {
+        let catalog = get_all_error_codes();
+        let variants = all_variants();
+
+        for variant in &variants {
+            let entries: Vec<&ErrorCode> = catalog
+                .iter()
+                .filter(|entry| entry.code == variant.code())
+                .collect();
+            assert_eq!(entries.len(), 1, "{} not catalogued once", variant.code());
+            assert_eq!(entries[0].http_status, variant.status_code().as_u16());
+        }
+
+        let mut codes: Vec<&str> = variants.iter().map(AppError::code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), variants.len(), "variants must not share codes");
+        assert_eq!(
+            catalog.len(),
+            variants.len(),
+            "catalog has codes no variant uses"
+        );
+    }
+
     #[test]
     fn test_error_catalog_size() {
         let catalog = get_all_error_codes();
@@ -5,9 +5,11 @@
 //!
 //! # Design
 //!
-//! - All GraphQL-layer errors carry a stable machine-readable `code` in the
-//!   `extensions` object so clients can branch on error type without parsing
-//!   the human-readable `message`.
+//! - All GraphQL-layer errors carry a stable machine-readable `code` and the
+//!   HTTP-equivalent `status` in the `extensions` object so clients can branch
+//!   on error type without parsing the human-readable `message`.
+//! - Resolvers calling into code that returns [`AppError`] convert it with
+//!   [`app_error`], which picks the matching code below.
 //! - Internal details (database messages, stack traces) are never forwarded to
 //!   the client; they are logged server-side via `tracing`.
 //! - The conversion from [`GraphQlError`] to [`async_graphql::Error`] is
@@ -23,10 +25,12 @@
 //! | `AUTHENTICATION_ERROR` | Request lacks valid credentials |
 //! | `AUTHORIZATION_ERROR` | Caller lacks permission for the resource |
 //! | `RATE_LIMITED` | Caller has exceeded the request rate limit |
+//! | `CONFLICT` | Request conflicts with the resource's current state |
 //! | `COMPLEXITY_ERROR` | Query exceeds depth / complexity / alias limits |
 //! | `DATABASE_ERROR` | Underlying database operation failed (details redacted) |
 //! | `INTERNAL_ERROR` | Unexpected server error (details redacted) |
 
+use crate::error::AppError;
 use async_graphql::Error as GqlError;
 use async_graphql::ErrorExtensions;
 
@@ -39,6 +43,7 @@ pub const CODE_NOT_FOUND: &str = "NOT_FOUND";
 pub const CODE_AUTHENTICATION: &str = "AUTHENTICATION_ERROR";
 pub const CODE_AUTHORIZATION: &str = "AUTHORIZATION_ERROR";
 pub const CODE_RATE_LIMITED: &str = "RATE_LIMITED";
+pub const CODE_CONFLICT: &str = "CONFLICT";
 pub const CODE_COMPLEXITY: &str = "COMPLEXITY_ERROR";
 pub const CODE_DATABASE: &str = "DATABASE_ERROR";
 pub const CODE_INTERNAL: &str = "INTERNAL_ERROR";
@@ -71,6 +76,9 @@ pub enum GraphQlError {
     /// `retry_after_secs` is a hint to the client; `0` means unknown.
     RateLimited { retry_after_secs: u64 },
 
+    /// The request conflicts with the current state of the resource.
+    Conflict(String),
+
     /// The query exceeds depth, complexity, or alias limits.
     Complexity(String),
 
@@ -96,20 +104,31 @@ impl GraphQlError {
             GraphQlError::Authentication => CODE_AUTHENTICATION,
             GraphQlError::Authorization => CODE_AUTHORIZATION,
             GraphQlError::RateLimited { .. } => CODE_RATE_LIMITED,
+            GraphQlError::Conflict(_) => CODE_CONFLICT,
             GraphQlError::Complexity(_) => CODE_COMPLEXITY,
             GraphQlError::Database(_) => CODE_DATABASE,
             GraphQlError::Internal(_) => CODE_INTERNAL,
         }
     }
 
-    /// Converts this error into an [`async_graphql::Error`] with a populated
-    /// `extensions.code` field (and `retryAfter` for rate-limit errors).
-    pub fn into_gql_error(self) -> GqlError {
-        let code = self.code();
-        let message = self.message();
+    /// Returns the HTTP status a REST endpoint would answer with.
+    pub fn status(&self) -> u16 {
+        match self {
+            GraphQlError::Validation(_) | GraphQlError::Complexity(_) => 400,
+            GraphQlError::NotFound(_) => 404,
+            GraphQlError::Authentication => 401,
+            GraphQlError::Authorization => 403,
+            GraphQlError::RateLimited { .. } => 429,
+            GraphQlError::Conflict(_) => 409,
+            GraphQlError::Database(_) | GraphQlError::Internal(_) => 500,
+        }
+    }
 
-        let mut err = GqlError::new(message);
-        err = err.extend_with(|_, e| e.set("code", code));
+    /// Converts this error into an [`async_graphql::Error`] with populated
+    /// `extensions.code` and `extensions.status` fields (and `retryAfter` for
+    /// rate-limit errors).
+    pub fn into_gql_error(self) -> GqlError {
+        let mut err = with_code(GqlError::new(self.message()), self.code(), self.status());
 
         if let GraphQlError::RateLimited { retry_after_secs } = &self {
             err = err.extend_with(|_, e| e.set("retryAfter", *retry_after_secs));
@@ -128,6 +147,7 @@ impl GraphQlError {
             GraphQlError::RateLimited { .. } => {
                 "Too many requests — rate limit exceeded".to_string()
             }
+            GraphQlError::Conflict(msg) => msg.clone(),
             GraphQlError::Complexity(msg) => msg.clone(),
             GraphQlError::Database(msg) => msg.clone(),
             GraphQlError::Internal(msg) => msg.clone(),
@@ -141,10 +161,58 @@ impl From<GraphQlError> for GqlError {
     }
 }
 
+fn with_code(err: GqlError, code: &'static str, status: u16) -> GqlError {
+    err.extend_with(|_, e| {
+        e.set("code", code);
+        e.set("status", status);
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Convenience constructors
 // ---------------------------------------------------------------------------
 
+/// Converts an [`AppError`] into a GraphQL error carrying the matching stable
+/// code and the status its REST response would have.
+///
+/// The `AppError` message is kept as `message`, except for database and
+/// internal failures, which are logged and redacted like [`database_error`]
+/// and [`internal_error`].
+pub fn app_error(err: AppError) -> GqlError {
+    let code = match &err {
+        AppError::Database(e) => return database_error(e),
+        AppError::DatabaseError(msg) => return database_error(msg),
+        AppError::Internal(msg) => return internal_error(msg),
+        AppError::Redis(e) => return internal_error(e),
+        AppError::Anyhow(e) => return internal_error(e),
+        AppError::NotFound(_) | AppError::TenantNotFound => CODE_NOT_FOUND,
+        AppError::Unauthorized(_)
+        | AppError::InvalidApiKey
+        | AppError::InvalidWebhookSignature
+        | AppError::AuthenticationFailed(_) => CODE_AUTHENTICATION,
+        AppError::InsufficientPermissions(_) => CODE_AUTHORIZATION,
+        AppError::RateLimitExceeded => CODE_RATE_LIMITED,
+        AppError::RequestTimeout(_) | AppError::ServiceBusy => CODE_INTERNAL,
+        AppError::Conflict(_)
+        | AppError::TransactionAlreadyProcessed(_)
+        | AppError::StaleTransition
+        | AppError::SettlementAlreadyExists(_) => CODE_CONFLICT,
+        AppError::Validation(_)
+        | AppError::BadRequest(_)
+        | AppError::InvalidTransactionAmount(_)
+        | AppError::AmountBelowMinimum(_)
+        | AppError::InvalidStellarAddress(_)
+        | AppError::InvalidStatusTransition(_)
+        | AppError::MalformedWebhookPayload(_)
+        | AppError::InvalidSettlementAmount(_) => CODE_VALIDATION,
+    };
+    with_code(
+        GqlError::new(err.to_string()),
+        code,
+        err.status_code().as_u16(),
+    )
+}
+
 /// Builds a validation error from a field name and reason.
 pub fn validation_error(field: &str, reason: &str) -> GqlError {
     GraphQlError::Validation(format!("Invalid '{}': {}", field, reason)).into()
@@ -189,6 +257,16 @@ mod tests {
             })
     }
 
+    fn ext_status(err: &GqlError) -> Option<u64> {
+        err.extensions
+            .as_ref()
+            .and_then(|e| e.get("status"))
+            .and_then(|v| match v {
+                async_graphql::Value::Number(n) => n.as_u64(),
+                _ => None,
+            })
+    }
+
     #[test]
     fn validation_error_has_correct_code() {
         let err: GqlError = GraphQlError::Validation("bad input".into()).into();
@@ -270,6 +348,48 @@ mod tests {
         assert_eq!(ext_code(&err).as_deref(), Some(CODE_NOT_FOUND));
     }
 
+    #[test]
+    fn typed_errors_carry_http_status() {
+        let err: GqlError = GraphQlError::Validation("bad input".into()).into();
+        assert_eq!(ext_status(&err), Some(400));
+        let err: GqlError = GraphQlError::Conflict("taken".into()).into();
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_CONFLICT));
+        assert_eq!(ext_status(&err), Some(409));
+    }
+
+    #[test]
+    fn app_error_keeps_message_and_maps_code_and_status() {
+        let err = app_error(AppError::NotFound("Transaction abc".into()));
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_NOT_FOUND));
+        assert_eq!(ext_status(&err), Some(404));
+        assert_eq!(err.message, "Not found: Transaction abc");
+
+        let err = app_error(AppError::InvalidStellarAddress("GXYZ".into()));
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_VALIDATION));
+        assert_eq!(ext_status(&err), Some(400));
+
+        let err = app_error(AppError::SettlementAlreadyExists("USDC".into()));
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_CONFLICT));
+        assert_eq!(ext_status(&err), Some(409));
+
+        let err = app_error(AppError::InsufficientPermissions("admin".into()));
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_AUTHORIZATION));
+        assert_eq!(ext_status(&err), Some(403));
+    }
+
+    #[test]
+    fn app_error_redacts_database_and_internal_causes() {
+        let err = app_error(AppError::DatabaseError("password=secret".into()));
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_DATABASE));
+        assert_eq!(ext_status(&err), Some(500));
+        assert!(!err.message.contains("secret"));
+
+        let err = app_error(AppError::Internal("stack trace".into()));
+        assert_eq!(ext_code(&err).as_deref(), Some(CODE_INTERNAL));
+        assert_eq!(ext_status(&err), Some(500));
+        assert!(!err.message.contains("stack trace"));
+    }
+
     #[test]
     fn all_variants_have_non_empty_codes() {
         let errors: Vec<GraphQlError> = vec![
@@ -280,6 +400,7 @@ mod tests {
             GraphQlError::RateLimited {
                 retry_after_secs: 0,
             },
+            GraphQlError::Conflict("c".into()),
             GraphQlError::Complexity("c".into()),
             GraphQlError::Database("d".into()),
             GraphQlError::Internal("i".into()),
@@ -0,0 +1,128 @@
+use crate::db::queries::{self, AuditLogRow, AuditSearchParams};
+use crate::graphql::error::{app_error, GraphQlError};
+use crate::graphql::pagination::CursorPagination;
+use crate::AppState;
+use async_graphql::connection::{Connection, Edge, EmptyFields};
+use async_graphql::{Context, Json, Object, Result, SimpleObject};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Extra fields on the `auditLogs` connection.
+#[derive(SimpleObject)]
+pub struct AuditLogConnectionFields {
+    /// Entries matching the filters, ignoring the cursor.
+    pub total_count: i64,
+}
+
+pub type AuditLogConnection =
+    Connection<String, AuditLogRow, AuditLogConnectionFields, EmptyFields>;
+
+#[Object]
+impl AuditLogRow {
+    async fn id(&self) -> String {
+        self.id.to_string()
+    }
+    async fn entity_id(&self) -> String {
+        self.entity_id.to_string()
+    }
+    async fn entity_type(&self) -> &str {
+        &self.entity_type
+    }
+    async fn action(&self) -> &str {
+        &self.action
+    }
+    /// State before the change, as recorded.
+    async fn old_val(&self) -> Option<Json<serde_json::Value>> {
+        self.old_val.clone().map(Json)
+    }
+    /// State after the change, as recorded.
+    async fn new_val(&self) -> Option<Json<serde_json::Value>> {
+        self.new_val.clone().map(Json)
+    }
+    async fn actor(&self) -> &str {
+        &self.actor
+    }
+    async fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+}
+
+/// Encodes the `(timestamp, id)` keyset position of an entry.
+fn encode_cursor(row: &AuditLogRow) -> String {
+    CursorPagination::encode_cursor(&format!(
+        "{},{}",
+        row.timestamp.timestamp_nanos_opt().unwrap_or(0),
+        row.id
+    ))
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime<Utc>, Uuid)> {
+    let raw = CursorPagination::decode_cursor(cursor).ok()?;
+    let (nanos, id) = raw.split_once(',')?;
+    Some((
+        DateTime::from_timestamp_nanos(nanos.parse().ok()?),
+        id.parse().ok()?,
+    ))
+}
+
+/// Audit log query resolver.
+#[derive(Default)]
+pub struct AuditQuery;
+
+#[Object]
+impl AuditQuery {
+    /// Audit history of one entity, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity_id` - The audited entity's UUID
+    /// * `entity_type` - Optional entity type, e.g. "transaction"
+    /// * `first` - Page size (default: 20, max: 100)
+    /// * `after` - `endCursor` of the previous page
+    async fn audit_logs(
+        &self,
+        ctx: &Context<'_>,
+        entity_id: Uuid,
+        entity_type: Option<String>,
+        first: Option<i64>,
+        after: Option<String>,
+    ) -> Result<AuditLogConnection> {
+        let pagination =
+            CursorPagination::new(after, None, first, None).map_err(GraphQlError::Validation)?;
+        let cursor = match pagination.after.as_deref() {
+            Some(after) => Some(
+                decode_cursor(after)
+                    .ok_or_else(|| GraphQlError::Validation("Invalid cursor".to_string()))?,
+            ),
+            None => None,
+        };
+        let page_size = pagination.page_size();
+
+        let state = ctx.data::<AppState>()?;
+        // One extra row tells us whether another page follows.
+        let params = AuditSearchParams {
+            entity_type: entity_type.as_deref(),
+            entity_id: Some(entity_id),
+            limit: page_size + 1,
+            cursor,
+            ..Default::default()
+        };
+        let (total_count, mut rows) = queries::search_audit_logs(&state.db, &params)
+            .await
+            .map_err(|e| app_error(e.into()))?;
+
+        let has_next_page = rows.len() as i64 > page_size;
+        rows.truncate(page_size as usize);
+
+        let mut connection = Connection::with_additional_fields(
+            cursor.is_some(),
+            has_next_page,
+            AuditLogConnectionFields { total_count },
+        );
+        connection.edges.extend(
+            rows.into_iter()
+                .map(|row| Edge::new(encode_cursor(&row), row)),
+        );
+        Ok(connection)
+    }
+}
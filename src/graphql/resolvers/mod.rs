@@ -1,13 +1,15 @@
+pub mod audit;
 pub mod settlement;
 pub mod transaction;
 
+pub use audit::AuditQuery;
 pub use settlement::SettlementQuery;
 pub use transaction::{TransactionMutation, TransactionQuery, TransactionSubscription};
 
 use async_graphql::MergedObject;
 
 #[derive(MergedObject, Default)]
-pub struct Query(TransactionQuery, SettlementQuery);
+pub struct Query(TransactionQuery, SettlementQuery, AuditQuery);
 
 pub mod mutation {
     use super::transaction::TransactionMutation;
@@ -1,15 +1,48 @@
-use crate::db::{models::Transaction, queries};
+use crate::db::models::Transaction;
+use crate::db::queries::{self, StatusOverride, TransactionEvent};
+use crate::error::AppError;
+use crate::graphql::error::{app_error, GraphQlError};
 use crate::graphql::input_validation::{
     validate_asset_code, validate_limit, validate_status, validate_stellar_account,
+    InputValidationError,
 };
 use crate::handlers::ws::TransactionStatusUpdate;
 use crate::AppState;
-use async_graphql::{Context, InputObject, Object, Result, Subscription};
+use async_graphql::{Context, InputObject, Json, Object, Result, SimpleObject, Subscription};
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use std::pin::Pin;
 use tokio_stream::StreamExt as _;
 use uuid::Uuid;
 
+/// Maps a lookup of transaction `id` to an [`AppError`], turning a missing row
+/// into `NotFound`.
+fn transaction_error(e: sqlx::Error, id: Uuid) -> AppError {
+    match e {
+        sqlx::Error::RowNotFound => AppError::NotFound(format!("Transaction {id}")),
+        e => e.into(),
+    }
+}
+
+#[Object]
+impl TransactionEvent {
+    /// Audit action, e.g. `created`, `status_update` or `status_override`.
+    #[graphql(name = "type")]
+    async fn event_type(&self) -> &str {
+        &self.event_type
+    }
+    async fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+    async fn actor(&self) -> Option<&str> {
+        self.actor.as_deref()
+    }
+    /// `{"old": ..., "new": ...}` as recorded in the audit log.
+    async fn detail(&self) -> Option<Json<serde_json::Value>> {
+        self.detail.clone().map(Json)
+    }
+}
+
 /// Filter criteria for transaction queries.
 ///
 /// All fields are optional and combined with AND logic.
@@ -20,6 +53,14 @@ pub struct TransactionFilter {
     pub stellar_account: Option<String>,
 }
 
+/// Per-id outcome of `forceCompleteTransactions`.
+#[derive(SimpleObject)]
+pub struct ForceCompleteResult {
+    pub id: Uuid,
+    pub success: bool,
+    pub message: String,
+}
+
 /// Transaction query resolver.
 ///
 /// # Idempotency
@@ -44,7 +85,7 @@ impl TransactionQuery {
         let state = ctx.data::<AppState>()?;
         queries::get_transaction(&state.db, id)
             .await
-            .map_err(|e| e.into())
+            .map_err(|e| app_error(transaction_error(e, id)))
     }
 
     /// List transactions with optional filtering.
@@ -66,25 +107,27 @@ impl TransactionQuery {
         offset: Option<i64>,
     ) -> Result<Vec<Transaction>> {
         let effective_limit = limit.unwrap_or(20);
-        validate_limit(effective_limit).map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let invalid = |e: InputValidationError| GraphQlError::Validation(e.to_string());
+        validate_limit(effective_limit).map_err(invalid)?;
 
         if let Some(ref f) = filter {
             if let Some(ref s) = f.status {
-                validate_status(s).map_err(|e| async_graphql::Error::new(e.to_string()))?;
+                validate_status(s).map_err(invalid)?;
             }
             if let Some(ref a) = f.asset_code {
-                validate_asset_code(a).map_err(|e| async_graphql::Error::new(e.to_string()))?;
+                validate_asset_code(a).map_err(invalid)?;
             }
             if let Some(ref acc) = f.stellar_account {
-                validate_stellar_account(acc)
-                    .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+                validate_stellar_account(acc).map_err(invalid)?;
             }
         }
 
         let _ = offset;
         let state = ctx.data::<AppState>()?;
 
-        let txs = queries::list_transactions(&state.db, effective_limit, None, false).await?;
+        let txs = queries::list_transactions(&state.db, effective_limit, None, false)
+            .await
+            .map_err(|e| app_error(e.into()))?;
 
         if let Some(f) = filter {
             let filtered = txs
@@ -149,28 +192,75 @@ impl TransactionMutation {
     ///
     /// # Side Effects
     ///
-    /// - Updates transaction status to 'completed'
+    /// - Updates transaction status to 'completed', bypassing the state
+    ///   machine, and records a `status_override` audit entry
     /// - Invalidates query cache for the asset
     /// - Triggers webhook delivery if configured
     async fn force_complete_transaction(&self, ctx: &Context<'_>, id: Uuid) -> Result<Transaction> {
         let state = ctx.data::<AppState>()?;
 
-        let asset_code: String =
-            sqlx::query_scalar("SELECT asset_code FROM transactions WHERE id = $1")
-                .bind(id)
-                .fetch_one(&state.db)
-                .await?;
+        let result = queries::force_transaction_status(&state.db, id, "completed", "graphql")
+            .await
+            .map_err(|e| app_error(e.into()))?
+            .ok_or_else(|| app_error(transaction_error(sqlx::Error::RowNotFound, id)))?;
 
-        let result = sqlx::query_as::<_, Transaction>(
-            "UPDATE transactions SET status = 'completed', updated_at = NOW() WHERE id = $1 RETURNING *"
-        )
-        .bind(id)
-        .fetch_one(&state.db)
-        .await?;
+        Ok(result)
+    }
 
-        crate::db::queries::invalidate_caches_for_asset(&asset_code).await;
+    /// Force complete several transactions, each on its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The transaction UUIDs to complete
+    /// * `reason` - Why the override is needed; recorded in each audit entry
+    ///
+    /// # Returns
+    ///
+    /// One result per id, in request order. A failure for one id does not
+    /// roll back the others.
+    ///
+    /// # Side Effects
+    ///
+    /// Same as `forceCompleteTransaction` for every id that is updated.
+    /// Ids already `completed` are reported as failures and left untouched.
+    async fn force_complete_transactions(
+        &self,
+        ctx: &Context<'_>,
+        ids: Vec<Uuid>,
+        reason: String,
+    ) -> Result<Vec<ForceCompleteResult>> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(GraphQlError::Validation("reason must not be empty".to_string()).into());
+        }
+        let state = ctx.data::<AppState>()?;
 
-        Ok(result)
+        let mut results = Vec::with_capacity(ids.len());
+        for id in ids {
+            let outcome = queries::force_transaction_status_with_reason(
+                &state.db,
+                id,
+                "completed",
+                "graphql",
+                reason,
+            )
+            .await;
+            let (success, message) = match outcome {
+                Ok(StatusOverride::Applied(_)) => (true, "completed".to_string()),
+                Ok(StatusOverride::Unchanged) => (false, "already completed".to_string()),
+                Ok(StatusOverride::NotFound) => (false, "not found".to_string()),
+                Err(e) => {
+                    tracing::error!(transaction_id = %id, error = %e, "force complete failed");
+                    (false, "database error".to_string())
+                }
+            };
+            results.push(ForceCompleteResult {
+                id,
+                success,
+                message,
+            });
+        }
+        Ok(results)
     }
 
     /// Replay a transaction from the dead letter queue.
@@ -215,30 +305,80 @@ impl TransactionSubscription {
         let state = ctx.data::<AppState>()?;
         let rx = state.tx_broadcast.subscribe();
 
-        let stream = tokio_stream::wrappers::BroadcastStream::new(rx).filter_map(move |result| {
-            match result {
-                Ok(update) => {
-                    // Apply optional filters
-                    let id_match = transaction_id
-                        .map(|id| update.transaction_id == id)
-                        .unwrap_or(true);
-                    let asset_match = asset_code
-                        .as_deref()
-                        .map(|a| update.message.as_deref() == Some(a))
-                        .unwrap_or(true);
-                    if id_match && asset_match {
-                        Some(update)
-                    } else {
-                        None
-                    }
-                }
-                Err(tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(n)) => {
-                    tracing::warn!("GraphQL subscription lagged by {} messages", n);
-                    None
-                }
+        Ok(Box::pin(status_updates(rx, transaction_id, asset_code)))
+    }
+}
+
+/// Filtered stream of broadcast updates for one subscription. Updates skipped
+/// because the subscriber fell behind are counted and logged, then the stream
+/// resumes from the oldest retained update.
+fn status_updates(
+    rx: tokio::sync::broadcast::Receiver<TransactionStatusUpdate>,
+    transaction_id: Option<Uuid>,
+    asset_code: Option<String>,
+) -> impl Stream<Item = TransactionStatusUpdate> + Send {
+    tokio_stream::wrappers::BroadcastStream::new(rx).filter_map(move |result| match result {
+        Ok(update) => {
+            // Apply optional filters
+            let id_match = transaction_id
+                .map(|id| update.transaction_id == id)
+                .unwrap_or(true);
+            let asset_match = asset_code
+                .as_deref()
+                .map(|a| update.message.as_deref() == Some(a))
+                .unwrap_or(true);
+            if id_match && asset_match {
+                Some(update)
+            } else {
+                None
             }
-        });
+        }
+        Err(tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged(n)) => {
+            crate::metrics::broadcast_messages_lagged_total()
+                .with_label_values(&["graphql"])
+                .inc_by(n);
+            tracing::warn!(
+                dropped = n,
+                "GraphQL subscription lagged by {} messages; consider raising BROADCAST_CAPACITY",
+                n
+            );
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(status: &str) -> TransactionStatusUpdate {
+        TransactionStatusUpdate {
+            transaction_id: Uuid::new_v4(),
+            tenant_id: Uuid::nil(),
+            status: status.to_string(),
+            timestamp: chrono::Utc::now(),
+            message: None,
+            stellar_account: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overrun_subscription_counts_lagged_messages() {
+        let lagged =
+            crate::metrics::broadcast_messages_lagged_total().with_label_values(&["graphql"]);
+        let before = lagged.get();
+
+        let (tx, rx) = tokio::sync::broadcast::channel(2);
+        for status in ["pending", "processing", "completed", "failed", "pending"] {
+            tx.send(update(status)).unwrap();
+        }
+        drop(tx);
+
+        let received: Vec<_> = status_updates(rx, None, None).collect().await;
 
-        Ok(Box::pin(stream))
+        // Only the two most recent updates survive; the other three are counted.
+        let statuses: Vec<_> = received.iter().map(|u| u.status.as_str()).collect();
+        assert_eq!(statuses, ["failed", "pending"]);
+        assert_eq!(lagged.get() - before, 3);
     }
 }
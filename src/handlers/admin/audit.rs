@@ -40,6 +40,8 @@ fn default_limit() -> i64 {
 // Response types
 // ---------------------------------------------------------------------------
 
+/// Not yet the shared [`crate::handlers::pagination::Page`] envelope: this
+/// handler is not mounted, so it keeps its original shape until it is.
 #[derive(Debug, Serialize)]
 pub struct AuditSearchResponse {
     pub total: i64,
@@ -0,0 +1,26 @@
+//! `GET /admin/config`: the effective configuration, for operators who need
+//! to confirm what a running instance was started with.
+
+use crate::config::ConfigInfo;
+use crate::middleware::auth::JwtSecret;
+use axum::{extract::State, middleware, routing::get, Json, Router};
+use std::sync::Arc;
+
+/// GET /admin/config
+///
+/// Returns the active profile, the profile defaults overridden by the
+/// environment and every configuration value, with credentials redacted.
+pub async fn get_config(State(info): State<Arc<ConfigInfo>>) -> Json<ConfigInfo> {
+    Json(info.as_ref().clone())
+}
+
+/// `/admin/config`, behind a bearer JWT signed with `jwt_secret` and carrying
+/// the `admin:config` scope.
+pub fn config_routes(jwt_secret: JwtSecret) -> Router<Arc<ConfigInfo>> {
+    Router::new()
+        .route("/admin/config", get(get_config))
+        .layer(middleware::from_fn_with_state(
+            jwt_secret,
+            crate::middleware::auth::config_auth,
+        ))
+}
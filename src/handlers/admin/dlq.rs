@@ -0,0 +1,238 @@
+//! Admin endpoints for inspecting and replaying the transaction DLQ.
+//!
+//! Routes sit behind the `admin_auth` layer applied to `admin_router` in
+//! `src/lib.rs`. Each replayed entry gets exactly one more pass through the
+//! [`TransactionProcessor`]; see
+//! [`TransactionProcessor::replay_dlq_entry`] for the idempotency rules.
+
+use crate::db::models::TransactionDlq;
+use crate::error::AppError;
+use crate::handlers::pagination::Page;
+use crate::services::transaction_processor::DlqReplayOutcome;
+use crate::services::TransactionProcessor;
+use crate::ApiState;
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// Upper bound on entries replayed by a single request.
+pub const MAX_REPLAY_BATCH: i64 = 500;
+
+pub fn dlq_routes() -> Router<ApiState> {
+    Router::new()
+        .route("/", get(list_dlq_entries))
+        .route("/replay", post(replay_dlq_entries))
+}
+
+/// Filters shared by listing and filter-based replay.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct DlqFilter {
+    /// Case-insensitive substring of `error_reason`.
+    pub error_reason: Option<String>,
+    /// Only entries moved to the DLQ at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only entries moved to the DLQ at or before this time.
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl DlqFilter {
+    fn push_where(&self, qb: &mut QueryBuilder<'_, Postgres>) {
+        qb.push(" WHERE TRUE");
+        if let Some(reason) = &self.error_reason {
+            qb.push(" AND error_reason ILIKE ");
+            qb.push_bind(format!("%{reason}%"));
+        }
+        if let Some(from) = self.from {
+            qb.push(" AND moved_to_dlq_at >= ");
+            qb.push_bind(from);
+        }
+        if let Some(to) = self.to {
+            qb.push(" AND moved_to_dlq_at <= ");
+            qb.push_bind(to);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListDlqQuery {
+    pub error_reason: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+/// GET /admin/dlq
+///
+/// Lists DLQ entries, newest first, optionally filtered by `error_reason`
+/// and a `from`/`to` window on `moved_to_dlq_at`.
+pub async fn list_dlq_entries(
+    State(state): State<ApiState>,
+    Query(query): Query<ListDlqQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let pool = &state.app_state.db;
+    let limit = query.limit.clamp(1, 100);
+    let offset = query.offset.max(0);
+    let filter = DlqFilter {
+        error_reason: query.error_reason,
+        from: query.from,
+        to: query.to,
+    };
+
+    let mut qb = QueryBuilder::new("SELECT * FROM transaction_dlq");
+    filter.push_where(&mut qb);
+    qb.push(" ORDER BY moved_to_dlq_at DESC, id LIMIT ");
+    qb.push_bind(limit);
+    qb.push(" OFFSET ");
+    qb.push_bind(offset);
+    let entries = qb
+        .build_query_as::<TransactionDlq>()
+        .fetch_all(pool)
+        .await?;
+
+    let mut count = QueryBuilder::new("SELECT COUNT(*) FROM transaction_dlq");
+    filter.push_where(&mut count);
+    let total: i64 = count.build_query_scalar().fetch_one(pool).await?;
+
+    Ok(Json(Page::new(entries, total, limit, offset)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayDlqRequest {
+    /// Explicit DLQ entry ids to replay.
+    pub ids: Option<Vec<Uuid>>,
+    /// Replay the oldest entries matching this filter instead.
+    pub filter: Option<DlqFilter>,
+    /// Maximum number of entries replayed by this request.
+    #[serde(default = "default_max_batch")]
+    pub max_batch: i64,
+    /// Caller-supplied label stored with the audit entries as unverified
+    /// metadata. The audit actor is always the authenticated admin.
+    pub requested_by: Option<String>,
+}
+
+fn default_max_batch() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+pub struct DlqReplayResult {
+    pub dlq_id: Uuid,
+    /// One of `replayed`, `already_completed`, `failed`, `not_found`, `skipped`.
+    pub outcome: &'static str,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayDlqResponse {
+    pub requested: usize,
+    pub replayed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub results: Vec<DlqReplayResult>,
+}
+
+/// POST /admin/dlq/replay
+///
+/// Replays either the given `ids` or up to `max_batch` entries matching
+/// `filter`, oldest first. Successfully replayed rows are removed from the
+/// DLQ; failures are returned per entry rather than failing the request.
+pub async fn replay_dlq_entries(
+    State(state): State<ApiState>,
+    Json(req): Json<ReplayDlqRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !(1..=MAX_REPLAY_BATCH).contains(&req.max_batch) {
+        return Err(AppError::BadRequest(format!(
+            "max_batch must be between 1 and {MAX_REPLAY_BATCH}"
+        )));
+    }
+    let pool = &state.app_state.db;
+
+    let ids = match (req.ids, req.filter) {
+        (Some(ids), None) => {
+            if ids.len() as i64 > req.max_batch {
+                return Err(AppError::BadRequest(format!(
+                    "{} ids exceed max_batch of {}",
+                    ids.len(),
+                    req.max_batch
+                )));
+            }
+            ids
+        }
+        (None, Some(filter)) => {
+            let mut qb = QueryBuilder::new("SELECT id FROM transaction_dlq");
+            filter.push_where(&mut qb);
+            qb.push(" ORDER BY moved_to_dlq_at ASC, id LIMIT ");
+            qb.push_bind(req.max_batch);
+            qb.build_query_scalar().fetch_all(pool).await?
+        }
+        _ => {
+            return Err(AppError::BadRequest(
+                "provide exactly one of 'ids' or 'filter'".to_string(),
+            ))
+        }
+    };
+
+    // Admin auth does not identify individual keys, so every replay is
+    // attributed to "admin"; `requested_by` is never trusted as the actor.
+    let actor = "admin";
+    let requested_by = req.requested_by.as_deref();
+    let processor = TransactionProcessor::new(pool.clone())
+        .with_retry_policy(state.app_state.retry_policy.clone());
+    let mut response = ReplayDlqResponse {
+        requested: ids.len(),
+        replayed: 0,
+        failed: 0,
+        skipped: 0,
+        results: Vec::with_capacity(ids.len()),
+    };
+
+    for dlq_id in ids {
+        let outcome = processor
+            .replay_dlq_entry(dlq_id, actor, requested_by)
+            .await
+            .map_err(|e| AppError::Internal(format!("DLQ replay of {dlq_id} failed: {e}")))?;
+        let (label, message) = match outcome {
+            DlqReplayOutcome::Replayed => ("replayed", None),
+            DlqReplayOutcome::AlreadyCompleted => ("already_completed", None),
+            DlqReplayOutcome::Failed(msg) => ("failed", Some(msg)),
+            DlqReplayOutcome::NotFound => ("not_found", None),
+            DlqReplayOutcome::Skipped(msg) => ("skipped", Some(msg)),
+        };
+        match label {
+            "replayed" | "already_completed" => response.replayed += 1,
+            "failed" => response.failed += 1,
+            _ => response.skipped += 1,
+        }
+        response.results.push(DlqReplayResult {
+            dlq_id,
+            outcome: label,
+            message,
+        });
+    }
+
+    tracing::info!(
+        actor,
+        requested_by,
+        requested = response.requested,
+        replayed = response.replayed,
+        failed = response.failed,
+        skipped = response.skipped,
+        "DLQ replay finished"
+    );
+
+    Ok(Json(response))
+}
@@ -0,0 +1,23 @@
+use crate::services::JobStatus;
+use crate::ApiState;
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct JobsResponse {
+    pub jobs: Vec<JobStatus>,
+}
+
+/// GET /admin/jobs — every registered background job, ordered by name, with
+/// its schedule, next run and the outcome of its latest executions.
+pub async fn list_jobs(State(state): State<ApiState>) -> Json<JobsResponse> {
+    let mut jobs: Vec<JobStatus> = state
+        .app_state
+        .job_scheduler
+        .get_job_status()
+        .await
+        .into_values()
+        .collect();
+    jobs.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(JobsResponse { jobs })
+}
@@ -1,8 +1,13 @@
 pub mod backup;
 pub mod bulk_status;
+pub mod config;
+pub mod dlq;
+pub mod jobs;
 pub mod locks;
+pub mod partitions;
 pub mod quota;
 pub mod reconciliation;
+pub mod settlement_config;
 pub mod webhook_replay;
 
 use crate::error::AppError;
@@ -21,6 +26,9 @@ use uuid;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateFlagRequest {
     pub enabled: bool,
+    /// Who made the change, recorded in the flag audit log; defaults to "admin".
+    #[serde(default)]
+    pub actor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -72,17 +80,21 @@ pub fn admin_routes() -> Router<sqlx::PgPool> {
     Router::new().route("/flags", get(|| async { StatusCode::NOT_IMPLEMENTED }))
 }
 
-/// Create webhook replay admin routes
-pub fn webhook_replay_routes() -> Router<sqlx::PgPool> {
+/// Create webhook replay admin routes; batch replays are bounded by `limits`.
+pub fn webhook_replay_routes(limits: webhook_replay::BatchReplayLimits) -> Router<sqlx::PgPool> {
     Router::new()
         .route(
             "/webhooks/failed",
             get(webhook_replay::list_failed_webhooks),
         )
+        .route(
+            "/webhooks/replay-history",
+            get(webhook_replay::list_replay_history),
+        )
         .route("/webhooks/replay/:id", post(webhook_replay::replay_webhook))
         .route(
             "/webhooks/replay/batch",
-            post(webhook_replay::batch_replay_webhooks),
+            post(webhook_replay::batch_replay_webhooks).layer(axum::Extension(limits)),
         )
         .route(
             "/webhooks/endpoints/:id/rate-limit",
@@ -122,10 +134,12 @@ pub async fn update_flag(
     // Validate flag name: must not be empty and not exceed 255 characters
     validate_required("name", &name).map_err(|e| AppError::BadRequest(e.to_string()))?;
     validate_max_len("name", &name, 255).map_err(|e| AppError::BadRequest(e.to_string()))?;
+    let actor = payload.actor.as_deref().unwrap_or("admin");
+    validate_max_len("actor", actor, 255).map_err(|e| AppError::BadRequest(e.to_string()))?;
 
     let flag = state
         .feature_flags
-        .update(&name, payload.enabled)
+        .update(&name, payload.enabled, actor)
         .await
         .map_err(|_| AppError::NotFound(format!("Feature flag '{}' not found", name)))?;
 
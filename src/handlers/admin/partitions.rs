@@ -0,0 +1,20 @@
+use crate::db::partition::PartitionManager;
+use crate::error::AppError;
+use crate::ApiState;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+/// GET /admin/partitions — list `transactions` partitions with their date
+/// ranges, row estimates and on-disk sizes, to inform retention tuning.
+pub async fn list_partitions(State(state): State<ApiState>) -> Result<impl IntoResponse, AppError> {
+    let partitions = PartitionManager::list_partitions(&state.app_state.db).await?;
+    let total_bytes: i64 = partitions.iter().map(|p| p.total_bytes).sum();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "partitions": partitions,
+            "total": partitions.len(),
+            "total_bytes": total_bytes,
+        })),
+    ))
+}
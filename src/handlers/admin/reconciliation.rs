@@ -1,5 +1,5 @@
+use crate::db::models::TransactionStatus;
 use crate::services::reconciliation::{ReconciliationReport, ReconciliationService};
-use crate::stellar::HorizonClient;
 use crate::ApiState;
 use axum::{
     extract::{Path, Query, State},
@@ -95,6 +95,9 @@ pub struct RunReconciliationRequest {
     pub account: String,
     #[serde(default)]
     period_hours: Option<i32>,
+    /// Statuses of the transactions to reconcile; defaults to `completed`.
+    #[serde(default)]
+    statuses: Vec<TransactionStatus>,
 }
 
 #[derive(Debug, Serialize)]
@@ -380,10 +383,12 @@ pub async fn run_reconciliation(
             .into_response();
     }
 
-    let horizon_client = HorizonClient::new(state.app_state.horizon_client.base_url.clone());
+    let horizon_client = state.app_state.horizon_client.clone();
     let pool = state.app_state.db.clone();
 
-    let svc = ReconciliationService::new(horizon_client.clone(), pool.clone());
+    let svc = ReconciliationService::new(horizon_client.clone(), pool.clone())
+        .with_page_limit(state.app_state.horizon_page_limit)
+        .with_statuses(payload.statuses);
 
     let end = Utc::now();
     let start = end - Duration::hours(period_hours as i64);
@@ -391,7 +396,11 @@ pub async fn run_reconciliation(
     let report = match svc.reconcile(&account, start, end).await {
         Ok(r) => r,
         Err(e) => {
-            tracing::error!("Reconciliation failed: {}", e);
+            tracing::error!(
+                request_id = ?crate::middleware::request_logger::current_request_id(),
+                "Reconciliation failed: {}",
+                e
+            );
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({
@@ -0,0 +1,126 @@
+//! Admin endpoints for per-asset settlement configuration.
+//!
+//! Routes sit behind the `admin_auth` layer applied to `admin_router` in
+//! `src/lib.rs`. Changes take effect on the next settlement run; assets
+//! without a row settle with the service defaults.
+
+use crate::db::queries;
+use crate::error::AppError;
+use crate::services::settlement::SettlementSchedule;
+use crate::validation::{validate_asset_code_format, validate_stellar_account, ASSET_CODE_MAX_LEN};
+use crate::ApiState;
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use sqlx::types::BigDecimal;
+use std::str::FromStr;
+
+pub fn settlement_config_routes() -> Router<ApiState> {
+    Router::new()
+        .route("/", get(list_settlement_configs))
+        .route(
+            "/:asset_code",
+            get(get_settlement_config).put(put_settlement_config),
+        )
+}
+
+/// Body of `PUT /admin/settlement-config/:asset_code`. Replaces the whole
+/// row; omitted fields revert to the service defaults.
+#[derive(Debug, Deserialize)]
+pub struct SettlementConfigRequest {
+    /// Decimal string, e.g. `"100.50"`.
+    pub min_amount: Option<String>,
+    pub min_tx_count: Option<i32>,
+    /// `hourly` (default), `daily` or `weekly`.
+    pub schedule: Option<String>,
+    pub target_account: Option<String>,
+}
+
+fn validate_asset_code(asset_code: &str) -> Result<(), AppError> {
+    validate_asset_code_format(asset_code).map_err(|_| {
+        AppError::BadRequest(format!(
+            "asset_code must be 1-{ASSET_CODE_MAX_LEN} uppercase letters or digits"
+        ))
+    })
+}
+
+/// GET /admin/settlement-config — every configured asset.
+pub async fn list_settlement_configs(
+    State(state): State<ApiState>,
+) -> Result<impl IntoResponse, AppError> {
+    let configs = queries::list_settlement_configs(&state.app_state.db).await?;
+    Ok(Json(configs))
+}
+
+/// GET /admin/settlement-config/:asset_code — `404` when the asset uses the
+/// defaults.
+pub async fn get_settlement_config(
+    State(state): State<ApiState>,
+    Path(asset_code): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let config = queries::get_settlement_config(&state.app_state.db, &asset_code)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("no settlement config for asset {asset_code}"))
+        })?;
+    Ok(Json(config))
+}
+
+/// PUT /admin/settlement-config/:asset_code — create or replace.
+pub async fn put_settlement_config(
+    State(state): State<ApiState>,
+    Path(asset_code): Path<String>,
+    Json(body): Json<SettlementConfigRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    validate_asset_code(&asset_code)?;
+
+    let min_amount = match body.min_amount.as_deref() {
+        Some(value) => {
+            let amount = BigDecimal::from_str(value).map_err(|_| {
+                AppError::BadRequest("min_amount must be a valid decimal".to_string())
+            })?;
+            if amount < BigDecimal::from(0) {
+                return Err(AppError::BadRequest(
+                    "min_amount must not be negative".to_string(),
+                ));
+            }
+            Some(amount)
+        }
+        None => None,
+    };
+    if body.min_tx_count.is_some_and(|n| n < 1) {
+        return Err(AppError::BadRequest(
+            "min_tx_count must be at least 1".to_string(),
+        ));
+    }
+    let schedule = match body.schedule.as_deref() {
+        Some(value) => SettlementSchedule::parse(value).ok_or_else(|| {
+            AppError::BadRequest("schedule must be one of: hourly, daily, weekly".to_string())
+        })?,
+        None => SettlementSchedule::default(),
+    };
+    if let Some(account) = body.target_account.as_deref() {
+        validate_stellar_account(account)
+            .map_err(|e| AppError::BadRequest(format!("target_account: {}", e.message)))?;
+    }
+
+    let config = queries::upsert_settlement_config(
+        &state.app_state.db,
+        &asset_code,
+        min_amount.as_ref(),
+        body.min_tx_count,
+        schedule.as_str(),
+        body.target_account.as_deref(),
+    )
+    .await?;
+    tracing::info!(
+        asset = %asset_code,
+        schedule = schedule.as_str(),
+        "Settlement config updated"
+    );
+    Ok(Json(config))
+}
@@ -1,17 +1,26 @@
-use crate::db::models::Transaction;
+use crate::db::models::{StoredWebhookPayload, Transaction};
 use crate::db::queries;
 use crate::error::AppError;
+use crate::handlers::pagination::Page;
+use crate::handlers::webhook::transaction_from_stored_payload;
+use crate::services::lock_manager::{Lock, LockManager};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
+/// Expiry of the per-transaction replay lock, in case its holder dies
+/// before releasing it.
+pub const REPLAY_LOCK_TTL_SECS: u64 = 30;
+
 /// Request to replay a single webhook
 #[derive(Debug, Deserialize)]
 pub struct ReplayWebhookRequest {
@@ -51,6 +60,76 @@ fn default_limit() -> i64 {
     50
 }
 
+impl ListFailedWebhooksQuery {
+    /// Pushes the `FROM`/`WHERE` clauses shared by the listing and its count.
+    fn push_filters(&self, qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>) {
+        qb.push(
+            " FROM transactions t
+         LEFT JOIN transaction_dlq d ON t.id = d.transaction_id
+         WHERE (t.status = 'failed' OR d.id IS NOT NULL)",
+        );
+        if let Some(asset_code) = &self.asset_code {
+            qb.push(" AND t.asset_code = ");
+            qb.push_bind(asset_code.clone());
+        }
+        if let Some(from_date) = self.from_date {
+            qb.push(" AND t.created_at >= ");
+            qb.push_bind(from_date);
+        }
+        if let Some(to_date) = self.to_date {
+            qb.push(" AND t.created_at <= ");
+            qb.push_bind(to_date);
+        }
+    }
+}
+
+/// Query parameters for listing replay history
+#[derive(Debug, Deserialize)]
+pub struct ReplayHistoryQuery {
+    /// Only attempts for this transaction
+    pub transaction_id: Option<Uuid>,
+    /// Only successful (`true`) or failed (`false`) attempts
+    pub success: Option<bool>,
+    /// Only dry-run (`true`) or real (`false`) attempts
+    pub dry_run: Option<bool>,
+    /// Only attempts replayed at or after this time
+    pub from_date: Option<DateTime<Utc>>,
+    /// Only attempts replayed at or before this time
+    pub to_date: Option<DateTime<Utc>>,
+    /// Maximum number of results to return
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    /// Offset for pagination
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl ReplayHistoryQuery {
+    fn push_filters(&self, qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>) {
+        qb.push(" WHERE TRUE");
+        if let Some(transaction_id) = self.transaction_id {
+            qb.push(" AND transaction_id = ");
+            qb.push_bind(transaction_id);
+        }
+        if let Some(success) = self.success {
+            qb.push(" AND success = ");
+            qb.push_bind(success);
+        }
+        if let Some(dry_run) = self.dry_run {
+            qb.push(" AND dry_run = ");
+            qb.push_bind(dry_run);
+        }
+        if let Some(from_date) = self.from_date {
+            qb.push(" AND replayed_at >= ");
+            qb.push_bind(from_date);
+        }
+        if let Some(to_date) = self.to_date {
+            qb.push(" AND replayed_at <= ");
+            qb.push_bind(to_date);
+        }
+    }
+}
+
 /// Response for a single replay attempt
 #[derive(Debug, Serialize)]
 pub struct ReplayResult {
@@ -59,6 +138,117 @@ pub struct ReplayResult {
     pub message: String,
     pub dry_run: bool,
     pub replayed_at: Option<DateTime<Utc>>,
+    /// Projected changes; only set for dry runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<ReplayDiff>,
+    /// Whether the re-delivered body is the stored original.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_source: Option<PayloadSource>,
+    /// The body that was (or, for dry runs, would be) re-delivered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+}
+
+/// Origin of the body a replay re-delivers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadSource {
+    /// The raw body captured in `webhook_payloads` at ingest.
+    Stored,
+    /// Rebuilt from the `transactions` row; used for transactions ingested
+    /// before payloads were stored. Fields not modeled there are lost.
+    Reconstructed,
+}
+
+/// The inbound webhook a replay re-delivers.
+#[derive(Debug, Clone)]
+pub struct ReplayPayload {
+    pub source: PayloadSource,
+    /// Endpoint tag the stored body was received on (`callback`,
+    /// `callback/transaction`); `None` for reconstructed payloads.
+    pub ingest_source: Option<String>,
+    pub headers: serde_json::Value,
+    pub body: Vec<u8>,
+}
+
+impl ReplayPayload {
+    fn stored(payload: StoredWebhookPayload) -> Self {
+        Self {
+            source: PayloadSource::Stored,
+            ingest_source: Some(payload.source),
+            headers: payload.headers,
+            body: payload.body,
+        }
+    }
+
+    /// Rebuilds a `/callback` body from the fields kept on `transaction`.
+    pub fn reconstruct(transaction: &Transaction) -> Self {
+        let body = serde_json::json!({
+            "stellar_account": transaction.stellar_account,
+            "amount": transaction.amount.to_string(),
+            "asset_code": transaction.asset_code,
+            "callback_type": transaction.callback_type,
+            "callback_status": transaction.callback_status,
+            "anchor_transaction_id": transaction.anchor_transaction_id,
+            "memo": transaction.memo,
+            "memo_type": transaction.memo_type,
+            "metadata": transaction.metadata,
+        });
+        Self {
+            source: PayloadSource::Reconstructed,
+            ingest_source: None,
+            headers: serde_json::json!({}),
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    /// The body as JSON; a body that is not valid JSON is returned as a
+    /// string so it can still be inspected.
+    pub fn body_json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&self.body).into_owned())
+        })
+    }
+
+    fn audit_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "pending",
+            "replayed_at": Utc::now(),
+            "payload_source": self.source,
+            "payload": self.body_json(),
+        })
+    }
+}
+
+/// A single field's current and projected value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// What a real replay would change on the transaction row.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayDiff {
+    pub status: FieldChange<String>,
+    pub updated_at: FieldChange<DateTime<Utc>>,
+}
+
+impl ReplayDiff {
+    /// Mirrors the status change applied by `reprocess_webhook`; fields it
+    /// re-applies from a stored payload are not projected.
+    pub fn project(transaction: &Transaction) -> Self {
+        Self {
+            status: FieldChange {
+                from: transaction.status.clone(),
+                to: "pending".to_string(),
+            },
+            updated_at: FieldChange {
+                from: transaction.updated_at,
+                to: Utc::now(),
+            },
+        }
+    }
 }
 
 /// Response for batch replay
@@ -70,11 +260,23 @@ pub struct BatchReplayResponse {
     pub results: Vec<ReplayResult>,
 }
 
-/// Response for listing failed webhooks
+/// A single recorded replay attempt
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReplayHistoryEntry {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub replayed_by: String,
+    pub dry_run: bool,
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub replayed_at: DateTime<Utc>,
+}
+
+/// Response for listing replay history
 #[derive(Debug, Serialize)]
-pub struct FailedWebhooksResponse {
+pub struct ReplayHistoryResponse {
     pub total: i64,
-    pub webhooks: Vec<FailedWebhookInfo>,
+    pub entries: Vec<ReplayHistoryEntry>,
 }
 
 /// Information about a failed webhook from audit logs
@@ -91,12 +293,12 @@ pub struct FailedWebhookInfo {
     pub retry_count: i32,
 }
 
-/// Retrieve the original webhook payload from audit logs
-async fn get_webhook_payload_from_audit(
+/// Loads the transaction and the webhook body to re-deliver: the raw payload
+/// stored at ingest when there is one, otherwise one rebuilt from the row.
+async fn load_replay_payload(
     pool: &PgPool,
     transaction_id: Uuid,
-) -> Result<Transaction, AppError> {
-    // First, try to get the transaction directly
+) -> Result<(Transaction, ReplayPayload), AppError> {
     let transaction = queries::get_transaction(pool, transaction_id)
         .await
         .map_err(|e| match e {
@@ -106,7 +308,12 @@ async fn get_webhook_payload_from_audit(
             _ => AppError::DatabaseError(e.to_string()),
         })?;
 
-    Ok(transaction)
+    let payload = match queries::get_webhook_payload(pool, transaction_id).await? {
+        Some(stored) => ReplayPayload::stored(stored),
+        None => ReplayPayload::reconstruct(&transaction),
+    };
+
+    Ok((transaction, payload))
 }
 
 /// List failed webhook attempts from audit logs
@@ -114,38 +321,21 @@ pub async fn list_failed_webhooks(
     State(pool): State<PgPool>,
     Query(params): Query<ListFailedWebhooksQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let limit = params.limit.min(100);
+    let limit = params.limit.clamp(1, 100);
+    let offset = params.offset.max(0);
 
     // Build query to find transactions with failed status or in DLQ
     let mut query_builder = sqlx::QueryBuilder::new(
-        "SELECT t.id, t.stellar_account, t.amount, t.asset_code, 
+        "SELECT t.id, t.stellar_account, t.amount, t.asset_code,
                 t.anchor_transaction_id, t.status, t.created_at,
                 COALESCE(d.retry_count, 0) as retry_count,
-                d.error_reason as last_error
-         FROM transactions t
-         LEFT JOIN transaction_dlq d ON t.id = d.transaction_id
-         WHERE (t.status = 'failed' OR d.id IS NOT NULL)",
+                d.error_reason as last_error",
     );
-
-    if let Some(asset_code) = &params.asset_code {
-        query_builder.push(" AND t.asset_code = ");
-        query_builder.push_bind(asset_code);
-    }
-
-    if let Some(from_date) = params.from_date {
-        query_builder.push(" AND t.created_at >= ");
-        query_builder.push_bind(from_date);
-    }
-
-    if let Some(to_date) = params.to_date {
-        query_builder.push(" AND t.created_at <= ");
-        query_builder.push_bind(to_date);
-    }
-
-    query_builder.push(" ORDER BY t.created_at DESC LIMIT ");
+    params.push_filters(&mut query_builder);
+    query_builder.push(" ORDER BY t.created_at DESC, t.id LIMIT ");
     query_builder.push_bind(limit);
     query_builder.push(" OFFSET ");
-    query_builder.push_bind(params.offset);
+    query_builder.push_bind(offset);
 
     let query = query_builder.build();
     let rows = query.fetch_all(&pool).await?;
@@ -165,22 +355,118 @@ pub async fn list_failed_webhooks(
         })
         .collect();
 
-    // Get total count
-    let count_query = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM transactions t
-         LEFT JOIN transaction_dlq d ON t.id = d.transaction_id
-         WHERE (t.status = 'failed' OR d.id IS NOT NULL)",
+    // Count with the same filters so `total` describes the filtered set
+    let mut count_builder = sqlx::QueryBuilder::new("SELECT COUNT(*)");
+    params.push_filters(&mut count_builder);
+    let total: i64 = count_builder.build_query_scalar().fetch_one(&pool).await?;
+
+    Ok(Json(Page::new(webhooks, total, limit, offset)))
+}
+
+/// List recorded replay attempts, newest first
+pub async fn list_replay_history(
+    State(pool): State<PgPool>,
+    Query(params): Query<ReplayHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let limit = params.limit.clamp(1, 100);
+
+    let mut query_builder = sqlx::QueryBuilder::new(
+        "SELECT id, transaction_id, replayed_by, dry_run, success, error_message, replayed_at
+         FROM webhook_replay_history",
     );
+    params.push_filters(&mut query_builder);
+    query_builder.push(" ORDER BY replayed_at DESC, id LIMIT ");
+    query_builder.push_bind(limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(params.offset.max(0));
+    let entries = query_builder
+        .build_query_as::<ReplayHistoryEntry>()
+        .fetch_all(&pool)
+        .await?;
+
+    let mut count_builder = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM webhook_replay_history");
+    params.push_filters(&mut count_builder);
+    let total: i64 = count_builder.build_query_scalar().fetch_one(&pool).await?;
 
-    let total = count_query.fetch_one(&pool).await.unwrap_or(0);
+    Ok(Json(ReplayHistoryResponse { total, entries }))
+}
+
+/// Outcome of trying to take the replay lock of one transaction.
+enum ReplayLock {
+    Held(Lock),
+    /// Another replay of the same transaction holds the lock.
+    Busy,
+    /// No lock manager, or Redis is unreachable: the replay runs unlocked.
+    Unavailable,
+}
 
-    Ok(Json(FailedWebhooksResponse { total, webhooks }))
+/// Takes the replay lock of `transaction_id` without waiting, so a second
+/// concurrent replay (e.g. a double-click) is turned away instead of
+/// reprocessing the transaction again.
+async fn try_lock_replay(lock_manager: Option<&LockManager>, transaction_id: Uuid) -> ReplayLock {
+    let Some(lock_manager) = lock_manager else {
+        return ReplayLock::Unavailable;
+    };
+    match lock_manager
+        .acquire(&format!("webhook_replay:{transaction_id}"), Duration::ZERO)
+        .await
+    {
+        Ok(Some(lock)) => ReplayLock::Held(lock),
+        Ok(None) => ReplayLock::Busy,
+        Err(e) => {
+            tracing::warn!(
+                %transaction_id,
+                error = %e,
+                "Replay lock unavailable, replaying without it"
+            );
+            ReplayLock::Unavailable
+        }
+    }
+}
+
+/// Takes the replay lock for a real replay of `transaction_id`; dry runs
+/// write nothing and take none. `Err` carries the unsuccessful result to
+/// report when another replay of the transaction holds the lock.
+async fn lock_replay(
+    lock_manager: Option<&LockManager>,
+    transaction_id: Uuid,
+    dry_run: bool,
+) -> Result<Option<Lock>, ReplayResult> {
+    if dry_run {
+        return Ok(None);
+    }
+    match try_lock_replay(lock_manager, transaction_id).await {
+        ReplayLock::Held(lock) => Ok(Some(lock)),
+        ReplayLock::Unavailable => Ok(None),
+        ReplayLock::Busy => Err(ReplayResult {
+            transaction_id,
+            success: false,
+            message: format!("Replay already in progress for transaction {transaction_id}"),
+            dry_run: false,
+            replayed_at: None,
+            diff: None,
+            payload_source: None,
+            payload: None,
+        }),
+    }
+}
+
+async fn release_replay_lock(lock: Option<Lock>, transaction_id: Uuid) {
+    if let Some(lock) = lock {
+        if let Err(e) = lock.release().await {
+            tracing::warn!(%transaction_id, error = %e, "Failed to release replay lock");
+        }
+    }
 }
 
 /// Replay a single webhook by transaction ID
+///
+/// Real replays of the same transaction are serialized through the
+/// [`LockManager`]: while one is running, another gets `409 Conflict`.
 pub async fn replay_webhook(
     State(pool): State<PgPool>,
     Path(transaction_id): Path<Uuid>,
+    lock_manager: Option<Extension<Arc<LockManager>>>,
     Json(request): Json<ReplayWebhookRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     tracing::info!(
@@ -189,33 +475,40 @@ pub async fn replay_webhook(
         request.dry_run
     );
 
-    // Retrieve the original payload from audit logs
-    let transaction = get_webhook_payload_from_audit(&pool, transaction_id).await?;
+    let lock_manager = lock_manager.as_ref().map(|Extension(m)| m.as_ref());
+    let lock = match lock_replay(lock_manager, transaction_id, request.dry_run).await {
+        Ok(lock) => lock,
+        Err(busy) => return Ok((StatusCode::CONFLICT, Json(busy))),
+    };
+
+    let result = replay_single(&pool, transaction_id, request.dry_run).await;
+    release_replay_lock(lock, transaction_id).await;
+
+    Ok((StatusCode::OK, Json(result?)))
+}
+
+/// Body of [`replay_webhook`], run while holding the replay lock.
+async fn replay_single(
+    pool: &PgPool,
+    transaction_id: Uuid,
+    dry_run: bool,
+) -> Result<ReplayResult, AppError> {
+    // Retrieve the original payload
+    let (transaction, payload) = load_replay_payload(pool, transaction_id).await?;
 
     // Validate that we can replay this transaction
-    if transaction.status == "completed" && !request.dry_run {
-        return Err(AppError::BadRequest(
-            "Cannot replay completed transaction without dry-run mode".to_string(),
-        ));
+    if transaction.status == "completed" && !dry_run {
+        let message = "Cannot replay completed transaction without dry-run mode".to_string();
+        let _ = track_replay_attempt(pool, &transaction, false, false, Some(message.clone())).await;
+        return Err(AppError::BadRequest(message));
     }
 
-    let result = if request.dry_run {
-        // Dry-run mode: validate payload without committing
-        let _ = track_replay_attempt(&pool, transaction_id, true, true, None).await;
-
-        ReplayResult {
-            transaction_id,
-            success: true,
-            message: format!(
-                "Dry-run successful: Would replay webhook for {} {} to {}",
-                transaction.amount, transaction.asset_code, transaction.stellar_account
-            ),
-            dry_run: true,
-            replayed_at: None,
-        }
+    let result = if dry_run {
+        // Dry-run mode: report the projected changes without writing anything
+        dry_run_result(&transaction, &payload)
     } else {
         // Actual replay: reprocess the webhook
-        match reprocess_webhook(&pool, &transaction).await {
+        match reprocess_webhook(pool, &transaction, &payload).await {
             Ok(_) => {
                 // Log the replay attempt in audit logs
                 let mut db_tx = pool.begin().await.map_err(|e| {
@@ -230,10 +523,7 @@ pub async fn replay_webhook(
                     Some(serde_json::json!({
                         "status": transaction.status,
                     })),
-                    Some(serde_json::json!({
-                        "status": "pending",
-                        "replayed_at": Utc::now(),
-                    })),
+                    Some(payload.audit_value()),
                     "admin",
                 )
                 .await?;
@@ -243,7 +533,7 @@ pub async fn replay_webhook(
                 })?;
 
                 // Track replay in history table
-                let _ = track_replay_attempt(&pool, transaction_id, false, true, None).await;
+                let _ = track_replay_attempt(pool, &transaction, false, true, None).await;
 
                 ReplayResult {
                     transaction_id,
@@ -251,18 +541,16 @@ pub async fn replay_webhook(
                     message: "Webhook replayed successfully".to_string(),
                     dry_run: false,
                     replayed_at: Some(Utc::now()),
+                    diff: None,
+                    payload_source: Some(payload.source),
+                    payload: Some(payload.body_json()),
                 }
             }
             Err(e) => {
                 let error_msg = format!("Failed to replay webhook: {e}");
-                let _ = track_replay_attempt(
-                    &pool,
-                    transaction_id,
-                    false,
-                    false,
-                    Some(error_msg.clone()),
-                )
-                .await;
+                let _ =
+                    track_replay_attempt(pool, &transaction, false, false, Some(error_msg.clone()))
+                        .await;
 
                 ReplayResult {
                     transaction_id,
@@ -270,156 +558,267 @@ pub async fn replay_webhook(
                     message: error_msg,
                     dry_run: false,
                     replayed_at: None,
+                    diff: None,
+                    payload_source: None,
+                    payload: None,
                 }
             }
         }
     };
 
-    Ok((StatusCode::OK, Json(result)))
+    Ok(result)
+}
+
+/// Ceiling and parallelism for [`batch_replay_webhooks`], loaded into
+/// [`Config`](crate::config::Config) at startup.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchReplayLimits {
+    /// Largest accepted `transaction_ids` list; bigger batches get `400`.
+    pub max_batch_size: usize,
+    /// Replays running at the same time within one batch.
+    pub concurrency: usize,
+}
+
+impl Default for BatchReplayLimits {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 500,
+            concurrency: 8,
+        }
+    }
 }
 
 /// Replay multiple webhooks in batch
+///
+/// Each real replay takes the same per-transaction lock as
+/// [`replay_webhook`]; an entry whose transaction is already being replayed
+/// is reported as unsuccessful.
 pub async fn batch_replay_webhooks(
     State(pool): State<PgPool>,
+    Extension(limits): Extension<BatchReplayLimits>,
+    lock_manager: Option<Extension<Arc<LockManager>>>,
     Json(request): Json<BatchReplayRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    let lock_manager = lock_manager.map(|Extension(m)| m);
+    let response = replay_batch(&pool, lock_manager, request, limits).await?;
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Replays every distinct id in `request` with at most `limits.concurrency`
+/// in flight. Repeated ids are replayed once; results keep the order in
+/// which each id first appears in `request.transaction_ids`.
+pub async fn replay_batch(
+    pool: &PgPool,
+    lock_manager: Option<Arc<LockManager>>,
+    request: BatchReplayRequest,
+    limits: BatchReplayLimits,
+) -> Result<BatchReplayResponse, AppError> {
+    let mut seen = std::collections::HashSet::new();
+    let transaction_ids: Vec<Uuid> = request
+        .transaction_ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    if transaction_ids.len() > limits.max_batch_size {
+        return Err(AppError::BadRequest(format!(
+            "Batch of {} exceeds the maximum of {} transactions",
+            transaction_ids.len(),
+            limits.max_batch_size
+        )));
+    }
+
     tracing::info!(
-        "Batch replaying {} webhooks (dry_run: {})",
-        request.transaction_ids.len(),
-        request.dry_run
+        "Batch replaying {} webhooks (dry_run: {}, concurrency: {})",
+        transaction_ids.len(),
+        request.dry_run,
+        limits.concurrency
     );
 
-    let mut results = Vec::new();
-    let mut successful = 0;
-    let mut failed = 0;
+    let dry_run = request.dry_run;
+    let mut pending = transaction_ids.into_iter().enumerate();
+    let mut in_flight = tokio::task::JoinSet::new();
+    let mut slots: Vec<Option<ReplayResult>> = Vec::new();
+    slots.resize_with(pending.len(), || None);
+
+    loop {
+        while in_flight.len() < limits.concurrency.max(1) {
+            let Some((index, transaction_id)) = pending.next() else {
+                break;
+            };
+            let pool = pool.clone();
+            let lock_manager = lock_manager.clone();
+            in_flight.spawn(async move {
+                let lock = match lock_replay(lock_manager.as_deref(), transaction_id, dry_run).await
+                {
+                    Ok(lock) => lock,
+                    Err(busy) => return (index, busy),
+                };
+                let result = replay_batch_item(&pool, transaction_id, dry_run).await;
+                release_replay_lock(lock, transaction_id).await;
+                (index, result)
+            });
+        }
 
-    for transaction_id in request.transaction_ids {
-        // Retrieve the original payload
-        let transaction = match get_webhook_payload_from_audit(&pool, transaction_id).await {
-            Ok(tx) => tx,
-            Err(e) => {
-                failed += 1;
-                results.push(ReplayResult {
-                    transaction_id,
-                    success: false,
-                    message: format!("Failed to retrieve transaction: {e}"),
-                    dry_run: request.dry_run,
-                    replayed_at: None,
-                });
-                continue;
+        match in_flight.join_next().await {
+            Some(Ok((index, result))) => slots[index] = Some(result),
+            Some(Err(e)) => {
+                return Err(AppError::Internal(format!("Replay task failed: {e}")));
             }
-        };
+            None => break,
+        }
+    }
 
-        // Validate that we can replay this transaction
-        if transaction.status == "completed" && !request.dry_run {
-            failed += 1;
-            results.push(ReplayResult {
+    let results: Vec<ReplayResult> = slots.into_iter().flatten().collect();
+    let successful = results.iter().filter(|r| r.success).count();
+
+    Ok(BatchReplayResponse {
+        total: results.len(),
+        successful,
+        failed: results.len() - successful,
+        results,
+    })
+}
+
+/// Replays a single batch entry, turning every failure into an
+/// unsuccessful [`ReplayResult`] rather than an error. Runs while holding
+/// the entry's replay lock.
+async fn replay_batch_item(pool: &PgPool, transaction_id: Uuid, dry_run: bool) -> ReplayResult {
+    // Retrieve the original payload
+    let (transaction, payload) = match load_replay_payload(pool, transaction_id).await {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            return ReplayResult {
                 transaction_id,
                 success: false,
-                message: "Cannot replay completed transaction without dry-run mode".to_string(),
-                dry_run: request.dry_run,
+                message: format!("Failed to retrieve transaction: {e}"),
+                dry_run,
                 replayed_at: None,
-            });
-            continue;
+                diff: None,
+                payload_source: None,
+                payload: None,
+            };
         }
+    };
+
+    // Validate that we can replay this transaction
+    if transaction.status == "completed" && !dry_run {
+        let message = "Cannot replay completed transaction without dry-run mode".to_string();
+        let _ = track_replay_attempt(pool, &transaction, false, false, Some(message.clone())).await;
+        return ReplayResult {
+            transaction_id,
+            success: false,
+            message,
+            dry_run,
+            replayed_at: None,
+            diff: None,
+            payload_source: None,
+            payload: None,
+        };
+    }
+
+    if dry_run {
+        return dry_run_result(&transaction, &payload);
+    }
+
+    match reprocess_webhook(pool, &transaction, &payload).await {
+        Ok(_) => {
+            // Log the replay attempt
+            if let Ok(mut db_tx) = pool.begin().await {
+                let _ = crate::db::audit::AuditLog::log(
+                    &mut db_tx,
+                    transaction_id,
+                    crate::db::audit::ENTITY_TRANSACTION,
+                    "webhook_replayed",
+                    Some(serde_json::json!({
+                        "status": transaction.status,
+                    })),
+                    Some(payload.audit_value()),
+                    "admin",
+                )
+                .await;
+                let _ = db_tx.commit().await;
+            }
 
-        let result = if request.dry_run {
-            let _ = track_replay_attempt(&pool, transaction_id, true, true, None).await;
-            successful += 1;
+            let _ = track_replay_attempt(pool, &transaction, false, true, None).await;
             ReplayResult {
                 transaction_id,
                 success: true,
-                message: format!(
-                    "Dry-run successful: Would replay webhook for {} {} to {}",
-                    transaction.amount, transaction.asset_code, transaction.stellar_account
-                ),
-                dry_run: true,
-                replayed_at: None,
+                message: "Webhook replayed successfully".to_string(),
+                dry_run: false,
+                replayed_at: Some(Utc::now()),
+                diff: None,
+                payload_source: Some(payload.source),
+                payload: Some(payload.body_json()),
             }
-        } else {
-            match reprocess_webhook(&pool, &transaction).await {
-                Ok(_) => {
-                    // Log the replay attempt
-                    if let Ok(mut db_tx) = pool.begin().await {
-                        let _ = crate::db::audit::AuditLog::log(
-                            &mut db_tx,
-                            transaction_id,
-                            crate::db::audit::ENTITY_TRANSACTION,
-                            "webhook_replayed",
-                            Some(serde_json::json!({
-                                "status": transaction.status,
-                            })),
-                            Some(serde_json::json!({
-                                "status": "pending",
-                                "replayed_at": Utc::now(),
-                            })),
-                            "admin",
-                        )
-                        .await;
-                        let _ = db_tx.commit().await;
-                    }
-
-                    let _ = track_replay_attempt(&pool, transaction_id, false, true, None).await;
-                    successful += 1;
-                    ReplayResult {
-                        transaction_id,
-                        success: true,
-                        message: "Webhook replayed successfully".to_string(),
-                        dry_run: false,
-                        replayed_at: Some(Utc::now()),
-                    }
-                }
-                Err(e) => {
-                    let error_msg = format!("Failed to replay webhook: {e}");
-                    let _ = track_replay_attempt(
-                        &pool,
-                        transaction_id,
-                        false,
-                        false,
-                        Some(error_msg.clone()),
-                    )
-                    .await;
-                    failed += 1;
-                    ReplayResult {
-                        transaction_id,
-                        success: false,
-                        message: error_msg,
-                        dry_run: false,
-                        replayed_at: None,
-                    }
-                }
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to replay webhook: {e}");
+            let _ = track_replay_attempt(pool, &transaction, false, false, Some(error_msg.clone()))
+                .await;
+            ReplayResult {
+                transaction_id,
+                success: false,
+                message: error_msg,
+                dry_run: false,
+                replayed_at: None,
+                diff: None,
+                payload_source: None,
+                payload: None,
             }
-        };
-
-        results.push(result);
+        }
     }
+}
 
-    let response = BatchReplayResponse {
-        total: results.len(),
-        successful,
-        failed,
-        results,
-    };
-
-    Ok((StatusCode::OK, Json(response)))
+/// Builds the dry-run result for `transaction`, including the body a real
+/// replay would re-deliver. Nothing is written: no status change, audit entry
+/// or replay-history row.
+fn dry_run_result(transaction: &Transaction, payload: &ReplayPayload) -> ReplayResult {
+    let diff = ReplayDiff::project(transaction);
+    ReplayResult {
+        transaction_id: transaction.id,
+        success: true,
+        message: format!(
+            "Dry-run successful: Would replay webhook for {} {} to {} ({} -> {})",
+            transaction.amount,
+            transaction.asset_code,
+            transaction.stellar_account,
+            diff.status.from,
+            diff.status.to
+        ),
+        dry_run: true,
+        replayed_at: None,
+        diff: Some(diff),
+        payload_source: Some(payload.source),
+        payload: Some(payload.body_json()),
+    }
 }
 
-/// Reprocess a webhook by updating its status to pending
-/// This respects idempotency keys and existing transaction state
-async fn reprocess_webhook(pool: &PgPool, transaction: &Transaction) -> Result<(), AppError> {
-    // Update transaction status to pending for reprocessing
-    sqlx::query(
-        "UPDATE transactions 
-         SET status = 'pending', updated_at = NOW() 
-         WHERE id = $1",
-    )
-    .bind(transaction.id)
-    .execute(pool)
-    .await?;
+/// Re-delivers `payload` to `transaction` and requeues it for processing.
+///
+/// A stored payload is decoded through the same validation as at ingest and
+/// its fields are written back to the row, so the processor works from what
+/// the anchor originally sent. A reconstructed payload carries nothing the
+/// row does not already have, so only the status is reset to pending.
+async fn reprocess_webhook(
+    pool: &PgPool,
+    transaction: &Transaction,
+    payload: &ReplayPayload,
+) -> Result<(), AppError> {
+    match &payload.ingest_source {
+        Some(source) => {
+            let redelivered = transaction_from_stored_payload(source, &payload.body)?;
+            queries::reapply_webhook_payload(pool, transaction.id, &redelivered).await?;
+        }
+        None => {
+            queries::update_transaction_status(pool, transaction.id, "pending").await?;
+        }
+    }
 
     tracing::info!(
-        "Transaction {} status updated to pending for reprocessing",
-        transaction.id
+        transaction_id = %transaction.id,
+        payload_source = ?payload.source,
+        "Webhook re-applied and transaction requeued as pending"
     );
 
     Ok(())
@@ -428,19 +827,20 @@ async fn reprocess_webhook(pool: &PgPool, transaction: &Transaction) -> Result<(
 /// Track replay attempt in the database
 async fn track_replay_attempt(
     pool: &PgPool,
-    transaction_id: Uuid,
+    transaction: &Transaction,
     dry_run: bool,
     success: bool,
     error_message: Option<String>,
 ) -> Result<(), AppError> {
     sqlx::query(
         r#"
-        INSERT INTO webhook_replay_history 
-        (transaction_id, replayed_by, dry_run, success, error_message, replayed_at)
-        VALUES ($1, $2, $3, $4, $5, NOW())
+        INSERT INTO webhook_replay_history
+        (transaction_id, transaction_created_at, replayed_by, dry_run, success, error_message, replayed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW())
         "#,
     )
-    .bind(transaction_id)
+    .bind(transaction.id)
+    .bind(transaction.created_at)
     .bind("admin")
     .bind(dry_run)
     .bind(success)
@@ -468,6 +868,9 @@ mod tests {
             message: "Test message".to_string(),
             dry_run: false,
             replayed_at: Some(Utc::now()),
+            diff: None,
+            payload_source: None,
+            payload: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -475,6 +878,64 @@ mod tests {
         assert!(json.contains("success"));
     }
 
+    #[test]
+    fn test_dry_run_diff_projects_failed_to_pending() {
+        let mut tx = Transaction::new(
+            "G".to_string() + &"A".repeat(55),
+            "10".parse().unwrap(),
+            "USDC".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        tx.status = "failed".to_string();
+
+        let result = dry_run_result(&tx, &ReplayPayload::reconstruct(&tx));
+        let diff = result.diff.expect("dry run carries a diff");
+        assert_eq!(
+            diff.status,
+            FieldChange {
+                from: "failed".to_string(),
+                to: "pending".to_string()
+            }
+        );
+        assert_eq!(diff.updated_at.from, tx.updated_at);
+        assert!(diff.updated_at.to >= tx.updated_at);
+        assert!(result.dry_run);
+        assert!(result.replayed_at.is_none());
+        assert_eq!(result.payload_source, Some(PayloadSource::Reconstructed));
+        assert_eq!(result.payload.unwrap()["asset_code"], "USDC");
+    }
+
+    #[test]
+    fn test_stored_payload_is_replayed_verbatim() {
+        let body = br#"{"amount":"10","asset_code":"USDC","anchor_extra":{"k":"v"}}"#;
+        let payload = ReplayPayload::stored(StoredWebhookPayload {
+            transaction_id: Uuid::new_v4(),
+            source: "callback".to_string(),
+            headers: serde_json::json!({"content-type": "application/json"}),
+            body: body.to_vec(),
+            received_at: Utc::now(),
+        });
+
+        assert_eq!(payload.source, PayloadSource::Stored);
+        assert_eq!(payload.body, body);
+        assert_eq!(payload.body_json()["anchor_extra"]["k"], "v");
+        let audit = payload.audit_value();
+        assert_eq!(audit["payload_source"], "stored");
+        assert_eq!(audit["payload"]["anchor_extra"]["k"], "v");
+    }
+
+    #[test]
+    fn test_batch_replay_limits_default() {
+        let limits = BatchReplayLimits::default();
+        assert_eq!(limits.max_batch_size, 500);
+        assert_eq!(limits.concurrency, 8);
+    }
+
     #[test]
     fn test_batch_replay_response_serialization() {
         let response = BatchReplayResponse {
@@ -1,34 +1,37 @@
+use crate::db::queries::TransactionFilters;
 use crate::error::AppError;
+use crate::handlers::search::TransactionFilterParams;
+use crate::services::export_jobs::{self, ExportJob, ExportJobService};
+use crate::tenant::OptionalTenant;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::{header, header::HeaderValue, HeaderMap, StatusCode},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
+    Json,
 };
 use chrono::{DateTime, Utc};
-use csv::Writer;
+use csv::WriterBuilder;
 use futures::stream::{Stream, StreamExt};
 use serde::Deserialize;
 use serde::Serialize;
+use sqlx::types::BigDecimal;
 use sqlx::{PgPool, Row};
 use std::pin::Pin;
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::db::models::Transaction;
 
 /// Query parameters for the export endpoint
 #[derive(Debug, Deserialize, Clone)]
 pub struct ExportQuery {
-    /// Export format: "csv" or "json"
+    /// Export format: "csv", "json" (a single enveloped document) or
+    /// "ndjson" (one record per line, streamed)
     #[serde(default = "default_format")]
     pub format: String,
-    /// Start date filter (inclusive) - format: YYYY-MM-DD
-    pub from: Option<String>,
-    /// End date filter (inclusive) - format: YYYY-MM-DD
-    pub to: Option<String>,
-    /// Filter by transaction status
-    pub status: Option<String>,
-    /// Filter by asset code
-    pub asset_code: Option<String>,
+    /// The same filters `GET /transactions/search` accepts
+    #[serde(flatten)]
+    pub filters: TransactionFilterParams,
 }
 
 fn default_format() -> String {
@@ -39,33 +42,23 @@ impl Default for ExportQuery {
     fn default() -> Self {
         Self {
             format: default_format(),
-            from: None,
-            to: None,
-            status: None,
-            asset_code: None,
+            filters: TransactionFilterParams::default(),
         }
     }
 }
 
 impl ExportQuery {
-    /// Validate the export query parameters before running the export.
-    pub fn validate(&self) -> Result<(), AppError> {
+    /// Validate the export query parameters before running the export,
+    /// returning the parsed filters.
+    pub fn validate(&self) -> Result<TransactionFilters, AppError> {
         let format = self.format.to_lowercase();
-        if format != "csv" && format != "json" {
+        if !matches!(format.as_str(), "csv" | "json" | "ndjson") {
             return Err(AppError::Validation(
-                "Export format must be either 'csv' or 'json'".to_string(),
+                "Export format must be one of 'csv', 'json' or 'ndjson'".to_string(),
             ));
         }
 
-        if let Some(ref from) = self.from {
-            parse_date(from).map(|_| ()).map_err(AppError::Validation)?;
-        }
-
-        if let Some(ref to) = self.to {
-            parse_date(to).map(|_| ()).map_err(AppError::Validation)?;
-        }
-
-        Ok(())
+        self.filters.parse()
     }
 }
 
@@ -136,71 +129,71 @@ impl From<&Transaction> for TransactionJsonRow {
 /// Batch size for cursor-based streaming
 const BATCH_SIZE: i64 = 1000;
 
+/// Version of the export record layout. Bump when a field is renamed,
+/// removed or changes type.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Response header carrying [`EXPORT_SCHEMA_VERSION`] on every export.
+pub const EXPORT_SCHEMA_VERSION_HEADER: &str = "x-export-schema-version";
+
 /// Type alias for the stream of CSV rows
 type CsvStream = Pin<Box<dyn Stream<Item = Result<String, sqlx::Error>> + Send>>;
 
 /// Type alias for the stream of JSON rows
 type JsonStream = Pin<Box<dyn Stream<Item = Result<String, sqlx::Error>> + Send>>;
 
-/// Parse date string to DateTime<Utc>
-fn parse_date(date_str: &str) -> Result<DateTime<Utc>, String> {
-    // Handle both YYYY-MM-DD and YYYY-MM-DDTHH:MM:SSZ formats
-    let date_str = if date_str.len() == 10 {
-        format!("{date_str}T00:00:00Z")
-    } else {
-        date_str.to_string()
-    };
-
-    DateTime::parse_from_rfc3339(&date_str)
-        .map(|dt| dt.with_timezone(&Utc))
-        .map_err(|e| format!("Invalid date format: {e}"))
-}
-
-/// Build SQL filter conditions based on query parameters
+/// Build SQL filter conditions from the parsed filters, restricted to
+/// `tenant_id`'s transactions when given. Soft-deleted transactions are
+/// always excluded, as they are from search.
 fn build_filter_conditions(
-    from: &Option<String>,
-    to: &Option<String>,
-    status: &Option<String>,
-    asset_code: &Option<String>,
+    filters: &TransactionFilters,
+    tenant_id: Option<Uuid>,
 ) -> (String, Vec<FilterValue>) {
-    let mut conditions = Vec::new();
+    let mut conditions = vec!["deleted_at IS NULL".to_string()];
     let mut params = Vec::new();
-    let mut param_count = 1;
 
-    if let Some(ref from_date) = from {
-        if let Ok(parsed) = parse_date(from_date) {
-            conditions.push(format!("created_at >= ${param_count}"));
-            params.push(FilterValue::DateTime(parsed));
-            param_count += 1;
-        }
+    if let Some(tenant_id) = tenant_id {
+        params.push(FilterValue::Uuid(tenant_id));
+        conditions.push(format!("tenant_id = ${}", params.len()));
     }
 
-    if let Some(ref to_date) = to {
-        if let Ok(parsed) = parse_date(to_date) {
-            // Add one day to include the entire end date
-            let end_of_day = parsed + chrono::Duration::days(1);
-            conditions.push(format!("created_at < ${param_count}"));
-            params.push(FilterValue::DateTime(end_of_day));
-            param_count += 1;
-        }
+    if let Some(from) = filters.from {
+        params.push(FilterValue::DateTime(from));
+        conditions.push(format!("created_at >= ${}", params.len()));
+    }
+
+    if let Some(to) = filters.to {
+        params.push(FilterValue::DateTime(to));
+        conditions.push(format!("created_at <= ${}", params.len()));
     }
 
-    if let Some(ref status_val) = status {
-        conditions.push(format!("status = ${param_count}"));
-        params.push(FilterValue::String(status_val.clone()));
-        param_count += 1;
+    if !filters.statuses.is_empty() {
+        params.push(FilterValue::Strings(filters.statuses.clone()));
+        conditions.push(format!("status = ANY(${})", params.len()));
     }
 
-    if let Some(ref asset) = asset_code {
-        conditions.push(format!("asset_code = ${param_count}"));
+    if let Some(ref asset) = filters.asset_code {
         params.push(FilterValue::String(asset.clone()));
+        conditions.push(format!("asset_code = ${}", params.len()));
     }
 
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", conditions.join(" AND "))
-    };
+    if let Some(ref account) = filters.stellar_account {
+        params.push(FilterValue::String(account.clone()));
+        conditions.push(format!("stellar_account = ${}", params.len()));
+    }
+
+    // Compared as NUMERIC, like search, so bounds stay exact and inclusive.
+    if let Some(ref min) = filters.min_amount {
+        params.push(FilterValue::Decimal(min.clone()));
+        conditions.push(format!("amount::numeric >= ${}::numeric", params.len()));
+    }
+
+    if let Some(ref max) = filters.max_amount {
+        params.push(FilterValue::Decimal(max.clone()));
+        conditions.push(format!("amount::numeric <= ${}::numeric", params.len()));
+    }
+
+    let where_clause = format!("WHERE {}", conditions.join(" AND "));
 
     (where_clause, params)
 }
@@ -208,16 +201,19 @@ fn build_filter_conditions(
 /// Filter value enum for dynamic parameter handling
 enum FilterValue {
     String(String),
+    Strings(Vec<String>),
+    Decimal(BigDecimal),
     DateTime(DateTime<Utc>),
+    Uuid(Uuid),
 }
 
 /// Create a CSV stream from database rows - truly streaming without buffering
-fn create_csv_stream(
+///
+/// With `tenant_id`, only that tenant's transactions are exported.
+pub(crate) fn create_csv_stream(
     pool: Arc<PgPool>,
-    from: Option<String>,
-    to: Option<String>,
-    status: Option<String>,
-    asset_code: Option<String>,
+    filters: TransactionFilters,
+    tenant_id: Option<Uuid>,
 ) -> CsvStream {
     let pool_clone = pool.clone();
 
@@ -225,27 +221,23 @@ fn create_csv_stream(
         let mut last_id: Option<uuid::Uuid> = None;
 
         // First, write CSV header
-        let headers = "id,stellar_account,amount,asset_code,status,created_at,updated_at,anchor_transaction_id,callback_type,callback_status";
+        let headers = "id,stellar_account,amount,asset_code,status,created_at,updated_at,anchor_transaction_id,callback_type,callback_status\n";
         yield Ok(headers.to_string());
 
         loop {
             // Build base query with filters
-            let (where_clause, params) = build_filter_conditions(&from, &to, &status, &asset_code);
+            let (where_clause, params) = build_filter_conditions(&filters, tenant_id);
 
             let mut sql = format!(
                 "SELECT id, stellar_account, amount, asset_code, status, created_at, updated_at,
                         anchor_transaction_id, callback_type, callback_status, settlement_id,
-                        memo, memo_type, metadata
+                        memo, memo_type, metadata, asset_issuer
                  FROM transactions {where_clause}"
             );
 
             // Add cursor and limit
             if let Some(id) = last_id {
-                if where_clause.is_empty() {
-                    sql = format!("{sql} WHERE id > '{id}' ORDER BY id ASC LIMIT {BATCH_SIZE}");
-                } else {
-                    sql = format!("{sql} AND id > '{id}' ORDER BY id ASC LIMIT {BATCH_SIZE}");
-                }
+                sql = format!("{sql} AND id > '{id}' ORDER BY id ASC LIMIT {BATCH_SIZE}");
             } else {
                 sql = format!("{sql} ORDER BY id ASC LIMIT {BATCH_SIZE}");
             }
@@ -259,9 +251,18 @@ fn create_csv_stream(
                     FilterValue::String(s) => {
                         query = query.bind(s.clone());
                     }
+                    FilterValue::Strings(values) => {
+                        query = query.bind(values.clone());
+                    }
+                    FilterValue::Decimal(d) => {
+                        query = query.bind(d.clone());
+                    }
                     FilterValue::DateTime(dt) => {
                         query = query.bind(*dt);
                     }
+                    FilterValue::Uuid(id) => {
+                        query = query.bind(*id);
+                    }
                 }
             }
 
@@ -289,12 +290,16 @@ fn create_csv_stream(
                             memo_type: row.get("memo_type"),
                             metadata: row.get("metadata"),
                             trace_id: None,
+                            asset_issuer: row.get("asset_issuer"),
+                            deleted_at: None,
+                            tenant_id: None,
                         };
 
                         last_id = Some(tx.id);
 
                         let csv_row = TransactionCsvRow::from(&tx);
-                        let mut wtr = Writer::from_writer(vec![]);
+                        // The header was emitted once above; don't repeat it per row.
+                        let mut wtr = WriterBuilder::new().has_headers(false).from_writer(vec![]);
                         wtr.serialize(csv_row).unwrap();
                         let csv_line = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
                         yield Ok(csv_line);
@@ -314,12 +319,12 @@ fn create_csv_stream(
 }
 
 /// Create a JSON stream from database rows - truly streaming without buffering
-fn create_json_stream(
+///
+/// With `tenant_id`, only that tenant's transactions are exported.
+pub(crate) fn create_json_stream(
     pool: Arc<PgPool>,
-    from: Option<String>,
-    to: Option<String>,
-    status: Option<String>,
-    asset_code: Option<String>,
+    filters: TransactionFilters,
+    tenant_id: Option<Uuid>,
 ) -> JsonStream {
     let pool_clone = pool.clone();
 
@@ -328,22 +333,18 @@ fn create_json_stream(
 
         loop {
             // Build base query with filters
-            let (where_clause, params) = build_filter_conditions(&from, &to, &status, &asset_code);
+            let (where_clause, params) = build_filter_conditions(&filters, tenant_id);
 
             let mut sql = format!(
                 "SELECT id, stellar_account, amount, asset_code, status, created_at, updated_at,
                         anchor_transaction_id, callback_type, callback_status, settlement_id,
-                        memo, memo_type, metadata
+                        memo, memo_type, metadata, asset_issuer
                  FROM transactions {where_clause}"
             );
 
             // Add cursor and limit
             if let Some(id) = last_id {
-                if where_clause.is_empty() {
-                    sql = format!("{sql} WHERE id > '{id}' ORDER BY id ASC LIMIT {BATCH_SIZE}");
-                } else {
-                    sql = format!("{sql} AND id > '{id}' ORDER BY id ASC LIMIT {BATCH_SIZE}");
-                }
+                sql = format!("{sql} AND id > '{id}' ORDER BY id ASC LIMIT {BATCH_SIZE}");
             } else {
                 sql = format!("{sql} ORDER BY id ASC LIMIT {BATCH_SIZE}");
             }
@@ -356,9 +357,18 @@ fn create_json_stream(
                     FilterValue::String(s) => {
                         query = query.bind(s.clone());
                     }
+                    FilterValue::Strings(values) => {
+                        query = query.bind(values.clone());
+                    }
+                    FilterValue::Decimal(d) => {
+                        query = query.bind(d.clone());
+                    }
                     FilterValue::DateTime(dt) => {
                         query = query.bind(*dt);
                     }
+                    FilterValue::Uuid(id) => {
+                        query = query.bind(*id);
+                    }
                 }
             }
 
@@ -386,6 +396,9 @@ fn create_json_stream(
                             memo_type: row.get("memo_type"),
                             metadata: row.get("metadata"),
                             trace_id: None,
+                            asset_issuer: row.get("asset_issuer"),
+                            deleted_at: None,
+                            tenant_id: None,
                         };
 
                         last_id = Some(tx.id);
@@ -408,10 +421,10 @@ fn create_json_stream(
     })
 }
 
-/// Helper function to convert a stream of strings into an Axum response
-/// Note: For production with 100k+ rows, you'd want to use true streaming.
-/// This implementation uses cursor-based pagination in the query but collects
-/// the final result. For true streaming, you'd need to use a different approach.
+/// Helper function to convert a stream of strings into a streamed Axum
+/// response. Rows are written as they are fetched, so memory stays flat for
+/// large exports and the compression layer can encode on the fly. The body
+/// ends at the first database error.
 async fn stream_to_response<S>(
     stream: S,
     content_type: &str,
@@ -422,17 +435,29 @@ where
 {
     use futures::stream::StreamExt;
 
-    // Collect all data from the stream
-    let mut all_data = String::new();
-    // Pin the stream to allow polling
-    let mut pinned_stream = Box::pin(stream);
-    while let Some(result) = pinned_stream.next().await {
-        match result {
-            Ok(s) => all_data.push_str(&s),
-            Err(_) => break,
-        }
-    }
+    let body = stream
+        .take_while(|result| {
+            if let Err(e) = result {
+                tracing::error!("Export stream aborted: {}", e);
+            }
+            futures::future::ready(result.is_ok())
+        })
+        .filter_map(|result| {
+            futures::future::ready(
+                result
+                    .ok()
+                    .map(|s| Ok::<_, std::convert::Infallible>(axum::body::Bytes::from(s))),
+            )
+        });
+
+    let headers = export_headers(content_type, filename);
+
+    Ok((StatusCode::OK, headers, axum::body::StreamBody::new(body)))
+}
 
+/// Content type, attachment filename and schema version headers shared by
+/// every export response.
+fn export_headers(content_type: &str, filename: &str) -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert(
         header::CONTENT_TYPE,
@@ -442,23 +467,47 @@ where
         header::CONTENT_DISPOSITION,
         HeaderValue::from_str(&format!("attachment; filename=\"{filename}\"")).unwrap(),
     );
+    headers.insert(
+        EXPORT_SCHEMA_VERSION_HEADER,
+        HeaderValue::from(EXPORT_SCHEMA_VERSION),
+    );
+    headers
+}
 
-    Ok((StatusCode::OK, headers, all_data))
+/// Collects the JSON records of `stream` into one
+/// `{"schema_version": 1, "count": N, "items": [...]}` document. Unlike the
+/// streamed formats, a database error fails the whole export.
+async fn json_envelope<S>(mut stream: S) -> Result<String, AppError>
+where
+    S: Stream<Item = Result<String, sqlx::Error>> + Unpin,
+{
+    let mut items = String::from("[");
+    let mut count: usize = 0;
+    while let Some(record) = stream.next().await {
+        let record = record.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if count > 0 {
+            items.push(',');
+        }
+        items.push_str(&record);
+        count += 1;
+    }
+    items.push(']');
+
+    Ok(format!(
+        r#"{{"schema_version":{EXPORT_SCHEMA_VERSION},"count":{count},"items":{items}}}"#
+    ))
 }
 
 /// Export transactions as CSV with true streaming
 pub async fn export_transactions_csv(
     State(state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
     Query(query): Query<ExportQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    query.validate()?;
+    let filters = query.validate()?;
     let pool = Arc::new(state.app_state.db);
-    let from = query.from.clone();
-    let to = query.to.clone();
-    let status = query.status.clone();
-    let asset_code = query.asset_code.clone();
 
-    let stream = create_csv_stream(pool, from, to, status, asset_code);
+    let stream = create_csv_stream(pool, filters, tenant.map(|t| t.tenant_id));
 
     // Generate filename with current date
     let filename = format!("transactions_{}.csv", Utc::now().format("%Y-%m"));
@@ -466,56 +515,188 @@ pub async fn export_transactions_csv(
     stream_to_response(stream, "text/csv", &filename).await
 }
 
-/// Export transactions as JSON with true streaming (JSON Lines format)
+/// Export transactions as a single JSON document with a schema envelope
 pub async fn export_transactions_json(
     State(state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
     Query(query): Query<ExportQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    query.validate()?;
+    let filters = query.validate()?;
     let pool = Arc::new(state.app_state.db);
-    let from = query.from.clone();
-    let to = query.to.clone();
-    let status = query.status.clone();
-    let asset_code = query.asset_code.clone();
 
-    let stream = create_json_stream(pool, from, to, status, asset_code);
+    let stream = create_json_stream(pool, filters, tenant.map(|t| t.tenant_id));
 
     // Generate filename with current date
     let filename = format!("transactions_{}.json", Utc::now().format("%Y-%m"));
 
-    stream_to_response(stream, "application/json", &filename).await
+    let body = json_envelope(stream).await?;
+    Ok((
+        StatusCode::OK,
+        export_headers("application/json", &filename),
+        body,
+    ))
+}
+
+/// Newline-terminates each JSON record for NDJSON output.
+fn ndjson_lines(stream: JsonStream) -> JsonStream {
+    Box::pin(stream.map(|record| record.map(|line| line + "\n")))
 }
 
-/// Main export handler that routes to CSV or JSON based on format parameter
+/// Main export handler that routes to CSV, JSON or NDJSON based on the
+/// format parameter
+///
+/// A request that identifies a tenant (`X-API-Key` or `X-Tenant-ID`) exports
+/// only that tenant's transactions; without one every transaction matching
+/// the filters is exported.
 pub async fn export_transactions(
     State(state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
     Query(query): Query<ExportQuery>,
-) -> Result<impl IntoResponse, AppError> {
-    query.validate()?;
+) -> Result<Response, AppError> {
+    let filters = query.validate()?;
     let pool = Arc::new(state.app_state.db);
-    let from = query.from.clone();
-    let to = query.to.clone();
-    let status = query.status.clone();
-    let asset_code = query.asset_code.clone();
     let format = query.format.clone();
+    let tenant_id = tenant.map(|t| t.tenant_id);
 
-    match format.to_lowercase().as_str() {
+    let response = match format.to_lowercase().as_str() {
         "json" => {
-            let stream = create_json_stream(pool, from, to, status, asset_code);
+            let stream = create_json_stream(pool, filters, tenant_id);
             let filename = format!("transactions_{}.json", Utc::now().format("%Y-%m"));
-            stream_to_response(stream, "application/json", &filename).await
+            let body = json_envelope(stream).await?;
+            (
+                StatusCode::OK,
+                export_headers("application/json", &filename),
+                body,
+            )
+                .into_response()
+        }
+        "ndjson" => {
+            let stream = ndjson_lines(create_json_stream(pool, filters, tenant_id));
+            let filename = format!("transactions_{}.ndjson", Utc::now().format("%Y-%m"));
+            stream_to_response(stream, "application/x-ndjson", &filename)
+                .await?
+                .into_response()
         }
         _ => {
-            let stream = create_csv_stream(pool, from, to, status, asset_code);
+            let stream = create_csv_stream(pool, filters, tenant_id);
             let filename = format!("transactions_{}.csv", Utc::now().format("%Y-%m"));
-            stream_to_response(stream, "text/csv", &filename).await
+            stream_to_response(stream, "text/csv", &filename)
+                .await?
+                .into_response()
         }
+    };
+    Ok(response)
+}
+
+/// Export job status, plus a download link once the artifact is ready.
+#[derive(Serialize)]
+pub struct ExportJobResponse {
+    #[serde(flatten)]
+    pub job: ExportJob,
+    pub download_url: Option<String>,
+}
+
+impl From<ExportJob> for ExportJobResponse {
+    fn from(job: ExportJob) -> Self {
+        let download_url = (job.status == export_jobs::STATUS_COMPLETED)
+            .then(|| format!("/export/jobs/{}/download", job.id));
+        Self { job, download_url }
     }
 }
 
+/// POST /export/jobs
+///
+/// Records an export with the same filters as `GET /export` and returns
+/// immediately; the scheduled export worker generates the file in the
+/// background. Poll `GET /export/jobs/:id` for progress. As with `GET /export`,
+/// a request that identifies a tenant only exports that tenant's transactions.
+pub async fn create_export_job(
+    State(state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
+    Json(query): Json<ExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    query.validate()?;
+    let job = export_job_service(&state)
+        .enqueue(&query, tenant.map(|t| t.tenant_id))
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    Ok((StatusCode::ACCEPTED, Json(ExportJobResponse::from(job))))
+}
+
+/// GET /export/jobs/:id
+pub async fn get_export_job(
+    State(state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let job = find_export_job(&state, id, tenant.map(|t| t.tenant_id)).await?;
+    Ok(Json(ExportJobResponse::from(job)))
+}
+
+/// GET /export/jobs/:id/download
+///
+/// Streams the generated artifact from disk. Returns 400 while the job is
+/// still pending or running, or if it failed.
+pub async fn download_export_job(
+    State(state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let job = find_export_job(&state, id, tenant.map(|t| t.tenant_id)).await?;
+    let path = match (job.status.as_str(), job.artifact_path.as_deref()) {
+        (export_jobs::STATUS_COMPLETED, Some(path)) => path,
+        _ => {
+            return Err(AppError::BadRequest(format!(
+                "export job {id} is {}, artifact not available",
+                job.status
+            )))
+        }
+    };
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to read export artifact: {e}")))?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(job.content_type()),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"{}\"", job.filename())).unwrap(),
+    );
+
+    Ok((
+        StatusCode::OK,
+        headers,
+        axum::body::StreamBody::new(tokio_util::io::ReaderStream::new(file)),
+    ))
+}
+
+fn export_job_service(state: &crate::ApiState) -> ExportJobService {
+    ExportJobService::new(state.app_state.db.clone(), &state.app_state.export_jobs_dir)
+}
+
+/// Looks up an export job. A tenant only sees the jobs it created; other
+/// tenants' jobs are reported as not found.
+async fn find_export_job(
+    state: &crate::ApiState,
+    id: Uuid,
+    tenant_id: Option<Uuid>,
+) -> Result<ExportJob, AppError> {
+    export_job_service(state)
+        .get_job(id)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .filter(|job| tenant_id.is_none() || job.tenant_id == tenant_id)
+        .ok_or_else(|| AppError::NotFound(format!("export job {id} not found")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::handlers::search::parse_date;
 
     #[test]
     fn test_default_format() {
@@ -550,6 +731,9 @@ mod tests {
             memo_type: None,
             metadata: None,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         };
 
         let csv_row = TransactionCsvRow::from(&tx);
@@ -578,6 +762,9 @@ mod tests {
             memo_type: None,
             metadata: None,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         };
 
         let json_row = TransactionJsonRow::from(&tx);
@@ -589,20 +776,46 @@ mod tests {
         );
     }
 
+    fn filters(params: TransactionFilterParams) -> TransactionFilters {
+        params.parse().unwrap()
+    }
+
     #[test]
     fn test_build_filter_conditions_no_filters() {
-        let (where_clause, params) = build_filter_conditions(&None, &None, &None, &None);
-        assert!(where_clause.is_empty());
+        let (where_clause, params) = build_filter_conditions(&TransactionFilters::default(), None);
+        assert_eq!(where_clause, "WHERE deleted_at IS NULL");
         assert!(params.is_empty());
     }
 
+    #[test]
+    fn test_build_filter_conditions_scopes_to_tenant() {
+        let tenant_id = Uuid::new_v4();
+        let (where_clause, params) = build_filter_conditions(
+            &filters(TransactionFilterParams {
+                asset_code: Some("USD".to_string()),
+                ..Default::default()
+            }),
+            Some(tenant_id),
+        );
+        assert_eq!(
+            where_clause,
+            "WHERE deleted_at IS NULL AND tenant_id = $1 AND asset_code = $2"
+        );
+        assert!(matches!(params[0], FilterValue::Uuid(id) if id == tenant_id));
+    }
+
     #[test]
     fn test_build_filter_conditions_with_date_range() {
-        let from = Some("2025-01-01".to_string());
-        let to = Some("2025-02-01".to_string());
-        let (where_clause, params) = build_filter_conditions(&from, &to, &None, &None);
+        let (where_clause, params) = build_filter_conditions(
+            &filters(TransactionFilterParams {
+                from: Some("2025-01-01".to_string()),
+                to: Some("2025-02-01".to_string()),
+                ..Default::default()
+            }),
+            None,
+        );
         assert!(where_clause.contains("created_at >="));
-        assert!(where_clause.contains("created_at <"));
+        assert!(where_clause.contains("created_at <="));
         assert_eq!(params.len(), 2);
     }
 
@@ -628,46 +841,89 @@ mod tests {
 
     #[test]
     fn test_build_filter_conditions_status_only() {
-        let status = Some("completed".to_string());
-        let (where_clause, params) = build_filter_conditions(&None, &None, &status, &None);
-        assert!(where_clause.contains("status ="));
+        let (where_clause, params) = build_filter_conditions(
+            &filters(TransactionFilterParams {
+                status: Some("completed".to_string()),
+                ..Default::default()
+            }),
+            None,
+        );
+        assert!(where_clause.contains("status = ANY("));
         assert_eq!(params.len(), 1);
     }
 
     #[test]
     fn test_build_filter_conditions_asset_code_only() {
-        let asset = Some("USDC".to_string());
-        let (where_clause, params) = build_filter_conditions(&None, &None, &None, &asset);
+        let (where_clause, params) = build_filter_conditions(
+            &filters(TransactionFilterParams {
+                asset_code: Some("USDC".to_string()),
+                ..Default::default()
+            }),
+            None,
+        );
         assert!(where_clause.contains("asset_code ="));
         assert_eq!(params.len(), 1);
     }
 
     #[test]
     fn test_build_filter_conditions_all_filters() {
-        let from = Some("2025-01-01".to_string());
-        let to = Some("2025-12-31".to_string());
-        let status = Some("pending".to_string());
-        let asset = Some("USD".to_string());
-        let (where_clause, params) = build_filter_conditions(&from, &to, &status, &asset);
+        let (where_clause, params) = build_filter_conditions(
+            &filters(TransactionFilterParams {
+                from: Some("2025-01-01".to_string()),
+                to: Some("2025-12-31".to_string()),
+                status: Some("pending,failed".to_string()),
+                asset_code: Some("USD".to_string()),
+                stellar_account: Some("GABC".to_string()),
+                min_amount: Some("10".to_string()),
+                max_amount: Some("100.50".to_string()),
+            }),
+            None,
+        );
         assert!(where_clause.starts_with("WHERE "));
-        assert_eq!(params.len(), 4);
+        assert!(where_clause.contains("stellar_account ="));
+        assert!(where_clause.contains("amount::numeric >="));
+        assert!(where_clause.contains("amount::numeric <="));
+        assert_eq!(params.len(), 7);
     }
 
     #[test]
-    fn test_build_filter_conditions_invalid_date_skipped() {
-        // Invalid dates should be silently skipped (no condition added)
-        let from = Some("bad-date".to_string());
-        let (where_clause, params) = build_filter_conditions(&from, &None, &None, &None);
-        assert!(where_clause.is_empty());
-        assert!(params.is_empty());
+    fn test_validate_rejects_invalid_filters() {
+        for filters in [
+            TransactionFilterParams {
+                from: Some("bad-date".to_string()),
+                ..Default::default()
+            },
+            TransactionFilterParams {
+                min_amount: Some("ten".to_string()),
+                ..Default::default()
+            },
+            TransactionFilterParams {
+                min_amount: Some("20".to_string()),
+                max_amount: Some("10".to_string()),
+                ..Default::default()
+            },
+        ] {
+            let q = ExportQuery {
+                filters,
+                ..Default::default()
+            };
+            assert!(q.validate().is_err(), "{q:?}");
+        }
     }
 
     #[test]
-    fn test_to_date_adds_one_day() {
-        // The `to` filter should include the entire end day by adding 1 day
-        let to = Some("2025-06-01".to_string());
-        let (where_clause, _) = build_filter_conditions(&None, &to, &None, &None);
-        assert!(where_clause.contains("created_at <"));
+    fn test_to_date_covers_whole_day() {
+        // A bare `to` date includes the entire end day.
+        let parsed = filters(TransactionFilterParams {
+            to: Some("2025-06-01".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            parsed.to.unwrap(),
+            parse_date("2025-06-02").unwrap() - chrono::Duration::microseconds(1)
+        );
+        let (where_clause, _) = build_filter_conditions(&parsed, None);
+        assert!(where_clause.contains("created_at <="));
     }
 
     #[test]
@@ -691,6 +947,9 @@ mod tests {
             memo_type: None,
             metadata: None,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         };
 
         let row = TransactionCsvRow::from(&tx);
@@ -720,6 +979,9 @@ mod tests {
             memo_type: None,
             metadata: None,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         };
 
         let row = TransactionJsonRow::from(&tx);
@@ -750,6 +1012,9 @@ mod tests {
             memo_type: None,
             metadata: None,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         };
 
         let row = TransactionCsvRow::from(&tx);
@@ -759,24 +1024,72 @@ mod tests {
     #[test]
     fn test_export_query_default_has_no_filters() {
         let q = ExportQuery::default();
-        assert!(q.from.is_none());
-        assert!(q.to.is_none());
-        assert!(q.status.is_none());
-        assert!(q.asset_code.is_none());
+        assert!(q.filters.from.is_none());
+        assert!(q.filters.to.is_none());
+        assert!(q.filters.status.is_none());
+        assert!(q.filters.asset_code.is_none());
+        assert_eq!(q.validate().unwrap(), TransactionFilters::default());
     }
 
     #[test]
     fn test_filter_param_count_matches_placeholders() {
         // Ensure the number of `$N` placeholders equals the number of params
-        let from = Some("2025-01-01".to_string());
-        let to = Some("2025-06-01".to_string());
-        let status = Some("pending".to_string());
-        let asset = Some("USD".to_string());
-        let (where_clause, params) = build_filter_conditions(&from, &to, &status, &asset);
+        let (where_clause, params) = build_filter_conditions(
+            &filters(TransactionFilterParams {
+                from: Some("2025-01-01".to_string()),
+                to: Some("2025-06-01".to_string()),
+                status: Some("pending".to_string()),
+                asset_code: Some("USD".to_string()),
+                stellar_account: Some("GABC".to_string()),
+                min_amount: Some("1".to_string()),
+                max_amount: Some("2".to_string()),
+            }),
+            None,
+        );
 
         let placeholder_count = (1..=params.len())
             .filter(|i| where_clause.contains(&format!("${i}")))
             .count();
         assert_eq!(placeholder_count, params.len());
     }
+
+    #[test]
+    fn test_validate_accepts_ndjson_and_rejects_unknown_format() {
+        for format in ["csv", "json", "ndjson", "NDJSON"] {
+            let q = ExportQuery {
+                format: format.to_string(),
+                ..Default::default()
+            };
+            assert!(q.validate().is_ok(), "{format}");
+        }
+        let q = ExportQuery {
+            format: "xml".to_string(),
+            ..Default::default()
+        };
+        assert!(q.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_envelope_wraps_records() {
+        let records = futures::stream::iter(vec![
+            Ok(r#"{"id":"a"}"#.to_string()),
+            Ok(r#"{"id":"b"}"#.to_string()),
+        ]);
+        let body: serde_json::Value =
+            serde_json::from_str(&json_envelope(records).await.unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "schema_version": EXPORT_SCHEMA_VERSION,
+                "count": 2,
+                "items": [{ "id": "a" }, { "id": "b" }],
+            })
+        );
+
+        let empty = futures::stream::iter(Vec::<Result<String, sqlx::Error>>::new());
+        let body: serde_json::Value =
+            serde_json::from_str(&json_envelope(empty).await.unwrap()).unwrap();
+        assert_eq!(body["count"], 0);
+        assert_eq!(body["items"], serde_json::json!([]));
+    }
 }
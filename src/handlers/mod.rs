@@ -17,13 +17,14 @@ pub mod ws;
 pub mod ws_error;
 
 pub use pagination::{
-    validate_pagination, PaginatedListResponse, PaginationHelper, PaginationQuery,
+    validate_pagination, Page, PaginatedListResponse, PaginationHelper, PaginationQuery,
 };
 
 use crate::error::AppError;
 use crate::ApiState;
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use utoipa::ToSchema;
 
 /// Helper service for checking dependency health.
@@ -114,7 +115,8 @@ pub async fn live() -> impl IntoResponse {
 /// - `(StatusCode::OK, ReadinessResponse)` — service is ready to accept traffic
 /// - `(StatusCode::SERVICE_UNAVAILABLE, ReadinessResponse)` — service is draining or not ready
 ///
-/// The response body includes `draining` flag indicating graceful shutdown state.
+/// The response body includes `draining` flag indicating graceful shutdown state,
+/// and `reasons` listing what is blocking readiness.
 ///
 /// # Use case
 /// Kubernetes readiness probes; remove from load balancer if this returns non-200.
@@ -128,17 +130,21 @@ pub async fn live() -> impl IntoResponse {
     tag = "Health"
 )]
 pub async fn ready(State(state): State<ApiState>) -> Result<impl IntoResponse, AppError> {
-    if state.app_state.readiness.is_ready() {
-        let response = ReadinessResponse {
-            status: "ready".to_string(),
-            draining: state.app_state.readiness.is_draining(),
-        };
+    let readiness = &state.app_state.readiness;
+    let response = ReadinessResponse {
+        status: if readiness.is_ready() {
+            "ready".to_string()
+        } else {
+            "not_ready".to_string()
+        },
+        draining: readiness.is_draining(),
+        reasons: readiness.reasons(),
+    };
+
+    if readiness.is_ready() {
         Ok((StatusCode::OK, Json(response)))
     } else {
-        let response = ReadinessResponse {
-            status: "not_ready".to_string(),
-            draining: state.app_state.readiness.is_draining(),
-        };
+        tracing::debug!(reasons = ?response.reasons, "Readiness probe: not ready");
         Ok((StatusCode::SERVICE_UNAVAILABLE, Json(response)))
     }
 }
@@ -182,6 +188,7 @@ pub async fn health(State(state): State<ApiState>) -> Result<impl IntoResponse,
         .app_state
         .ws_connection_count
         .load(std::sync::atomic::Ordering::Relaxed);
+    let dependencies = check_dependencies(&state.app_state).await;
 
     let health_response = HealthStatus {
         status: if db_status == "connected" {
@@ -195,11 +202,70 @@ pub async fn health(State(state): State<ApiState>) -> Result<impl IntoResponse,
         pending_queue_depth,
         current_batch_size,
         ws_connection_count,
+        dependencies,
     };
 
     Ok((db_status_code, Json(health_response)))
 }
 
+/// Probes Postgres (and the read replica, when configured), Redis, Horizon and
+/// backup freshness (when configured) concurrently, timing each one against
+/// the configured latency threshold.
+async fn check_dependencies(
+    app_state: &crate::AppState,
+) -> HashMap<String, crate::health::DependencyStatus> {
+    use crate::health::{run_check, HorizonChecker, PostgresChecker, RedisChecker};
+
+    let threshold = std::time::Duration::from_millis(app_state.health_latency_threshold_ms);
+    let postgres = PostgresChecker::new(app_state.db.clone());
+    // An open query-cache breaker means Redis has been failing repeatedly;
+    // report it as degraded without waiting on another connection attempt.
+    let redis = RedisChecker::with_circuit_state(
+        app_state.redis_url.clone(),
+        app_state.query_cache.circuit_state(),
+    );
+    let horizon = HorizonChecker::new(app_state.horizon_client.clone());
+
+    let replica = async {
+        match app_state.pool_manager.replica() {
+            Some(pool) => {
+                let checker = PostgresChecker::replica(pool.clone());
+                Some(run_check(&checker, threshold).await)
+            }
+            None => None,
+        }
+    };
+
+    let backups = async {
+        match &app_state.backup_checker {
+            Some(checker) => Some(run_check(checker, threshold).await),
+            None => None,
+        }
+    };
+
+    let (postgres, redis, horizon, replica, backups) = tokio::join!(
+        run_check(&postgres, threshold),
+        run_check(&redis, threshold),
+        run_check(&horizon, threshold),
+        replica,
+        backups,
+    );
+
+    let mut dependencies = HashMap::new();
+    dependencies.insert("postgres".to_string(), postgres);
+    dependencies.insert("redis".to_string(), redis);
+    dependencies.insert("horizon".to_string(), horizon);
+
+    if let Some(replica) = replica {
+        dependencies.insert("postgres_replica".to_string(), replica);
+    }
+    if let Some(backups) = backups {
+        dependencies.insert("backups".to_string(), backups);
+    }
+
+    dependencies
+}
+
 /// Response from the liveness probe endpoint (/live).
 ///
 /// Indicates whether the application process is running.
@@ -221,6 +287,9 @@ pub struct ReadinessResponse {
     pub status: String,
     /// true if the service is in graceful shutdown mode (/admin/drain was called)
     pub draining: bool,
+    /// Why the service is not ready, e.g. "draining: graceful shutdown in progress"
+    /// or "database: not initialized"; empty when ready
+    pub reasons: Vec<String>,
 }
 
 /// Response from the health check endpoint (/health).
@@ -243,6 +312,11 @@ pub struct HealthStatus {
     pub current_batch_size: u64,
     /// Number of active WebSocket connections
     pub ws_connection_count: usize,
+    /// Per-dependency status and `latency_ms`; a reachable dependency slower
+    /// than `HEALTH_LATENCY_THRESHOLD_MS` is reported as "degraded". Only the
+    /// database decides the overall `status`.
+    #[schema(value_type = Object)]
+    pub dependencies: HashMap<String, crate::health::DependencyStatus>,
 }
 
 /// Database connection pool statistics.
@@ -261,13 +335,62 @@ pub struct DbPoolStats {
     pub usage_percent: f32,
 }
 
+/// Git commit the binary was built from, set via the `GIT_SHA` build-time
+/// environment variable.
+const GIT_SHA: &str = match option_env!("GIT_SHA") {
+    Some(sha) => sha,
+    None => "unknown",
+};
+
+/// When the binary was built, set via the `BUILD_TIMESTAMP` build-time
+/// environment variable.
+const BUILD_TIMESTAMP: &str = match option_env!("BUILD_TIMESTAMP") {
+    Some(timestamp) => timestamp,
+    None => "unknown",
+};
+
+/// Build and deployment details of the running process.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VersionResponse {
+    /// Crate version from `Cargo.toml`
+    pub version: String,
+    /// Git commit SHA, or `unknown` when not supplied at build time
+    pub git_sha: String,
+    /// Build timestamp, or `unknown` when not supplied at build time
+    pub build_timestamp: String,
+    /// Active environment profile (`development`, `staging`, `production`)
+    pub profile: String,
+}
+
+/// Version endpoint — reports what is deployed.
+///
+/// Unauthenticated like the health probes, so it can be checked during an
+/// incident without credentials.
+#[utoipa::path(
+    get,
+    path = "/version",
+    responses(
+        (status = 200, description = "Build and version information", body = VersionResponse)
+    ),
+    tag = "Health"
+)]
+pub async fn version(State(state): State<ApiState>) -> impl IntoResponse {
+    let response = VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: GIT_SHA.to_string(),
+        build_timestamp: BUILD_TIMESTAMP.to_string(),
+        profile: state.app_state.app_env.as_str().to_string(),
+    };
+    (StatusCode::OK, Json(response))
+}
+
 /// Error catalog endpoint
 /// Returns all available error codes and their descriptions
 pub async fn error_catalog() -> Result<impl IntoResponse, AppError> {
     let errors = crate::error::get_all_error_codes();
     let catalog = crate::error::ErrorCatalogResponse {
         errors,
-        version: "1.0.0".to_string(),
+        version: "1.1.0".to_string(),
     };
 
     Ok((StatusCode::OK, Json(catalog)))
@@ -293,6 +416,7 @@ mod tests {
         let ready = ReadinessResponse {
             status: "ready".to_string(),
             draining: false,
+            reasons: vec![],
         };
         assert_eq!(ready.status, "ready");
         assert!(!ready.draining);
@@ -300,6 +424,7 @@ mod tests {
         let not_ready = ReadinessResponse {
             status: "not_ready".to_string(),
             draining: true,
+            reasons: vec!["draining: graceful shutdown in progress".to_string()],
         };
         assert_eq!(not_ready.status, "not_ready");
         assert!(not_ready.draining);
@@ -321,6 +446,7 @@ mod tests {
             pending_queue_depth: 100,
             current_batch_size: 50,
             ws_connection_count: 10,
+            dependencies: HashMap::new(),
         };
         assert_eq!(healthy.status, "healthy");
         assert_eq!(healthy.db, "connected");
@@ -338,6 +464,7 @@ mod tests {
             pending_queue_depth: 0,
             current_batch_size: 0,
             ws_connection_count: 0,
+            dependencies: HashMap::new(),
         };
         assert_eq!(unhealthy.status, "unhealthy");
         assert_eq!(unhealthy.db, "disconnected");
@@ -95,6 +95,50 @@ impl<T> PaginatedListResponse<T> {
     }
 }
 
+/// Offset-paginated list envelope shared by the listing endpoints, so clients
+/// can page through any of them with the same code.
+///
+/// Used by the failed webhook, admin DLQ and settlement listings. The audit
+/// log search in `handlers/admin/audit.rs` is not mounted and still has its
+/// own `total`/`data`/`next_cursor` envelope.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[aliases(SettlementListResponse = Page<crate::db::models::Settlement>)]
+pub struct Page<T> {
+    /// The rows on this page.
+    pub items: Vec<T>,
+    /// Rows matching the query across all pages.
+    pub total: i64,
+    /// Page size applied to this response.
+    pub limit: i64,
+    /// Rows skipped before this page.
+    pub offset: i64,
+    /// Whether more rows follow this page.
+    pub has_more: bool,
+    /// `offset` of the next page, or `null` on the last page.
+    pub next_offset: Option<i64>,
+    /// Keyset cursor for the next page, on endpoints that also accept one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// A page of `items` starting at `offset`, with `has_more` derived from
+    /// `total`.
+    pub fn new(items: Vec<T>, total: i64, limit: i64, offset: i64) -> Self {
+        let next = offset + items.len() as i64;
+        let has_more = !items.is_empty() && next < total;
+        Self {
+            items,
+            total,
+            limit,
+            offset,
+            has_more,
+            next_offset: has_more.then_some(next),
+            next_cursor: None,
+        }
+    }
+}
+
 /// Helper struct for managing pagination parameters and offsets.
 pub struct PaginationHelper {
     page: u32,
@@ -242,4 +286,28 @@ mod tests {
         assert_eq!(response.page, 1);
         assert_eq!(response.page_size, 20);
     }
+
+    #[test]
+    fn test_page_has_more_until_last_page() {
+        let first: Page<i32> = Page::new(vec![1, 2], 5, 2, 0);
+        assert!(first.has_more);
+        assert_eq!(first.next_offset, Some(2));
+        let last: Page<i32> = Page::new(vec![5], 5, 2, 4);
+        assert!(!last.has_more);
+        let past_end: Page<i32> = Page::new(vec![], 5, 2, 10);
+        assert!(!past_end.has_more);
+
+        let json = serde_json::to_value(&last).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "items": [5],
+                "total": 5,
+                "limit": 2,
+                "offset": 4,
+                "has_more": false,
+                "next_offset": null
+            })
+        );
+    }
 }
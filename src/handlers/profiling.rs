@@ -1,19 +1,38 @@
 use crate::error::AppError;
+use crate::middleware::auth::JwtSecret;
 use axum::{
     extract::{Path, State},
     http::StatusCode,
+    middleware,
     response::IntoResponse,
-    Json,
+    routing::{get, post},
+    Json, Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path as FsPath, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::AppState;
+use crate::{ApiState, AppState};
+
+/// Directory flamegraphs are written to unless overridden.
+pub const DEFAULT_PROFILE_DIR: &str = "./profiling_data";
+
+/// Flamegraphs older than this are removed by [`ProfilingManager::cleanup_expired`].
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Minimum time between two session starts unless overridden.
+pub const DEFAULT_MIN_START_INTERVAL: Duration = Duration::from_secs(60);
+
+/// CPU sample rate used when a start request does not give one.
+pub const DEFAULT_SAMPLE_RATE_HZ: u32 = 100;
+
+/// Highest CPU sample rate accepted unless overridden.
+pub const DEFAULT_MAX_SAMPLE_RATE_HZ: u32 = 1000;
 
 /// Configuration for profiling sessions
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,7 +55,7 @@ pub struct ProfilingSession {
     pub end_time: Option<u64>,
     pub duration_secs: u64,
     pub profile_type: String,
-    pub status: String, // "running", "completed", "failed"
+    pub status: String, // "running", "completed", "stopped", "failed"
     pub flamegraph_path: Option<String>,
     pub data_size_bytes: Option<u64>,
 }
@@ -65,11 +84,136 @@ fn default_generate_flamegraph() -> bool {
     true
 }
 
+/// A flamegraph left on disk by a finished profiling session.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredSession {
+    pub session_id: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified_at: DateTime<Utc>,
+}
+
+/// Why a profiling start or stop was rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum ProfilingError {
+    #[error("Profiling session {} is already in progress", .0.session_id)]
+    AlreadyRunning(RunningSession),
+
+    #[error("No profiling session in progress")]
+    NotRunning,
+
+    #[error("Unknown profile type '{0}'. Supported types: cpu, memory")]
+    UnknownProfileType(String),
+
+    #[error("Sample rate {sample_rate} Hz is out of range; expected 1-{max} Hz")]
+    InvalidSampleRate { sample_rate: u32, max: u32 },
+
+    #[error("Profiling sessions are rate limited; retry in {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+
+    #[error("Memory profiling is unavailable: this build was compiled without the `heap-profiling` feature")]
+    UnsupportedBackend,
+}
+
+/// The session blocking a new start, and how long until it ends on its own.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningSession {
+    pub session_id: String,
+    pub profile_type: String,
+    pub remaining_secs: u64,
+}
+
+impl RunningSession {
+    fn of(session: &ProfilingSession) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        Self {
+            session_id: session.session_id.clone(),
+            profile_type: session.profile_type.clone(),
+            remaining_secs: (session.start_time + session.duration_secs).saturating_sub(now),
+        }
+    }
+}
+
+impl IntoResponse for ProfilingError {
+    fn into_response(self) -> axum::response::Response {
+        let message = self.to_string();
+        match self {
+            ProfilingError::AlreadyRunning(running) => (
+                StatusCode::CONFLICT,
+                [(
+                    axum::http::header::RETRY_AFTER,
+                    running.remaining_secs.to_string(),
+                )],
+                Json(json!({
+                    "status": StatusCode::CONFLICT.as_u16(),
+                    "code": "PROFILING_IN_PROGRESS",
+                    "error": message,
+                    "running_session": running,
+                })),
+            )
+                .into_response(),
+            ProfilingError::RateLimited { retry_after_secs } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(
+                    axum::http::header::RETRY_AFTER,
+                    retry_after_secs.to_string(),
+                )],
+                Json(json!({
+                    "status": StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                    "code": "PROFILING_RATE_LIMITED",
+                    "error": message,
+                })),
+            )
+                .into_response(),
+            ProfilingError::UnsupportedBackend => (
+                StatusCode::NOT_IMPLEMENTED,
+                Json(json!({
+                    "status": StatusCode::NOT_IMPLEMENTED.as_u16(),
+                    "code": "PROFILING_BACKEND_UNAVAILABLE",
+                    "error": message,
+                })),
+            )
+                .into_response(),
+            _ => AppError::BadRequest(message).into_response(),
+        }
+    }
+}
+
+/// Handle to the background task driving the active session.
+struct ActiveTask {
+    stop: tokio::sync::oneshot::Sender<()>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+#[derive(Clone, Copy)]
+enum ProfileKind {
+    Cpu { sample_rate: u32 },
+    Memory,
+}
+
+impl ProfileKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ProfileKind::Cpu { .. } => "cpu",
+            ProfileKind::Memory => "memory",
+        }
+    }
+}
+
 /// Global profiling state
 #[derive(Clone)]
 pub struct ProfilingManager {
     is_profiling: Arc<AtomicBool>,
     current_session: Arc<tokio::sync::Mutex<Option<ProfilingSession>>>,
+    active: Arc<tokio::sync::Mutex<Option<ActiveTask>>>,
+    output_dir: PathBuf,
+    retention: Duration,
+    min_start_interval: Duration,
+    max_sample_rate: u32,
+    last_start: Arc<std::sync::Mutex<Option<Instant>>>,
 }
 
 impl ProfilingManager {
@@ -77,6 +221,104 @@ impl ProfilingManager {
         Self {
             is_profiling: Arc::new(AtomicBool::new(false)),
             current_session: Arc::new(tokio::sync::Mutex::new(None)),
+            active: Arc::new(tokio::sync::Mutex::new(None)),
+            output_dir: PathBuf::from(DEFAULT_PROFILE_DIR),
+            retention: DEFAULT_RETENTION,
+            min_start_interval: DEFAULT_MIN_START_INTERVAL,
+            max_sample_rate: DEFAULT_MAX_SAMPLE_RATE_HZ,
+            last_start: Arc::new(std::sync::Mutex::new(None)),
+        }
+    }
+
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = dir.into();
+        self
+    }
+
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Minimum time between two session starts, counted from the previous
+    /// start. Starts inside the window are rejected with
+    /// [`ProfilingError::RateLimited`].
+    pub fn with_min_start_interval(mut self, interval: Duration) -> Self {
+        self.min_start_interval = interval;
+        self
+    }
+
+    /// Highest CPU sample rate (Hz) a session may be started with; faster
+    /// rates are rejected with [`ProfilingError::InvalidSampleRate`].
+    pub fn with_max_sample_rate(mut self, max_hz: u32) -> Self {
+        self.max_sample_rate = max_hz;
+        self
+    }
+
+    /// Directory flamegraphs are written to and served from.
+    pub fn output_dir(&self) -> &FsPath {
+        &self.output_dir
+    }
+
+    /// Lists flamegraphs stored in the output directory, newest first.
+    /// A missing directory means nothing has been profiled yet.
+    pub fn list_sessions(&self) -> std::io::Result<Vec<StoredSession>> {
+        let entries = match fs::read_dir(&self.output_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut sessions = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("svg") {
+                continue;
+            }
+            let Some(session_id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let metadata = fs::metadata(&path)?;
+            if !metadata.is_file() {
+                continue;
+            }
+            sessions.push(StoredSession {
+                session_id: session_id.to_string(),
+                path: path.to_string_lossy().to_string(),
+                size_bytes: metadata.len(),
+                modified_at: metadata.modified()?.into(),
+            });
+        }
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.modified_at));
+        Ok(sessions)
+    }
+
+    /// Deletes stored flamegraphs last modified more than `max_age` ago and
+    /// returns how many were removed.
+    pub fn cleanup_older_than(&self, max_age: Duration) -> std::io::Result<usize> {
+        let cutoff =
+            Utc::now() - chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        let mut removed = 0;
+        for session in self.list_sessions()? {
+            if session.modified_at < cutoff {
+                fs::remove_file(&session.path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Applies the configured retention window. Runs before every new
+    /// session so the output directory cannot grow without bound.
+    pub fn cleanup_expired(&self) -> std::io::Result<usize> {
+        self.cleanup_older_than(self.retention)
+    }
+
+    fn prune_before_session(&self) {
+        match self.cleanup_expired() {
+            Ok(0) => {}
+            Ok(removed) => tracing::info!(removed, "Removed expired flamegraphs"),
+            Err(e) => tracing::warn!("Failed to clean up old flamegraphs: {}", e),
         }
     }
 
@@ -90,174 +332,162 @@ impl ProfilingManager {
         self.current_session.lock().await.clone()
     }
 
-    /// Start a CPU profiling session
+    /// Start a CPU profiling session. `sample_rate` must be between 1 Hz
+    /// and the configured maximum; `pprof` misbehaves outside that range.
     pub async fn start_cpu_profiling(
         &self,
         duration_secs: u64,
         sample_rate: u32,
-    ) -> Result<ProfilingSession, String> {
-        if self.is_profiling.load(Ordering::Relaxed) {
-            return Err("Profiling session already in progress".to_string());
+    ) -> Result<ProfilingSession, ProfilingError> {
+        if !(1..=self.max_sample_rate).contains(&sample_rate) {
+            return Err(ProfilingError::InvalidSampleRate {
+                sample_rate,
+                max: self.max_sample_rate,
+            });
         }
-
-        let session_id = format!(
-            "profile-cpu-{}",
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_millis()
-        );
-
-        let start_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let session = ProfilingSession {
-            session_id: session_id.clone(),
-            start_time,
-            end_time: None,
-            duration_secs,
-            profile_type: "cpu".to_string(),
-            status: "running".to_string(),
-            flamegraph_path: None,
-            data_size_bytes: None,
-        };
-
-        self.is_profiling.store(true, Ordering::Relaxed);
-        *self.current_session.lock().await = Some(session.clone());
-
-        // Start the profiler in a background task
-        let session_id = session_id.clone();
-        let is_profiling = self.is_profiling.clone();
-        let current_session = self.current_session.clone();
-
-        tokio::spawn(async move {
-            match run_cpu_profiling(&session_id, duration_secs, sample_rate).await {
-                Ok(flamegraph_path) => {
-                    if let Some(session) = current_session.lock().await.as_mut() {
-                        session.status = "completed".to_string();
-                        session.end_time = Some(
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs(),
-                        );
-                        session.flamegraph_path = Some(flamegraph_path);
-
-                        if let Ok(metadata) =
-                            fs::metadata(session.flamegraph_path.as_ref().unwrap())
-                        {
-                            session.data_size_bytes = Some(metadata.len());
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("CPU profiling failed: {}", e);
-                    if let Some(session) = current_session.lock().await.as_mut() {
-                        session.status = format!("failed: {e}");
-                        session.end_time = Some(
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs(),
-                        );
-                    }
-                }
-            }
-            is_profiling.store(false, Ordering::Relaxed);
-        });
-
-        Ok(session)
+        self.start_session(ProfileKind::Cpu { sample_rate }, duration_secs)
+            .await
     }
 
-    /// Start a memory profiling session
+    /// Start a memory profiling session. Fails with
+    /// [`ProfilingError::UnsupportedBackend`] unless the `heap-profiling`
+    /// allocator is compiled in.
     pub async fn start_memory_profiling(
         &self,
         duration_secs: u64,
-    ) -> Result<ProfilingSession, String> {
-        if self.is_profiling.load(Ordering::Relaxed) {
-            return Err("Profiling session already in progress".to_string());
+    ) -> Result<ProfilingSession, ProfilingError> {
+        if !cfg!(feature = "heap-profiling") {
+            return Err(ProfilingError::UnsupportedBackend);
         }
+        self.start_session(ProfileKind::Memory, duration_secs).await
+    }
 
-        let session_id = format!(
-            "profile-memory-{}",
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_millis()
-        );
+    /// Claims the rate-limit slot for a new session, or reports how long
+    /// until the next start is allowed.
+    fn claim_start(&self) -> Result<(), ProfilingError> {
+        let mut last_start = self.last_start.lock().unwrap();
+        if let Some(elapsed) = last_start.map(|at| at.elapsed()) {
+            if elapsed < self.min_start_interval {
+                let remaining = self.min_start_interval - elapsed;
+                return Err(ProfilingError::RateLimited {
+                    retry_after_secs: remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0),
+                });
+            }
+        }
+        *last_start = Some(Instant::now());
+        Ok(())
+    }
 
-        let start_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+    async fn start_session(
+        &self,
+        kind: ProfileKind,
+        duration_secs: u64,
+    ) -> Result<ProfilingSession, ProfilingError> {
+        // The flag and the session are only changed together under this
+        // lock, so a set flag always comes with the session holding it.
+        let mut current = self.current_session.lock().await;
+        if let Some(running) = current
+            .as_ref()
+            .filter(|_| self.is_profiling.load(Ordering::SeqCst))
+        {
+            return Err(ProfilingError::AlreadyRunning(RunningSession::of(running)));
+        }
+        self.claim_start()?;
+        self.prune_before_session();
 
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let session_id = format!("profile-{}-{}", kind.as_str(), now.as_millis());
         let session = ProfilingSession {
             session_id: session_id.clone(),
-            start_time,
+            start_time: now.as_secs(),
             end_time: None,
             duration_secs,
-            profile_type: "memory".to_string(),
+            profile_type: kind.as_str().to_string(),
             status: "running".to_string(),
             flamegraph_path: None,
             data_size_bytes: None,
         };
+        *current = Some(session.clone());
+        self.is_profiling.store(true, Ordering::SeqCst);
+        drop(current);
 
-        self.is_profiling.store(true, Ordering::Relaxed);
-        *self.current_session.lock().await = Some(session.clone());
-
-        // Start memory profiling in background
-        let session_id = session_id.clone();
+        // Hold the slot while spawning so the task cannot finish and look for
+        // its own handle before it has been stored.
+        let mut active = self.active.lock().await;
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
         let is_profiling = self.is_profiling.clone();
         let current_session = self.current_session.clone();
+        let task_slot = self.active.clone();
+        let output_dir = self.output_dir.clone();
 
-        tokio::spawn(async move {
-            match run_memory_profiling(&session_id, duration_secs).await {
-                Ok(flamegraph_path) => {
-                    if let Some(session) = current_session.lock().await.as_mut() {
-                        session.status = "completed".to_string();
-                        session.end_time = Some(
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs(),
-                        );
-                        session.flamegraph_path = Some(flamegraph_path);
+        let handle = tokio::spawn(async move {
+            let result = match kind {
+                ProfileKind::Cpu { sample_rate } => {
+                    run_cpu_profiling(
+                        &output_dir,
+                        &session_id,
+                        duration_secs,
+                        sample_rate,
+                        stop_rx,
+                    )
+                    .await
+                }
+                ProfileKind::Memory => {
+                    run_memory_profiling(&output_dir, &session_id, duration_secs, stop_rx).await
+                }
+            };
+            // `stop_profiling` takes the slot before signalling, so an empty
+            // slot here means the session was cut short.
+            let stopped = task_slot.lock().await.take().is_none();
 
-                        if let Ok(metadata) =
-                            fs::metadata(session.flamegraph_path.as_ref().unwrap())
-                        {
-                            session.data_size_bytes = Some(metadata.len());
-                        }
+            let mut current = current_session.lock().await;
+            if let Some(session) = current.as_mut() {
+                session.end_time = Some(
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                );
+                match result {
+                    Ok(flamegraph_path) => {
+                        session.status = if stopped { "stopped" } else { "completed" }.to_string();
+                        session.data_size_bytes =
+                            fs::metadata(&flamegraph_path).ok().map(|m| m.len());
+                        session.flamegraph_path = Some(flamegraph_path);
                     }
-                }
-                Err(e) => {
-                    tracing::error!("Memory profiling failed: {}", e);
-                    if let Some(session) = current_session.lock().await.as_mut() {
+                    Err(e) => {
+                        tracing::error!("{} profiling failed: {}", kind.as_str(), e);
                         session.status = format!("failed: {e}");
-                        session.end_time = Some(
-                            SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs(),
-                        );
                     }
                 }
             }
-            is_profiling.store(false, Ordering::Relaxed);
+            is_profiling.store(false, Ordering::SeqCst);
+        });
+        *active = Some(ActiveTask {
+            stop: stop_tx,
+            handle,
         });
 
         Ok(session)
     }
 
-    /// Stop profiling if any session is in progress
-    pub async fn stop_profiling(&self) -> Result<(), String> {
-        if !self.is_profiling.load(Ordering::Relaxed) {
-            return Err("No profiling session in progress".to_string());
+    /// Stop the in-progress session early. The profiler is interrupted, the
+    /// data gathered so far is written out, and the session is marked
+    /// `stopped` before this returns.
+    pub async fn stop_profiling(&self) -> Result<(), ProfilingError> {
+        let Some(task) = self.active.lock().await.take() else {
+            return Err(ProfilingError::NotRunning);
+        };
+        // The task may already be finishing, in which case nobody is listening.
+        let _ = task.stop.send(());
+        if let Err(e) = task.handle.await {
+            tracing::error!("Profiling task ended abnormally: {}", e);
+            let mut current = self.current_session.lock().await;
+            if let Some(session) = current.as_mut() {
+                session.status = format!("failed: {e}");
+            }
+            self.is_profiling.store(false, Ordering::SeqCst);
         }
-
-        self.is_profiling.store(false, Ordering::Relaxed);
         Ok(())
     }
 }
@@ -268,20 +498,28 @@ impl Default for ProfilingManager {
     }
 }
 
+/// Sleeps for the session duration, returning early once the session is stopped.
+async fn wait_or_stop(duration_secs: u64, stop: tokio::sync::oneshot::Receiver<()>) {
+    tokio::select! {
+        _ = tokio::time::sleep(Duration::from_secs(duration_secs)) => {}
+        _ = stop => {}
+    }
+}
+
 /// Run CPU profiling with pprof
 async fn run_cpu_profiling(
+    profile_dir: &FsPath,
     session_id: &str,
     duration_secs: u64,
     sample_rate: u32,
+    stop: tokio::sync::oneshot::Receiver<()>,
 ) -> Result<String, String> {
     // Ensure profiling output directory exists
-    let profile_dir = PathBuf::from("./profiling_data");
-    fs::create_dir_all(&profile_dir).map_err(|e| e.to_string())?;
+    fs::create_dir_all(profile_dir).map_err(|e| e.to_string())?;
 
     let guard = pprof::ProfilerGuard::new(sample_rate as i32).map_err(|e| e.to_string())?;
 
-    // Sleep for the specified duration
-    tokio::time::sleep(tokio::time::Duration::from_secs(duration_secs)).await;
+    wait_or_stop(duration_secs, stop).await;
 
     // Stop profiling
     match guard.report().build() {
@@ -301,44 +539,127 @@ async fn run_cpu_profiling(
 }
 
 /// Run memory profiling
-async fn run_memory_profiling(session_id: &str, duration_secs: u64) -> Result<String, String> {
+///
+/// Records every allocation made during the session through dhat and renders
+/// the allocating stacks, weighted by bytes, as a flamegraph.
+#[cfg(feature = "heap-profiling")]
+async fn run_memory_profiling(
+    profile_dir: &FsPath,
+    session_id: &str,
+    duration_secs: u64,
+    stop: tokio::sync::oneshot::Receiver<()>,
+) -> Result<String, String> {
     // Ensure profiling output directory exists
-    let profile_dir = PathBuf::from("./profiling_data");
-    fs::create_dir_all(&profile_dir).map_err(|e| e.to_string())?;
+    fs::create_dir_all(profile_dir).map_err(|e| e.to_string())?;
+
+    // dhat panics if a second profiler is built while one is running, which
+    // can happen when a stopped session's timer has not yet elapsed.
+    static DHAT_ACTIVE: AtomicBool = AtomicBool::new(false);
+    if DHAT_ACTIVE.swap(true, Ordering::SeqCst) {
+        return Err("A heap profile from an earlier session is still recording".to_string());
+    }
 
-    // For memory profiling, we'll collect allocator stats if available
-    // This is a placeholder that creates a dummy SVG file
-    tokio::time::sleep(tokio::time::Duration::from_secs(duration_secs)).await;
+    let dhat_path = profile_dir.join(format!("{session_id}.dhat.json"));
+    let profiler = dhat::Profiler::builder()
+        .file_name(&dhat_path)
+        .trim_backtraces(Some(32))
+        .build();
+    wait_or_stop(duration_secs, stop).await;
+    // Dropping the profiler stops recording and writes the dhat output.
+    drop(profiler);
+    DHAT_ACTIVE.store(false, Ordering::SeqCst);
+
+    let raw = fs::read_to_string(&dhat_path).map_err(|e| e.to_string());
+    let _ = fs::remove_file(&dhat_path);
+    let folded = dhat_to_folded(&raw?)?;
+    if folded.is_empty() {
+        return Err("No allocations were recorded during the session".to_string());
+    }
 
     let flamegraph_path = profile_dir.join(format!("{session_id}.svg"));
-    let placeholder_svg = format!(
-        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
-         <svg viewBox=\"0 0 1024 512\" xmlns=\"http://www.w3.org/2000/svg\">\n  \
-         <rect width=\"1024\" height=\"512\" fill=\"#f0f0f0\"/>\n  \
-         <text x=\"512\" y=\"256\" font-size=\"24\" text-anchor=\"middle\" dominant-baseline=\"middle\">\n    \
-         Memory Profiling Session: {session_id}\n  \
-         </text>\n  \
-         <text x=\"512\" y=\"300\" font-size=\"14\" text-anchor=\"middle\" fill=\"#666\">\n    \
-         Memory profiling data would appear here\n  \
-         </text>\n\
-         </svg>"
-    );
-
-    fs::write(&flamegraph_path, placeholder_svg).map_err(|e| e.to_string())?;
+    let flamegraph_file = fs::File::create(&flamegraph_path).map_err(|e| e.to_string())?;
+    let mut options = inferno::flamegraph::Options::default();
+    options.title = format!("Allocations: {session_id}");
+    options.count_name = "bytes".to_string();
+    inferno::flamegraph::from_lines(
+        &mut options,
+        folded.iter().map(String::as_str),
+        flamegraph_file,
+    )
+    .map_err(|e| e.to_string())?;
 
     Ok(flamegraph_path.to_string_lossy().to_string())
 }
 
+/// Converts dhat's JSON output into folded stacks (`root;..;leaf bytes`),
+/// one line per allocation site, weighted by total bytes allocated.
+#[cfg(feature = "heap-profiling")]
+fn dhat_to_folded(raw: &str) -> Result<Vec<String>, String> {
+    #[derive(Deserialize)]
+    struct DhatOutput {
+        pps: Vec<ProgramPoint>,
+        ftbl: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct ProgramPoint {
+        tb: u64,
+        fs: Vec<usize>,
+    }
+
+    let output: DhatOutput = serde_json::from_str(raw).map_err(|e| e.to_string())?;
+    let mut lines = Vec::with_capacity(output.pps.len());
+    for pp in output.pps.iter().filter(|pp| pp.tb > 0) {
+        // dhat lists frames innermost first; folded stacks start at the root.
+        let stack: Vec<String> = pp
+            .fs
+            .iter()
+            .rev()
+            .filter_map(|&i| output.ftbl.get(i))
+            .map(|frame| frame_name(frame).replace(';', ":"))
+            .collect();
+        if !stack.is_empty() {
+            lines.push(format!("{} {}", stack.join(";"), pp.tb));
+        }
+    }
+    Ok(lines)
+}
+
+/// Strips the address prefix and source location from a dhat frame, e.g.
+/// `0x1234: alloc::vec::Vec<T>::push (src/vec.rs:10:5)` becomes
+/// `alloc::vec::Vec<T>::push`.
+#[cfg(feature = "heap-profiling")]
+fn frame_name(frame: &str) -> &str {
+    let name = frame.split_once(": ").map_or(frame, |(_, rest)| rest);
+    match name.rfind(" (") {
+        Some(idx) if name.ends_with(')') => &name[..idx],
+        _ => name,
+    }
+}
+
+/// Without the `heap-profiling` feature there is no allocator backend to
+/// record with; [`ProfilingManager::start_memory_profiling`] rejects the
+/// session before this is reached.
+#[cfg(not(feature = "heap-profiling"))]
+async fn run_memory_profiling(
+    _profile_dir: &FsPath,
+    _session_id: &str,
+    _duration_secs: u64,
+    _stop: tokio::sync::oneshot::Receiver<()>,
+) -> Result<String, String> {
+    Err(ProfilingError::UnsupportedBackend.to_string())
+}
+
 /// HTTP handler to start profiling
 pub async fn start_profiling(
     State(state): State<AppState>,
     Json(req): Json<StartProfilingRequest>,
-) -> Result<impl IntoResponse, AppError> {
+) -> Result<impl IntoResponse, ProfilingError> {
     let profile_type = req.profile_type.to_lowercase();
 
     let result = match profile_type.as_str() {
         "cpu" => {
-            let sample_rate = req.sample_rate.unwrap_or(100);
+            let sample_rate = req.sample_rate.unwrap_or(DEFAULT_SAMPLE_RATE_HZ);
             state
                 .profiling_manager
                 .start_cpu_profiling(req.duration_secs, sample_rate)
@@ -350,16 +671,14 @@ pub async fn start_profiling(
                 .start_memory_profiling(req.duration_secs)
                 .await
         }
-        _ => Err(format!(
-            "Unknown profile type '{profile_type}'. Supported types: cpu, memory"
-        )),
+        _ => Err(ProfilingError::UnknownProfileType(profile_type)),
     };
 
     match result {
         Ok(session) => Ok((StatusCode::OK, Json(session))),
         Err(e) => {
-            tracing::error!("Failed to start profiling: {}", e);
-            Err(AppError::Internal(e))
+            tracing::warn!("Failed to start profiling: {}", e);
+            Err(e)
         }
     }
 }
@@ -381,7 +700,9 @@ pub async fn get_profiling_status(
 }
 
 /// HTTP handler to stop profiling
-pub async fn stop_profiling(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+pub async fn stop_profiling(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ProfilingError> {
     match state.profiling_manager.stop_profiling().await {
         Ok(_) => Ok((
             StatusCode::OK,
@@ -390,19 +711,21 @@ pub async fn stop_profiling(State(state): State<AppState>) -> Result<impl IntoRe
             })),
         )),
         Err(e) => {
-            tracing::error!("Failed to stop profiling: {}", e);
-            Err(AppError::BadRequest(e))
+            tracing::warn!("Failed to stop profiling: {}", e);
+            Err(e)
         }
     }
 }
 
 /// HTTP handler to serve a flamegraph SVG
 pub async fn get_flamegraph(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(session_id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
-    let profile_dir = PathBuf::from("./profiling_data");
-    let flamegraph_path = profile_dir.join(format!("{session_id}.svg"));
+    let flamegraph_path = state
+        .profiling_manager
+        .output_dir()
+        .join(format!("{session_id}.svg"));
 
     match tokio::fs::read_to_string(&flamegraph_path).await {
         Ok(content) => Ok((
@@ -417,10 +740,52 @@ pub async fn get_flamegraph(
     }
 }
 
+/// Session control routes under `/admin/profiling`. Each requires a bearer
+/// JWT signed with `jwt_secret` and carrying the `admin:profiling` scope.
+pub fn profiling_routes(jwt_secret: JwtSecret) -> Router<AppState> {
+    Router::new()
+        .route("/admin/profiling/start", post(start_profiling))
+        .route("/admin/profiling/stop", post(stop_profiling))
+        .route("/admin/profiling/status", get(get_profiling_status))
+        .route(
+            "/admin/profiling/flamegraph/:session_id",
+            get(get_flamegraph),
+        )
+        .layer(middleware::from_fn_with_state(
+            jwt_secret,
+            crate::middleware::auth::profiling_auth,
+        ))
+}
+
+/// GET /admin/profiling/sessions
+///
+/// Lists flamegraphs kept on disk, newest first, with their sizes and
+/// modification times.
+pub async fn list_profiling_sessions(
+    State(state): State<ApiState>,
+) -> Result<impl IntoResponse, AppError> {
+    let sessions = state
+        .app_state
+        .profiling_manager
+        .list_sessions()
+        .map_err(|e| AppError::Internal(format!("Failed to list profiling sessions: {e}")))?;
+    let total_bytes: u64 = sessions.iter().map(|s| s.size_bytes).sum();
+
+    Ok(Json(json!({
+        "sessions": sessions,
+        "total": sessions.len(),
+        "total_bytes": total_bytes
+    })))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Profilers are process-wide, so tests that run one take turns.
+    #[cfg(feature = "heap-profiling")]
+    static PROFILER: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
     #[test]
     fn test_profiling_manager_creation() {
         let manager = ProfilingManager::new();
@@ -441,10 +806,311 @@ mod tests {
         assert!(default_generate_flamegraph());
     }
 
+    /// Marks a session as running without starting a profiler, so the
+    /// start checks can be exercised on builds without a memory backend.
+    async fn hold_slot(
+        manager: &ProfilingManager,
+        profile_type: &str,
+        duration_secs: u64,
+    ) -> ProfilingSession {
+        let session = ProfilingSession {
+            session_id: format!("profile-{profile_type}-test"),
+            start_time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            end_time: None,
+            duration_secs,
+            profile_type: profile_type.to_string(),
+            status: "running".to_string(),
+            flamegraph_path: None,
+            data_size_bytes: None,
+        };
+        *manager.current_session.lock().await = Some(session.clone());
+        manager.is_profiling.store(true, Ordering::SeqCst);
+        session
+    }
+
     #[tokio::test]
     async fn test_profiling_status_when_idle() {
         let manager = ProfilingManager::new();
         assert!(!manager.is_profiling());
         assert!(manager.get_current_session().await.is_none());
     }
+
+    #[tokio::test]
+    async fn test_concurrent_start_is_rejected_with_running_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new().with_output_dir(dir.path());
+        let running = hold_slot(&manager, "memory", 60).await;
+
+        let err = manager.start_cpu_profiling(5, 100).await.unwrap_err();
+        let ProfilingError::AlreadyRunning(blocking) = &err else {
+            panic!("expected AlreadyRunning, got {err:?}");
+        };
+        assert_eq!(blocking.session_id, running.session_id);
+        assert_eq!(blocking.profile_type, "memory");
+        assert!((59..=60).contains(&blocking.remaining_secs));
+
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let retry_after = response.headers()[axum::http::header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse::<u64>()
+            .unwrap();
+        assert!((59..=60).contains(&retry_after));
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "PROFILING_IN_PROGRESS");
+        assert_eq!(body["running_session"]["session_id"], running.session_id);
+    }
+
+    #[cfg(feature = "heap-profiling")]
+    #[tokio::test]
+    async fn test_stop_interrupts_in_progress_session() {
+        let _profiler = PROFILER.lock().await;
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new()
+            .with_output_dir(dir.path())
+            .with_min_start_interval(Duration::ZERO);
+        manager.start_memory_profiling(300).await.unwrap();
+        assert!(manager.is_profiling());
+        // Let the session start, then give a heap profile something to
+        // record before cutting it short.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let churn: Vec<Vec<u8>> = (0..16).map(|_| vec![0; 1024]).collect();
+        drop(churn);
+
+        tokio::time::timeout(Duration::from_secs(10), manager.stop_profiling())
+            .await
+            .expect("stop should not wait for the full session duration")
+            .unwrap();
+
+        assert!(!manager.is_profiling());
+        let session = manager.get_current_session().await.unwrap();
+        assert_eq!(session.status, "stopped");
+        assert!(session.end_time.is_some());
+        assert!(FsPath::new(session.flamegraph_path.as_deref().unwrap()).exists());
+
+        assert!(matches!(
+            manager.stop_profiling().await,
+            Err(ProfilingError::NotRunning)
+        ));
+        // The slot is free again once the stopped session has wound down.
+        manager.start_memory_profiling(300).await.unwrap();
+        manager.stop_profiling().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_session_starts_are_rate_limited() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new().with_output_dir(dir.path());
+        // A session started a moment ago and has since finished.
+        *manager.last_start.lock().unwrap() = Some(Instant::now());
+
+        let err = manager.start_cpu_profiling(60, 100).await.unwrap_err();
+        let ProfilingError::RateLimited { retry_after_secs } = err else {
+            panic!("expected RateLimited, got {err:?}");
+        };
+        assert!((59..=60).contains(&retry_after_secs));
+        assert!(!manager.is_profiling());
+        assert!(manager.get_current_session().await.is_none());
+
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers()[axum::http::header::RETRY_AFTER],
+            retry_after_secs.to_string().as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_sample_rates_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new().with_output_dir(dir.path());
+
+        for sample_rate in [0, DEFAULT_MAX_SAMPLE_RATE_HZ + 1, u32::MAX] {
+            let err = manager
+                .start_cpu_profiling(60, sample_rate)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, ProfilingError::InvalidSampleRate { sample_rate: rate, max: 1000 } if rate == sample_rate),
+                "{sample_rate}: {err:?}"
+            );
+            assert_eq!(err.into_response().status(), StatusCode::BAD_REQUEST);
+        }
+        assert!(!manager.is_profiling());
+        // Rejected starts do not use up the rate-limit window.
+        assert!(manager.last_start.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sample_rate_within_bounds_is_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new()
+            .with_output_dir(dir.path())
+            .with_max_sample_rate(250);
+        // Occupy the slot so an accepted rate stops at the next check
+        // instead of starting a real profiler.
+        hold_slot(&manager, "memory", 60).await;
+
+        for sample_rate in [1, 250] {
+            let err = manager
+                .start_cpu_profiling(60, sample_rate)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, ProfilingError::AlreadyRunning(_)),
+                "{sample_rate}: {err:?}"
+            );
+        }
+        assert!(matches!(
+            manager.start_cpu_profiling(60, 251).await,
+            Err(ProfilingError::InvalidSampleRate { max: 250, .. })
+        ));
+    }
+
+    #[cfg(not(feature = "heap-profiling"))]
+    #[tokio::test]
+    async fn test_memory_profiling_without_backend_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new().with_output_dir(dir.path());
+
+        let err = manager.start_memory_profiling(60).await.unwrap_err();
+        assert!(matches!(err, ProfilingError::UnsupportedBackend));
+        assert!(!manager.is_profiling());
+        assert!(manager.get_current_session().await.is_none());
+        // A rejected start does not use up the rate-limit window.
+        assert!(manager.last_start.lock().unwrap().is_none());
+
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "PROFILING_BACKEND_UNAVAILABLE");
+        assert!(body["error"].as_str().unwrap().contains("heap-profiling"));
+    }
+
+    fn write_session(dir: &FsPath, session_id: &str, contents: &str, age: Duration) {
+        let path = dir.join(format!("{session_id}.svg"));
+        fs::write(&path, contents).unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_list_sessions_reports_stored_flamegraphs_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new().with_output_dir(dir.path());
+
+        write_session(
+            dir.path(),
+            "profile-cpu-1",
+            "<svg/>",
+            Duration::from_secs(7200),
+        );
+        write_session(
+            dir.path(),
+            "profile-memory-2",
+            "<svg></svg>",
+            Duration::ZERO,
+        );
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let sessions = manager.list_sessions().unwrap();
+        let ids: Vec<_> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+        assert_eq!(ids, ["profile-memory-2", "profile-cpu-1"]);
+        assert_eq!(sessions[0].size_bytes, 11);
+        assert_eq!(sessions[1].size_bytes, 6);
+        assert!(sessions[0].modified_at > sessions[1].modified_at);
+    }
+
+    #[test]
+    fn test_list_sessions_with_missing_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new().with_output_dir(dir.path().join("absent"));
+        assert!(manager.list_sessions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cleanup_removes_only_expired_flamegraphs() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ProfilingManager::new()
+            .with_output_dir(dir.path())
+            .with_retention(Duration::from_secs(24 * 60 * 60));
+
+        write_session(
+            dir.path(),
+            "old-1",
+            "<svg/>",
+            Duration::from_secs(3 * 24 * 60 * 60),
+        );
+        write_session(
+            dir.path(),
+            "old-2",
+            "<svg/>",
+            Duration::from_secs(25 * 60 * 60),
+        );
+        write_session(dir.path(), "recent", "<svg/>", Duration::from_secs(60 * 60));
+
+        assert_eq!(manager.cleanup_expired().unwrap(), 2);
+        let remaining: Vec<_> = manager
+            .list_sessions()
+            .unwrap()
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect();
+        assert_eq!(remaining, ["recent"]);
+
+        assert_eq!(manager.cleanup_older_than(Duration::ZERO).unwrap(), 1);
+        assert!(manager.list_sessions().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "heap-profiling")]
+    #[test]
+    fn test_dhat_output_folds_root_first_weighted_by_bytes() {
+        let raw = r#"{
+            "pps": [
+                { "tb": 1024, "tbk": 2, "fs": [2, 1] },
+                { "tb": 0, "tbk": 0, "fs": [1] }
+            ],
+            "ftbl": [
+                "[root]",
+                "0x10: synapse_core::main (src/main.rs:1:1)",
+                "0x20: alloc::vec::Vec<T>::push (library/alloc/src/vec/mod.rs:10:5)"
+            ]
+        }"#;
+        let folded = dhat_to_folded(raw).unwrap();
+        assert_eq!(folded, ["synapse_core::main;alloc::vec::Vec<T>::push 1024"]);
+    }
+
+    #[cfg(feature = "heap-profiling")]
+    #[tokio::test]
+    async fn test_memory_profile_records_real_allocations() {
+        let _profiler = PROFILER.lock().await;
+        let dir = tempfile::tempdir().unwrap();
+        let churn = tokio::spawn(async {
+            let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_millis(900);
+            let mut kept = Vec::new();
+            while tokio::time::Instant::now() < deadline {
+                kept.push(vec![0u8; 4096]);
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            }
+            kept.len()
+        });
+
+        let (_stop_tx, stop_rx) = tokio::sync::oneshot::channel();
+        let path = run_memory_profiling(dir.path(), "profile-memory-test", 1, stop_rx)
+            .await
+            .unwrap();
+        assert!(churn.await.unwrap() > 0);
+
+        let svg = fs::read_to_string(&path).unwrap();
+        assert!(!svg.is_empty());
+        assert!(svg.contains("<svg"));
+        assert!(!svg.contains("Memory profiling data would appear here"));
+        assert!(!dir.path().join("profile-memory-test.dhat.json").exists());
+    }
 }
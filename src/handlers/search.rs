@@ -1,5 +1,7 @@
 use crate::db::pool_manager::PoolManager;
+use crate::db::queries::{SearchCursor, SearchSort, TransactionFilters};
 use crate::error::AppError;
+use crate::tenant::OptionalTenant;
 use crate::utils::cursor as cursor_util;
 use axum::{
     extract::{Query, State},
@@ -7,96 +9,236 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use sqlx::types::BigDecimal;
 use std::str::FromStr;
 use tracing::instrument;
 
-#[derive(Debug, Deserialize)]
-pub struct SearchQuery {
+/// Transaction filters accepted by both `GET /transactions/search` and
+/// `GET /export`, as they arrive on the query string.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransactionFilterParams {
+    /// One status or a comma-separated list, e.g. `failed,pending`.
     pub status: Option<String>,
     pub asset_code: Option<String>,
+    pub stellar_account: Option<String>,
+    /// Inclusive lower bound, as a decimal.
     pub min_amount: Option<String>,
+    /// Inclusive upper bound, as a decimal.
     pub max_amount: Option<String>,
+    /// RFC 3339 timestamp, or `YYYY-MM-DD` for the start of that day (UTC).
     pub from: Option<String>,
+    /// RFC 3339 timestamp, or `YYYY-MM-DD` for the end of that day (UTC);
+    /// inclusive.
     pub to: Option<String>,
-    pub stellar_account: Option<String>,
+}
+
+/// Parses a filter date given as `YYYY-MM-DD` (midnight UTC) or RFC 3339.
+pub(crate) fn parse_date(date_str: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+    DateTime::parse_from_rfc3339(date_str)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid date format: {e}"))
+}
+
+fn parse_amount(value: &Option<String>, name: &str) -> Result<Option<BigDecimal>, AppError> {
+    value
+        .as_deref()
+        .map(|value| {
+            BigDecimal::from_str(value).map_err(|_| {
+                AppError::BadRequest(format!("Invalid '{name}': must be a valid decimal"))
+            })
+        })
+        .transpose()
+}
+
+impl TransactionFilterParams {
+    pub fn parse(&self) -> Result<TransactionFilters, AppError> {
+        let statuses = self
+            .status
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|status| !status.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let min_amount = parse_amount(&self.min_amount, "min_amount")?;
+        let max_amount = parse_amount(&self.max_amount, "max_amount")?;
+        if let (Some(min), Some(max)) = (&min_amount, &max_amount) {
+            if min > max {
+                return Err(AppError::BadRequest(
+                    "Invalid amount range: 'min_amount' must not exceed 'max_amount'".to_string(),
+                ));
+            }
+        }
+
+        let from = self
+            .from
+            .as_deref()
+            .map(parse_date)
+            .transpose()
+            .map_err(|e| AppError::BadRequest(format!("Invalid 'from' date: {e}")))?;
+        let to = self
+            .to
+            .as_deref()
+            .map(|value| {
+                // A bare date covers the whole day.
+                let is_date = NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok();
+                parse_date(value).map(|to| {
+                    if is_date {
+                        to + Duration::days(1) - Duration::microseconds(1)
+                    } else {
+                        to
+                    }
+                })
+            })
+            .transpose()
+            .map_err(|e| AppError::BadRequest(format!("Invalid 'to' date: {e}")))?;
+
+        Ok(TransactionFilters {
+            statuses,
+            asset_code: self.asset_code.clone(),
+            stellar_account: self.stellar_account.clone(),
+            min_amount,
+            max_amount,
+            from,
+            to,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    #[serde(flatten)]
+    pub filters: TransactionFilterParams,
+    /// JSON object the transaction's `metadata` must contain, e.g.
+    /// `{"order_id":"X"}`.
+    pub metadata_contains: Option<String>,
+    /// `created_at` (default) or `amount`, newest/largest first.
+    pub sort: Option<SearchSort>,
+    /// Only valid with the `sort` it was issued for.
     pub cursor: Option<String>,
     pub limit: Option<i64>,
+    /// Also match soft-deleted transactions.
+    #[serde(default)]
+    pub include_deleted: bool,
+    /// Search every partition when no date range is given, instead of the
+    /// default window.
+    #[serde(default)]
+    pub all_time: bool,
+}
+
+/// Days a search covers when it names no date range, unless configured
+/// otherwise with `SEARCH_DEFAULT_WINDOW_DAYS`.
+pub const DEFAULT_SEARCH_WINDOW_DAYS: u32 = 90;
+
+/// Limits a search without `from` or `to` to the last `window_days`, so it
+/// only touches recent partitions. Returns whether the window was applied;
+/// `all_time` or a zero window leave the filters unbounded.
+fn apply_default_window(
+    filters: &mut TransactionFilters,
+    all_time: bool,
+    window_days: u32,
+    now: DateTime<Utc>,
+) -> bool {
+    if all_time || window_days == 0 || filters.from.is_some() || filters.to.is_some() {
+        return false;
+    }
+    filters.from = Some(now - Duration::days(window_days.into()));
+    true
+}
+
+/// Decodes `cursor` for `sort`, rejecting cursors issued under another sort.
+fn decode_cursor(cursor: &str, sort: SearchSort) -> Result<SearchCursor, String> {
+    let (value, id) = cursor_util::decode_sorted(cursor, sort.as_str())?;
+    match sort {
+        SearchSort::CreatedAt => DateTime::parse_from_rfc3339(&value)
+            .map(|ts| SearchCursor::CreatedAt(ts.with_timezone(&Utc), id))
+            .map_err(|e| format!("timestamp parse error: {e}")),
+        SearchSort::Amount => BigDecimal::from_str(&value)
+            .map(|amount| SearchCursor::Amount(amount, id))
+            .map_err(|e| format!("amount parse error: {e}")),
+    }
 }
 
+fn encode_cursor(cursor: &SearchCursor) -> String {
+    let sort = cursor.sort().as_str();
+    match cursor {
+        SearchCursor::CreatedAt(ts, id) => cursor_util::encode_sorted(sort, &ts.to_rfc3339(), *id),
+        SearchCursor::Amount(amount, id) => {
+            cursor_util::encode_sorted(sort, &amount.to_string(), *id)
+        }
+    }
+}
+
+/// Parses `metadata_contains`, which must be a JSON object.
+fn parse_metadata_filter(value: &str) -> Result<serde_json::Value, AppError> {
+    match serde_json::from_str(value) {
+        Ok(object @ serde_json::Value::Object(_)) => Ok(object),
+        _ => Err(AppError::BadRequest(
+            "Invalid 'metadata_contains': must be a JSON object".to_string(),
+        )),
+    }
+}
+
+/// `GET /transactions/search`. Without a date range only the last
+/// `default_window_days` are searched unless `all_time=true`; the response's
+/// `effective_window` reports the range actually used. With `tenant_id` set
+/// only that tenant's transactions are searched.
 #[instrument(name = "search.transactions", skip(pool_manager, params))]
 pub async fn search_transactions(
     State(pool_manager): State<PoolManager>,
+    default_window_days: u32,
+    tenant_id: Option<uuid::Uuid>,
     Query(params): Query<SearchQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let limit = params.limit.unwrap_or(25).min(100);
+    let sort = params.sort.unwrap_or_default();
 
     let decoded_cursor = if let Some(ref c) = params.cursor {
-        match cursor_util::decode(c) {
-            Ok((ts, id)) => Some((ts, id)),
+        match decode_cursor(c, sort) {
+            Ok(cursor) => Some(cursor),
             Err(e) => return Err(AppError::BadRequest(format!("Invalid cursor: {e}"))),
         }
     } else {
         None
     };
 
-    let min_amount = match params.min_amount {
-        Some(value) => Some(BigDecimal::from_str(&value).map_err(|_| {
-            AppError::BadRequest("Invalid 'min_amount': must be a valid decimal".to_string())
-        })?),
-        None => None,
-    };
-
-    let max_amount = match params.max_amount {
-        Some(value) => Some(BigDecimal::from_str(&value).map_err(|_| {
-            AppError::BadRequest("Invalid 'max_amount': must be a valid decimal".to_string())
-        })?),
-        None => None,
-    };
-
-    let from_date = match params.from {
-        Some(value) => Some(
-            DateTime::parse_from_rfc3339(&value)
-                .map_err(|_| {
-                    AppError::BadRequest("Invalid 'from' date: must be RFC 3339 format".to_string())
-                })?
-                .with_timezone(&Utc),
-        ),
-        None => None,
-    };
+    let mut filters = params.filters.parse()?;
+    let default_window = apply_default_window(
+        &mut filters,
+        params.all_time,
+        default_window_days,
+        Utc::now(),
+    );
 
-    let to_date = match params.to {
-        Some(value) => Some(
-            DateTime::parse_from_rfc3339(&value)
-                .map_err(|_| {
-                    AppError::BadRequest("Invalid 'to' date: must be RFC 3339 format".to_string())
-                })?
-                .with_timezone(&Utc),
-        ),
+    let metadata_contains = match params.metadata_contains {
+        Some(value) => Some(parse_metadata_filter(&value)?),
         None => None,
     };
 
     let (pool, replica_used) = pool_manager.read_pool().await;
     let (total, transactions) = crate::db::queries::search_transactions(
         pool,
-        params.status.as_deref(),
-        params.asset_code.as_deref(),
-        min_amount.as_ref(),
-        max_amount.as_ref(),
-        from_date,
-        to_date,
-        params.stellar_account.as_deref(),
+        tenant_id,
+        &filters,
+        metadata_contains.as_ref(),
+        sort,
         limit,
-        decoded_cursor,
+        decoded_cursor.as_ref(),
+        params.include_deleted,
     )
     .await?;
 
     let next_cursor = if transactions.len() == limit as usize {
         transactions
             .last()
-            .map(|tx| cursor_util::encode(tx.created_at, tx.id))
+            .map(|tx| encode_cursor(&SearchCursor::after(sort, tx)))
     } else {
         None
     };
@@ -104,6 +246,11 @@ pub async fn search_transactions(
     let mut resp = serde_json::json!({
         "total": total,
         "results": transactions,
+        "effective_window": {
+            "from": filters.from,
+            "to": filters.to,
+            "default_applied": default_window,
+        },
     });
 
     if let Some(cursor) = next_cursor {
@@ -120,10 +267,53 @@ pub async fn search_transactions(
     Ok(response)
 }
 
-/// Wrapper for use with ApiState in create_app
+/// Wrapper for use with ApiState in create_app. A request that identifies a
+/// tenant only searches that tenant's transactions.
 pub async fn search_transactions_wrapper(
     State(api_state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
     Query(params): Query<SearchQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    search_transactions(State(api_state.app_state.pool_manager), Query(params)).await
+    search_transactions(
+        State(api_state.app_state.pool_manager),
+        api_state.app_state.search_default_window_days,
+        tenant.map(|t| t.tenant_id),
+        Query(params),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        parse_date("2026-06-30").unwrap()
+    }
+
+    #[test]
+    fn test_default_window_applies_without_date_range() {
+        let mut filters = TransactionFilters::default();
+        assert!(apply_default_window(&mut filters, false, 90, now()));
+        assert_eq!(filters.from, Some(parse_date("2026-04-01").unwrap()));
+        assert_eq!(filters.to, None);
+    }
+
+    #[test]
+    fn test_default_window_is_overridden() {
+        // By `all_time`, a disabled window, or any explicit bound.
+        let mut filters = TransactionFilters::default();
+        assert!(!apply_default_window(&mut filters, true, 90, now()));
+        assert!(!apply_default_window(&mut filters, false, 0, now()));
+        assert_eq!(filters, TransactionFilters::default());
+
+        let to = parse_date("2020-01-01").unwrap();
+        let mut filters = TransactionFilters {
+            to: Some(to),
+            ..Default::default()
+        };
+        assert!(!apply_default_window(&mut filters, false, 90, now()));
+        assert_eq!(filters.from, None);
+        assert_eq!(filters.to, Some(to));
+    }
 }
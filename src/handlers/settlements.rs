@@ -1,4 +1,6 @@
+use crate::db::queries::SettlementFilter;
 use crate::error::AppError;
+use crate::handlers::pagination::{Page, SettlementListResponse};
 use crate::utils::cursor as cursor_util;
 use crate::validation::{validate_max_len, validate_required};
 use crate::ApiState;
@@ -7,36 +9,42 @@ use axum::{
     http::{HeaderValue, StatusCode},
     response::{IntoResponse, Json, Response},
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 pub struct SettlementListQuery {
+    pub asset_code: Option<String>,
+    pub status: Option<String>,
+    /// RFC 3339 lower bound on `created_at` (inclusive)
+    pub from: Option<String>,
+    /// RFC 3339 upper bound on `created_at` (inclusive)
+    pub to: Option<String>,
     pub cursor: Option<String>,
     pub limit: Option<i64>,
+    /// Rows to skip, counted after `cursor` when one is given
+    pub offset: Option<i64>,
     /// "forward" (default) or "backward"
     pub direction: Option<String>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
-pub struct SettlementListResponse {
-    pub settlements: Vec<crate::db::models::Settlement>,
-    pub next_cursor: Option<String>,
-    pub has_more: bool,
-}
-
 #[utoipa::path(
     get,
     path = "/settlements",
     params(
+        ("asset_code" = Option<String>, Query, description = "Filter by asset code"),
+        ("status" = Option<String>, Query, description = "Filter by settlement status"),
+        ("from" = Option<String>, Query, description = "Created at or after (RFC 3339)"),
+        ("to" = Option<String>, Query, description = "Created at or before (RFC 3339)"),
         ("cursor" = Option<String>, Query, description = "Pagination cursor"),
         ("limit" = Option<i64>, Query, description = "Page size (1-100, default 10)"),
+        ("offset" = Option<i64>, Query, description = "Rows to skip, after `cursor` when given (default 0)"),
         ("direction" = Option<String>, Query, description = "\"forward\" (default) or \"backward\""),
     ),
     responses(
         (status = 200, description = "List of settlements", body = SettlementListResponse),
-        (status = 400, description = "Invalid cursor"),
+        (status = 400, description = "Invalid cursor, date or date range"),
         (status = 500, description = "Internal server error"),
     ),
     tag = "Settlements"
@@ -46,6 +54,7 @@ pub async fn list_settlements(
     Query(params): Query<SettlementListQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let limit = params.limit.unwrap_or(10).clamp(1, 100);
+    let offset = params.offset.unwrap_or(0).max(0);
     let backward = params.direction.as_deref() == Some("backward");
 
     let decoded_cursor = if let Some(ref c) = params.cursor {
@@ -57,11 +66,32 @@ pub async fn list_settlements(
         None
     };
 
+    let filter = SettlementFilter {
+        asset_code: params.asset_code,
+        status: params.status,
+        from: parse_date_param("from", params.from.as_deref())?,
+        to: parse_date_param("to", params.to.as_deref())?,
+    };
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if from > to {
+            return Err(AppError::BadRequest(
+                "Invalid date range: 'from' must not be after 'to'".to_string(),
+            ));
+        }
+    }
+
     let fetch_limit = limit + 1;
     let (pool, replica_used) = state.app_state.pool_manager.read_pool().await;
-    let mut settlements =
-        crate::db::queries::list_settlements_cursor(pool, fetch_limit, decoded_cursor, backward)
-            .await?;
+    let mut settlements = crate::db::queries::list_settlements_cursor(
+        pool,
+        &filter,
+        fetch_limit,
+        offset,
+        decoded_cursor,
+        backward,
+    )
+    .await?;
+    let total = crate::db::queries::count_settlements(pool, &filter).await?;
 
     let has_more = settlements.len() as i64 > limit;
     if has_more {
@@ -73,9 +103,9 @@ pub async fn list_settlements(
         .map(|s| cursor_util::encode(s.created_at, s.id));
 
     let body = SettlementListResponse {
-        settlements,
-        next_cursor,
         has_more,
+        next_cursor,
+        ..Page::new(settlements, total, limit, offset)
     };
 
     let mut response: Response = Json(body).into_response();
@@ -88,6 +118,18 @@ pub async fn list_settlements(
     Ok(response)
 }
 
+fn parse_date_param(name: &str, value: Option<&str>) -> Result<Option<DateTime<Utc>>, AppError> {
+    value
+        .map(|v| {
+            DateTime::parse_from_rfc3339(v)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|_| {
+                    AppError::BadRequest(format!("Invalid '{name}' date: must be RFC 3339 format"))
+                })
+        })
+        .transpose()
+}
+
 #[utoipa::path(
     get,
     path = "/settlements/{id}",
@@ -126,6 +168,116 @@ pub async fn get_settlement(
     Ok(response)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SettlementTransactionsQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// A transaction rolled up into a settlement.
+#[derive(Debug, Serialize)]
+pub struct SettlementMember {
+    pub id: Uuid,
+    pub amount: sqlx::types::BigDecimal,
+    pub asset_code: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<crate::db::models::Transaction> for SettlementMember {
+    fn from(tx: crate::db::models::Transaction) -> Self {
+        Self {
+            id: tx.id,
+            amount: tx.amount,
+            asset_code: tx.asset_code,
+            status: tx.status,
+            created_at: tx.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SettlementTransactionsResponse {
+    pub settlement_id: Uuid,
+    pub transactions: Vec<SettlementMember>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/settlements/{id}/transactions",
+    params(
+        ("id" = Uuid, Path, description = "Settlement ID"),
+        ("cursor" = Option<String>, Query, description = "Pagination cursor"),
+        ("limit" = Option<i64>, Query, description = "Page size (1-100, default 50)"),
+    ),
+    responses(
+        (status = 200, description = "Transactions included in the settlement"),
+        (status = 400, description = "Invalid cursor"),
+        (status = 404, description = "Settlement not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "Settlements"
+)]
+pub async fn list_settlement_transactions(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SettlementTransactionsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let decoded_cursor = match params.cursor.as_deref().map(cursor_util::decode) {
+        Some(Ok(pair)) => Some(pair),
+        Some(Err(e)) => return Err(AppError::BadRequest(format!("invalid cursor: {}", e))),
+        None => None,
+    };
+
+    let (pool, replica_used) = state.app_state.pool_manager.read_pool().await;
+    // Distinguish an unknown settlement from one with no members.
+    crate::db::queries::get_settlement(pool, id)
+        .await
+        .map_err(|e| {
+            if matches!(e, sqlx::Error::RowNotFound) {
+                AppError::NotFound(format!("Settlement {} not found", id))
+            } else {
+                AppError::from(e)
+            }
+        })?;
+
+    let mut rows = crate::db::queries::list_settlement_transactions_cursor(
+        pool,
+        id,
+        limit + 1,
+        decoded_cursor,
+    )
+    .await?;
+    let has_more = rows.len() as i64 > limit;
+    if has_more {
+        rows.truncate(limit as usize);
+    }
+    let next_cursor = if has_more {
+        rows.last().map(|t| cursor_util::encode(t.created_at, t.id))
+    } else {
+        None
+    };
+
+    let body = SettlementTransactionsResponse {
+        settlement_id: id,
+        transactions: rows.into_iter().map(SettlementMember::from).collect(),
+        next_cursor,
+        has_more,
+    };
+
+    let mut response: Response = Json(body).into_response();
+    if replica_used {
+        response
+            .headers_mut()
+            .insert("X-Read-Consistency", HeaderValue::from_static("eventual"));
+    }
+
+    Ok(response)
+}
+
 /// Request body for admin settlement status changes.
 #[derive(Debug, Deserialize)]
 pub struct UpdateSettlementStatusRequest {
@@ -135,6 +287,9 @@ pub struct UpdateSettlementStatusRequest {
     pub new_total: Option<String>,
     /// Actor performing the change (defaults to "admin").
     pub actor: Option<String>,
+    /// Settlement `version` the caller last read. When given, the change is
+    /// rejected with 409 if the settlement has been updated since.
+    pub version: Option<i32>,
 }
 
 impl UpdateSettlementStatusRequest {
@@ -188,8 +343,9 @@ pub async fn update_settlement_status(
     let service = crate::services::SettlementService::new(state.app_state.db.clone());
 
     let settlement = service
-        .update_status(
+        .update_status_at_version(
             id,
+            payload.version,
             &payload.status,
             payload.reason.as_deref(),
             new_total.as_ref(),
@@ -211,6 +367,7 @@ mod tests {
             reason: Some("testing".to_string()),
             new_total: None,
             actor: Some("admin".to_string()),
+            version: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -222,6 +379,7 @@ mod tests {
             reason: None,
             new_total: None,
             actor: None,
+            version: None,
         };
         assert!(req.validate().is_err());
     }
@@ -233,6 +391,7 @@ mod tests {
             reason: None,
             new_total: None,
             actor: None,
+            version: None,
         };
         assert!(req.validate().is_err());
     }
@@ -244,6 +403,7 @@ mod tests {
             reason: Some("a".repeat(256)),
             new_total: None,
             actor: None,
+            version: None,
         };
         assert!(req.validate().is_err());
     }
@@ -255,6 +415,7 @@ mod tests {
             reason: None,
             new_total: None,
             actor: Some("a".repeat(51)),
+            version: None,
         };
         assert!(req.validate().is_err());
     }
@@ -267,6 +428,7 @@ mod tests {
             reason: None,
             new_total: None,
             actor: None,
+            version: None,
         };
         assert!(req.validate().is_ok());
 
@@ -276,6 +438,7 @@ mod tests {
             reason: Some("a".repeat(255)),
             new_total: None,
             actor: None,
+            version: None,
         };
         assert!(req.validate().is_ok());
 
@@ -285,6 +448,7 @@ mod tests {
             reason: None,
             new_total: None,
             actor: Some("a".repeat(50)),
+            version: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -1,9 +1,10 @@
-use crate::db::queries::{AssetStats, DailyTotal, StatusCount};
+use crate::db::queries::{AssetDailyTotal, AssetStats, DailyTotal, StatusCount};
 use crate::error::AppError;
 use crate::services::query_cache::{
-    cache_key_asset_stats, cache_key_daily_totals, cache_key_status_counts, CacheConfig,
+    cache_key_asset_stats, cache_key_daily_totals, cache_key_daily_totals_by_asset,
+    cache_key_status_counts, CacheConfig,
 };
-use crate::validation::validate_range;
+use crate::validation::{validate_asset_code_format, validate_range};
 use crate::ApiState;
 use axum::{
     extract::State,
@@ -11,16 +12,24 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{NaiveDate, Utc};
 use serde::Deserialize;
 use std::time::Duration;
 
 const MIN_DAYS: i32 = 1;
 const MAX_DAYS: i32 = 365;
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct DailyTotalsQuery {
     #[serde(default = "default_days")]
     days: i32,
+    /// Switches to the per-asset series of [`AssetDailyTotal`]s.
+    asset: Option<String>,
+    /// First day of the per-asset series (`YYYY-MM-DD`, UTC); defaults to
+    /// `days - 1` days before `to`.
+    from: Option<NaiveDate>,
+    /// Last day of the per-asset series, inclusive; defaults to today (UTC).
+    to: Option<NaiveDate>,
 }
 
 fn default_days() -> i32 {
@@ -30,7 +39,33 @@ fn default_days() -> i32 {
 impl DailyTotalsQuery {
     pub fn validate(&self) -> Result<(), AppError> {
         validate_range("days", self.days as i64, MIN_DAYS as i64, MAX_DAYS as i64)
-            .map_err(|e| AppError::BadRequest(e.to_string()))
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if self.asset.is_none() && (self.from.is_some() || self.to.is_some()) {
+            return Err(AppError::BadRequest(
+                "'from' and 'to' require 'asset'".to_string(),
+            ));
+        }
+        if let Some(asset) = &self.asset {
+            validate_asset_code_format(asset).map_err(|e| AppError::BadRequest(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// The inclusive day range of the per-asset series.
+    fn asset_range(&self) -> Result<(NaiveDate, NaiveDate), AppError> {
+        let to = self.to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = self
+            .from
+            .unwrap_or_else(|| to - chrono::Duration::days(i64::from(self.days) - 1));
+        if from > to {
+            return Err(AppError::BadRequest(
+                "'from' must not be after 'to'".to_string(),
+            ));
+        }
+        let days = (to - from).num_days() + 1;
+        validate_range("days", days, MIN_DAYS as i64, MAX_DAYS as i64)
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        Ok((from, to))
     }
 }
 
@@ -95,6 +130,10 @@ pub async fn daily_totals(
     axum::extract::Query(query): axum::extract::Query<DailyTotalsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     query.validate()?;
+    if let Some(asset) = &query.asset {
+        let (from, to) = query.asset_range()?;
+        return asset_daily_totals(&state, asset, from, to).await;
+    }
 
     let cache_key = cache_key_daily_totals(query.days);
     let config = CacheConfig::default();
@@ -142,6 +181,46 @@ pub async fn daily_totals(
     )
 }
 
+/// Per-day series of one asset for `GET /stats/daily?asset=...`.
+async fn asset_daily_totals(
+    state: &ApiState,
+    asset: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Response, AppError> {
+    let cache_key = cache_key_daily_totals_by_asset(asset, from, to);
+    let config = CacheConfig::default();
+
+    if let Ok(Some(cached)) = state
+        .app_state
+        .query_cache
+        .get::<Vec<AssetDailyTotal>>(&cache_key)
+        .await
+    {
+        return Ok((StatusCode::OK, Json(cached)).into_response());
+    }
+
+    let (pool, replica_used) = state.app_state.pool_manager.read_pool().await;
+    let totals = crate::db::queries::daily_totals_by_asset(pool, asset, from, to).await?;
+    let _ = state
+        .app_state
+        .query_cache
+        .set(
+            &cache_key,
+            &totals,
+            Duration::from_secs(config.daily_totals_ttl),
+        )
+        .await;
+
+    let mut response: Response = (StatusCode::OK, Json(totals)).into_response();
+    if replica_used {
+        response
+            .headers_mut()
+            .insert("X-Read-Consistency", HeaderValue::from_static("eventual"));
+    }
+    Ok(response)
+}
+
 pub async fn asset_stats(State(state): State<ApiState>) -> Result<impl IntoResponse, AppError> {
     let cache_key = cache_key_asset_stats();
     let config = CacheConfig::default();
@@ -201,53 +280,144 @@ pub async fn cache_metrics(State(state): State<ApiState>) -> Result<impl IntoRes
     Ok((StatusCode::OK, Json(combined_metrics)))
 }
 
+/// Prometheus text exposition of [`crate::metrics::prometheus_registry`],
+/// including per-`query_type` query cache hit/miss counters.
+pub async fn prometheus_metrics() -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        crate::metrics::render_prometheus(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 8, d).unwrap()
+    }
+
+    #[test]
+    fn test_asset_range_defaults_to_days_ending_on_to() {
+        let query = DailyTotalsQuery {
+            days: 7,
+            asset: Some("USDC".to_string()),
+            to: Some(day(10)),
+            ..Default::default()
+        };
+        assert!(query.validate().is_ok());
+        assert_eq!(query.asset_range().unwrap(), (day(4), day(10)));
+    }
+
+    #[test]
+    fn test_asset_range_rejects_inverted_or_oversized_ranges() {
+        let inverted = DailyTotalsQuery {
+            days: 7,
+            asset: Some("USDC".to_string()),
+            from: Some(day(10)),
+            to: Some(day(9)),
+        };
+        assert!(inverted.asset_range().is_err());
+
+        let oversized = DailyTotalsQuery {
+            days: 7,
+            asset: Some("USDC".to_string()),
+            from: Some(day(1) - chrono::Duration::days(400)),
+            to: Some(day(1)),
+        };
+        assert!(oversized.asset_range().is_err());
+    }
+
+    #[test]
+    fn test_date_range_requires_asset() {
+        let query = DailyTotalsQuery {
+            days: 7,
+            from: Some(day(1)),
+            ..Default::default()
+        };
+        assert!(query.validate().is_err());
+    }
+
     #[test]
     fn test_daily_totals_query_valid_days() {
-        let query = DailyTotalsQuery { days: 7 };
+        let query = DailyTotalsQuery {
+            days: 7,
+            ..Default::default()
+        };
         assert!(query.validate().is_ok());
 
-        let query = DailyTotalsQuery { days: 1 };
+        let query = DailyTotalsQuery {
+            days: 1,
+            ..Default::default()
+        };
         assert!(query.validate().is_ok());
 
-        let query = DailyTotalsQuery { days: 365 };
+        let query = DailyTotalsQuery {
+            days: 365,
+            ..Default::default()
+        };
         assert!(query.validate().is_ok());
     }
 
     #[test]
     fn test_daily_totals_query_invalid_days_too_low() {
-        let query = DailyTotalsQuery { days: 0 };
+        let query = DailyTotalsQuery {
+            days: 0,
+            ..Default::default()
+        };
         assert!(query.validate().is_err());
 
-        let query = DailyTotalsQuery { days: -1 };
+        let query = DailyTotalsQuery {
+            days: -1,
+            ..Default::default()
+        };
         assert!(query.validate().is_err());
     }
 
     #[test]
     fn test_daily_totals_query_invalid_days_too_high() {
-        let query = DailyTotalsQuery { days: 366 };
+        let query = DailyTotalsQuery {
+            days: 366,
+            ..Default::default()
+        };
         assert!(query.validate().is_err());
 
-        let query = DailyTotalsQuery { days: 1000 };
+        let query = DailyTotalsQuery {
+            days: 1000,
+            ..Default::default()
+        };
         assert!(query.validate().is_err());
     }
 
     #[test]
     fn test_daily_totals_query_boundary_values() {
         // Test boundary values
-        let query = DailyTotalsQuery { days: MIN_DAYS };
+        let query = DailyTotalsQuery {
+            days: MIN_DAYS,
+            ..Default::default()
+        };
         assert!(query.validate().is_ok(), "should accept MIN_DAYS");
 
-        let query = DailyTotalsQuery { days: MAX_DAYS };
+        let query = DailyTotalsQuery {
+            days: MAX_DAYS,
+            ..Default::default()
+        };
         assert!(query.validate().is_ok(), "should accept MAX_DAYS");
 
-        let query = DailyTotalsQuery { days: MIN_DAYS - 1 };
+        let query = DailyTotalsQuery {
+            days: MIN_DAYS - 1,
+            ..Default::default()
+        };
         assert!(query.validate().is_err(), "should reject below MIN_DAYS");
 
-        let query = DailyTotalsQuery { days: MAX_DAYS + 1 };
+        let query = DailyTotalsQuery {
+            days: MAX_DAYS + 1,
+            ..Default::default()
+        };
         assert!(query.validate().is_err(), "should reject above MAX_DAYS");
     }
 }
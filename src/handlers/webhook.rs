@@ -1,24 +1,33 @@
+use crate::db::models::NewWebhookPayload;
 use crate::db::models::Transaction as TxModel;
 use crate::db::{models::Transaction, queries};
 use crate::error::AppError;
+use crate::handlers::ws::TransactionStatusUpdate;
+use crate::middleware::callback_trace;
+use crate::services::payment_matching;
+use crate::services::processor;
+use crate::services::transaction_processor::TransactionProcessor;
+use crate::tenant::OptionalTenant;
 use crate::utils::cursor as cursor_util;
 use crate::validation::{
-    sanitize_string, validate_asset_code, validate_max_len, validate_positive_amount,
-    validate_stellar_address, AMOUNT_INPUT_MAX_LEN, ANCHOR_TRANSACTION_ID_MAX_LEN,
-    CALLBACK_STATUS_MAX_LEN, CALLBACK_TYPE_MAX_LEN,
+    normalize_asset_code, sanitize_string, validate_asset_code, validate_max_len,
+    validate_positive_amount, validate_stellar_address, AMOUNT_INPUT_MAX_LEN,
+    ANCHOR_TRANSACTION_ID_MAX_LEN, CALLBACK_STATUS_MAX_LEN, CALLBACK_TYPE_MAX_LEN,
 };
 use crate::{ApiState, AppState};
+use async_trait::async_trait;
 use axum::{
-    extract::{Path, Query, State},
-    http::{HeaderValue, StatusCode},
+    body::{Body, Bytes},
+    extract::{FromRequest, Path, Query, State},
+    http::{HeaderMap, HeaderValue, Request, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sqlx::types::BigDecimal;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
-use tracing::instrument;
+use tracing::{instrument, Instrument};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -28,6 +37,9 @@ pub struct CallbackPayload {
     pub stellar_account: String,
     pub amount: String,
     pub asset_code: String,
+    /// Issuing account of `asset_code`; omit for native XLM.
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
     pub callback_type: Option<String>,
     pub callback_status: Option<String>,
     pub anchor_transaction_id: Option<String>,
@@ -37,6 +49,149 @@ pub struct CallbackPayload {
     pub metadata: Option<serde_json::Value>,
 }
 
+impl CallbackPayload {
+    /// Validates the payload and builds the transaction it describes.
+    pub fn into_transaction(self) -> Result<Transaction, AppError> {
+        validate_memo_type(&self.memo_type)?;
+
+        let amount = BigDecimal::from_str(&self.amount)
+            .map_err(|_| AppError::Validation(format!("Invalid amount: {}", self.amount)))?;
+
+        Ok(Transaction::new(
+            self.stellar_account,
+            amount,
+            self.asset_code,
+            self.anchor_transaction_id,
+            self.callback_type,
+            self.callback_status,
+            self.memo,
+            self.memo_type,
+            self.metadata,
+        )
+        .with_asset_issuer(self.asset_issuer))
+    }
+}
+
+/// Decodes a body stored in `webhook_payloads` by [`callback`] or
+/// [`transaction_callback`] into the transaction it describes, applying the
+/// same validation as at ingest. `source` is the stored payload's source tag.
+pub fn transaction_from_stored_payload(source: &str, body: &[u8]) -> Result<Transaction, AppError> {
+    let invalid = |e: serde_json::Error| {
+        AppError::Validation(format!("Stored '{source}' payload is not valid: {e}"))
+    };
+    match source {
+        "callback" => serde_json::from_slice::<CallbackPayload>(body)
+            .map_err(invalid)?
+            .into_transaction(),
+        "callback/transaction" => serde_json::from_slice::<WebhookTransactionRequest>(body)
+            .map_err(invalid)?
+            .into_transaction(),
+        other => Err(AppError::Validation(format!(
+            "Unsupported stored payload source '{other}'"
+        ))),
+    }
+}
+
+/// Headers never written to `webhook_payloads`: they carry caller credentials
+/// and are not needed to replay the body.
+const REDACTED_PAYLOAD_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+
+/// JSON body extractor that also keeps the request headers and the body bytes
+/// exactly as received, so the handler can store them for replay.
+///
+/// Decoding is delegated to [`Json`], so rejections (missing content type,
+/// malformed JSON, unknown fields) are unchanged.
+pub struct RawJson<T> {
+    pub value: T,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+#[async_trait]
+impl<S, T> FromRequest<S, Body> for RawJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<Body>, state: &S) -> Result<Self, Self::Rejection> {
+        let (parts, body) = req.into_parts();
+        let headers = parts.headers.clone();
+        let body = Bytes::from_request(Request::from_parts(parts, body), state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let mut replayed = Request::new(Body::from(body.clone()));
+        *replayed.headers_mut() = headers.clone();
+        let Json(value) = Json::<T>::from_request(replayed, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        Ok(Self {
+            value,
+            headers,
+            body,
+        })
+    }
+}
+
+/// Wraps an already decoded body, re-encoding it as the "received" bytes.
+/// Used when handlers are called directly rather than through the router.
+impl<T: Serialize> From<Json<T>> for RawJson<T> {
+    fn from(Json(value): Json<T>) -> Self {
+        let body = serde_json::to_vec(&value)
+            .map(Bytes::from)
+            .unwrap_or_default();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        Self {
+            value,
+            headers,
+            body,
+        }
+    }
+}
+
+impl<T> RawJson<T> {
+    /// The captured request as a [`NewWebhookPayload`] tagged with `source`.
+    pub fn to_payload(&self, source: &str) -> NewWebhookPayload {
+        NewWebhookPayload {
+            source: source.to_string(),
+            headers: headers_to_json(&self.headers),
+            body: self.body.to_vec(),
+        }
+    }
+}
+
+/// Flattens `headers` into a JSON object keyed by lowercase header name,
+/// dropping [`REDACTED_PAYLOAD_HEADERS`]. Repeated headers are joined with
+/// `", "`.
+fn headers_to_json(headers: &HeaderMap) -> serde_json::Value {
+    let mut map = serde_json::Map::new();
+    for name in headers.keys() {
+        if REDACTED_PAYLOAD_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        let joined = headers
+            .get_all(name)
+            .iter()
+            .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        map.insert(name.as_str().to_string(), serde_json::Value::String(joined));
+    }
+    serde_json::Value::Object(map)
+}
+
 /// Minimal webhook payload carrying an opaque event identifier.
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct WebhookPayload {
@@ -52,6 +207,9 @@ pub struct WebhookTransactionRequest {
     pub stellar_address: String,
     pub amount: String,
     pub asset_code: String,
+    /// Issuing account of `asset_code`; omit for native XLM.
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
     pub anchor_transaction_id: Option<String>,
     pub callback_type: Option<String>,
     pub callback_status: Option<String>,
@@ -68,6 +226,7 @@ struct ValidatedWebhookTransaction {
     stellar_address: String,
     amount: BigDecimal,
     asset_code: String,
+    asset_issuer: Option<String>,
     anchor_transaction_id: Option<String>,
     callback_type: Option<String>,
     callback_status: Option<String>,
@@ -83,7 +242,7 @@ fn validate_webhook_payload(
     payload: WebhookTransactionRequest,
 ) -> Result<ValidatedWebhookTransaction, AppError> {
     let stellar_address = sanitize_string(&payload.stellar_address);
-    let asset_code = sanitize_string(&payload.asset_code);
+    let asset_issuer = sanitize_optional(payload.asset_issuer);
     let amount_str = sanitize_string(&payload.amount);
     let anchor_transaction_id = sanitize_optional(payload.anchor_transaction_id);
     let callback_type = sanitize_optional(payload.callback_type);
@@ -91,7 +250,15 @@ fn validate_webhook_payload(
 
     validate_stellar_address(&stellar_address)
         .map_err(|err| AppError::Validation(err.to_string()))?;
+    let asset_code = normalize_asset_code(&payload.asset_code)
+        .map_err(|err| AppError::Validation(err.to_string()))?;
     validate_asset_code(&asset_code).map_err(|err| AppError::Validation(err.to_string()))?;
+    if let Some(asset_issuer) = &asset_issuer {
+        validate_stellar_address(asset_issuer).map_err(|mut err| {
+            err.field = "asset_issuer";
+            AppError::Validation(err.to_string())
+        })?;
+    }
     validate_max_len("amount", &amount_str, AMOUNT_INPUT_MAX_LEN)
         .map_err(|err| AppError::Validation(err.to_string()))?;
     if let Some(anchor_transaction_id) = &anchor_transaction_id {
@@ -120,12 +287,33 @@ fn validate_webhook_payload(
         stellar_address,
         amount,
         asset_code,
+        asset_issuer,
         anchor_transaction_id,
         callback_type,
         callback_status,
     })
 }
 
+impl WebhookTransactionRequest {
+    /// Validates and sanitizes the request and builds the transaction it
+    /// describes.
+    fn into_transaction(self) -> Result<Transaction, AppError> {
+        let payload = validate_webhook_payload(self)?;
+        Ok(Transaction::new(
+            payload.stellar_address,
+            payload.amount,
+            payload.asset_code,
+            payload.anchor_transaction_id,
+            payload.callback_type,
+            payload.callback_status,
+            None, // memo
+            None, // memo_type
+            None, // metadata
+        )
+        .with_asset_issuer(payload.asset_issuer))
+    }
+}
+
 /// Process a raw transaction callback from an external anchor.
 ///
 /// Validates and sanitizes all fields before inserting the transaction into the
@@ -135,12 +323,14 @@ fn validate_webhook_payload(
 /// # Errors
 /// - `400 Bad Request` – validation fails (invalid address, amount, field length, etc.)
 /// - `500 Internal Server Error` – database insertion fails
-#[instrument(name = "webhook.transaction_callback", skip(state, payload))]
+#[instrument(name = "webhook.transaction_callback", skip(state, request))]
 pub async fn transaction_callback(
     State(state): State<ApiState>,
-    Json(payload): Json<WebhookTransactionRequest>,
+    OptionalTenant(tenant): OptionalTenant,
+    request: RawJson<WebhookTransactionRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    let payload = validate_webhook_payload(payload)?;
+    let raw = request.to_payload("callback/transaction");
+    let tx = request.value.into_transaction()?;
 
     let trace_id = opentelemetry::global::get_text_map_propagator(|propagator| {
         let mut carrier = std::collections::HashMap::new();
@@ -148,20 +338,12 @@ pub async fn transaction_callback(
         carrier.get("traceparent").cloned()
     });
 
-    let tx = Transaction::new(
-        payload.stellar_address,
-        payload.amount,
-        payload.asset_code,
-        payload.anchor_transaction_id,
-        payload.callback_type,
-        payload.callback_status,
-        None, // memo
-        None, // memo_type
-        None, // metadata
-    )
-    .with_trace_id(trace_id);
+    let tx = tx
+        .with_trace_id(trace_id)
+        .with_tenant_id(tenant.map(|t| t.tenant_id));
 
-    let (result, is_new) = queries::insert_transaction(&state.app_state.db, &tx).await?;
+    let (result, is_new) =
+        queries::insert_transaction_with_payload(&state.app_state.db, &tx, &raw).await?;
 
     let status = if is_new {
         StatusCode::CREATED
@@ -187,6 +369,7 @@ mod tests {
             stellar_address: "G".to_owned() + &"A".repeat(55),
             amount: "42.50".to_string(),
             asset_code: "USD".to_string(),
+            asset_issuer: None,
             anchor_transaction_id: Some("anchor-1".to_string()),
             callback_type: Some("deposit".to_string()),
             callback_status: Some("completed".to_string()),
@@ -224,12 +407,34 @@ mod tests {
     #[test]
     fn validate_webhook_payload_rejects_invalid_asset_code() {
         let mut payload = valid_payload();
-        payload.asset_code = "usd".to_string();
+        payload.asset_code = "US D".to_string();
 
         let parsed = validate_webhook_payload(payload);
         assert!(parsed.is_err());
     }
 
+    #[test]
+    fn validate_webhook_payload_normalizes_asset_code_case() {
+        let mut payload = valid_payload();
+        payload.asset_code = " usd ".to_string();
+
+        let parsed = validate_webhook_payload(payload).unwrap();
+        assert_eq!(parsed.asset_code, "USD");
+    }
+
+    #[test]
+    fn validate_webhook_payload_checks_asset_issuer() {
+        let mut payload = valid_payload();
+        payload.asset_issuer = Some("G".to_owned() + &"B".repeat(55));
+        let parsed = validate_webhook_payload(payload).unwrap();
+        assert_eq!(parsed.asset_issuer, Some("G".to_owned() + &"B".repeat(55)));
+
+        let mut payload = valid_payload();
+        payload.asset_issuer = Some("NOT-AN-ACCOUNT".to_string());
+        let err = validate_webhook_payload(payload).err().unwrap();
+        assert!(err.to_string().contains("asset_issuer"), "{err}");
+    }
+
     #[test]
     fn validate_webhook_payload_rejects_invalid_amount() {
         let mut payload = valid_payload();
@@ -307,6 +512,83 @@ mod tests {
         payload.callback_status = Some("a".repeat(21));
         assert!(validate_webhook_payload(payload).is_err());
     }
+
+    #[test]
+    fn headers_to_json_drops_credentials_and_joins_repeats() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", HeaderValue::from_static("secret"));
+        headers.insert("Authorization", HeaderValue::from_static("Bearer secret"));
+        headers.insert(
+            "X-Webhook-Timestamp",
+            HeaderValue::from_static("1700000000"),
+        );
+        headers.append("X-Forwarded-For", HeaderValue::from_static("10.0.0.1"));
+        headers.append("X-Forwarded-For", HeaderValue::from_static("10.0.0.2"));
+
+        let json = headers_to_json(&headers);
+        assert!(json.get("x-api-key").is_none());
+        assert!(json.get("authorization").is_none());
+        assert_eq!(json["x-webhook-timestamp"], "1700000000");
+        assert_eq!(json["x-forwarded-for"], "10.0.0.1, 10.0.0.2");
+    }
+
+    #[test]
+    fn transaction_from_stored_payload_decodes_each_ingest_source() {
+        let account = "G".to_owned() + &"A".repeat(55);
+        let callback = serde_json::json!({
+            "stellar_account": account,
+            "amount": "25.00",
+            "asset_code": "USDC",
+            "memo": "42",
+            "memo_type": "id",
+            "metadata": {"kyc_level": 2},
+            "anchor_extra": {"route": "bank-7"},
+        });
+        let tx =
+            transaction_from_stored_payload("callback", callback.to_string().as_bytes()).unwrap();
+        assert_eq!(tx.amount.to_string(), "25.00");
+        assert_eq!(tx.memo_type.as_deref(), Some("id"));
+        assert_eq!(tx.metadata.unwrap()["kyc_level"], 2);
+
+        let body = serde_json::to_vec(&serde_json::json!({
+            "stellar_address": account,
+            "amount": " 42.50 ",
+            "asset_code": "usd",
+            "anchor_transaction_id": "anchor-1",
+        }))
+        .unwrap();
+        let tx = transaction_from_stored_payload("callback/transaction", &body).unwrap();
+        assert_eq!(tx.asset_code, "USD");
+        assert_eq!(tx.anchor_transaction_id.as_deref(), Some("anchor-1"));
+
+        assert!(transaction_from_stored_payload("callback", b"not json").is_err());
+        assert!(transaction_from_stored_payload("webhook", b"{}").is_err());
+    }
+
+    #[tokio::test]
+    async fn raw_json_keeps_the_exact_body_and_rejects_like_json() {
+        let body = r#"{"stellar_address":"GA","amount":"1",  "asset_code":"USD"}"#;
+        let request = Request::builder()
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let raw = RawJson::<WebhookTransactionRequest>::from_request(request, &())
+            .await
+            .unwrap();
+        assert_eq!(raw.body, body.as_bytes());
+        assert_eq!(raw.value.amount, "1");
+        assert_eq!(raw.to_payload("callback/transaction").body, body.as_bytes());
+
+        let request = Request::builder()
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"stellar_address":"GA","unexpected":1}"#))
+            .unwrap();
+        let rejection = RawJson::<WebhookTransactionRequest>::from_request(request, &())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
 
 /// Response body for the generic webhook endpoint.
@@ -330,31 +612,75 @@ fn validate_memo_type(memo_type: &Option<String>) -> Result<(), AppError> {
     }
 }
 
+/// Query parameters for [`callback`].
+#[derive(Debug, Default, Deserialize)]
+pub struct CallbackQuery {
+    /// Run the processing pipeline before responding instead of leaving the
+    /// transaction to the background processor.
+    #[serde(default)]
+    pub sync: bool,
+}
+
+/// Response to an accepted callback: the stored transaction and where to poll
+/// for its status.
+#[derive(Debug, Serialize)]
+pub struct CallbackAccepted {
+    #[serde(flatten)]
+    pub transaction: Transaction,
+    pub status_url: String,
+}
+
+impl CallbackAccepted {
+    fn new(transaction: Transaction) -> Self {
+        let status_url = format!("/transactions/{}", transaction.id);
+        Self {
+            transaction,
+            status_url,
+        }
+    }
+}
+
 /// Receive a fiat deposit callback from the Stellar Anchor Platform.
 ///
 /// Applies back-pressure when the pending queue exceeds `MAX_PENDING_QUEUE`
 /// (default 10 000), returning `503 Service Unavailable` with a `Retry-After: 30`
-/// header. On success, inserts the transaction and returns `201 Created`.
+/// header. On success, persists the transaction as `pending` and returns
+/// `202 Accepted` with its id and a `status_url` (also sent as `Location`);
+/// the processor pool picks it up from there. With `?sync=true` the
+/// transaction is processed before responding and `201 Created` is returned
+/// with its resulting state; the row is leased while it is processed, and if
+/// a pool worker leased it first the response is `202 Accepted` instead. The
+/// transaction is stamped with the tenant the
+/// request's `X-API-Key` or `X-Tenant-ID` resolves to.
+/// Replays of an already-seen `anchor_transaction_id` are idempotent: no new row
+/// is written and the existing transaction is returned with `200 OK`.
 ///
 /// # Errors
 /// - `400 Bad Request` – invalid `memo_type` or unparseable `amount`
+/// - `404 Not Found` – the request names an unknown or inactive tenant
 /// - `503 Service Unavailable` – queue depth exceeded
 /// - `500 Internal Server Error` – database error
 #[utoipa::path(
     post,
     path = "/callback",
     request_body = CallbackPayload,
+    params(
+        ("sync" = Option<bool>, Query, description = "Process the transaction before responding")
+    ),
     responses(
-        (status = 201, description = "Transaction created", body = crate::schemas::TransactionSchema),
+        (status = 202, description = "Transaction accepted for asynchronous processing", body = crate::schemas::TransactionSchema),
+        (status = 201, description = "Transaction created and processed (sync=true)", body = crate::schemas::TransactionSchema),
+        (status = 200, description = "Transaction already exists for this anchor_transaction_id", body = crate::schemas::TransactionSchema),
         (status = 400, description = "Invalid payload"),
         (status = 500, description = "Processing error")
     ),
     tag = "Webhooks"
 )]
-#[instrument(name = "webhook.callback", skip(state, payload))]
 pub async fn callback(
     State(state): State<ApiState>,
-    Json(payload): Json<CallbackPayload>,
+    OptionalTenant(tenant): OptionalTenant,
+    Query(query): Query<CallbackQuery>,
+    request: RawJson<CallbackPayload>,
 ) -> Result<impl IntoResponse, AppError> {
     // Back-pressure: reject if pending queue exceeds threshold
     let depth = state.app_state.pending_queue_depth.load(Ordering::Relaxed);
@@ -370,42 +696,98 @@ pub async fn callback(
         );
         // Emit metric counter via tracing event (metrics crate not available)
         tracing::info!(counter.callback_rejected_backpressure = 1u64);
-        let mut response = axum::response::Response::new(axum::body::boxed(
-            axum::body::Full::from(r#"{"error":"service busy, retry later"}"#),
-        ));
-        *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+        let mut response = AppError::ServiceBusy.into_response();
         response
             .headers_mut()
             .insert("Retry-After", HeaderValue::from_static("30"));
-        return Ok(response.into_response());
+        return Ok(response);
     }
 
-    validate_memo_type(&payload.memo_type)?;
+    let raw = request.to_payload("callback");
+    let mut tx = request
+        .value
+        .into_transaction()?
+        .with_tenant_id(tenant.map(|t| t.tenant_id));
+
+    if tx.memo.is_some()
+        && state
+            .app_state
+            .feature_flags
+            .is_enabled(payment_matching::PAYMENT_MATCHING_FLAG)
+            .await
+            .unwrap_or(false)
+    {
+        payment_matching::annotate_with_chain_payment(
+            &state.app_state.horizon_client,
+            &mut tx,
+            state.app_state.horizon_page_limit,
+        )
+        .await;
+    }
 
-    let amount = sqlx::types::BigDecimal::from_str(&payload.amount)
-        .map_err(|_| AppError::Validation(format!("Invalid amount: {}", payload.amount)))?;
+    let insert_span =
+        tracing::info_span!("webhook.db_insert", transaction.id = tracing::field::Empty);
+    let (mut result, is_new) =
+        queries::insert_transaction_with_payload(&state.app_state.db, &tx, &raw)
+            .instrument(insert_span.clone())
+            .await?;
+    insert_span.record("transaction.id", tracing::field::display(result.id));
+    callback_trace::record_transaction_id(result.id);
+
+    let status = match (is_new, query.sync) {
+        (false, _) => StatusCode::OK,
+        (true, false) => StatusCode::ACCEPTED,
+        (true, true) => {
+            let db = &state.app_state.db;
+            // Lease the row like a pool worker would, so a concurrent batch
+            // cannot pick it up too. A worker that got there first owns it and
+            // the caller is told the transaction is being processed async.
+            if processor::claim_transaction(db, result.id).await? {
+                // Failures are recorded (retry/DLQ) by the processor itself;
+                // the caller gets whatever state the transaction ended up in.
+                let processor = TransactionProcessor::new(db.clone())
+                    .with_retry_policy(state.app_state.retry_policy.clone());
+                if let Err(e) = processor.process_transaction(result.id).await {
+                    tracing::warn!(transaction_id = %result.id, "Synchronous processing failed: {}", e);
+                }
+                processor::release_claims(db, &[result.id]).await?;
+                result = queries::get_transaction(db, result.id).await?;
+                StatusCode::CREATED
+            } else {
+                StatusCode::ACCEPTED
+            }
+        }
+    };
 
-    let tx = Transaction::new(
-        payload.stellar_account,
-        amount,
-        payload.asset_code,
-        payload.anchor_transaction_id,
-        payload.callback_type,
-        payload.callback_status,
-        payload.memo,
-        payload.memo_type,
-        payload.metadata,
-    );
+    if is_new {
+        broadcast_status(&state.app_state, &result);
+    }
 
-    let (result, is_new) = queries::insert_transaction(&state.app_state.db, &tx).await?;
+    let body = CallbackAccepted::new(result);
+    Ok((
+        status,
+        [(axum::http::header::LOCATION, body.status_url.clone())],
+        Json(body),
+    )
+        .into_response())
+}
 
-    let status = if is_new {
-        StatusCode::CREATED
-    } else {
-        StatusCode::OK
+/// Publishes a newly accepted transaction to WebSocket and GraphQL
+/// subscribers, tagged with its tenant so tenant-scoped sockets receive it.
+/// Having no subscribers is not an error.
+fn broadcast_status(state: &AppState, transaction: &Transaction) {
+    let _span =
+        tracing::info_span!("webhook.broadcast", transaction.id = %transaction.id).entered();
+    let update = TransactionStatusUpdate {
+        transaction_id: transaction.id,
+        tenant_id: transaction.tenant_id.unwrap_or_else(Uuid::nil),
+        status: transaction.status.clone(),
+        timestamp: transaction.updated_at,
+        message: None,
+        stellar_account: Some(transaction.stellar_account.clone()),
     };
-
-    Ok((status, Json(result)).into_response())
+    let receivers = state.tx_broadcast.send(update).unwrap_or(0);
+    tracing::debug!(receivers, "Broadcast transaction status");
 }
 
 /// Generic webhook receiver for event-driven integrations.
@@ -442,14 +824,26 @@ pub async fn handle_webhook(
     Ok((StatusCode::OK, Json(response)))
 }
 
+/// Query parameters for [`get_transaction`].
+#[derive(Debug, Default, Deserialize)]
+pub struct GetTransactionQuery {
+    /// Also return the transaction when it has been soft-deleted.
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
 /// Get a specific transaction
 ///
-/// Returns details for a specific transaction by ID
+/// Returns details for a specific transaction by ID. When the request
+/// identifies a tenant (`X-API-Key` or `X-Tenant-ID`), a transaction of any
+/// other tenant is reported as not found. A soft-deleted transaction is
+/// only returned with `include_deleted=true`.
 #[utoipa::path(
     get,
     path = "/transactions/{id}",
     params(
-        ("id" = String, Path, description = "Transaction ID")
+        ("id" = String, Path, description = "Transaction ID"),
+        ("include_deleted" = Option<bool>, Query, description = "Also return a soft-deleted transaction")
     ),
     responses(
         (status = 200, description = "Transaction found", body = crate::schemas::TransactionSchema),
@@ -462,15 +856,24 @@ pub async fn handle_webhook(
 pub async fn get_transaction(
     State(state): State<ApiState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<GetTransactionQuery>,
+    OptionalTenant(tenant): OptionalTenant,
 ) -> Result<impl IntoResponse, AppError> {
     let (pool, replica_used) = state.app_state.pool_manager.read_pool().await;
 
-    let transaction = queries::get_transaction(pool, id)
-        .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => AppError::NotFound(format!("Transaction {} not found", id)),
-            _ => AppError::DatabaseError(e.to_string()),
-        })?;
+    let transaction = if query.include_deleted {
+        let tenant_id = tenant.as_ref().map(|t| t.tenant_id);
+        queries::get_transaction_including_deleted(pool, tenant_id, id).await
+    } else {
+        match &tenant {
+            Some(tenant) => queries::get_transaction_for_tenant(pool, tenant.tenant_id, id).await,
+            None => queries::get_transaction(pool, id).await,
+        }
+    };
+    let transaction = transaction.map_err(|e| match e {
+        sqlx::Error::RowNotFound => AppError::NotFound(format!("Transaction {} not found", id)),
+        _ => AppError::DatabaseError(e.to_string()),
+    })?;
 
     let mut response: Response = Json(transaction).into_response();
     if replica_used {
@@ -482,6 +885,45 @@ pub async fn get_transaction(
     Ok(response)
 }
 
+/// Get the event timeline of a transaction
+///
+/// Returns the transaction's creation and every audited change to it (status
+/// updates, overrides, settlement assignment, ...) oldest first. Tenant
+/// scoping matches `GET /transactions/{id}`; a soft-deleted transaction keeps
+/// its timeline, ending in its `deleted` event.
+#[utoipa::path(
+    get,
+    path = "/transactions/{id}/events",
+    params(
+        ("id" = String, Path, description = "Transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Timeline of the transaction, oldest first"),
+        (status = 404, description = "Transaction not found"),
+        (status = 500, description = "Database error")
+    ),
+    tag = "Transactions"
+)]
+#[instrument(name = "webhook.get_transaction_events", skip(state), fields(transaction.id = %id))]
+pub async fn get_transaction_events(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+    OptionalTenant(tenant): OptionalTenant,
+) -> Result<impl IntoResponse, AppError> {
+    let (pool, _) = state.app_state.pool_manager.read_pool().await;
+
+    let tenant_id = tenant.as_ref().map(|t| t.tenant_id);
+    queries::get_transaction_including_deleted(pool, tenant_id, id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => AppError::NotFound(format!("Transaction {} not found", id)),
+            _ => AppError::DatabaseError(e.to_string()),
+        })?;
+
+    let events = queries::get_transaction_events(pool, id).await?;
+    Ok(Json(events))
+}
+
 /// Query parameters for paginated transaction listing.
 #[derive(Debug, Deserialize)]
 pub struct ListQuery {
@@ -500,7 +942,8 @@ pub struct ListQuery {
 /// Fetches up to `limit` transactions (max 100, default 25). Supports forward
 /// and backward traversal via an opaque `cursor` and optional ISO 8601 date
 /// range filters (`from_date` / `to_date`). Reads from a replica when available;
-/// in that case the response includes `X-Read-Consistency: eventual`.
+/// in that case the response includes `X-Read-Consistency: eventual`. Requests
+/// that identify a tenant only see that tenant's transactions.
 ///
 /// # Errors
 /// - `400 Bad Request` – invalid cursor, unparseable dates, or `from_date >= to_date`
@@ -521,6 +964,7 @@ pub struct ListQuery {
 )]
 pub async fn list_transactions(
     State(state): State<AppState>,
+    OptionalTenant(tenant): OptionalTenant,
     Query(params): Query<ListQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let limit = params.limit.unwrap_or(25).min(100);
@@ -569,15 +1013,31 @@ pub async fn list_transactions(
     // fetch one extra to determine has_more
     let fetch_limit = limit + 1;
     let (pool, replica_used) = state.pool_manager.read_pool().await;
-    let mut rows = queries::list_transactions_filtered(
-        pool,
-        fetch_limit,
-        decoded_cursor,
-        backward,
-        from_date,
-        to_date,
-    )
-    .await?;
+    let mut rows = match &tenant {
+        Some(tenant) => {
+            queries::list_transactions_for_tenant(
+                pool,
+                tenant.tenant_id,
+                fetch_limit,
+                decoded_cursor,
+                backward,
+                from_date,
+                to_date,
+            )
+            .await?
+        }
+        None => {
+            queries::list_transactions_filtered(
+                pool,
+                fetch_limit,
+                decoded_cursor,
+                backward,
+                from_date,
+                to_date,
+            )
+            .await?
+        }
+    };
 
     let has_more = rows.len() as i64 > limit;
     if has_more {
@@ -613,6 +1073,7 @@ pub async fn list_transactions(
 /// keeping the router's state type consistent without duplicating handler code.
 pub async fn list_transactions_api(
     State(api_state): State<crate::ApiState>,
+    OptionalTenant(tenant): OptionalTenant,
     Query(params): Query<ListQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     // forward to the AppState-based handler
@@ -661,15 +1122,31 @@ pub async fn list_transactions_api(
 
     let fetch_limit = limit + 1;
     let (pool, replica_used) = app_state.pool_manager.read_pool().await;
-    let mut rows = queries::list_transactions_filtered(
-        pool,
-        fetch_limit,
-        decoded_cursor,
-        backward,
-        from_date,
-        to_date,
-    )
-    .await?;
+    let mut rows = match &tenant {
+        Some(tenant) => {
+            queries::list_transactions_for_tenant(
+                pool,
+                tenant.tenant_id,
+                fetch_limit,
+                decoded_cursor,
+                backward,
+                from_date,
+                to_date,
+            )
+            .await?
+        }
+        None => {
+            queries::list_transactions_filtered(
+                pool,
+                fetch_limit,
+                decoded_cursor,
+                backward,
+                from_date,
+                to_date,
+            )
+            .await?
+        }
+    };
 
     let has_more = rows.len() as i64 > limit;
     if has_more {
@@ -1,7 +1,7 @@
 use axum::{
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
-        ConnectInfo, Query, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        ConnectInfo, Path, Query, State,
     },
     response::IntoResponse,
 };
@@ -17,6 +17,7 @@ use uuid::Uuid;
 use crate::AppState;
 
 use crate::handlers::ws_error::{validate_message_size, validate_ws_token};
+use crate::middleware::auth::{JwtClaims, JwtSecret};
 
 /// How often to send a ping frame to the client.
 const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
@@ -30,6 +31,14 @@ const RESYNC_DEFAULT_LIMIT: i64 = 20;
 /// Maximum number of events a client may request in a single resync.
 const RESYNC_MAX_LIMIT: i64 = 100;
 
+/// Number of lag events after which a client is treated as a slow consumer
+/// and disconnected; it can reconnect and `resync` to catch up.
+const SLOW_CONSUMER_LAG_LIMIT: u32 = 5;
+
+/// Close code sent when `/ws/transactions/:id` names an unknown transaction
+/// (application-private range, mirroring HTTP 404).
+const CLOSE_TRANSACTION_NOT_FOUND: u16 = 4404;
+
 // ── Wire types ───────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
@@ -39,18 +48,57 @@ pub struct TransactionStatusUpdate {
     pub status: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub message: Option<String>,
+    #[serde(default)]
+    pub stellar_account: Option<String>,
+}
+
+/// Per-connection subscription filters. Unset fields match every update.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubscriptionFilters {
+    pub status: Option<String>,
+    pub stellar_account: Option<String>,
+}
+
+impl SubscriptionFilters {
+    pub fn matches(&self, update: &TransactionStatusUpdate) -> bool {
+        self.matches_fields(&update.status, update.stellar_account.as_deref())
+    }
+
+    /// Same check against a stored transaction, for `resync` results.
+    pub fn matches_transaction(&self, transaction: &crate::db::models::Transaction) -> bool {
+        self.matches_fields(&transaction.status, Some(&transaction.stellar_account))
+    }
+
+    fn matches_fields(&self, status: &str, stellar_account: Option<&str>) -> bool {
+        if let Some(expected) = &self.status {
+            if !expected.eq_ignore_ascii_case(status) {
+                return false;
+            }
+        }
+        if let Some(account) = &self.stellar_account {
+            if stellar_account != Some(account.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 /// Messages the server pushes to the client.
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum ServerMessage {
-    /// Notification that messages were dropped due to the client being slow.
-    MessagesDropped { count: u64 },
+    /// Current state of the transaction, sent first on `/ws/transactions/:id`.
+    Snapshot(TransactionStatusUpdate),
+    /// Notification that `missed` updates were skipped because the client fell
+    /// behind the broadcast channel; the connection stays open.
+    Lagged { missed: u64 },
     /// Response to a client `resync` request — latest N events from the DB.
     Resync {
         events: Vec<crate::db::models::Transaction>,
     },
+    /// Acknowledges a `subscribe`/`unsubscribe` action with the filters now in effect.
+    Subscribed { filters: SubscriptionFilters },
 }
 
 /// Messages the client may send to the server.
@@ -61,6 +109,19 @@ enum ClientMessage {
     Resync { limit: Option<i64> },
 }
 
+/// Subscription actions, sent as `{"action":"subscribe","filters":{...}}`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientAction {
+    /// Replace the connection's filters; only matching updates are forwarded.
+    Subscribe {
+        #[serde(default)]
+        filters: SubscriptionFilters,
+    },
+    /// Clear all filters and receive every update again.
+    Unsubscribe,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct WsQuery {
     token: Option<String>,
@@ -74,31 +135,135 @@ pub async fn ws_handler(
     State(state): State<AppState>,
     connect_info: Option<ConnectInfo<SocketAddr>>,
 ) -> impl IntoResponse {
-    let token = match params.token {
-        Some(t) => match validate_ws_token(&t) {
-            Ok(_) => t,
-            Err(_) => {
-                tracing::warn!("Invalid WebSocket authentication token");
-                return axum::http::StatusCode::UNAUTHORIZED.into_response();
-            }
-        },
-        None => {
-            tracing::warn!("Missing WebSocket authentication token");
-            return axum::http::StatusCode::UNAUTHORIZED.into_response();
-        }
+    let claims = match authorize(&state.jwt_secret, params.token.as_deref()) {
+        Ok(claims) => claims,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let client_addr = client_addr(connect_info);
+    ws.on_upgrade(move |socket| handle_socket(socket, state, client_addr, claims.tenant_id, None))
+}
+
+/// Streams updates for a single transaction, starting with a snapshot of its
+/// current state from the database.
+pub async fn transaction_ws_handler(
+    ws: WebSocketUpgrade,
+    Path(transaction_id): Path<Uuid>,
+    Query(params): Query<WsQuery>,
+    State(state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> impl IntoResponse {
+    let claims = match authorize(&state.jwt_secret, params.token.as_deref()) {
+        Ok(claims) => claims,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    let client_addr = client_addr(connect_info);
+    ws.on_upgrade(move |socket| {
+        handle_socket(
+            socket,
+            state,
+            client_addr,
+            claims.tenant_id,
+            Some(transaction_id),
+        )
+    })
+}
+
+/// Validates the `?token=` JWT before the upgrade; the connection is scoped to
+/// the tenant in its claims.
+fn authorize(secret: &JwtSecret, token: Option<&str>) -> Result<JwtClaims, axum::http::StatusCode> {
+    let Some(token) = token else {
+        tracing::warn!("Missing WebSocket authentication token");
+        return Err(axum::http::StatusCode::UNAUTHORIZED);
     };
 
-    let client_addr = connect_info
+    if validate_ws_token(token).is_err() {
+        tracing::warn!("Invalid WebSocket authentication token");
+        return Err(axum::http::StatusCode::UNAUTHORIZED);
+    }
+
+    secret.verify(token).map_err(|e| {
+        tracing::warn!(error = %e, "Invalid WebSocket authentication token");
+        axum::http::StatusCode::UNAUTHORIZED
+    })
+}
+
+fn client_addr(connect_info: Option<ConnectInfo<SocketAddr>>) -> String {
+    connect_info
         .map(|ci| ci.0.to_string())
-        .unwrap_or_else(|| "unknown".to_string());
+        .unwrap_or_else(|| "unknown".to_string())
+}
 
-    let _ = token; // validated above
-    ws.on_upgrade(move |socket| handle_socket(socket, state, client_addr))
+/// Loads the current state of a transaction in the broadcast wire format.
+async fn load_snapshot(
+    pool: &sqlx::PgPool,
+    transaction_id: Uuid,
+) -> Result<Option<TransactionStatusUpdate>, sqlx::Error> {
+    let row: Option<(String, chrono::DateTime<chrono::Utc>, String, Option<Uuid>)> =
+        sqlx::query_as(
+            "SELECT status, updated_at, stellar_account, tenant_id FROM transactions WHERE id = $1",
+        )
+        .bind(transaction_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map(
+        |(status, updated_at, stellar_account, tenant_id)| TransactionStatusUpdate {
+            transaction_id,
+            tenant_id: tenant_id.unwrap_or_else(Uuid::nil),
+            status,
+            timestamp: updated_at,
+            message: None,
+            stellar_account: Some(stellar_account),
+        },
+    ))
 }
 
 // ── Per-connection handler ───────────────────────────────────────────────────
 
-async fn handle_socket(socket: WebSocket, state: AppState, client_addr: String) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: AppState,
+    client_addr: String,
+    tenant_id: Uuid,
+    transaction_id: Option<Uuid>,
+) {
+    // Subscribe before reading the snapshot so no update committed in between
+    // is lost; anything buffered here is delivered after the snapshot.
+    let mut rx = state.tx_broadcast.subscribe();
+
+    let (sender, mut receiver) = socket.split();
+    let sender = Arc::new(Mutex::new(sender));
+
+    if let Some(id) = transaction_id {
+        let snapshot = match load_snapshot(&state.db, id).await {
+            // Other tenants' transactions are reported exactly like missing ones.
+            Ok(Some(update)) if update.tenant_id == tenant_id => update,
+            Ok(_) => {
+                tracing::info!(client_addr = %client_addr, transaction_id = %id, "WebSocket subscription to unknown transaction");
+                close_with_reason(
+                    &sender,
+                    CLOSE_TRANSACTION_NOT_FOUND,
+                    "transaction not found",
+                )
+                .await;
+                return;
+            }
+            Err(e) => {
+                tracing::error!(client_addr = %client_addr, transaction_id = %id, "Failed to load transaction snapshot: {}", e);
+                close_with_reason(&sender, 1011, "failed to load transaction").await;
+                return;
+            }
+        };
+        if let Ok(json) = serde_json::to_string(&ServerMessage::Snapshot(snapshot)) {
+            let mut s = sender.lock().await;
+            if s.send(Message::Text(json)).await.is_err() {
+                return;
+            }
+        }
+    }
+
     let count = state.ws_connection_count.fetch_add(1, Ordering::Relaxed) + 1;
     tracing::info!(
         client_addr = %client_addr,
@@ -106,28 +271,35 @@ async fn handle_socket(socket: WebSocket, state: AppState, client_addr: String)
         "WebSocket connection opened"
     );
 
-    let (sender, mut receiver) = socket.split();
-    let sender = Arc::new(Mutex::new(sender));
-
     // Shared flag: did we receive a pong since the last ping?
     let pong_received = Arc::new(std::sync::atomic::AtomicBool::new(true));
 
     // Per-client dropped-message counter (metric).
     let messages_dropped_total = Arc::new(std::sync::atomic::AtomicU64::new(0));
 
-    let mut rx = state.tx_broadcast.subscribe();
+    // Filters set by the client's `subscribe` action, applied before serializing.
+    let filters = Arc::new(std::sync::RwLock::new(SubscriptionFilters::default()));
 
     // ── Receive task ─────────────────────────────────────────────────────────
     let pong_flag = Arc::clone(&pong_received);
     let recv_addr = client_addr.clone();
     let recv_sender = Arc::clone(&sender);
     let recv_state = state.clone();
+    let recv_filters = Arc::clone(&filters);
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = receiver.next().await {
             match msg {
                 Message::Text(text) => {
                     tracing::debug!(client_addr = %recv_addr, "Received text: {}", text);
-                    handle_client_message(&text, &recv_sender, &recv_state, &recv_addr).await;
+                    handle_client_message(
+                        &text,
+                        &recv_sender,
+                        &recv_state,
+                        tenant_id,
+                        &recv_filters,
+                        &recv_addr,
+                    )
+                    .await;
                 }
                 Message::Pong(_) => {
                     tracing::trace!(client_addr = %recv_addr, "Received pong");
@@ -150,8 +322,10 @@ async fn handle_socket(socket: WebSocket, state: AppState, client_addr: String)
     let pong_flag2 = Arc::clone(&pong_received);
     let dropped_counter = Arc::clone(&messages_dropped_total);
     let send_addr = client_addr.clone();
+    let send_filters = Arc::clone(&filters);
     let mut send_task = tokio::spawn(async move {
         let mut heartbeat_interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+        let mut lag_events: u32 = 0;
 
         loop {
             tokio::select! {
@@ -182,6 +356,18 @@ async fn handle_socket(socket: WebSocket, state: AppState, client_addr: String)
                 result = rx.recv() => {
                     match result {
                         Ok(update) => {
+                            if update.tenant_id != tenant_id
+                                || transaction_id.is_some_and(|id| id != update.transaction_id)
+                            {
+                                continue;
+                            }
+                            let forward = send_filters
+                                .read()
+                                .map(|f| f.matches(&update))
+                                .unwrap_or(true);
+                            if !forward {
+                                continue;
+                            }
                             let json = match serde_json::to_string(&update) {
                                 Ok(j) => j,
                                 Err(e) => {
@@ -199,14 +385,33 @@ async fn handle_socket(socket: WebSocket, state: AppState, client_addr: String)
                         // ── Backpressure: client is too slow ─────────────
                         Err(broadcast::error::RecvError::Lagged(n)) => {
                             let total = dropped_counter.fetch_add(n, Ordering::Relaxed) + n;
+                            lag_events += 1;
+                            crate::metrics::ws_messages_lagged_total().add(n, &[]);
+                            crate::metrics::broadcast_messages_lagged_total()
+                                .with_label_values(&["websocket"])
+                                .inc_by(n);
                             tracing::warn!(
                                 client_addr = %send_addr,
                                 dropped = n,
                                 ws_messages_dropped_total = total,
-                                "Client lagged — sending messages_dropped notification"
+                                lag_events,
+                                "Client lagged — sending lagged notification; consider raising BROADCAST_CAPACITY"
                             );
 
-                            let notification = ServerMessage::MessagesDropped { count: n };
+                            if lag_events >= SLOW_CONSUMER_LAG_LIMIT {
+                                tracing::warn!(
+                                    client_addr = %send_addr,
+                                    lag_events,
+                                    "Disconnecting slow WebSocket consumer"
+                                );
+                                crate::metrics::ws_slow_consumer_disconnects_total().add(1, &[]);
+                                close_with_reason(&sender_clone, 1008, "slow consumer").await;
+                                break;
+                            }
+
+                            // The receiver has already skipped ahead to the oldest
+                            // retained message, so streaming simply resumes from there.
+                            let notification = ServerMessage::Lagged { missed: n };
                             if let Ok(json) = serde_json::to_string(&notification) {
                                 let mut s = sender_clone.lock().await;
                                 // Best-effort: ignore send error here, the next recv will catch a dead socket
@@ -248,12 +453,27 @@ async fn handle_socket(socket: WebSocket, state: AppState, client_addr: String)
     );
 }
 
+async fn close_with_reason(
+    sender: &Mutex<impl SinkExt<Message, Error = axum::Error> + Unpin>,
+    code: u16,
+    reason: &'static str,
+) {
+    let frame = CloseFrame {
+        code,
+        reason: reason.into(),
+    };
+    let mut s = sender.lock().await;
+    let _ = s.send(Message::Close(Some(frame))).await;
+}
+
 // ── Client message handler ───────────────────────────────────────────────────
 
 async fn handle_client_message(
     text: &str,
     sender: &Arc<Mutex<impl SinkExt<Message, Error = axum::Error> + Unpin + Send>>,
     state: &AppState,
+    tenant_id: Uuid,
+    filters: &std::sync::RwLock<SubscriptionFilters>,
     client_addr: &str,
 ) {
     // Validate message size first
@@ -266,6 +486,30 @@ async fn handle_client_message(
         return;
     }
 
+    // Subscription actions use an `action` tag rather than `type`.
+    if let Ok(action) = serde_json::from_str::<ClientAction>(text) {
+        let new_filters = match action {
+            ClientAction::Subscribe { filters } => filters,
+            ClientAction::Unsubscribe => SubscriptionFilters::default(),
+        };
+        tracing::info!(
+            client_addr = %client_addr,
+            filters = ?new_filters,
+            "Client updated subscription filters"
+        );
+        if let Ok(mut current) = filters.write() {
+            *current = new_filters.clone();
+        }
+        let response = ServerMessage::Subscribed {
+            filters: new_filters,
+        };
+        if let Ok(json) = serde_json::to_string(&response) {
+            let mut s = sender.lock().await;
+            let _ = s.send(Message::Text(json)).await;
+        }
+        return;
+    }
+
     // Validate message structure
     let msg: ClientMessage = match serde_json::from_str(text) {
         Ok(m) => m,
@@ -287,8 +531,12 @@ async fn handle_client_message(
                 "Client requested resync"
             );
 
-            let events = match crate::db::queries::list_transactions(&state.db, limit, None, false)
-                .await
+            // Scoped like the live stream: the connection's tenant only, and
+            // only rows its subscription filters would let through.
+            let events = match crate::db::queries::list_transactions_for_tenant(
+                &state.db, tenant_id, limit, None, false, None, None,
+            )
+            .await
             {
                 Ok(rows) => rows,
                 Err(e) => {
@@ -296,6 +544,13 @@ async fn handle_client_message(
                     return;
                 }
             };
+            let events = match filters.read() {
+                Ok(filters) => events
+                    .into_iter()
+                    .filter(|tx| filters.matches_transaction(tx))
+                    .collect(),
+                Err(_) => Vec::new(),
+            };
 
             let response = ServerMessage::Resync { events };
             if let Ok(json) = serde_json::to_string(&response) {
@@ -376,11 +631,10 @@ mod tests {
     }
 
     #[test]
-    fn test_server_message_messages_dropped_serialization() {
-        let msg = ServerMessage::MessagesDropped { count: 42 };
+    fn test_server_message_lagged_serialization() {
+        let msg = ServerMessage::Lagged { missed: 42 };
         let json = serde_json::to_string(&msg).unwrap();
-        assert!(json.contains("messages_dropped"));
-        assert!(json.contains("42"));
+        assert_eq!(json, r#"{"type":"lagged","missed":42}"#);
     }
 
     #[test]
@@ -391,12 +645,56 @@ mod tests {
             status: "completed".to_string(),
             timestamp: chrono::Utc::now(),
             message: Some("Transaction processed".to_string()),
+            stellar_account: None,
         };
         let json = serde_json::to_string(&update).unwrap();
         assert!(json.contains("completed"));
         assert!(json.contains("Transaction processed"));
     }
 
+    fn update(status: &str, account: Option<&str>) -> TransactionStatusUpdate {
+        TransactionStatusUpdate {
+            transaction_id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            status: status.to_string(),
+            timestamp: chrono::Utc::now(),
+            message: None,
+            stellar_account: account.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_subscription_filters_default_matches_all() {
+        let filters = SubscriptionFilters::default();
+        assert!(filters.matches(&update("pending", None)));
+        assert!(filters.matches(&update("completed", Some("GABC"))));
+    }
+
+    #[test]
+    fn test_subscription_filters_status_and_account() {
+        let filters = SubscriptionFilters {
+            status: Some("completed".to_string()),
+            stellar_account: Some("GABC".to_string()),
+        };
+        assert!(filters.matches(&update("completed", Some("GABC"))));
+        assert!(!filters.matches(&update("pending", Some("GABC"))));
+        assert!(!filters.matches(&update("completed", Some("GXYZ"))));
+        assert!(!filters.matches(&update("completed", None)));
+    }
+
+    #[test]
+    fn test_client_action_subscribe_deserialization() {
+        let json = r#"{"action":"subscribe","filters":{"status":"completed"}}"#;
+        match serde_json::from_str::<ClientAction>(json).unwrap() {
+            ClientAction::Subscribe { filters } => {
+                assert_eq!(filters.status.as_deref(), Some("completed"));
+                assert_eq!(filters.stellar_account, None);
+            }
+            ClientAction::Unsubscribe => panic!("expected subscribe"),
+        }
+        assert!(serde_json::from_str::<ClientAction>(r#"{"type":"resync"}"#).is_err());
+    }
+
     #[test]
     fn test_ws_query_token_present() {
         let json = r#"{"token": "test_token"}"#;
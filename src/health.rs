@@ -1,9 +1,21 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
 
+/// Latency above which a reachable dependency is reported as `degraded`
+/// unless `HEALTH_LATENCY_THRESHOLD_MS` says otherwise.
+pub const DEFAULT_LATENCY_THRESHOLD_MS: u64 = 1000;
+
+/// Age after which the newest backup is reported as stale unless
+/// `BACKUP_MAX_AGE_HOURS` says otherwise: a daily backup plus an hour of slack.
+pub const DEFAULT_BACKUP_MAX_AGE_HOURS: u64 = 25;
+
+/// How long a single dependency check may run before it counts as unhealthy.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Severity level for a dependency
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -25,6 +37,23 @@ pub struct HealthResponse {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum DependencyStatus {
+    /// The newest backup is older than the configured maximum age. Reported
+    /// with `status: "degraded"`.
+    Stale {
+        status: String,
+        severity: DependencySeverity,
+        age_secs: u64,
+        max_age_secs: u64,
+    },
+    /// Reachable, but slower than the configured latency threshold. Listed
+    /// before `Healthy` so untagged deserialization does not drop
+    /// `threshold_ms`.
+    Degraded {
+        status: String,
+        severity: DependencySeverity,
+        latency_ms: u64,
+        threshold_ms: u64,
+    },
     Healthy {
         status: String,
         severity: DependencySeverity,
@@ -37,18 +66,73 @@ pub enum DependencyStatus {
     },
 }
 
+impl DependencyStatus {
+    /// Downgrades a healthy result to `degraded` when it took longer than
+    /// `threshold`. Unhealthy and already-degraded results pass through.
+    pub fn with_latency_threshold(self, threshold: Duration) -> Self {
+        let threshold_ms = threshold.as_millis() as u64;
+        match self {
+            DependencyStatus::Healthy {
+                severity,
+                latency_ms,
+                ..
+            } if latency_ms > threshold_ms => DependencyStatus::Degraded {
+                status: "degraded".to_string(),
+                severity,
+                latency_ms,
+                threshold_ms,
+            },
+            other => other,
+        }
+    }
+}
+
 #[async_trait]
 pub trait DependencyChecker: Send + Sync {
     async fn check(&self) -> DependencyStatus;
+
+    /// Severity reported when the check does not finish in time.
+    fn severity(&self) -> DependencySeverity {
+        DependencySeverity::NonCritical
+    }
+}
+
+/// Runs `checker` under the standard timeout and grades the result against
+/// `latency_threshold`.
+pub async fn run_check(
+    checker: &dyn DependencyChecker,
+    latency_threshold: Duration,
+) -> DependencyStatus {
+    match timeout(CHECK_TIMEOUT, checker.check()).await {
+        Ok(status) => status.with_latency_threshold(latency_threshold),
+        Err(_) => DependencyStatus::Unhealthy {
+            status: "unhealthy".to_string(),
+            severity: checker.severity(),
+            error: "timeout".to_string(),
+        },
+    }
 }
 
 pub struct PostgresChecker {
     pool: sqlx::PgPool,
+    severity: DependencySeverity,
 }
 
 impl PostgresChecker {
     pub fn new(pool: sqlx::PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            severity: DependencySeverity::Critical,
+        }
+    }
+
+    /// Checks a read replica. Reads fall back to the primary, so a replica
+    /// outage only degrades the service.
+    pub fn replica(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            severity: DependencySeverity::NonCritical,
+        }
     }
 }
 
@@ -59,16 +143,20 @@ impl DependencyChecker for PostgresChecker {
         match sqlx::query("SELECT 1").execute(&self.pool).await {
             Ok(_) => DependencyStatus::Healthy {
                 status: "healthy".to_string(),
-                severity: DependencySeverity::Critical,
+                severity: self.severity,
                 latency_ms: start.elapsed().as_millis() as u64,
             },
             Err(e) => DependencyStatus::Unhealthy {
                 status: "unhealthy".to_string(),
-                severity: DependencySeverity::Critical,
+                severity: self.severity,
                 error: e.to_string(),
             },
         }
     }
+
+    fn severity(&self) -> DependencySeverity {
+        self.severity
+    }
 }
 
 pub struct RedisChecker {
@@ -171,48 +259,77 @@ impl DependencyChecker for HorizonChecker {
     }
 }
 
+/// Checks that the newest backup listed by [`BackupService`] is younger than
+/// `max_age`. Backups are not needed to serve traffic, so a stale or missing
+/// backup only degrades the service.
+///
+/// [`BackupService`]: crate::services::BackupService
+#[derive(Clone)]
+pub struct BackupChecker {
+    service: Arc<crate::services::BackupService>,
+    max_age: Duration,
+}
+
+impl BackupChecker {
+    pub fn new(service: Arc<crate::services::BackupService>, max_age: Duration) -> Self {
+        Self { service, max_age }
+    }
+}
+
+#[async_trait]
+impl DependencyChecker for BackupChecker {
+    async fn check(&self) -> DependencyStatus {
+        let start = Instant::now();
+        let unhealthy = |error: String| DependencyStatus::Unhealthy {
+            status: "unhealthy".to_string(),
+            severity: DependencySeverity::NonCritical,
+            error,
+        };
+
+        let backups = match self.service.list_backups().await {
+            Ok(backups) => backups,
+            Err(e) => return unhealthy(format!("{e:#}")),
+        };
+        // `list_backups` returns newest first.
+        let Some(latest) = backups.first() else {
+            return unhealthy("no backups found".to_string());
+        };
+
+        let age_secs = (chrono::Utc::now() - latest.timestamp).num_seconds().max(0) as u64;
+        let max_age_secs = self.max_age.as_secs();
+        if age_secs > max_age_secs {
+            return DependencyStatus::Stale {
+                status: "degraded".to_string(),
+                severity: DependencySeverity::NonCritical,
+                age_secs,
+                max_age_secs,
+            };
+        }
+        DependencyStatus::Healthy {
+            status: "healthy".to_string(),
+            severity: DependencySeverity::NonCritical,
+            latency_ms: start.elapsed().as_millis() as u64,
+        }
+    }
+}
+
 pub async fn check_health(
     postgres: PostgresChecker,
     redis: RedisChecker,
     horizon: HorizonChecker,
     start_time: Instant,
+    latency_threshold: Duration,
 ) -> HealthResponse {
-    let timeout_duration = Duration::from_secs(5);
-
     let (postgres_result, redis_result, horizon_result) = tokio::join!(
-        timeout(timeout_duration, postgres.check()),
-        timeout(timeout_duration, redis.check()),
-        timeout(timeout_duration, horizon.check())
+        run_check(&postgres, latency_threshold),
+        run_check(&redis, latency_threshold),
+        run_check(&horizon, latency_threshold)
     );
 
     let mut dependencies = HashMap::new();
-
-    dependencies.insert(
-        "postgres".to_string(),
-        postgres_result.unwrap_or_else(|_| DependencyStatus::Unhealthy {
-            status: "unhealthy".to_string(),
-            severity: DependencySeverity::Critical,
-            error: "timeout".to_string(),
-        }),
-    );
-
-    dependencies.insert(
-        "redis".to_string(),
-        redis_result.unwrap_or_else(|_| DependencyStatus::Unhealthy {
-            status: "unhealthy".to_string(),
-            severity: DependencySeverity::NonCritical,
-            error: "timeout".to_string(),
-        }),
-    );
-
-    dependencies.insert(
-        "horizon".to_string(),
-        horizon_result.unwrap_or_else(|_| DependencyStatus::Unhealthy {
-            status: "unhealthy".to_string(),
-            severity: DependencySeverity::NonCritical,
-            error: "timeout".to_string(),
-        }),
-    );
+    dependencies.insert("postgres".to_string(), postgres_result);
+    dependencies.insert("redis".to_string(), redis_result);
+    dependencies.insert("horizon".to_string(), horizon_result);
 
     let overall_status = determine_overall_status(&dependencies);
 
@@ -234,6 +351,9 @@ fn determine_overall_status(dependencies: &HashMap<String, DependencyStatus>) ->
                 DependencySeverity::Critical => has_critical_failure = true,
                 DependencySeverity::NonCritical => has_non_critical_failure = true,
             },
+            DependencyStatus::Degraded { .. } | DependencyStatus::Stale { .. } => {
+                has_non_critical_failure = true
+            }
             DependencyStatus::Healthy { .. } => {}
         }
     }
@@ -25,6 +25,12 @@ pub mod ws;
 
 pub use config::assets::AssetCache;
 
+/// Memory profiling sessions can only see allocations made through dhat's
+/// allocator, so it is installed process-wide when the feature is on.
+#[cfg(feature = "heap-profiling")]
+#[global_allocator]
+static HEAP_PROFILER_ALLOC: dhat::Alloc = dhat::Alloc;
+
 use crate::db::pool_manager::PoolManager;
 use crate::graphql::schema::AppSchema;
 use crate::handlers::profiling::ProfilingManager;
@@ -68,6 +74,41 @@ pub struct AppState {
     pub metrics_handle: crate::metrics::MetricsHandle,
     /// Active WebSocket connection count
     pub ws_connection_count: Arc<AtomicUsize>,
+    /// Browser origins allowed by the CORS layer; empty means same-origin only.
+    pub cors_allowed_origins: Vec<String>,
+    /// Records requested per Horizon payments page when reconciling.
+    pub horizon_page_limit: u32,
+    /// Latency above which `/health` reports a reachable dependency as degraded.
+    pub health_latency_threshold_ms: u64,
+    /// Applied to the callback routes' idempotency check when its store fails.
+    pub idempotency_failure_mode: crate::middleware::idempotency::IdempotencyFailureMode,
+    /// Shared by the webhook and callback routes to cap concurrent processing.
+    pub webhook_permits: Arc<tokio::sync::Semaphore>,
+    /// Environment profile the process was started with, reported by `/version`.
+    pub app_env: crate::config::AppEnv,
+    /// Freshness check of the newest backup reported by `/health`; `None`
+    /// leaves backups out of the report.
+    pub backup_checker: Option<crate::health::BackupChecker>,
+    /// Time limit on producing a response, applied to every route except
+    /// exports; zero disables it.
+    pub request_timeout: std::time::Duration,
+    /// Days searched when a search names no date range and doesn't ask for
+    /// `all_time`; zero searches all partitions.
+    pub search_default_window_days: u32,
+    /// Directory export job artifacts are written to and served from.
+    pub export_jobs_dir: std::path::PathBuf,
+    /// Redacted effective configuration served by `GET /admin/config`.
+    pub config_info: Arc<crate::config::ConfigInfo>,
+    /// Background job scheduler, whose job status `GET /admin/jobs` reports.
+    pub job_scheduler: Arc<crate::services::JobScheduler>,
+    /// Key the WebSocket, profiling and config bearer tokens are verified
+    /// against; unset rejects every token.
+    pub jwt_secret: crate::middleware::auth::JwtSecret,
+    /// Retry policy for transactions processed outside the processor pool
+    /// (synchronous callbacks, DLQ replays).
+    pub retry_policy: crate::services::transaction_processor::RetryPolicy,
+    /// Ceiling and parallelism for batch webhook replays.
+    pub batch_replay_limits: crate::handlers::admin::webhook_replay::BatchReplayLimits,
 }
 
 impl AppState {
@@ -109,6 +150,25 @@ impl AppState {
             current_batch_size: Arc::new(AtomicU64::new(10)),
             metrics_handle: crate::metrics::init_metrics().unwrap(),
             ws_connection_count: Arc::new(AtomicUsize::new(0)),
+            cors_allowed_origins: Vec::new(),
+            horizon_page_limit: crate::stellar::client::MAX_PAGE_LIMIT,
+            health_latency_threshold_ms: crate::health::DEFAULT_LATENCY_THRESHOLD_MS,
+            idempotency_failure_mode: Default::default(),
+            webhook_permits: Arc::new(tokio::sync::Semaphore::new(
+                crate::middleware::concurrency::DEFAULT_WEBHOOK_MAX_CONCURRENCY,
+            )),
+            app_env: Default::default(),
+            backup_checker: None,
+            request_timeout: std::time::Duration::from_secs(
+                crate::middleware::timeout::DEFAULT_REQUEST_TIMEOUT_SECS,
+            ),
+            search_default_window_days: crate::handlers::search::DEFAULT_SEARCH_WINDOW_DAYS,
+            export_jobs_dir: crate::services::export_jobs::DEFAULT_EXPORT_JOBS_DIR.into(),
+            config_info: Default::default(),
+            job_scheduler: Default::default(),
+            jwt_secret: Default::default(),
+            retry_policy: Default::default(),
+            batch_replay_limits: Default::default(),
         }
     }
 }
@@ -125,7 +185,50 @@ impl std::fmt::Debug for ApiState {
     }
 }
 
+impl axum::extract::FromRef<ApiState> for AppState {
+    fn from_ref(api_state: &ApiState) -> Self {
+        api_state.app_state.clone()
+    }
+}
+
+/// Idempotency-Key store for the callback routes, backed by Redis with the
+/// `idempotency_keys` table as fallback. When both fail, the configured
+/// failure mode decides whether the callback runs. Keys live in the
+/// `callback` namespace so they never collide with another route's.
+fn callback_idempotency_service(
+    app_state: &AppState,
+) -> Result<crate::middleware::idempotency::IdempotencyService, redis::RedisError> {
+    crate::middleware::idempotency::IdempotencyService::new(
+        &app_state.redis_url,
+        app_state.db.clone(),
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+    )
+    .map(|service| {
+        service
+            .with_failure_mode(app_state.idempotency_failure_mode)
+            .with_namespace("callback")
+    })
+}
+
 pub fn create_app(app_state: AppState) -> Router {
+    let cors = middleware::cors::cors_layer(&app_state.cors_allowed_origins);
+    let tenant_cors = axum_middleware::from_fn_with_state(
+        app_state.clone(),
+        middleware::cors::tenant_cors_middleware,
+    );
+    let tenant_context = axum_middleware::from_fn_with_state(
+        app_state.clone(),
+        middleware::tenant::tenant_context_middleware,
+    );
+    let request_timeout = axum_middleware::from_fn_with_state(
+        app_state.request_timeout,
+        crate::middleware::timeout::request_timeout_middleware,
+    );
     let graphql_schema = crate::graphql::schema::build_schema(app_state.clone());
     let api_state = ApiState {
         app_state: app_state.clone(),
@@ -133,12 +236,35 @@ pub fn create_app(app_state: AppState) -> Router {
     };
 
     // Callback routes: signature verification + api_key_auth + validation + quota
+    // + idempotency. Idempotency is innermost so a cached response is only
+    // replayed to an authenticated caller; callbacks without an
+    // `Idempotency-Key` are still deduplicated on `anchor_transaction_id`.
+    // The concurrency cap sits closest to the handlers so idempotent replays
+    // and rejected requests never hold a processing permit.
     let mut callback_routes = Router::new()
         .route("/callback", post(handlers::webhook::callback))
         .route(
             "/callback/transaction",
             post(handlers::webhook::transaction_callback),
         )
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.webhook_permits.clone(),
+            crate::middleware::concurrency::webhook_concurrency_middleware,
+        ));
+
+    match callback_idempotency_service(&app_state) {
+        Ok(service) => {
+            callback_routes = callback_routes.layer(axum_middleware::from_fn_with_state(
+                service,
+                crate::middleware::idempotency::idempotency_middleware,
+            ));
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Callback idempotency disabled: invalid Redis URL");
+        }
+    }
+
+    let mut callback_routes = callback_routes
         .layer(axum_middleware::from_fn_with_state(
             app_state.clone(),
             crate::middleware::quota::rate_limit_middleware,
@@ -158,9 +284,18 @@ pub fn create_app(app_state: AppState) -> Router {
         callback_routes = callback_routes.layer(axum::Extension(store.clone()));
     }
 
+    // Added last so it is outermost: every callback layer runs in the root span.
+    let callback_routes = callback_routes.layer(axum_middleware::from_fn(
+        crate::middleware::callback_trace::callback_trace_middleware,
+    ));
+
     // Webhook route: signature verification + validation + quota
     let mut webhook_routes = Router::new()
         .route("/webhook", post(handlers::webhook::handle_webhook))
+        .layer(axum_middleware::from_fn_with_state(
+            app_state.webhook_permits.clone(),
+            crate::middleware::concurrency::webhook_concurrency_middleware,
+        ))
         .layer(axum_middleware::from_fn_with_state(
             app_state.clone(),
             crate::middleware::quota::rate_limit_middleware,
@@ -183,21 +318,37 @@ pub fn create_app(app_state: AppState) -> Router {
     // Core API routes (shared between versioned and unversioned)
     let core_routes = Router::new()
         .route("/transactions/:id", get(handlers::webhook::get_transaction))
+        .route(
+            "/transactions/:id/events",
+            get(handlers::webhook::get_transaction_events),
+        )
         .route(
             "/transactions",
             get(handlers::webhook::list_transactions_api),
         )
         .route(
             "/transactions/search",
-            get(handlers::search::search_transactions_wrapper),
+            get(handlers::search::search_transactions_wrapper)
+                .layer(middleware::compression::compression_layer()),
+        )
+        .route(
+            "/settlements",
+            get(handlers::settlements::list_settlements)
+                .layer(middleware::compression::compression_layer()),
         )
-        .route("/settlements", get(handlers::settlements::list_settlements))
         .route(
             "/settlements/:id",
-            get(handlers::settlements::get_settlement),
+            get(handlers::settlements::get_settlement)
+                .layer(middleware::compression::compression_layer()),
+        )
+        .route(
+            "/settlements/:id/transactions",
+            get(handlers::settlements::list_settlement_transactions)
+                .layer(middleware::compression::compression_layer()),
         )
         .merge(callback_routes.clone())
-        .merge(webhook_routes.clone());
+        .merge(webhook_routes.clone())
+        .layer(request_timeout.clone());
 
     // V1 routes — stable, with deprecation headers
     let v1_routes = core_routes.clone().layer(axum_middleware::from_fn(
@@ -217,7 +368,26 @@ pub fn create_app(app_state: AppState) -> Router {
         .route("/live", get(handlers::live))
         .route("/ready", get(handlers::ready))
         .route("/health", get(handlers::health))
-        .route("/errors", get(handlers::error_catalog));
+        .route("/version", get(handlers::version))
+        .route("/errors", get(handlers::error_catalog))
+        .layer(request_timeout.clone());
+
+    // Webhook replay routes; single replays are serialized per transaction
+    // through the distributed lock manager.
+    let mut webhook_replay_routes =
+        handlers::admin::webhook_replay_routes(app_state.batch_replay_limits);
+    match crate::services::lock_manager::LockManager::new(
+        &app_state.redis_url,
+        handlers::admin::webhook_replay::REPLAY_LOCK_TTL_SECS,
+    ) {
+        Ok(lock_manager) => {
+            webhook_replay_routes =
+                webhook_replay_routes.layer(axum::Extension(Arc::new(lock_manager)));
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Webhook replay lock disabled: invalid Redis URL");
+        }
+    }
 
     // Admin routes — auth + SecretsStore injected for rotation-aware auth
     let mut admin_router = Router::new()
@@ -226,12 +396,14 @@ pub fn create_app(app_state: AppState) -> Router {
             patch(handlers::admin::bulk_status::bulk_update_status_api),
         )
         .route("/graphql", post(handlers::graphql::graphql_handler))
-        .route("/export", get(handlers::export::export_transactions))
+        .route("/export/jobs", post(handlers::export::create_export_job))
+        .route("/export/jobs/:id", get(handlers::export::get_export_job))
         // Stats endpoints
         .route("/stats/status", get(handlers::stats::status_counts))
         .route("/stats/daily", get(handlers::stats::daily_totals))
         .route("/stats/assets", get(handlers::stats::asset_stats))
         .route("/cache/metrics", get(handlers::stats::cache_metrics))
+        .route("/metrics", get(handlers::stats::prometheus_metrics))
         // Admin: webhook endpoint health scores
         .route(
             "/admin/webhooks/health",
@@ -263,11 +435,23 @@ pub fn create_app(app_state: AppState) -> Router {
             "/admin/locks",
             get(handlers::admin::locks::list_active_locks),
         )
+        // Admin: scheduled background jobs
+        .route("/admin/jobs", get(handlers::admin::jobs::list_jobs))
+        // Admin: transactions partition sizes
+        .route(
+            "/admin/partitions",
+            get(handlers::admin::partitions::list_partitions),
+        )
         // Admin: settlement dispute workflow
         .route(
             "/admin/settlements/:id/status",
             axum::routing::patch(handlers::settlements::update_settlement_status),
         )
+        // Admin: per-asset settlement configuration
+        .nest(
+            "/admin/settlement-config",
+            handlers::admin::settlement_config::settlement_config_routes(),
+        )
         // Admin: reconciliation reports
         .nest(
             "/admin/reconciliation",
@@ -275,6 +459,31 @@ pub fn create_app(app_state: AppState) -> Router {
         )
         // Admin: point-in-time-recovery backup restores
         .nest("/admin/backup", handlers::admin::backup::backup_routes())
+        // Admin: stored profiling flamegraphs
+        .route(
+            "/admin/profiling/sessions",
+            get(handlers::profiling::list_profiling_sessions),
+        )
+        // Admin: DLQ inspection and replay
+        .nest("/admin/dlq", handlers::admin::dlq::dlq_routes())
+        // Admin: webhook replay and replay history
+        .nest(
+            "/admin",
+            webhook_replay_routes.with_state(app_state.db.clone()),
+        )
+        .layer(request_timeout)
+        // Export downloads stream large result sets for as long as they
+        // take, so they are registered after the request timeout.
+        .route(
+            "/export",
+            get(handlers::export::export_transactions)
+                .layer(middleware::compression::compression_layer()),
+        )
+        .route(
+            "/export/jobs/:id/download",
+            get(handlers::export::download_export_job)
+                .layer(middleware::compression::compression_layer()),
+        )
         .layer(axum_middleware::from_fn(
             crate::middleware::auth::admin_auth,
         ));
@@ -300,14 +509,33 @@ pub fn create_app(app_state: AppState) -> Router {
         .merge(
             Router::new()
                 .route("/ws", get(handlers::ws::ws_handler))
+                .route(
+                    "/ws/transactions/:id",
+                    get(handlers::ws::transaction_ws_handler),
+                )
                 .route(
                     "/reconnect/status",
                     get(handlers::reconnection::reconnect_status),
                 )
                 .route("/reconnect", post(handlers::reconnection::reconnect))
+                // Profiling session control, behind its own scoped JWT auth
+                .merge(handlers::profiling::profiling_routes(
+                    app_state.jwt_secret.clone(),
+                ))
+                // Effective configuration, behind its own scoped JWT auth
+                .merge(
+                    handlers::admin::config::config_routes(app_state.jwt_secret.clone())
+                        .with_state(app_state.config_info.clone()),
+                )
                 .with_state(app_state),
         )
         .layer(axum_middleware::from_fn(
             middleware::request_logger::request_logger_middleware,
         ))
+        // Outside every auth layer, so preflights are answered first.
+        .layer(cors)
+        .layer(tenant_cors)
+        // Resolves the request's tenant for the CORS layer, the tenant
+        // extractors and per-tenant metrics.
+        .layer(tenant_context)
 }
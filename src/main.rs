@@ -1,10 +1,11 @@
+use anyhow::Context;
 use clap::Parser;
 use sqlx::migrate::Migrator;
 use std::{net::SocketAddr, path::Path, sync::atomic::AtomicU64, sync::Arc};
 use synapse_core::cli;
 use synapse_core::cli::{
     BackupCommands, Cli, Commands, DbCommands, GraphqlCommands, SettlementsCommands, StatsCommands,
-    TxCommands,
+    TenantCommands, TxCommands,
 };
 use synapse_core::{
     config, db,
@@ -13,6 +14,7 @@ use synapse_core::{
     handlers::ws::TransactionStatusUpdate,
     metrics,
     middleware::idempotency::IdempotencyService,
+    readiness::subsystem,
     schemas,
     secrets::SecretsStore,
     services::{
@@ -22,7 +24,6 @@ use synapse_core::{
     AppState, ReadinessState,
 };
 use tokio::sync::broadcast;
-use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
@@ -32,16 +33,19 @@ use utoipa_swagger_ui::SwaggerUi;
 #[openapi(
     paths(
         handlers::health,
+        handlers::version,
         handlers::webhook::handle_webhook,
         handlers::webhook::callback,
         handlers::webhook::get_transaction,
+        handlers::webhook::get_transaction_events,
         handlers::webhook::list_transactions,
     ),
     components(
         schemas(
             handlers::HealthStatus,
             handlers::DbPoolStats,
-            handlers::settlements::SettlementListResponse,
+            handlers::VersionResponse,
+            handlers::pagination::SettlementListResponse,
             handlers::webhook::WebhookPayload,
             handlers::webhook::WebhookResponse,
             handlers::webhook::CallbackPayload,
@@ -93,8 +97,11 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    synapse_core::utils::sanitize::set_sensitive_keys(&config.log_sensitive_keys);
+
     match cli.command {
-        Some(Commands::Serve) | None => serve(config, tracer_manager).await,
+        Some(Commands::Serve { skip_migrate }) => serve(config, tracer_manager, skip_migrate).await,
+        None => serve(config, tracer_manager, false).await,
         Some(Commands::Tx(tx_cmd)) => match tx_cmd {
             TxCommands::ForceComplete { tx_id } => {
                 let pool = db::create_pool(&config).await?;
@@ -112,7 +119,20 @@ async fn main() -> anyhow::Result<()> {
                 start,
                 end,
                 format,
-            } => cli::handle_tx_reconcile(&config, &account, &start, &end, &format).await,
+                statuses,
+                max_span_days,
+            } => {
+                cli::handle_tx_reconcile(
+                    &config,
+                    &account,
+                    &start,
+                    &end,
+                    &format,
+                    &statuses,
+                    max_span_days,
+                )
+                .await
+            }
             TxCommands::Search {
                 status,
                 asset_code,
@@ -167,7 +187,17 @@ async fn main() -> anyhow::Result<()> {
             } => cli::handle_backup_restore_pitr(&config, &timestamp, dry_run, yes).await,
             BackupCommands::Cleanup => cli::handle_backup_cleanup(&config).await,
         },
+        Some(Commands::Tenant(tenant_cmd)) => {
+            let pool = db::create_pool(&config).await?;
+            match tenant_cmd {
+                TenantCommands::List { format } => cli::handle_tenant_list(&pool, &format).await,
+                TenantCommands::Show { id, format } => {
+                    cli::handle_tenant_show(&pool, id, &format).await
+                }
+            }
+        }
         Some(Commands::Config) => cli::handle_config_validate(&config),
+        Some(Commands::Health { json }) => cli::handle_health(&config, json).await,
         Some(Commands::Stats(stats_cmd)) => match stats_cmd {
             StatsCommands::Status { url, json } => cli::handle_stats_status(&url, json).await,
             StatsCommands::Daily { url, days, json } => {
@@ -189,6 +219,7 @@ async fn main() -> anyhow::Result<()> {
 async fn serve(
     config: config::Config,
     tracer_manager: synapse_core::telemetry::TracerManager,
+    skip_migrate: bool,
 ) -> anyhow::Result<()> {
     let pool = db::create_pool(&config).await?;
 
@@ -206,23 +237,50 @@ async fn serve(
         tracing::info!("No replica configured - all queries will use primary database");
     }
 
-    // Run migrations
-    let migrator = Migrator::new(Path::new("./migrations")).await?;
-    migrator.run(&pool).await?;
-    tracing::info!("Database migrations completed");
+    if skip_migrate {
+        synapse_core::startup::ensure_no_pending_migrations(&pool, &config.migrations_dir).await?;
+        tracing::info!("Migrations skipped; database schema is up to date");
+    } else {
+        let migrator = Migrator::new(Path::new(&config.migrations_dir))
+            .await
+            .with_context(|| format!("Failed to read migrations from {}", config.migrations_dir))?;
+        migrator.run(&pool).await?;
+        tracing::info!("Database migrations completed");
+    }
 
     // Initialize resource limiters for background tasks
     let settlement_limiter = ResourceLimiter::new(TaskLimits::new(1, 120), "settlement");
     let webhook_limiter = ResourceLimiter::new(TaskLimits::new(10, 60), "webhook");
 
     // Initialize partition manager (runs every 24 hours)
-    let partition_manager = db::partition::PartitionManager::new(pool.clone(), 24, None);
+    let mut partition_manager = db::partition::PartitionManager::with_config(
+        pool.clone(),
+        24,
+        None,
+        db::partition::PartitionConfig::from_config(&config),
+    );
+    if config.partition_drop_after_archive {
+        partition_manager =
+            partition_manager.with_backup(Arc::new(synapse_core::services::BackupService::new(
+                config.database_url.clone(),
+                config.backup_dir.clone().into(),
+                config.backup_encryption_key.clone(),
+            )));
+    }
     partition_manager.start();
-    tracing::info!("Partition manager started");
+    tracing::info!(
+        strategy = ?config.partition_strategy,
+        months_ahead = config.partition_months_ahead,
+        retention_months = config.partition_retention_months,
+        "Partition manager started"
+    );
 
     // Initialize Stellar Horizon client
-    let horizon_client = HorizonClient::new(config.stellar_horizon_url.clone());
+    let horizon_client = HorizonClient::with_endpoints(config.stellar_horizon_urls());
+    synapse_core::startup::ensure_horizon_network(&horizon_client, &config.stellar_network).await?;
     tracing::info!(
+        network = config.stellar_network.as_str(),
+        fallbacks = config.stellar_horizon_fallback_urls.len(),
         "Stellar Horizon client initialized with URL: {}",
         config.stellar_horizon_url
     );
@@ -234,36 +292,6 @@ async fn serve(
         config.settlement_min_tx_count,
     );
 
-    // Start background settlement worker
-    let settlement_pool = pool.clone();
-    let settlement_max_batch = config.settlement_max_batch_size;
-    let settlement_min_tx = config.settlement_min_tx_count;
-    let settlement_limiter_clone = settlement_limiter.clone();
-    tokio::spawn(async move {
-        let service = SettlementService::with_config(
-            settlement_pool,
-            settlement_max_batch,
-            settlement_min_tx,
-        );
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600)); // Default to hourly
-        loop {
-            interval.tick().await;
-            tracing::info!("Running scheduled settlement job...");
-            match settlement_limiter_clone
-                .run(async { service.run_settlements().await })
-                .await
-            {
-                Ok(Ok(results)) => {
-                    if !results.is_empty() {
-                        tracing::info!("Successfully generated {} settlements", results.len());
-                    }
-                }
-                Ok(Err(e)) => tracing::error!("Scheduled settlement job failed: {:?}", e),
-                Err(e) => tracing::error!("Settlement task resource limit error: {}", e),
-            }
-        }
-    });
-
     // Start background webhook delivery worker (runs every 30 seconds)
     let webhook_pool = pool.clone();
     let redis_url = config.redis_url.clone();
@@ -315,11 +343,16 @@ async fn serve(
         Arc::clone(&idempotency_lock_contention),
         Arc::clone(&idempotency_errors),
         Arc::clone(&idempotency_fallback_count),
-    )?;
-    tracing::info!("Redis idempotency service initialized");
+    )?
+    .with_failure_mode(config.idempotency_failure_mode);
+    tracing::info!(
+        failure_mode = config.idempotency_failure_mode.as_str(),
+        "Redis idempotency service initialized"
+    );
 
     // Initialize query cache
     let query_cache = synapse_core::services::QueryCache::new(&config.redis_url).await?;
+    synapse_core::db::queries::register_invalidation_cache(query_cache.clone());
     tracing::info!("Query cache initialized");
 
     // Warm cache on startup
@@ -327,15 +360,26 @@ async fn serve(
     if let Err(e) = query_cache.warm_cache(&pool, &cache_config).await {
         tracing::warn!("Failed to warm cache on startup: {:?}", e);
     }
+    if let Err(e) = query_cache
+        .warm_settlement_aggregates(&pool, &cache_config)
+        .await
+    {
+        tracing::warn!("Failed to warm settlement aggregates on startup: {:?}", e);
+    }
 
-    // Create broadcast channel for WebSocket notifications.
-    // Capacity of 100: slow subscribers will receive a RecvError::Lagged — the WS handler
-    // detects this, notifies the client with a "messages_dropped" frame, and offers resync.
-    let (tx_broadcast, _) = broadcast::channel::<TransactionStatusUpdate>(100);
-    tracing::info!("WebSocket broadcast channel initialized");
+    // Create broadcast channel for WebSocket and GraphQL subscribers.
+    // Subscribers more than BROADCAST_CAPACITY updates behind receive
+    // RecvError::Lagged, counted in `broadcast_messages_lagged_total`.
+    let (tx_broadcast, _) =
+        broadcast::channel::<TransactionStatusUpdate>(config.broadcast_capacity);
+    tracing::info!(
+        capacity = config.broadcast_capacity,
+        "WebSocket broadcast channel initialized"
+    );
 
     // Initialize feature flags service
-    let feature_flags = FeatureFlagService::new(pool.clone());
+    let feature_flags =
+        FeatureFlagService::new(pool.clone()).with_defaults(config.feature_flag_defaults.clone());
     tracing::info!("Feature flags service initialized");
 
     // Initialize secrets store and start rotation task (if Vault is configured).
@@ -375,10 +419,11 @@ async fn serve(
         feature_flags,
         redis_url: config.redis_url.clone(),
         start_time: std::time::Instant::now(),
-        readiness: ReadinessState::new(),
+        readiness: ReadinessState::new()
+            .with_required_dependencies(config.readiness_required_dependencies.iter().copied()),
         tx_broadcast,
         query_cache,
-        profiling_manager: crate::handlers::profiling::ProfilingManager::new(),
+        profiling_manager: config.profiling_manager(),
         tenant_configs: std::sync::Arc::new(tokio::sync::RwLock::new(
             std::collections::HashMap::new(),
         )),
@@ -387,14 +432,47 @@ async fn serve(
         current_batch_size: current_batch_size.clone(),
         metrics_handle,
         ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        cors_allowed_origins: config.cors_allowed_origins.clone(),
+        horizon_page_limit: config.horizon_page_limit,
+        health_latency_threshold_ms: config.health_latency_threshold_ms,
+        idempotency_failure_mode: config.idempotency_failure_mode,
+        webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(
+            config.webhook_max_concurrency,
+        )),
+        app_env: config.app_env.clone(),
+        backup_checker: Some(synapse_core::health::BackupChecker::new(
+            Arc::new(synapse_core::services::BackupService::new(
+                config.database_url.clone(),
+                config.backup_dir.clone().into(),
+                config.backup_encryption_key.clone(),
+            )),
+            std::time::Duration::from_secs(config.backup_max_age_hours * 3600),
+        )),
+        request_timeout: std::time::Duration::from_secs(config.request_timeout_secs),
+        search_default_window_days: config.search_default_window_days,
+        export_jobs_dir: config.export_jobs_dir.clone().into(),
+        config_info: Arc::new(config.info()),
+        job_scheduler: Arc::new(synapse_core::services::JobScheduler::new()),
+        jwt_secret: synapse_core::middleware::auth::JwtSecret::new(config.jwt_secret.as_deref()),
+        retry_policy: config.retry_policy(),
+        batch_replay_limits: config.batch_replay_limits(),
     };
 
+    // Migrations ran above, before the pool was handed to AppState.
+    app_state.readiness.mark_initialized(subsystem::MIGRATIONS);
+
     // Load tenant configs on startup
     if let Err(e) = app_state.load_tenant_configs().await {
         tracing::warn!("Failed to load tenant configs on startup: {}", e);
+        app_state
+            .readiness
+            .mark_unavailable(subsystem::TENANT_CACHE, format!("load failed: {e}"));
     } else {
         let count = app_state.tenant_configs.read().await.len();
         tracing::info!(count, "Tenant configs loaded on startup");
+        app_state
+            .readiness
+            .mark_initialized(subsystem::TENANT_CACHE);
     }
 
     // Background task: reload tenant configs every 60 seconds
@@ -429,7 +507,6 @@ async fn serve(
     // Concurrent processor pool
     let processor_pool = synapse_core::services::processor::ProcessorPool::new(
         pool.clone(),
-        horizon_client.clone(),
         config.processor_workers,
         config.processor_poll_interval_ms,
         config.processor_min_batch,
@@ -437,11 +514,13 @@ async fn serve(
         config.processor_scaling_factor,
         current_batch_size,
         pending_queue_depth,
-    );
+    )
+    .with_concurrency(config.processor_concurrency)
+    .with_retry_policy(config.retry_policy());
     let _processor_shutdown = processor_pool.start();
 
     // Register and start scheduled jobs
-    let scheduler = synapse_core::services::JobScheduler::new();
+    let scheduler = app_state.job_scheduler.clone();
     let stellar_account = std::env::var("RECONCILIATION_ACCOUNT").ok();
 
     if let Some(account) = stellar_account {
@@ -449,6 +528,7 @@ async fn serve(
             pool: pool.clone(),
             horizon_client: horizon_client.clone(),
             stellar_account: account,
+            page_limit: config.horizon_page_limit,
         };
         if let Err(e) = scheduler.register_job(Box::new(recon_job)).await {
             tracing::warn!("Failed to register reconciliation job: {}", e);
@@ -456,11 +536,40 @@ async fn serve(
     } else {
         tracing::info!("RECONCILIATION_ACCOUNT not set — daily reconciliation job not scheduled");
     }
+    let settlement_job = synapse_core::services::SettlementJob::new(
+        SettlementService::with_config(
+            pool.clone(),
+            config.settlement_max_batch_size,
+            config.settlement_min_tx_count,
+        ),
+        config.settlement_schedule.clone(),
+    )
+    .with_limiter(settlement_limiter);
+    if let Err(e) = scheduler.register_job(Box::new(settlement_job)).await {
+        tracing::warn!("Failed to register settlement job: {}", e);
+    }
+    let export_worker = synapse_core::services::export_jobs::ExportJobWorker::new(
+        synapse_core::services::export_jobs::ExportJobService::new(
+            pool.clone(),
+            &config.export_jobs_dir,
+        ),
+    );
+    if let Err(e) = scheduler.register_job(Box::new(export_worker)).await {
+        tracing::warn!("Failed to register export job worker: {}", e);
+    }
     if let Err(e) = scheduler.start().await {
         tracing::warn!("Failed to start job scheduler: {}", e);
     }
     tracing::info!("Job scheduler started");
 
+    if let Err(e) = app_state
+        .readiness
+        .run_initialization_checks(&pool, &config.redis_url, &config.stellar_horizon_url)
+        .await
+    {
+        tracing::error!("Service will report not ready: {}", e);
+    }
+
     let app = synapse_core::create_app(app_state.clone());
     let readiness = app_state.readiness.clone();
 
@@ -468,28 +577,11 @@ async fn serve(
     let app =
         app.merge(SwaggerUi::new("/api/docs").url("/api/docs/openapi.json", ApiDoc::openapi()));
 
-    // Configure CORS if allowed origins are specified.
-    let app = if !config.cors_allowed_origins.is_empty() {
-        let origins: Vec<_> = config
-            .cors_allowed_origins
-            .iter()
-            .filter_map(|o| o.parse::<axum::http::HeaderValue>().ok())
-            .collect();
-        tracing::info!(
-            "CORS enabled for origins: {:?}",
-            config.cors_allowed_origins
-        );
-        let cors = CorsLayer::new()
-            .allow_origin(AllowOrigin::list(origins))
-            .allow_methods(AllowMethods::any())
-            .allow_headers(AllowHeaders::any())
-            .allow_credentials(true)
-            .max_age(std::time::Duration::from_secs(3600));
-        app.layer(cors)
-    } else {
-        tracing::info!("CORS disabled (no allowed origins configured)");
-        app
-    };
+    // Reject oversized POST bodies before any layer buffers them.
+    let app = app.layer(axum::middleware::from_fn_with_state(
+        config.max_body_bytes,
+        synapse_core::middleware::body_limit::body_limit_middleware,
+    ));
 
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server_port));
     tracing::info!("listening on {}", addr);
@@ -526,6 +618,11 @@ async fn serve(
         })
         .await?;
 
+    // Let running jobs finish before their database connections go away.
+    if let Err(e) = scheduler.stop().await {
+        tracing::warn!("Failed to stop job scheduler: {}", e);
+    }
+
     // Gracefully drain and close the database pool before exiting.
     synapse_core::db::graceful_shutdown(&pool).await;
 
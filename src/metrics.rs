@@ -16,6 +16,28 @@
 //! | `db_pool_idle_connections`        | Gauge      | Idle DB connections                          |
 //! | `db_query_timeout_total`          | Counter    | Number of timed-out DB queries               |
 //! | `pending_queue_depth`             | Gauge      | Depth of the pending transaction queue       |
+//! | `ws_messages_lagged_total`        | Counter    | Broadcasts skipped by lagging WS clients     |
+//! | `ws_slow_consumer_disconnects_total` | Counter | WS clients disconnected for lagging too often |
+//! | `settlements_created_total`       | Counter    | Settlements produced by the scheduled job    |
+//! | `settlement_amount_total`         | Counter    | Total value settled by the scheduled job     |
+//! | `settlement_job_failures_total`   | Counter    | Failed scheduled settlement runs             |
+//!
+//! ## Prometheus
+//!
+//! A few instruments are also kept in a process-wide Prometheus registry and
+//! served in text format at `GET /metrics` (admin-authenticated):
+//!
+//! | Name                  | Labels       | Description                                |
+//! |-----------------------|--------------|--------------------------------------------|
+//! | `cache_hits_total`    | `query_type` | Query cache lookups served from cache      |
+//! | `cache_misses_total`  | `query_type` | Query cache lookups that fell through      |
+//! | `tenant_requests_total` | `tenant_id` | Requests resolved to a tenant            |
+//! | `tenant_errors_total` | `tenant_id`  | Tenant requests answered with 4xx or 5xx   |
+//!
+//! `query_type` is derived from the cache key (see
+//! [`crate::services::query_cache::query_type_for_key`]). At most
+//! [`MAX_TENANT_LABELS`] distinct tenants get their own `tenant_id` label;
+//! later tenants are counted under [`OVERFLOW_TENANT_LABEL`].
 //!
 //! ## Configuration
 //!
@@ -37,7 +59,10 @@ use opentelemetry_sdk::{
     },
     runtime,
 };
-use std::sync::OnceLock;
+use prometheus::{Encoder, IntCounterVec, Opts, Registry, TextEncoder};
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
 // Global meter handle
@@ -178,6 +203,200 @@ pub fn lock_hold_duration_ms() -> Histogram<f64> {
         .init()
 }
 
+/// Broadcast messages skipped because a WebSocket client fell behind.
+pub fn ws_messages_lagged_total() -> Counter<u64> {
+    meter()
+        .u64_counter("ws_messages_lagged_total")
+        .with_description("Total number of broadcast messages missed by lagging WebSocket clients")
+        .init()
+}
+
+/// WebSocket clients disconnected after lagging repeatedly.
+pub fn ws_slow_consumer_disconnects_total() -> Counter<u64> {
+    meter()
+        .u64_counter("ws_slow_consumer_disconnects_total")
+        .with_description("Total number of WebSocket clients disconnected as slow consumers")
+        .init()
+}
+
+/// Settlements produced by the scheduled settlement job.
+pub fn settlements_created_total() -> Counter<u64> {
+    meter()
+        .u64_counter("settlements_created_total")
+        .with_description("Total number of settlements produced by the scheduled settlement job")
+        .init()
+}
+
+/// Value settled by the scheduled settlement job, summed across assets.
+pub fn settlement_amount_total() -> Counter<f64> {
+    meter()
+        .f64_counter("settlement_amount_total")
+        .with_description("Total amount settled by the scheduled settlement job")
+        .init()
+}
+
+/// Scheduled settlement runs that returned an error.
+pub fn settlement_job_failures_total() -> Counter<u64> {
+    meter()
+        .u64_counter("settlement_job_failures_total")
+        .with_description("Total number of failed scheduled settlement runs")
+        .init()
+}
+
+// ---------------------------------------------------------------------------
+// Prometheus registry
+// ---------------------------------------------------------------------------
+
+static PROMETHEUS_REGISTRY: OnceLock<Registry> = OnceLock::new();
+static QUERY_CACHE_HITS: OnceLock<IntCounterVec> = OnceLock::new();
+static QUERY_CACHE_MISSES: OnceLock<IntCounterVec> = OnceLock::new();
+static BROADCAST_MESSAGES_LAGGED: OnceLock<IntCounterVec> = OnceLock::new();
+static TENANT_REQUESTS: OnceLock<IntCounterVec> = OnceLock::new();
+static TENANT_ERRORS: OnceLock<IntCounterVec> = OnceLock::new();
+static TENANT_LABELS: OnceLock<TenantLabels> = OnceLock::new();
+
+/// Distinct tenants given their own `tenant_id` label before the rest are
+/// bucketed into [`OVERFLOW_TENANT_LABEL`].
+pub const MAX_TENANT_LABELS: usize = 100;
+
+/// `tenant_id` label shared by tenants beyond [`MAX_TENANT_LABELS`].
+pub const OVERFLOW_TENANT_LABEL: &str = "other";
+
+/// Registry rendered by [`render_prometheus`].
+pub fn prometheus_registry() -> &'static Registry {
+    PROMETHEUS_REGISTRY.get_or_init(Registry::new)
+}
+
+fn registered_counter_vec(name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(Opts::new(name, help), labels)
+        .expect("static Prometheus counter definition is valid");
+    prometheus_registry()
+        .register(Box::new(counter.clone()))
+        .expect("Prometheus counter registered once");
+    counter
+}
+
+/// Query cache hits, labelled by `query_type`.
+pub fn query_cache_hits_total() -> &'static IntCounterVec {
+    QUERY_CACHE_HITS.get_or_init(|| {
+        registered_counter_vec(
+            "cache_hits_total",
+            "Number of query cache hits",
+            &["query_type"],
+        )
+    })
+}
+
+/// Query cache misses, labelled by `query_type`.
+pub fn query_cache_misses_total() -> &'static IntCounterVec {
+    QUERY_CACHE_MISSES.get_or_init(|| {
+        registered_counter_vec(
+            "cache_misses_total",
+            "Number of query cache misses",
+            &["query_type"],
+        )
+    })
+}
+
+/// Status updates skipped by lagging broadcast subscribers, labelled by
+/// `subscriber` (`websocket` or `graphql`).
+pub fn broadcast_messages_lagged_total() -> &'static IntCounterVec {
+    BROADCAST_MESSAGES_LAGGED.get_or_init(|| {
+        registered_counter_vec(
+            "broadcast_messages_lagged_total",
+            "Number of status updates dropped for subscribers that fell behind the broadcast channel",
+            &["subscriber"],
+        )
+    })
+}
+
+/// Requests resolved to a tenant, labelled by `tenant_id`.
+pub fn tenant_requests_total() -> &'static IntCounterVec {
+    TENANT_REQUESTS.get_or_init(|| {
+        registered_counter_vec(
+            "tenant_requests_total",
+            "Number of requests resolved to a tenant",
+            &["tenant_id"],
+        )
+    })
+}
+
+/// Tenant requests answered with a 4xx or 5xx status, labelled by
+/// `tenant_id`.
+pub fn tenant_errors_total() -> &'static IntCounterVec {
+    TENANT_ERRORS.get_or_init(|| {
+        registered_counter_vec(
+            "tenant_errors_total",
+            "Number of tenant requests answered with a client or server error",
+            &["tenant_id"],
+        )
+    })
+}
+
+/// Bounds the `tenant_id` label's cardinality: the first `capacity` tenants
+/// seen keep their own label, every later one maps to
+/// [`OVERFLOW_TENANT_LABEL`].
+pub struct TenantLabels {
+    capacity: usize,
+    seen: Mutex<HashSet<Uuid>>,
+}
+
+impl TenantLabels {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Label to record `tenant_id` under.
+    pub fn label(&self, tenant_id: Uuid) -> String {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        if seen.contains(&tenant_id) || seen.len() < self.capacity {
+            seen.insert(tenant_id);
+            tenant_id.to_string()
+        } else {
+            OVERFLOW_TENANT_LABEL.to_string()
+        }
+    }
+
+    /// Counts a request of `tenant_id` that was answered with `status`.
+    pub fn record(&self, tenant_id: Uuid, status: axum::http::StatusCode) {
+        let label = self.label(tenant_id);
+        tenant_requests_total().with_label_values(&[&label]).inc();
+        if status.is_client_error() || status.is_server_error() {
+            tenant_errors_total().with_label_values(&[&label]).inc();
+        }
+    }
+}
+
+/// Counts a request of `tenant_id` that was answered with `status`, under
+/// the process-wide [`MAX_TENANT_LABELS`] cap.
+pub fn record_tenant_request(tenant_id: Uuid, status: axum::http::StatusCode) {
+    TENANT_LABELS
+        .get_or_init(|| TenantLabels::new(MAX_TENANT_LABELS))
+        .record(tenant_id, status);
+}
+
+/// Renders every metric in [`prometheus_registry`] in the text exposition
+/// format.
+pub fn render_prometheus() -> String {
+    // Touch the lazily registered counters so they are listed even before
+    // the first cache lookup.
+    let _ = (
+        query_cache_hits_total(),
+        query_cache_misses_total(),
+        broadcast_messages_lagged_total(),
+        tenant_requests_total(),
+        tenant_errors_total(),
+    );
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&prometheus_registry().gather(), &mut buffer) {
+        tracing::error!(error = %e, "Failed to encode Prometheus metrics");
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
 // ---------------------------------------------------------------------------
 // Provider initialisation
 // ---------------------------------------------------------------------------
@@ -294,4 +513,49 @@ mod tests {
         // init_metrics requires a running OTLP endpoint; just verify it compiles.
         let _ = init_metrics;
     }
+
+    #[test]
+    fn test_render_prometheus_includes_query_type_label() {
+        query_cache_hits_total()
+            .with_label_values(&["render_test"])
+            .inc();
+
+        let text = render_prometheus();
+        assert!(text.contains("# TYPE cache_hits_total counter"));
+        assert!(text.contains(r#"cache_hits_total{query_type="render_test"}"#));
+    }
+
+    #[test]
+    fn test_tenant_counters_bucket_overflow_into_other() {
+        use axum::http::StatusCode;
+
+        let count =
+            |counter: &IntCounterVec, label: &str| counter.with_label_values(&[label]).get();
+        let other_requests = count(tenant_requests_total(), OVERFLOW_TENANT_LABEL);
+        let other_errors = count(tenant_errors_total(), OVERFLOW_TENANT_LABEL);
+
+        let labels = TenantLabels::new(2);
+        let (acme, globex, initech) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        labels.record(acme, StatusCode::OK);
+        labels.record(acme, StatusCode::OK);
+        labels.record(globex, StatusCode::INTERNAL_SERVER_ERROR);
+        labels.record(initech, StatusCode::NOT_FOUND);
+        // Tenants that got a label keep it once the cap is reached.
+        labels.record(acme, StatusCode::BAD_REQUEST);
+
+        let acme = acme.to_string();
+        let globex = globex.to_string();
+        assert_eq!(count(tenant_requests_total(), &acme), 3);
+        assert_eq!(count(tenant_errors_total(), &acme), 1);
+        assert_eq!(count(tenant_requests_total(), &globex), 1);
+        assert_eq!(count(tenant_errors_total(), &globex), 1);
+        assert_eq!(count(tenant_requests_total(), &initech.to_string()), 0);
+        // Other tests may record overflow concurrently, so only a lower bound holds.
+        assert!(count(tenant_requests_total(), OVERFLOW_TENANT_LABEL) > other_requests);
+        assert!(count(tenant_errors_total(), OVERFLOW_TENANT_LABEL) > other_errors);
+
+        let text = render_prometheus();
+        assert!(text.contains(&format!(r#"tenant_requests_total{{tenant_id="{acme}"}} 3"#)));
+        assert!(text.contains(r#"tenant_requests_total{tenant_id="other"}"#));
+    }
 }
@@ -1,14 +1,22 @@
 use axum::{
     body::Body,
-    extract::ConnectInfo,
+    extract::{ConnectInfo, State},
     http::{Request, StatusCode},
     middleware::Next,
     response::Response,
 };
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::secrets::SecretsStore;
 
+type HmacSha256 = Hmac<Sha256>;
+
 /// API key authentication middleware for callback/webhook endpoints.
 /// Requires `X-API-Key` header matching a key in the `tenants` table.
 /// Returns 401 on missing or invalid key and logs the source IP.
@@ -99,6 +107,177 @@ pub async fn admin_auth(req: Request<Body>, next: Next<Body>) -> Result<Response
     }
 }
 
+/// Bearer JWT auth for the profiling endpoints. Profiling holds a
+/// process-wide lock and slows the service down, so the token must carry the
+/// [`PROFILING_SCOPE`] scope. Returns 401 for a missing or invalid token and
+/// 403 when the scope is absent.
+pub async fn profiling_auth(
+    State(secret): State<JwtSecret>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Result<Response, StatusCode> {
+    require_scope(&secret, &req, PROFILING_SCOPE)?;
+    Ok(next.run(req).await)
+}
+
+/// Bearer JWT auth for `GET /admin/config`. The token must carry the
+/// [`CONFIG_SCOPE`] scope; 401 for a missing or invalid token, 403 without it.
+pub async fn config_auth(
+    State(secret): State<JwtSecret>,
+    req: Request<Body>,
+    next: Next<Body>,
+) -> Result<Response, StatusCode> {
+    require_scope(&secret, &req, CONFIG_SCOPE)?;
+    Ok(next.run(req).await)
+}
+
+fn require_scope(
+    secret: &JwtSecret,
+    req: &Request<Body>,
+    scope: &str,
+) -> Result<JwtClaims, StatusCode> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let claims = secret.verify(token).map_err(|e| {
+        tracing::warn!(error = %e, "Bearer token rejected");
+        StatusCode::UNAUTHORIZED
+    })?;
+    if !claims.has_scope(scope) {
+        tracing::warn!(sub = %claims.sub, scope, "Bearer token lacks required scope");
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(claims)
+}
+
+/// Claims carried by the HS256 bearer tokens issued to API clients.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JwtClaims {
+    pub sub: String,
+    pub tenant_id: Uuid,
+    /// Expiry as Unix seconds.
+    pub exp: i64,
+    /// Permissions granted to the bearer, e.g. `admin:profiling`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+}
+
+impl JwtClaims {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Scope required to start, stop and read CPU/memory profiling sessions.
+pub const PROFILING_SCOPE: &str = "admin:profiling";
+
+/// Scope required to read the effective configuration.
+pub const CONFIG_SCOPE: &str = "admin:config";
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum JwtError {
+    #[error("JWT_SECRET is not configured")]
+    MissingSecret,
+    #[error("malformed token")]
+    Malformed,
+    #[error("unsupported signing algorithm")]
+    UnsupportedAlgorithm,
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("token expired")]
+    Expired,
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+/// Shortest `JWT_SECRET` accepted at startup: an HS256 key should carry at
+/// least as many bytes as the SHA-256 output.
+pub const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// HS256 key bearer tokens are verified against, loaded once from
+/// `Config::jwt_secret`. Without a key every token is rejected.
+#[derive(Clone, Default)]
+pub struct JwtSecret(Option<Arc<[u8]>>);
+
+impl JwtSecret {
+    /// An empty secret is treated as unset, so it can never verify a token.
+    pub fn new(secret: Option<&str>) -> Self {
+        Self(
+            secret
+                .filter(|s| !s.is_empty())
+                .map(|s| Arc::from(s.as_bytes())),
+        )
+    }
+
+    /// Verifies a bearer JWT. Fails closed if no secret is configured.
+    pub fn verify(&self, token: &str) -> Result<JwtClaims, JwtError> {
+        let secret = self.0.as_deref().ok_or(JwtError::MissingSecret)?;
+        decode_jwt(token, secret)
+    }
+}
+
+impl std::fmt::Debug for JwtSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = if self.0.is_some() { "set" } else { "unset" };
+        f.debug_tuple("JwtSecret").field(&state).finish()
+    }
+}
+
+/// Decodes and verifies an HS256 JWT, checking the signature (in constant
+/// time) and the `exp` claim.
+pub fn decode_jwt(token: &str, secret: &[u8]) -> Result<JwtClaims, JwtError> {
+    let (signing_input, signature) = token.rsplit_once('.').ok_or(JwtError::Malformed)?;
+    let (header, payload) = signing_input
+        .split_once('.')
+        .filter(|(_, payload)| !payload.contains('.'))
+        .ok_or(JwtError::Malformed)?;
+
+    let header: JwtHeader = decode_segment(header)?;
+    if header.alg != "HS256" {
+        return Err(JwtError::UnsupportedAlgorithm);
+    }
+
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| JwtError::Malformed)?;
+    let mut mac = HmacSha256::new_from_slice(secret).map_err(|_| JwtError::InvalidSignature)?;
+    mac.update(signing_input.as_bytes());
+    mac.verify_slice(&signature)
+        .map_err(|_| JwtError::InvalidSignature)?;
+
+    let claims: JwtClaims = decode_segment(payload)?;
+    if claims.exp <= chrono::Utc::now().timestamp() {
+        return Err(JwtError::Expired);
+    }
+    Ok(claims)
+}
+
+/// Issues an HS256 JWT for `claims`.
+pub fn encode_jwt(claims: &JwtClaims, secret: &[u8]) -> String {
+    let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"HS256","typ":"JWT"}"#);
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).unwrap_or_default());
+    let signing_input = format!("{}.{}", header, payload);
+
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(signing_input.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    format!("{}.{}", signing_input, signature)
+}
+
+fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, JwtError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| JwtError::Malformed)?;
+    serde_json::from_slice(&bytes).map_err(|_| JwtError::Malformed)
+}
+
 /// Constant-time byte slice equality check to prevent timing attacks.
 fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
     use subtle::ConstantTimeEq;
@@ -155,4 +334,79 @@ mod tests {
             "Empty X-API-Key should be treated as missing"
         );
     }
+
+    fn claims(exp_offset: i64) -> JwtClaims {
+        JwtClaims {
+            sub: "client".to_string(),
+            tenant_id: Uuid::new_v4(),
+            exp: chrono::Utc::now().timestamp() + exp_offset,
+            scopes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_jwt_round_trip() {
+        let claims = claims(60);
+        let token = encode_jwt(&claims, b"secret");
+        assert_eq!(decode_jwt(&token, b"secret"), Ok(claims.clone()));
+        assert_eq!(JwtSecret::new(Some("secret")).verify(&token), Ok(claims));
+    }
+
+    #[test]
+    fn test_jwt_scopes_round_trip_and_default_to_none() {
+        let mut scoped = claims(60);
+        scoped.scopes = vec![PROFILING_SCOPE.to_string()];
+        let decoded = decode_jwt(&encode_jwt(&scoped, b"secret"), b"secret").unwrap();
+        assert!(decoded.has_scope(PROFILING_SCOPE));
+
+        // Tokens issued before scopes existed carry none.
+        let decoded = decode_jwt(&encode_jwt(&claims(60), b"secret"), b"secret").unwrap();
+        assert!(decoded.scopes.is_empty());
+        assert!(!decoded.has_scope(PROFILING_SCOPE));
+    }
+
+    #[test]
+    fn test_jwt_rejects_wrong_secret_and_tampering() {
+        let token = encode_jwt(&claims(60), b"secret");
+        assert_eq!(
+            decode_jwt(&token, b"other"),
+            Err(JwtError::InvalidSignature)
+        );
+
+        let forged_payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims(60)).unwrap());
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged = format!("{}.{}.{}", parts[0], forged_payload, parts[2]);
+        assert_eq!(
+            decode_jwt(&forged, b"secret"),
+            Err(JwtError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_jwt_rejects_expired_and_malformed() {
+        let token = encode_jwt(&claims(-1), b"secret");
+        assert_eq!(decode_jwt(&token, b"secret"), Err(JwtError::Expired));
+        assert_eq!(decode_jwt("not-a-jwt", b"secret"), Err(JwtError::Malformed));
+        assert_eq!(decode_jwt("a.b.c.d", b"secret"), Err(JwtError::Malformed));
+    }
+
+    #[test]
+    fn test_jwt_secret_unset_or_empty_fails_closed() {
+        // HMAC accepts an empty key, so a token signed with "" must not pass.
+        let token = encode_jwt(&claims(60), b"");
+        for secret in [JwtSecret::default(), JwtSecret::new(Some(""))] {
+            assert_eq!(secret.verify(&token), Err(JwtError::MissingSecret));
+        }
+    }
+
+    #[test]
+    fn test_jwt_rejects_non_hs256() {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"none"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims(60)).unwrap());
+        let token = format!("{}.{}.", header, payload);
+        assert_eq!(
+            decode_jwt(&token, b"secret"),
+            Err(JwtError::UnsupportedAlgorithm)
+        );
+    }
 }
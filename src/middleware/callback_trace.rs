@@ -0,0 +1,58 @@
+//! Root span for the anchor callback path.
+//!
+//! [`callback_trace_middleware`] wraps the whole callback stack — signature
+//! and API key checks, schema validation, idempotency, and the handler — in a
+//! single `webhook.callback` span, so the OTLP exporter ships one trace per
+//! delivery:
+//!
+//! ```text
+//! webhook.callback        tenant.id, transaction.id, http.status_code
+//! ├── webhook.validate
+//! ├── idempotency.check   (only with an Idempotency-Key header)
+//! ├── webhook.db_insert   transaction.id
+//! └── webhook.broadcast   transaction.id
+//! ```
+//!
+//! `transaction.id` is only known once the handler has written the row, so
+//! the handler records it on the root span itself via [`record_transaction_id`].
+
+use axum::{body::Body, http::Request, middleware::Next, response::Response};
+use tracing::{field::Empty, Instrument};
+use uuid::Uuid;
+
+/// Name of the root span opened for every callback delivery.
+pub const CALLBACK_SPAN: &str = "webhook.callback";
+
+/// Runs the rest of the callback stack inside a `webhook.callback` span.
+///
+/// `tenant.id` is taken from the `X-Tenant-ID` header when it holds a UUID;
+/// `transaction.id` and `http.status_code` are filled in as they become known.
+pub async fn callback_trace_middleware(request: Request<Body>, next: Next<Body>) -> Response {
+    let tenant_id = request
+        .headers()
+        .get("x-tenant-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| Uuid::parse_str(v.trim()).ok());
+
+    let span = tracing::info_span!(
+        CALLBACK_SPAN,
+        tenant.id = Empty,
+        transaction.id = Empty,
+        http.status_code = Empty,
+    );
+    if let Some(tenant_id) = tenant_id {
+        span.record("tenant.id", tracing::field::display(tenant_id));
+    }
+
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.status_code", response.status().as_u16());
+    response
+}
+
+/// Tags the current `webhook.callback` span with the transaction it produced.
+///
+/// Must be called from the handler itself, which runs directly inside the
+/// root span; outside of [`callback_trace_middleware`] it is a no-op.
+pub fn record_transaction_id(transaction_id: Uuid) {
+    tracing::Span::current().record("transaction.id", tracing::field::display(transaction_id));
+}
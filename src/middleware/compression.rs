@@ -0,0 +1,118 @@
+//! Response compression for routes that return large JSON or CSV bodies.
+//!
+//! Applied per route in `src/lib.rs` (export, search and settlements) rather
+//! than globally, so small API responses skip the encoder entirely. The
+//! encoding is negotiated from `Accept-Encoding`; gzip and brotli are
+//! supported. Bodies are compressed chunk by chunk, so streamed responses
+//! stay streamed.
+
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+/// Responses with a known size below this many bytes are sent uncompressed.
+/// Streamed bodies have no known size and are always compressed.
+pub const MIN_COMPRESS_BYTES: u16 = 1024;
+
+/// Layer compressing responses of at least [`MIN_COMPRESS_BYTES`], skipping
+/// content types that are already compressed or must not be buffered.
+pub fn compression_layer() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().gzip(true).br(true).compress_when(
+        SizeAbove::new(MIN_COMPRESS_BYTES)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::const_new("text/event-stream")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{Body, Bytes, StreamBody},
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use std::io::Read;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/large",
+                get(|| async { "a,b,c\n".repeat(1000) }).layer(compression_layer()),
+            )
+            .route(
+                "/small",
+                get(|| async { "tiny" }).layer(compression_layer()),
+            )
+            .route(
+                "/stream",
+                get(|| async {
+                    let rows = (0..500)
+                        .map(|i| Ok::<_, std::io::Error>(Bytes::from(format!("{i},row\n"))));
+                    StreamBody::new(futures::stream::iter(rows))
+                })
+                .layer(compression_layer()),
+            )
+    }
+
+    async fn get_with_encoding(uri: &str, encoding: Option<&str>) -> axum::response::Response {
+        let mut request = Request::builder().uri(uri);
+        if let Some(encoding) = encoding {
+            request = request.header(header::ACCEPT_ENCODING, encoding);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn gunzip(bytes: &[u8]) -> String {
+        let mut out = String::new();
+        flate2::read::GzDecoder::new(bytes)
+            .read_to_string(&mut out)
+            .unwrap();
+        out
+    }
+
+    #[tokio::test]
+    async fn test_large_body_is_gzipped_when_accepted() {
+        let response = get_with_encoding("/large", Some("gzip")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert!(bytes.len() < 6000);
+        assert_eq!(gunzip(&bytes), "a,b,c\n".repeat(1000));
+    }
+
+    #[tokio::test]
+    async fn test_body_is_untouched_without_accept_encoding() {
+        let response = get_with_encoding("/large", None).await;
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(bytes.len(), 6000);
+    }
+
+    #[tokio::test]
+    async fn test_small_body_is_not_compressed() {
+        let response = get_with_encoding("/small", Some("gzip, br")).await;
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&bytes[..], b"tiny");
+    }
+
+    #[tokio::test]
+    async fn test_streamed_body_is_compressed_on_the_fly() {
+        let response = get_with_encoding("/stream", Some("gzip")).await;
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert!(response.headers().get(header::CONTENT_LENGTH).is_none());
+
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let expected: String = (0..500).map(|i| format!("{i},row\n")).collect();
+        assert_eq!(gunzip(&bytes), expected);
+    }
+}
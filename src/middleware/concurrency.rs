@@ -0,0 +1,90 @@
+//! Cap on concurrently processed inbound webhook and callback requests.
+//!
+//! One semaphore, sized from `Config::webhook_max_concurrency`, is shared by
+//! every webhook route so a burst of callbacks cannot take the whole DB pool.
+//! Requests that find no free permit are turned away instead of queued.
+
+use crate::error::AppError;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Permits when `WEBHOOK_MAX_CONCURRENCY` is unset.
+pub const DEFAULT_WEBHOOK_MAX_CONCURRENCY: usize = 64;
+
+/// Seconds a rejected caller is asked to wait. Saturation clears as soon as
+/// in-flight requests finish, so this is much shorter than the queue-depth
+/// back-pressure delay.
+const RETRY_AFTER_SECS: &str = "1";
+
+/// Holds a permit from `permits` for the rest of the request, or responds
+/// with [`AppError::ServiceBusy`] (`503`, `ERR_UNAVAILABLE_001`) and
+/// `Retry-After` when none is free.
+pub async fn webhook_concurrency_middleware(
+    State(permits): State<Arc<Semaphore>>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let Ok(_permit) = permits.try_acquire() else {
+        tracing::warn!(
+            path = %request.uri().path(),
+            "webhook_rejected_concurrency: no processing permit available"
+        );
+        let mut response = AppError::ServiceBusy.into_response();
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from_static(RETRY_AFTER_SECS),
+        );
+        return response;
+    };
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn app(permits: Arc<Semaphore>) -> Router {
+        Router::new()
+            .route("/callback", post(|| async { StatusCode::OK }))
+            .layer(middleware::from_fn_with_state(
+                permits,
+                webhook_concurrency_middleware,
+            ))
+    }
+
+    fn request() -> Request<Body> {
+        Request::post("/callback").body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_with_retry_after_when_saturated() {
+        let permits = Arc::new(Semaphore::new(1));
+        let held = permits.clone().try_acquire_owned().unwrap();
+
+        let response = app(permits.clone()).oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers()[header::RETRY_AFTER], RETRY_AFTER_SECS);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            crate::error::PROBLEM_JSON_CONTENT_TYPE
+        );
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "ERR_UNAVAILABLE_001");
+
+        drop(held);
+        let response = app(permits.clone()).oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(permits.available_permits(), 1, "permit is released");
+    }
+}
@@ -0,0 +1,328 @@
+//! Cross-origin resource sharing for browser clients (dashboards, admin UI).
+//!
+//! Built from `CORS_ALLOWED_ORIGINS` and applied outermost in
+//! `create_app`, so preflight requests are answered before auth, quota or
+//! signature checks run. Origins outside the allowlist get no
+//! `Access-Control-Allow-*` headers and the browser blocks the request; an
+//! empty allowlist leaves the API same-origin only.
+//!
+//! Tenants may list their own origins (`tenants.cors_allowed_origins`).
+//! [`tenant_cors_middleware`] runs just outside the CORS layer, takes the
+//! tenant resolved by `tenant_context_middleware` and attaches its origins as
+//! [`TenantOrigins`], which then replace the global allowlist for that request. Preflights carry no tenant
+//! headers, so they are checked against the global list plus every tenant's
+//! origins; the actual request is still checked against its own tenant.
+
+use crate::tenant::TenantContext;
+use crate::AppState;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, HeaderName, HeaderValue, Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// How long browsers may cache a preflight response.
+pub const PREFLIGHT_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// Methods used by the public and admin APIs.
+const ALLOWED_METHODS: [Method; 6] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+    Method::OPTIONS,
+];
+
+/// Request headers the API reads. Listed explicitly because a wildcard
+/// cannot be combined with credentials.
+const ALLOWED_HEADERS: [HeaderName; 12] = [
+    header::AUTHORIZATION,
+    header::CONTENT_TYPE,
+    header::ACCEPT,
+    header::ACCEPT_ENCODING,
+    HeaderName::from_static("x-api-key"),
+    HeaderName::from_static("x-tenant-id"),
+    HeaderName::from_static("x-request-id"),
+    HeaderName::from_static("idempotency-key"),
+    HeaderName::from_static("x-idempotency-key"),
+    HeaderName::from_static("x-webhook-signature"),
+    HeaderName::from_static("x-webhook-timestamp"),
+    HeaderName::from_static("x-stellar-signature"),
+];
+
+/// Response headers browser clients may read.
+const EXPOSED_HEADERS: [HeaderName; 5] = [
+    HeaderName::from_static("x-request-id"),
+    HeaderName::from_static("x-idempotent-replayed"),
+    HeaderName::from_static("api-version"),
+    HeaderName::from_static("deprecation"),
+    HeaderName::from_static("sunset"),
+];
+
+/// Origins allowed for the request in place of the global allowlist, set by
+/// [`tenant_cors_middleware`] and read by the layer from [`cors_layer`].
+#[derive(Debug, Clone)]
+pub struct TenantOrigins(pub Vec<String>);
+
+impl TenantOrigins {
+    fn allows(&self, origin: &HeaderValue) -> bool {
+        self.0.iter().any(|o| o.as_bytes() == origin.as_bytes())
+    }
+}
+
+/// Attaches [`TenantOrigins`] to browser requests whose tenant lists its own
+/// CORS origins.
+///
+/// Requests without an `Origin` header pass straight through. The tenant is
+/// the one [`crate::middleware::tenant::tenant_context_middleware`] resolved,
+/// so it must run outside this layer; requests without one keep the global
+/// allowlist.
+pub async fn tenant_cors_middleware(
+    State(state): State<AppState>,
+    mut request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    if !request.headers().contains_key(header::ORIGIN) {
+        return next.run(request).await;
+    }
+
+    let is_preflight = request.method() == Method::OPTIONS
+        && request
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+
+    let origins = if is_preflight {
+        let tenants = state.tenant_configs.read().await;
+        let tenant_origins: Vec<String> = tenants
+            .values()
+            .flat_map(|t| t.cors_allowed_origins.iter().cloned())
+            .collect();
+        (!tenant_origins.is_empty()).then(|| {
+            TenantOrigins(
+                state
+                    .cors_allowed_origins
+                    .iter()
+                    .cloned()
+                    .chain(tenant_origins)
+                    .collect(),
+            )
+        })
+    } else {
+        request
+            .extensions()
+            .get::<TenantContext>()
+            .map(|tenant| tenant.config.cors_allowed_origins.clone())
+            .filter(|origins| !origins.is_empty())
+            .map(TenantOrigins)
+    };
+
+    if let Some(origins) = origins {
+        request.extensions_mut().insert(origins);
+    }
+    next.run(request).await
+}
+
+/// Builds the CORS layer for `allowed_origins`.
+///
+/// `*` anywhere in the list allows every origin, without credentials.
+/// Otherwise only the listed origins are allowed, with credentials; entries
+/// that are not valid header values are skipped with a warning. An empty
+/// list allows no cross-origin requests. Either way, a [`TenantOrigins`]
+/// extension on the request replaces the list for that request.
+pub fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    let base = CorsLayer::new()
+        .allow_methods(ALLOWED_METHODS.to_vec())
+        .allow_headers(ALLOWED_HEADERS.to_vec())
+        .expose_headers(EXPOSED_HEADERS.to_vec())
+        .max_age(PREFLIGHT_MAX_AGE);
+
+    if allowed_origins.iter().any(|o| o == "*") {
+        tracing::info!("CORS enabled for any origin");
+        return base.allow_origin(AllowOrigin::any());
+    }
+
+    let origins: Vec<HeaderValue> = allowed_origins
+        .iter()
+        .filter_map(|origin| match origin.parse::<HeaderValue>() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!(origin = %origin, "Ignoring invalid CORS origin");
+                None
+            }
+        })
+        .collect();
+
+    if origins.is_empty() {
+        tracing::info!("CORS limited to tenant origins (no global origins configured)");
+    } else {
+        tracing::info!(origins = ?allowed_origins, "CORS enabled");
+    }
+
+    let origins: Arc<[HeaderValue]> = origins.into();
+    base.allow_origin(AllowOrigin::predicate(move |origin, parts| {
+        match parts.extensions.get::<TenantOrigins>() {
+            Some(tenant) => tenant.allows(origin),
+            None => origins.contains(origin),
+        }
+    }))
+    .allow_credentials(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn app(allowed: &[&str]) -> Router {
+        let allowed: Vec<String> = allowed.iter().map(|s| s.to_string()).collect();
+        Router::new()
+            .route("/transactions", get(|| async { "ok" }))
+            .layer(cors_layer(&allowed))
+    }
+
+    async fn preflight(app: Router, origin: &str) -> axum::response::Response {
+        app.oneshot(
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/transactions")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "x-api-key")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn allow_origin(response: &axum::response::Response) -> Option<&str> {
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .map(|v| v.to_str().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_preflight_from_allowed_origin() {
+        let app = app(&["https://dash.example.com", "https://ops.example.com"]);
+        let response = preflight(app, "https://ops.example.com").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(allow_origin(&response), Some("https://ops.example.com"));
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS],
+            HeaderValue::from_static("true")
+        );
+        let methods = headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap();
+        assert!(methods.contains("PATCH"));
+        let allowed_headers = headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap();
+        assert!(allowed_headers.contains("x-api-key"));
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "3600");
+    }
+
+    #[tokio::test]
+    async fn test_preflight_from_disallowed_origin() {
+        let app = app(&["https://dash.example.com"]);
+        let response = preflight(app, "https://evil.example.com").await;
+
+        assert_eq!(allow_origin(&response), None);
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_allows_any_origin_without_credentials() {
+        let response = preflight(app(&["*"]), "https://anywhere.example.com").await;
+
+        assert_eq!(allow_origin(&response), Some("*"));
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_empty_allowlist_is_same_origin_only() {
+        let response = preflight(app(&[]), "https://dash.example.com").await;
+        assert_eq!(allow_origin(&response), None);
+
+        let response = app(&[])
+            .oneshot(
+                Request::builder()
+                    .uri("/transactions")
+                    .header(header::ORIGIN, "https://dash.example.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(allow_origin(&response), None);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_origins_replace_global_allowlist() {
+        let tenant_origins = |mut request: Request<Body>, next: Next<Body>| async move {
+            request
+                .extensions_mut()
+                .insert(TenantOrigins(vec!["https://acme.example.com".to_string()]));
+            next.run(request).await
+        };
+        let app =
+            app(&["https://dash.example.com"]).layer(axum::middleware::from_fn(tenant_origins));
+        let get = |origin: &'static str| {
+            Request::builder()
+                .uri("/transactions")
+                .header(header::ORIGIN, origin)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(get("https://acme.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(allow_origin(&response), Some("https://acme.example.com"));
+
+        for origin in ["https://other.example.com", "https://dash.example.com"] {
+            let response = app.clone().oneshot(get(origin)).await.unwrap();
+            assert_eq!(allow_origin(&response), None, "{origin}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simple_request_exposes_headers() {
+        let response = app(&["https://dash.example.com"])
+            .oneshot(
+                Request::builder()
+                    .uri("/transactions")
+                    .header(header::ORIGIN, "https://dash.example.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(allow_origin(&response), Some("https://dash.example.com"));
+        let exposed = response.headers()[header::ACCESS_CONTROL_EXPOSE_HEADERS]
+            .to_str()
+            .unwrap();
+        assert!(exposed.contains("x-request-id"));
+    }
+}
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
+use tracing::Instrument;
 
 // ── Circuit breaker type alias ────────────────────────────────────────────────
 
@@ -94,6 +95,40 @@ impl From<redis::RedisError> for RedisError {
 
 // ── IdempotencyService ────────────────────────────────────────────────────────
 
+/// What the middleware does with a keyed request when the idempotency check
+/// itself fails, i.e. Redis is unreachable and the database fallback errors too.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotencyFailureMode {
+    /// Log the failure and run the request without deduplication.
+    FailOpen,
+    /// Reject the request with 503 so the client retries once the store is back.
+    #[default]
+    FailClosed,
+}
+
+impl IdempotencyFailureMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdempotencyFailureMode::FailOpen => "fail_open",
+            IdempotencyFailureMode::FailClosed => "fail_closed",
+        }
+    }
+}
+
+impl std::str::FromStr for IdempotencyFailureMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fail_open" | "open" => Ok(IdempotencyFailureMode::FailOpen),
+            "fail_closed" | "closed" => Ok(IdempotencyFailureMode::FailClosed),
+            other => Err(format!(
+                "unknown idempotency failure mode '{other}', expected 'fail_open' or 'fail_closed'"
+            )),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct IdempotencyService {
     client: Client,
@@ -104,6 +139,9 @@ pub struct IdempotencyService {
     lock_contention: Arc<AtomicU64>,
     errors: Arc<AtomicU64>,
     fallback_count: Arc<AtomicU64>,
+    failure_mode: IdempotencyFailureMode,
+    namespace: Option<String>,
+    ttl: Duration,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -145,12 +183,21 @@ struct LockValue {
     token: String,
 }
 
-fn _cache_key(tenant_id: &str, key: &str) -> String {
-    format!("idempotency:{tenant_id}:{key}")
+/// How long a completed response is replayed when no TTL is configured.
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 3600);
+
+fn _cache_key(namespace: Option<&str>, tenant_id: &str, key: &str) -> String {
+    match namespace {
+        Some(ns) => format!("idempotency:{ns}:{tenant_id}:{key}"),
+        None => format!("idempotency:{tenant_id}:{key}"),
+    }
 }
 
-fn _lock_key(tenant_id: &str, key: &str) -> String {
-    format!("idempotency:lock:{tenant_id}:{key}")
+fn _lock_key(namespace: Option<&str>, tenant_id: &str, key: &str) -> String {
+    match namespace {
+        Some(ns) => format!("idempotency:lock:{ns}:{tenant_id}:{key}"),
+        None => format!("idempotency:lock:{tenant_id}:{key}"),
+    }
 }
 
 fn _lock_value(token: &str) -> String {
@@ -190,16 +237,66 @@ impl IdempotencyService {
             lock_contention,
             errors,
             fallback_count,
+            failure_mode: IdempotencyFailureMode::default(),
+            namespace: None,
+            ttl: DEFAULT_IDEMPOTENCY_TTL,
         })
     }
 
+    pub fn with_failure_mode(mut self, mode: IdempotencyFailureMode) -> Self {
+        self.failure_mode = mode;
+        self
+    }
+
+    /// Scopes keys to one route group, so the same `Idempotency-Key` sent to
+    /// two namespaced routes is tracked separately on each.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// How long a completed response is replayed for. Defaults to
+    /// [`DEFAULT_IDEMPOTENCY_TTL`].
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn failure_mode(&self) -> IdempotencyFailureMode {
+        self.failure_mode
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    fn cache_key(&self, tenant_id: &str, key: &str) -> String {
+        _cache_key(self.namespace(), tenant_id, key)
+    }
+
+    fn lock_key(&self, tenant_id: &str, key: &str) -> String {
+        _lock_key(self.namespace(), tenant_id, key)
+    }
+
+    /// Row key in the `idempotency_keys` fallback table.
+    fn db_key(&self, key: &str) -> String {
+        match self.namespace() {
+            Some(ns) => format!("{ns}:{key}"),
+            None => key.to_string(),
+        }
+    }
+
     pub async fn check_idempotency(
         &self,
         tenant_id: &str,
         key: &str,
     ) -> Result<IdempotencyStatus, Box<dyn std::error::Error + Send + Sync>> {
-        let cache_key = _cache_key(tenant_id, key);
-        let lock_key = _lock_key(tenant_id, key);
+        let cache_key = self.cache_key(tenant_id, key);
+        let lock_key = self.lock_key(tenant_id, key);
 
         match self.client.get_multiplexed_async_connection().await {
             Ok(mut conn) => {
@@ -262,10 +359,11 @@ impl IdempotencyService {
         &self,
         key: &str,
     ) -> Result<IdempotencyStatus, Box<dyn std::error::Error + Send + Sync>> {
-        use chrono::{Duration, Utc};
+        use chrono::Utc;
 
+        let key = self.db_key(key);
         // Check if key exists in database
-        if let Some(db_key) = crate::db::queries::check_idempotency_key(&self.pool, key).await? {
+        if let Some(db_key) = crate::db::queries::check_idempotency_key(&self.pool, &key).await? {
             match db_key.status.as_str() {
                 "completed" => {
                     if let Some(response_json) = db_key.response {
@@ -303,10 +401,11 @@ impl IdempotencyService {
             }
         } else {
             // Key doesn't exist, try to insert as processing
-            let expires_at = Utc::now() + Duration::hours(24);
+            let expires_at = Utc::now()
+                + chrono::Duration::from_std(self.ttl).unwrap_or(chrono::Duration::hours(24));
             crate::db::queries::insert_idempotency_key(
                 &self.pool,
-                key,
+                &key,
                 "processing",
                 None,
                 expires_at,
@@ -327,8 +426,8 @@ impl IdempotencyService {
             return self.store_response_db(key, &response).await;
         }
 
-        let cache_key = _cache_key(tenant_id, key);
-        let lock_key = _lock_key(tenant_id, key);
+        let cache_key = self.cache_key(tenant_id, key);
+        let lock_key = self.lock_key(tenant_id, key);
         let data = serde_json::to_string(&response)?;
 
         match self.client.get_multiplexed_async_connection().await {
@@ -340,7 +439,7 @@ impl IdempotencyService {
                     .key(&lock_key)
                     .key(&cache_key)
                     .arg(lock_token.expect("checked above"))
-                    .arg(86400)
+                    .arg(self.ttl.as_secs().max(1))
                     .arg(&data)
                     .invoke_async::<_, u32>(&mut conn)
                     .await?;
@@ -365,8 +464,12 @@ impl IdempotencyService {
         response: &CachedResponse,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let response_json = serde_json::to_value(response)?;
-        crate::db::queries::update_idempotency_key_response(&self.pool, key, &response_json)
-            .await?;
+        crate::db::queries::update_idempotency_key_response(
+            &self.pool,
+            &self.db_key(key),
+            &response_json,
+        )
+        .await?;
         Ok(())
     }
 
@@ -376,7 +479,7 @@ impl IdempotencyService {
         key: &str,
         lock_token: Option<&str>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let lock_key = _lock_key(tenant_id, key);
+        let lock_key = self.lock_key(tenant_id, key);
 
         match self.client.get_multiplexed_async_connection().await {
             Ok(mut conn) => {
@@ -565,13 +668,23 @@ fn idempotency_trace_span(idempotency_key: &str, tenant_id: &str) -> tracing::Sp
     )
 }
 
+/// Headers carrying the idempotency key, in lookup order: the standard
+/// `Idempotency-Key` first, then the legacy `X-Idempotency-Key`.
+pub const IDEMPOTENCY_KEY_HEADERS: [&str; 2] = ["idempotency-key", "x-idempotency-key"];
+
+fn idempotency_key_header(request: &Request<Body>) -> Option<&axum::http::HeaderValue> {
+    IDEMPOTENCY_KEY_HEADERS
+        .iter()
+        .find_map(|name| request.headers().get(*name))
+}
+
 /// Middleware to handle idempotency for webhook requests
 pub async fn idempotency_middleware(
     State(service): State<IdempotencyService>,
     request: Request<Body>,
     next: Next<Body>,
 ) -> Response {
-    let idempotency_key = match request.headers().get("x-idempotency-key") {
+    let idempotency_key = match idempotency_key_header(&request) {
         Some(key) => match key.to_str() {
             Ok(k) => match validate_idempotency_key(k) {
                 Ok(validated) => validated,
@@ -599,11 +712,13 @@ pub async fn idempotency_middleware(
     };
 
     let tenant_id = extract_tenant_id(&request);
+    // Only the lookup runs in the span; the handler is a sibling of it under
+    // the request's root span rather than nested inside the check.
     let span = idempotency_trace_span(&idempotency_key, &tenant_id);
-    let _enter = span.enter();
 
     match service
         .check_idempotency(&tenant_id, &idempotency_key)
+        .instrument(span)
         .await
     {
         Ok(IdempotencyStatus::New { lock_token }) => {
@@ -729,8 +844,27 @@ pub async fn idempotency_middleware(
         }
         Err(e) => {
             service.errors.fetch_add(1, Ordering::Relaxed);
-            tracing::error!("Idempotency check failed: {}", e);
-            next.run(request).await
+            match service.failure_mode {
+                IdempotencyFailureMode::FailOpen => {
+                    tracing::error!(
+                        "Idempotency check failed, processing without deduplication: {}",
+                        e
+                    );
+                    next.run(request).await
+                }
+                IdempotencyFailureMode::FailClosed => {
+                    tracing::error!("Idempotency check failed, rejecting request: {}", e);
+                    (
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        [(axum::http::header::RETRY_AFTER, "5")],
+                        Json(serde_json::json!({
+                            "error": "Idempotency store unavailable",
+                            "retry_after": 5
+                        })),
+                    )
+                        .into_response()
+                }
+            }
         }
     }
 }
@@ -875,6 +1009,23 @@ mod tests {
         assert_eq!(tenant_id.as_deref(), Some("tenant-a"));
     }
 
+    #[test]
+    fn test_failure_mode_parses_and_defaults_to_fail_closed() {
+        assert_eq!(
+            IdempotencyFailureMode::default(),
+            IdempotencyFailureMode::FailClosed
+        );
+        assert_eq!(
+            "fail_open".parse::<IdempotencyFailureMode>(),
+            Ok(IdempotencyFailureMode::FailOpen)
+        );
+        assert_eq!(
+            " Closed ".parse::<IdempotencyFailureMode>(),
+            Ok(IdempotencyFailureMode::FailClosed)
+        );
+        assert!("sometimes".parse::<IdempotencyFailureMode>().is_err());
+    }
+
     #[test]
     fn test_validate_idempotency_key_success() {
         assert_eq!(validate_idempotency_key("abc123").unwrap(), "abc123");
@@ -885,6 +1036,22 @@ mod tests {
         assert_eq!(validate_idempotency_key("  abc123  ").unwrap(), "abc123");
     }
 
+    #[test]
+    fn test_idempotency_key_header_prefers_standard_name() {
+        let request = Request::builder()
+            .header("X-Idempotency-Key", "legacy")
+            .header("Idempotency-Key", "standard")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(idempotency_key_header(&request).unwrap(), "standard");
+
+        let request = Request::builder()
+            .header("X-Idempotency-Key", "legacy")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(idempotency_key_header(&request).unwrap(), "legacy");
+    }
+
     #[test]
     fn test_validate_idempotency_key_empty_or_whitespace() {
         assert!(validate_idempotency_key("").is_err());
@@ -914,4 +1081,23 @@ mod tests {
         let too_long_key = "a".repeat(IDEMPOTENCY_KEY_MAX_LENGTH + 1);
         assert!(validate_idempotency_key(&too_long_key).is_err());
     }
+
+    #[test]
+    fn test_namespace_scopes_cache_and_lock_keys() {
+        assert_eq!(_cache_key(None, "t1", "k"), "idempotency:t1:k");
+        assert_eq!(_lock_key(None, "t1", "k"), "idempotency:lock:t1:k");
+        assert_eq!(
+            _cache_key(Some("callback"), "t1", "k"),
+            "idempotency:callback:t1:k"
+        );
+        // Stale lock recovery derives the cache key by dropping `lock:`.
+        assert_eq!(
+            _lock_key(Some("callback"), "t1", "k").replacen("idempotency:lock:", "idempotency:", 1),
+            _cache_key(Some("callback"), "t1", "k")
+        );
+        assert_ne!(
+            _cache_key(Some("callback"), "t1", "k"),
+            _cache_key(Some("webhook"), "t1", "k")
+        );
+    }
 }
@@ -1,4 +1,9 @@
 pub mod auth;
+pub mod body_limit;
+pub mod callback_trace;
+pub mod compression;
+pub mod concurrency;
+pub mod cors;
 pub mod error_enrichment;
 pub mod idempotency;
 pub mod ip_filter;
@@ -7,5 +12,6 @@ pub mod quota;
 pub mod request_logger;
 pub mod signature_verification;
 pub mod tenant;
+pub mod timeout;
 pub mod validate;
 pub mod versioning;
@@ -3,10 +3,12 @@
 //! For every request the middleware:
 //! - Reads the `X-Request-Id` header if present, otherwise generates a new
 //!   UUID v4 as the correlation ID.
-//! - Stores the correlation ID in a task-local so that all `tracing` spans
-//!   emitted during the request automatically include it.
+//! - Stores the correlation ID in a task-local ([`current_request_id`]) so
+//!   downstream clients such as `HorizonClient` can forward it.
 //! - Logs method, path, status, duration, body size, and client IP at INFO
 //!   level in a structured format.
+//! - Runs the handler inside an `http.request` span so spans opened by
+//!   downstream calls (Horizon, reconciliation, DB) nest under it.
 //! - Attaches the correlation ID to the response as `X-Request-Id`.
 //! - Includes the correlation ID in error responses produced by [`AppError`].
 
@@ -18,12 +20,25 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use std::{net::SocketAddr, time::Instant};
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::error::RequestId;
 
 const _MAX_BODY_LOG_SIZE: usize = 1024; // 1 KB limit for body logging
 
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Correlation ID of the request currently being handled, if any.
+///
+/// Only set inside the handler future run by [`request_logger_middleware`];
+/// work spawned onto other tasks does not inherit it.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
 /// Axum middleware function.
 ///
 /// Mount with:
@@ -91,9 +106,9 @@ pub async fn request_logger_middleware(mut req: Request<Body>, next: Next<Body>)
                     correlation_id = %correlation_id,
                     method = %method,
                     path = %uri.path(),
-                    "Request body too large or failed to read"
+                    "Failed to read request body"
                 );
-                return (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response();
+                return (StatusCode::BAD_REQUEST, "Failed to read request body").into_response();
             }
         };
 
@@ -131,7 +146,18 @@ pub async fn request_logger_middleware(mut req: Request<Body>, next: Next<Body>)
     // -----------------------------------------------------------------------
     // 4. Run the inner handler
     // -----------------------------------------------------------------------
-    let mut response = next.run(req).await;
+    let request_span = tracing::info_span!(
+        "http.request",
+        http.method = %method,
+        http.target = %uri.path(),
+        correlation_id = %correlation_id,
+        http.status_code = tracing::field::Empty
+    );
+    let mut response = REQUEST_ID
+        .scope(correlation_id.clone(), next.run(req))
+        .instrument(request_span.clone())
+        .await;
+    request_span.record("http.status_code", response.status().as_u16());
 
     // -----------------------------------------------------------------------
     // 5. Log response
@@ -1,24 +1,37 @@
-use axum::{body::Body, http::Request, middleware::Next, response::Response};
+use crate::tenant::{resolve_request_tenant, TenantContext};
+use crate::AppState;
+use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
 use uuid::Uuid;
 
-/// Middleware that extracts tenant context from the request and stores it in extensions.
-/// The context is then available to handlers and can be used to establish database sessions.
+/// Middleware that resolves the tenant named by the request's `X-API-Key` or
+/// `X-Tenant-ID` header and stores its [`TenantContext`] in extensions.
 ///
-/// This middleware runs after auth and expects TenantContext to already be extracted
-/// by the router layer (via FromRequestParts).
-pub async fn tenant_context_middleware(req: Request<Body>, next: Next<Body>) -> Response {
-    // TenantContext is extracted by handlers via FromRequestParts, not here.
-    // This middleware is a placeholder for future cross-cutting concerns like:
-    // - Logging tenant_id with all requests
-    // - Enforcing tenant-specific rate limits
-    // - Auditing tenant operations
-    next.run(req).await
+/// Layers inside it (CORS) and the `TenantContext`/`OptionalTenant`
+/// extractors reuse the stored context instead of resolving it again. It never
+/// rejects a request: one naming an unknown or inactive tenant passes through
+/// without a context and is left to the extractors to refuse. Responses to
+/// resolved requests are counted per tenant via
+/// [`crate::metrics::record_tenant_request`].
+pub async fn tenant_context_middleware(
+    State(state): State<AppState>,
+    mut req: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let Some(tenant) = resolve_request_tenant(req.headers(), &state).await else {
+        return next.run(req).await;
+    };
+
+    let tenant_id = tenant.tenant_id;
+    req.extensions_mut().insert(tenant);
+    let response = next.run(req).await;
+    crate::metrics::record_tenant_request(tenant_id, response.status());
+    response
 }
 
 /// Extract tenant_id from request for use in database queries.
 /// Returns None if tenant context is not available (fail-closed).
-pub fn get_tenant_id_from_request(_req: &Request<Body>) -> Option<Uuid> {
-    // Tenant context would be set by upstream middleware
-    // For now, this is a utility for future use
-    None
+pub fn get_tenant_id_from_request(req: &Request<Body>) -> Option<Uuid> {
+    req.extensions()
+        .get::<TenantContext>()
+        .map(|tenant| tenant.tenant_id)
 }
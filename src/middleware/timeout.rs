@@ -0,0 +1,135 @@
+//! Upper bound on how long a request may take to produce a response.
+//!
+//! Applied by `create_app` to each route group with `AppState::request_timeout`
+//! (`REQUEST_TIMEOUT_SECS`), so a handler stuck on a slow dependency such as
+//! Horizon cannot hold its connection indefinitely. Long-running routes
+//! (exports) are registered outside the layer and opt out.
+//!
+//! A route that needs a different budget is registered after the group's
+//! layer, like the exports, and carries its own instance of the middleware:
+//!
+//! ```ignore
+//! .layer(request_timeout)
+//! .route(
+//!     "/slow",
+//!     post(handler).layer(axum_middleware::from_fn_with_state(
+//!         Duration::from_secs(120),
+//!         request_timeout_middleware,
+//!     )),
+//! )
+//! ```
+
+use crate::error::AppError;
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+
+/// Timeout when `REQUEST_TIMEOUT_SECS` is unset.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Responds with [`AppError::RequestTimeout`] (`504`, `ERR_TIMEOUT_001`) when
+/// the rest of the stack takes longer than `timeout`; the handler's future is
+/// dropped at that point. A zero timeout disables the limit.
+pub async fn request_timeout_middleware(
+    State(timeout): State<Duration>,
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    if timeout.is_zero() {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(
+                path = %path,
+                timeout_ms = timeout.as_millis() as u64,
+                "request_timed_out: no response within the request timeout"
+            );
+            AppError::RequestTimeout(timeout).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PROBLEM_JSON_CONTENT_TYPE;
+    use axum::{
+        http::{header, StatusCode},
+        middleware,
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    async fn slow() -> StatusCode {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        StatusCode::OK
+    }
+
+    fn app(timeout: Duration) -> Router {
+        Router::new()
+            .route("/slow", get(slow))
+            .layer(middleware::from_fn_with_state(
+                timeout,
+                request_timeout_middleware,
+            ))
+            .route(
+                "/slow/override",
+                get(slow).layer(middleware::from_fn_with_state(
+                    Duration::from_secs(5),
+                    request_timeout_middleware,
+                )),
+            )
+    }
+
+    async fn get_path(timeout: Duration, path: &str) -> Response {
+        app(timeout)
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn get_slow(timeout: Duration) -> StatusCode {
+        get_path(timeout, "/slow").await.status()
+    }
+
+    #[tokio::test]
+    async fn test_slow_handler_gets_504_after_timeout() {
+        let started = std::time::Instant::now();
+        let response = get_path(Duration::from_millis(50), "/slow").await;
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(50), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(200), "{elapsed:?}");
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "ERR_TIMEOUT_001");
+        assert_eq!(body["status"], 504);
+    }
+
+    #[tokio::test]
+    async fn test_route_override_replaces_group_timeout() {
+        let response = get_path(Duration::from_millis(50), "/slow/override").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_handler_within_timeout_or_disabled_completes() {
+        assert_eq!(get_slow(Duration::from_secs(5)).await, StatusCode::OK);
+        assert_eq!(get_slow(Duration::ZERO).await, StatusCode::OK);
+    }
+}
@@ -1,12 +1,15 @@
+use crate::middleware::versioning::ApiVersion;
+use crate::validation::SCHEMA_PAYLOAD_MAX_BYTES;
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    http::{header, HeaderValue, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
 use jsonschema::JSONSchema;
 use serde_json::{json, Value};
+use tracing::Instrument;
 
 /// Validation error response
 #[derive(Debug, serde::Serialize)]
@@ -22,43 +25,86 @@ struct ValidationDetail {
 }
 
 /// Validate request body against JSON schema
+///
+/// Bodies over [`SCHEMA_PAYLOAD_MAX_BYTES`] are rejected with `413` before
+/// they are parsed.
 pub async fn validate_with_schema(
     schema: &'static JSONSchema,
     request: Request<Body>,
     next: Next<Body>,
 ) -> Response {
+    match normalize_and_validate(schema, |_| false, request).await {
+        Ok(request) => next.run(request).await,
+        Err(response) => response,
+    }
+}
+
+/// Like [`validate_with_schema`], but first lets `normalize` canonicalize the
+/// parsed payload. When it reports a change, the normalized payload is
+/// validated and forwarded in place of the original body.
+///
+/// Returns the request to forward, or the error response to send instead.
+async fn normalize_and_validate(
+    schema: &'static JSONSchema,
+    normalize: fn(&mut Value) -> bool,
+    request: Request<Body>,
+) -> Result<Request<Body>, Response> {
     // Extract body
-    let (parts, body) = request.into_parts();
+    let (mut parts, body) = request.into_parts();
 
     let bytes = match hyper::body::to_bytes(body).await {
         Ok(b) => b,
         Err(e) => {
-            return (
+            return Err((
                 StatusCode::BAD_REQUEST,
                 Json(json!({
                     "error": "Failed to read request body",
                     "details": [{"field": "body", "message": e.to_string()}]
                 })),
             )
-                .into_response();
+                .into_response());
         }
     };
 
+    if bytes.len() > SCHEMA_PAYLOAD_MAX_BYTES {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({
+                "error": "Payload too large",
+                "details": [{
+                    "field": "body",
+                    "message": format!("body exceeds {SCHEMA_PAYLOAD_MAX_BYTES} bytes")
+                }]
+            })),
+        )
+            .into_response());
+    }
+
     // Parse JSON
-    let payload: Value = match serde_json::from_slice(&bytes) {
+    let mut payload: Value = match serde_json::from_slice(&bytes) {
         Ok(v) => v,
         Err(e) => {
-            return (
+            return Err((
                 StatusCode::BAD_REQUEST,
                 Json(json!({
                     "error": "Invalid JSON",
                     "details": [{"field": "body", "message": e.to_string()}]
                 })),
             )
-                .into_response();
+                .into_response());
         }
     };
 
+    let bytes = if normalize(&mut payload) {
+        let normalized = hyper::body::Bytes::from(payload.to_string());
+        parts
+            .headers
+            .insert(header::CONTENT_LENGTH, HeaderValue::from(normalized.len()));
+        normalized
+    } else {
+        bytes
+    };
+
     // Validate against schema
     if let Err(errors) = schema.validate(&payload) {
         let details: Vec<ValidationDetail> = errors
@@ -68,29 +114,58 @@ pub async fn validate_with_schema(
             })
             .collect();
 
-        return (
+        return Err((
             StatusCode::BAD_REQUEST,
             Json(ValidationErrorResponse {
                 error: "Payload validation failed".to_string(),
                 details,
             }),
         )
-            .into_response();
+            .into_response());
     }
 
     // Reconstruct request with original body (convert Bytes to Vec<u8>)
-    let request = Request::from_parts(parts, Body::from(bytes.to_vec()));
-    next.run(request).await
+    Ok(Request::from_parts(parts, Body::from(bytes.to_vec())))
+}
+
+/// Trims and uppercases a string `asset_code`, so `usd` is accepted and
+/// stored as `USD` rather than fragmenting settlements. A code that is empty
+/// afterwards still fails the schema.
+fn normalize_asset_code(payload: &mut Value) -> bool {
+    match payload.get_mut("asset_code") {
+        Some(Value::String(code)) => {
+            let normalized = code.trim().to_ascii_uppercase();
+            let changed = normalized != *code;
+            *code = normalized;
+            changed
+        }
+        _ => false,
+    }
 }
 
 /// Middleware factory for callback endpoint validation
+///
+/// Uses the schema for the [`ApiVersion`] resolved by the versioning
+/// middleware, falling back to the strict v1 schema when none was set.
+/// `asset_code` is normalized to its canonical case before validation.
+/// Validation runs in a `webhook.validate` span; the rest of the stack does not.
 pub async fn validate_callback(request: Request<Body>, next: Next<Body>) -> Response {
-    validate_with_schema(
-        &crate::validation::schemas::SCHEMAS.callback_v1,
+    let version = request
+        .extensions()
+        .get::<ApiVersion>()
+        .copied()
+        .unwrap_or(ApiVersion::V1);
+    let validated = normalize_and_validate(
+        crate::validation::schemas::SCHEMAS.callback(version),
+        normalize_asset_code,
         request,
-        next,
     )
-    .await
+    .instrument(tracing::info_span!("webhook.validate", api.version = ?version))
+    .await;
+    match validated {
+        Ok(request) => next.run(request).await,
+        Err(response) => response,
+    }
 }
 
 /// Middleware factory for webhook endpoint validation
@@ -141,6 +216,69 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_validate_callback_normalizes_asset_code() {
+        let app = Router::new()
+            .route("/callback", post(test_handler))
+            .layer(axum::middleware::from_fn(validate_callback));
+
+        for (asset_code, expected) in [(" usd ", StatusCode::OK), ("   ", StatusCode::BAD_REQUEST)]
+        {
+            let payload = json!({
+                "stellar_account": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                "amount": "100.50",
+                "asset_code": asset_code
+            });
+            let request = Request::builder()
+                .method("POST")
+                .uri("/callback")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                .unwrap();
+
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), expected, "{asset_code:?}");
+            if expected == StatusCode::OK {
+                let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+                let echoed: Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(echoed["asset_code"], "USD");
+            }
+        }
+    }
+
+    async fn post_callback(payload: &Value) -> StatusCode {
+        let app = Router::new()
+            .route("/callback", post(test_handler))
+            .layer(axum::middleware::from_fn(validate_callback));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/callback")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(payload).unwrap()))
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_validate_callback_limits_metadata() {
+        let mut payload = json!({
+            "stellar_account": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "amount": "100.50",
+            "asset_code": "USD",
+            "metadata": {"reference_id": "INV-1042", "nested": {"tier": 2}}
+        });
+        assert_eq!(post_callback(&payload).await, StatusCode::OK);
+
+        let many: serde_json::Map<String, Value> = (0..=crate::validation::METADATA_MAX_PROPERTIES)
+            .map(|i| (format!("k{i}"), json!(i)))
+            .collect();
+        payload["metadata"] = many.into();
+        assert_eq!(post_callback(&payload).await, StatusCode::BAD_REQUEST);
+
+        payload["metadata"] = json!({"blob": "x".repeat(SCHEMA_PAYLOAD_MAX_BYTES)});
+        assert_eq!(post_callback(&payload).await, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     #[tokio::test]
     async fn test_validate_callback_missing_required_field() {
         let app = Router::new()
@@ -227,6 +365,47 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    async fn post_versioned_callback(version: ApiVersion, payload: &Value) -> StatusCode {
+        let app = Router::new()
+            .route("/callback", post(test_handler))
+            .layer(axum::middleware::from_fn(validate_callback))
+            .layer(axum::middleware::from_fn(
+                move |mut req: Request<Body>, next: Next<Body>| async move {
+                    req.extensions_mut().insert(version);
+                    next.run(req).await
+                },
+            ));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/callback")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(payload).unwrap()))
+            .unwrap();
+
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_validate_callback_selects_schema_by_api_version() {
+        let payload = json!({
+            "stellar_account": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "amount": "100.50",
+            "asset_code": "USD",
+            "idempotency_key": "idem-123",
+            "tenant_ref": "acme"
+        });
+
+        assert_eq!(
+            post_versioned_callback(ApiVersion::V2, &payload).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            post_versioned_callback(ApiVersion::V1, &payload).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
     #[tokio::test]
     async fn test_validate_webhook_valid_payload() {
         let app = Router::new()
@@ -5,6 +5,25 @@ use axum::{
 };
 use std::str::FromStr;
 
+/// API version resolved from the route prefix a request came in on.
+///
+/// The version middlewares store it in the request extensions so inner
+/// layers (e.g. payload validation) can pick version-specific behaviour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+}
+
 pub async fn inject_deprecation_headers<B>(req: Request<B>, next: Next<B>) -> AxumResponse {
     let mut response = next.run(req).await;
 
@@ -40,8 +59,9 @@ pub async fn inject_api_version_header<B>(
 }
 
 /// Middleware factory for V1 routes — adds `API-Version: v1` and deprecation headers.
-pub async fn v1_version_middleware<B>(req: Request<B>, next: Next<B>) -> AxumResponse {
-    let mut response = inject_api_version_header("v1", req, next).await;
+pub async fn v1_version_middleware<B>(mut req: Request<B>, next: Next<B>) -> AxumResponse {
+    req.extensions_mut().insert(ApiVersion::V1);
+    let mut response = inject_api_version_header(ApiVersion::V1.as_str(), req, next).await;
     response.headers_mut().insert(
         HeaderName::from_str("Deprecation").unwrap(),
         HeaderValue::from_static("true"),
@@ -54,6 +74,7 @@ pub async fn v1_version_middleware<B>(req: Request<B>, next: Next<B>) -> AxumRes
 }
 
 /// Middleware factory for V2 routes — adds `API-Version: v2`.
-pub async fn v2_version_middleware<B>(req: Request<B>, next: Next<B>) -> AxumResponse {
-    inject_api_version_header("v2", req, next).await
+pub async fn v2_version_middleware<B>(mut req: Request<B>, next: Next<B>) -> AxumResponse {
+    req.extensions_mut().insert(ApiVersion::V2);
+    inject_api_version_header(ApiVersion::V2.as_str(), req, next).await
 }
@@ -1,7 +1,28 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
+/// Subsystems that must initialize before the service reports ready.
+pub mod subsystem {
+    pub const MIGRATIONS: &str = "migrations";
+    pub const TENANT_CACHE: &str = "tenant_cache";
+    pub const DATABASE: &str = "database";
+
+    /// Every subsystem tracked from startup, in reporting order.
+    pub const ALL: [&str; 3] = [MIGRATIONS, TENANT_CACHE, DATABASE];
+
+    pub const REDIS: &str = "redis";
+    pub const HORIZON: &str = "horizon";
+
+    /// Dependencies that only block readiness when configured as required
+    /// (`READINESS_REQUIRED_DEPENDENCIES`).
+    pub const OPTIONAL: [&str; 2] = [REDIS, HORIZON];
+}
+
+/// Reason reported for a subsystem nobody has marked yet.
+const NOT_INITIALIZED: &str = "not initialized";
+
 /// Readiness state for the application.
 /// Used for Kubernetes readiness probes and connection draining.
 #[derive(Clone)]
@@ -13,32 +34,68 @@ pub struct ReadinessState {
     drain_timeout_secs: u64,
     /// Flag indicating if drain has started
     is_draining: Arc<AtomicBool>,
+    /// Subsystems that have not initialized or are unavailable, with why.
+    pending: Arc<RwLock<BTreeMap<&'static str, String>>>,
+    /// Optional dependencies configured as required for readiness.
+    required: Arc<BTreeSet<&'static str>>,
 }
 
 impl ReadinessState {
     /// Create a new readiness state with default drain timeout (30s)
     /// Initially starts as NOT READY until initialization is complete
     pub fn new() -> Self {
-        Self {
-            is_ready: Arc::new(AtomicBool::new(false)),
-            drain_timeout_secs: 30,
-            is_draining: Arc::new(AtomicBool::new(false)),
-        }
+        Self::with_drain_timeout(30)
     }
 
     /// Create a new readiness state with custom drain timeout
     /// Initially starts as NOT READY until initialization is complete
     pub fn with_drain_timeout(drain_timeout_secs: u64) -> Self {
+        let pending = subsystem::ALL
+            .into_iter()
+            .map(|name| (name, NOT_INITIALIZED.to_string()))
+            .collect();
         Self {
             is_ready: Arc::new(AtomicBool::new(false)),
             drain_timeout_secs,
             is_draining: Arc::new(AtomicBool::new(false)),
+            pending: Arc::new(RwLock::new(pending)),
+            required: Arc::default(),
         }
     }
 
-    /// Check if the application is ready to accept traffic
+    /// Makes each of `dependencies` (from [`subsystem::OPTIONAL`]) block
+    /// readiness until it is marked initialized. The database, migrations and
+    /// tenant cache always do.
+    pub fn with_required_dependencies(
+        mut self,
+        dependencies: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        let required: BTreeSet<_> = dependencies.into_iter().collect();
+        {
+            let mut pending = self.pending.write().unwrap();
+            for name in &required {
+                pending.insert(name, NOT_INITIALIZED.to_string());
+            }
+        }
+        self.required = Arc::new(required);
+        self
+    }
+
+    /// Whether `subsystem` being unavailable makes the service not ready.
+    pub fn is_required(&self, subsystem: &str) -> bool {
+        subsystem::ALL.contains(&subsystem) || self.required.contains(subsystem)
+    }
+
+    /// Check if the application is ready to accept traffic: initialization
+    /// finished, not draining, and no required subsystem is unavailable.
     pub fn is_ready(&self) -> bool {
         self.is_ready.load(Ordering::SeqCst)
+            && !self
+                .pending
+                .read()
+                .unwrap()
+                .keys()
+                .any(|name| self.is_required(name))
     }
 
     /// Check if the application is draining (stopping accepting new connections)
@@ -51,12 +108,50 @@ impl ReadinessState {
         Duration::from_secs(self.drain_timeout_secs)
     }
 
-    /// Mark the application as ready to accept traffic
+    /// Mark the application as ready to accept traffic.
+    /// Clears any subsystem still listed as pending.
     pub fn set_ready(&self) {
+        self.pending.write().unwrap().clear();
         self.is_ready.store(true, Ordering::SeqCst);
         self.is_draining.store(false, Ordering::SeqCst);
     }
 
+    /// Record that `subsystem` finished initializing.
+    pub fn mark_initialized(&self, subsystem: &'static str) {
+        self.pending.write().unwrap().remove(subsystem);
+    }
+
+    /// Record that `subsystem` is blocking readiness because of `reason`.
+    pub fn mark_unavailable(&self, subsystem: &'static str, reason: impl Into<String>) {
+        self.pending
+            .write()
+            .unwrap()
+            .insert(subsystem, reason.into());
+    }
+
+    /// Why the service is not ready, for the /ready payload; empty when ready.
+    ///
+    /// Draining is reported first, followed by one `"<subsystem>: <reason>"`
+    /// entry per required subsystem that has not initialized. Optional
+    /// dependencies that are down are not listed.
+    pub fn reasons(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.is_draining() {
+            reasons.push("draining: graceful shutdown in progress".to_string());
+        }
+        if !self.is_ready() {
+            reasons.extend(
+                self.pending
+                    .read()
+                    .unwrap()
+                    .iter()
+                    .filter(|(name, _)| self.is_required(name))
+                    .map(|(name, reason)| format!("{name}: {reason}")),
+            );
+        }
+        reasons
+    }
+
     /// Mark the application as not ready (draining)
     /// This stops accepting new connections but allows in-flight requests to complete
     pub fn set_not_ready(&self) {
@@ -104,44 +199,71 @@ impl ReadinessState {
         tracing::info!("✓ Database pool warm-up already completed during pool creation");
 
         // Check 2: Verify Redis connection
-        match self.check_redis(redis_url).await {
-            Ok(_) => {
-                tracing::info!("✓ Redis connection verified");
-            }
-            Err(e) => {
-                tracing::warn!("⚠ Redis check failed (non-critical): {}", e);
-                // Continue - Redis is non-critical
-            }
-        }
+        let redis = self.check_redis(redis_url).await;
+        self.record_dependency(subsystem::REDIS, "Redis connection verified", redis);
 
         // Check 3: Verify Horizon connectivity
-        match self.check_horizon(horizon_url).await {
-            Ok(_) => {
-                tracing::info!("✓ Horizon connectivity verified");
-            }
-            Err(e) => {
-                tracing::warn!("⚠ Horizon check failed (non-critical): {}", e);
-                // Continue - Horizon is non-critical
-            }
-        }
+        let horizon = self.check_horizon(horizon_url).await;
+        self.record_dependency(subsystem::HORIZON, "Horizon connectivity verified", horizon);
 
         // Check 4: Verify database connectivity
         match sqlx::query("SELECT 1").execute(pool).await {
             Ok(_) => {
                 tracing::info!("✓ Database connectivity verified");
+                self.mark_initialized(subsystem::DATABASE);
             }
             Err(e) => {
                 let err = InitializationError::DatabaseCheck(e.to_string());
                 tracing::error!("✗ Database check failed (critical): {}", err);
+                self.mark_unavailable(subsystem::DATABASE, format!("unreachable: {e}"));
                 return Err(err);
             }
         }
 
+        let failed: Vec<String> = self
+            .pending
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| subsystem::OPTIONAL.contains(name) && self.is_required(name))
+            .map(|(name, reason)| format!("{name}: {reason}"))
+            .collect();
+        if !failed.is_empty() {
+            // Everything else is up: readiness now follows the failed
+            // dependencies, and flips once they are marked initialized.
+            self.is_ready.store(true, Ordering::SeqCst);
+            return Err(InitializationError::RequiredDependency(failed.join("; ")));
+        }
+
         tracing::info!("All initialization checks passed - marking service as ready");
         self.set_ready();
         Ok(())
     }
 
+    /// Marks an optional dependency initialized or unavailable after its
+    /// startup check, logging at error level only when it is required.
+    fn record_dependency(
+        &self,
+        subsystem: &'static str,
+        verified: &str,
+        result: Result<(), String>,
+    ) {
+        match result {
+            Ok(()) => {
+                tracing::info!("✓ {}", verified);
+                self.mark_initialized(subsystem);
+            }
+            Err(e) if self.is_required(subsystem) => {
+                tracing::error!("✗ {} check failed (required): {}", subsystem, e);
+                self.mark_unavailable(subsystem, e);
+            }
+            Err(e) => {
+                tracing::warn!("⚠ {} check failed (non-critical): {}", subsystem, e);
+                self.mark_unavailable(subsystem, e);
+            }
+        }
+    }
+
     /// Check Redis connectivity by sending PING
     async fn check_redis(&self, redis_url: &str) -> Result<(), String> {
         match redis::Client::open(redis_url) {
@@ -180,12 +302,17 @@ impl ReadinessState {
 #[derive(Debug)]
 pub enum InitializationError {
     DatabaseCheck(String),
+    /// A dependency configured as required for readiness is unavailable.
+    RequiredDependency(String),
 }
 
 impl std::fmt::Display for InitializationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             InitializationError::DatabaseCheck(msg) => write!(f, "Database check failed: {msg}"),
+            InitializationError::RequiredDependency(msg) => {
+                write!(f, "Required dependency unavailable: {msg}")
+            }
         }
     }
 }
@@ -273,6 +400,64 @@ mod tests {
         assert_eq!(state.drain_timeout().as_secs(), 60);
     }
 
+    #[test]
+    fn test_reasons_before_init_list_uninitialized_subsystems() {
+        let state = ReadinessState::new();
+        state.mark_initialized(subsystem::MIGRATIONS);
+        state.mark_unavailable(subsystem::DATABASE, "unreachable: connection refused");
+
+        assert_eq!(
+            state.reasons(),
+            vec![
+                "database: unreachable: connection refused".to_string(),
+                "tenant_cache: not initialized".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reasons_while_draining() {
+        let state = ReadinessState::new();
+        state.set_ready();
+        assert!(state.reasons().is_empty());
+
+        state.start_drain();
+        let reasons = state.reasons();
+        assert_eq!(reasons.len(), 1);
+        assert!(reasons[0].starts_with("draining"));
+    }
+
+    #[test]
+    fn test_optional_dependency_only_blocks_when_required() {
+        let optional = ReadinessState::new();
+        let required = ReadinessState::new().with_required_dependencies([subsystem::HORIZON]);
+        for state in [&optional, &required] {
+            state.set_ready();
+            state.mark_unavailable(subsystem::HORIZON, "connection refused");
+        }
+
+        assert!(optional.is_ready());
+        assert!(optional.reasons().is_empty());
+        assert!(!required.is_ready());
+        assert_eq!(required.reasons(), vec!["horizon: connection refused"]);
+
+        required.mark_initialized(subsystem::HORIZON);
+        assert!(required.is_ready());
+    }
+
+    #[test]
+    fn test_required_dependency_blocks_until_initialized() {
+        let state = ReadinessState::new().with_required_dependencies([subsystem::REDIS]);
+        for name in subsystem::ALL {
+            state.mark_initialized(name);
+        }
+        assert!(state
+            .reasons()
+            .contains(&"redis: not initialized".to_string()));
+        assert!(!state.is_required(subsystem::HORIZON));
+        assert!(state.is_required(subsystem::DATABASE));
+    }
+
     #[test]
     fn test_default_drain_timeout() {
         let state = ReadinessState::new();
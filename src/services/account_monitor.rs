@@ -1,4 +1,4 @@
-use crate::stellar::client::HorizonClient;
+use crate::stellar::client::{HorizonClient, MAX_PAGE_LIMIT};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::time::Duration;
@@ -18,30 +18,6 @@ pub struct Payment {
     pub memo_type: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct PaymentRecord {
-    id: String,
-    from: String,
-    to: String,
-    amount: String,
-    asset_code: String,
-    #[serde(default)]
-    memo: Option<String>,
-    #[serde(default)]
-    memo_type: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct PaymentsResponse {
-    #[serde(rename = "_embedded")]
-    embedded: Embedded,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct Embedded {
-    records: Vec<PaymentRecord>,
-}
-
 pub struct AccountMonitor {
     horizon_client: HorizonClient,
     pool: PgPool,
@@ -114,26 +90,12 @@ impl AccountMonitor {
         account: &str,
         cursor: Option<&str>,
     ) -> anyhow::Result<Vec<Payment>> {
-        let mut url = format!(
-            "{}/accounts/{}/payments?order=asc&limit=200",
-            self.horizon_client.base_url.trim_end_matches('/'),
-            account
-        );
-
-        if let Some(c) = cursor {
-            url.push_str(&format!("&cursor={c}"));
-        }
-
-        let response = self.horizon_client.client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Horizon API error: {}", response.status()));
-        }
-
-        let payments_response: PaymentsResponse = response.json().await?;
+        let page = self
+            .horizon_client
+            .get_account_payments(account, cursor, MAX_PAGE_LIMIT)
+            .await?;
 
-        Ok(payments_response
-            .embedded
+        Ok(page
             .records
             .into_iter()
             .map(|r| Payment {
@@ -218,13 +180,14 @@ impl AccountMonitor {
                 .map_err(|e| anyhow::anyhow!("{e}"))?;
 
             // Update transaction to completed and record horizon_payment_id
-            sqlx::query(
-                "UPDATE transactions SET status = 'completed', horizon_payment_id = $1, updated_at = NOW() WHERE id = $2"
+            let asset_code: String = sqlx::query_scalar(
+                "UPDATE transactions SET status = 'completed', horizon_payment_id = $1, updated_at = NOW() WHERE id = $2 RETURNING asset_code"
             )
             .bind(&payment.id)
             .bind(tx_id)
-            .execute(&self.pool)
+            .fetch_one(&self.pool)
             .await?;
+            crate::db::queries::invalidate_caches_for_asset(&asset_code).await;
 
             info!("Completed transaction {} via payment monitoring", tx_id);
         } else {
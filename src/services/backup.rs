@@ -12,6 +12,8 @@ pub enum BackupType {
     Hourly,
     Daily,
     Monthly,
+    /// Single-table dump of an archived transactions partition.
+    Partition,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,19 +43,34 @@ impl BackupService {
     }
 
     pub async fn create_backup(&self, backup_type: BackupType) -> Result<BackupMetadata> {
+        self.create_backup_inner(backup_type, None).await
+    }
+
+    /// Dump a single table (e.g. `archive.transactions_y2024m01`) through the
+    /// same compress/encrypt/checksum pipeline as a full backup.
+    pub async fn backup_table(&self, table: &str) -> Result<BackupMetadata> {
+        self.create_backup_inner(BackupType::Partition, Some(table))
+            .await
+    }
+
+    async fn create_backup_inner(
+        &self,
+        backup_type: BackupType,
+        table: Option<&str>,
+    ) -> Result<BackupMetadata> {
         // Ensure backup directory exists
         fs::create_dir_all(&self.backup_dir)
             .await
             .context("Failed to create backup directory")?;
 
         let timestamp = Utc::now();
-        let filename = self.generate_filename(backup_type, timestamp);
+        let filename = self.generate_filename(backup_type, timestamp, table);
         let backup_path = self.backup_dir.join(&filename);
         let temp_path = self.backup_dir.join(format!("{filename}.tmp"));
 
         // Run pg_dump
         tracing::info!("Running pg_dump for {:?} backup", backup_type);
-        self.run_pg_dump(&temp_path).await?;
+        self.run_pg_dump(&temp_path, table).await?;
 
         // Compress the backup
         tracing::info!("Compressing backup");
@@ -181,6 +198,8 @@ impl BackupService {
                 BackupType::Hourly => hourly_backups.push(backup),
                 BackupType::Daily => daily_backups.push(backup),
                 BackupType::Monthly => monthly_backups.push(backup),
+                // Partition dumps are the only copy of dropped partitions; never prune them.
+                BackupType::Partition => {}
             }
         }
 
@@ -223,15 +242,18 @@ impl BackupService {
         Ok(())
     }
 
-    async fn run_pg_dump(&self, output_path: &Path) -> Result<()> {
-        let output = Command::new("pg_dump")
+    async fn run_pg_dump(&self, output_path: &Path, table: Option<&str>) -> Result<()> {
+        let mut command = Command::new("pg_dump");
+        command
             .arg(&self.database_url)
             .arg("--format=plain")
             .arg("--no-owner")
             .arg("--no-acl")
-            .arg(format!("--file={}", output_path.display()))
-            .output()
-            .context("Failed to execute pg_dump")?;
+            .arg(format!("--file={}", output_path.display()));
+        if let Some(table) = table {
+            command.arg(format!("--table={table}"));
+        }
+        let output = command.output().context("Failed to execute pg_dump")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -412,11 +434,20 @@ impl BackupService {
         Ok(())
     }
 
-    fn generate_filename(&self, backup_type: BackupType, timestamp: DateTime<Utc>) -> String {
+    fn generate_filename(
+        &self,
+        backup_type: BackupType,
+        timestamp: DateTime<Utc>,
+        table: Option<&str>,
+    ) -> String {
         let type_str = match backup_type {
-            BackupType::Hourly => "hourly",
-            BackupType::Daily => "daily",
-            BackupType::Monthly => "monthly",
+            BackupType::Hourly => "hourly".to_string(),
+            BackupType::Daily => "daily".to_string(),
+            BackupType::Monthly => "monthly".to_string(),
+            BackupType::Partition => match table {
+                Some(table) => format!("partition_{}", table.replace('.', "_")),
+                None => "partition".to_string(),
+            },
         };
 
         let date_str = timestamp.format("%Y%m%d_%H%M%S");
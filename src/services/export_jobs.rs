@@ -0,0 +1,269 @@
+//! Background transaction exports.
+//!
+//! `GET /export` serves the whole result set inline and times out on large
+//! exports. An export job instead records the request in `export_jobs`; the
+//! [`ExportJobWorker`] (run by the [`JobScheduler`](crate::services::JobScheduler))
+//! claims pending jobs, streams the rows to a file under the configured output
+//! directory and drives each row through `pending -> running -> completed|failed`.
+//! Clients poll `GET /export/jobs/:id` and download the artifact once completed.
+//!
+//! A claimed job is leased via `claimed_until` and the lease is renewed while
+//! the artifact is written. A job left `running` by a worker that died is
+//! claimed again once its lease expires.
+
+use crate::handlers::export::{create_csv_stream, create_json_stream, ExportQuery};
+use crate::handlers::search::TransactionFilterParams;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_FAILED: &str = "failed";
+
+/// Output directory used when `EXPORT_JOBS_DIR` is not set.
+pub const DEFAULT_EXPORT_JOBS_DIR: &str = "/tmp/synapse_exports";
+
+/// How long a claimed job is reserved for its worker without a renewal.
+const CLAIM_LEASE_SECS: f64 = 300.0;
+
+/// A persisted export request.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize)]
+pub struct ExportJob {
+    pub id: Uuid,
+    pub format: String,
+    pub filters: serde_json::Value,
+    pub status: String,
+    /// Tenant whose transactions are exported; `None` exports every tenant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<Uuid>,
+    #[serde(skip)]
+    pub artifact_path: Option<String>,
+    pub row_count: Option<i64>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ExportJob {
+    /// Content type of the generated artifact.
+    pub fn content_type(&self) -> &'static str {
+        if self.format == "json" {
+            "application/json"
+        } else {
+            "text/csv"
+        }
+    }
+
+    /// Download filename offered to clients.
+    pub fn filename(&self) -> String {
+        format!("transactions_{}.{}", self.id, self.format)
+    }
+}
+
+const EXPORT_JOB_COLUMNS: &str =
+    "id, format, filters, status, tenant_id, artifact_path, row_count, \
+     error_message, created_at, started_at, completed_at";
+
+pub struct ExportJobService {
+    pool: PgPool,
+    output_dir: PathBuf,
+}
+
+impl ExportJobService {
+    pub fn new(pool: PgPool, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            pool,
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// Records a pending export for `query`, limited to `tenant_id`'s
+    /// transactions when set. The query must already be validated.
+    /// JSON artifacts are always written one record per line, so `ndjson`
+    /// is recorded as `json`.
+    pub async fn enqueue(
+        &self,
+        query: &ExportQuery,
+        tenant_id: Option<Uuid>,
+    ) -> Result<ExportJob, sqlx::Error> {
+        let filters = serde_json::to_value(&query.filters).unwrap_or_default();
+
+        sqlx::query_as(&format!(
+            "INSERT INTO export_jobs (format, filters, tenant_id) VALUES ($1, $2, $3) \
+             RETURNING {EXPORT_JOB_COLUMNS}"
+        ))
+        .bind(match query.format.to_lowercase().as_str() {
+            "ndjson" => "json".to_string(),
+            format => format.to_string(),
+        })
+        .bind(filters)
+        .bind(tenant_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn get_job(&self, id: Uuid) -> Result<Option<ExportJob>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {EXPORT_JOB_COLUMNS} FROM export_jobs WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Atomically moves the oldest pending job, or a running job whose lease
+    /// has expired, to `running` and leases it. `SKIP LOCKED` lets several
+    /// workers drain the queue without claiming the same job.
+    pub async fn claim_next(&self) -> Result<Option<ExportJob>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "UPDATE export_jobs SET status = '{STATUS_RUNNING}', started_at = NOW(), \
+                 claimed_until = NOW() + make_interval(secs => $1) \
+             WHERE id = ( \
+                 SELECT id FROM export_jobs \
+                 WHERE status = '{STATUS_PENDING}' \
+                    OR (status = '{STATUS_RUNNING}' \
+                        AND (claimed_until IS NULL OR claimed_until <= NOW())) \
+                 ORDER BY created_at LIMIT 1 FOR UPDATE SKIP LOCKED \
+             ) \
+             RETURNING {EXPORT_JOB_COLUMNS}"
+        ))
+        .bind(CLAIM_LEASE_SECS)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Extends the lease on a job this worker is still processing.
+    async fn renew_lease(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE export_jobs SET claimed_until = NOW() + make_interval(secs => $1) \
+             WHERE id = $2",
+        )
+        .bind(CLAIM_LEASE_SECS)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Generates the artifact for a claimed job and records the outcome.
+    pub async fn process(&self, job: &ExportJob) -> Result<(), sqlx::Error> {
+        let path = self.output_dir.join(job.filename());
+        match self.write_artifact(job, &path).await {
+            Ok(rows) => {
+                sqlx::query(
+                    "UPDATE export_jobs SET status = $1, artifact_path = $2, row_count = $3, \
+                     completed_at = NOW(), claimed_until = NULL WHERE id = $4",
+                )
+                .bind(STATUS_COMPLETED)
+                .bind(path.to_string_lossy().as_ref())
+                .bind(rows)
+                .bind(job.id)
+                .execute(&self.pool)
+                .await?;
+                tracing::info!(job_id = %job.id, rows, path = %path.display(), "Export job completed");
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&path).await;
+                sqlx::query(
+                    "UPDATE export_jobs SET status = $1, error_message = $2, \
+                     completed_at = NOW(), claimed_until = NULL WHERE id = $3",
+                )
+                .bind(STATUS_FAILED)
+                .bind(e.to_string())
+                .bind(job.id)
+                .execute(&self.pool)
+                .await?;
+                tracing::error!(job_id = %job.id, error = %e, "Export job failed");
+            }
+        }
+        Ok(())
+    }
+
+    /// Claims and processes pending jobs until the queue is empty. Returns the
+    /// number of jobs processed.
+    pub async fn run_pending(&self) -> Result<usize, sqlx::Error> {
+        let mut processed = 0;
+        while let Some(job) = self.claim_next().await? {
+            self.process(&job).await?;
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    async fn write_artifact(&self, job: &ExportJob, path: &Path) -> anyhow::Result<i64> {
+        tokio::fs::create_dir_all(&self.output_dir).await?;
+        let mut file = tokio::fs::File::create(path).await?;
+
+        // Jobs recorded before a filter existed simply lack its key.
+        let filters = serde_json::from_value::<TransactionFilterParams>(job.filters.clone())?
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid export filters: {e}"))?;
+        let pool = Arc::new(self.pool.clone());
+        let (mut stream, header_chunks) = if job.format == "json" {
+            (create_json_stream(pool, filters, job.tenant_id), 0)
+        } else {
+            (create_csv_stream(pool, filters, job.tenant_id), 1)
+        };
+
+        // Both streams yield one record per chunk; CSV chunks are already
+        // newline-terminated, JSON records are not.
+        let renew_every = Duration::from_secs_f64(CLAIM_LEASE_SECS / 2.0);
+        let mut renewed_at = Instant::now();
+        let mut chunks: i64 = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            if renewed_at.elapsed() >= renew_every {
+                self.renew_lease(job.id).await?;
+                renewed_at = Instant::now();
+            }
+            file.write_all(chunk.as_bytes()).await?;
+            if !chunk.ends_with('\n') {
+                file.write_all(b"\n").await?;
+            }
+            chunks += 1;
+        }
+        file.flush().await?;
+
+        Ok((chunks - header_chunks).max(0))
+    }
+}
+
+/// Scheduled worker that drains pending export jobs every 10 seconds.
+pub struct ExportJobWorker {
+    service: ExportJobService,
+}
+
+impl ExportJobWorker {
+    pub fn new(service: ExportJobService) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl crate::services::scheduler::Job for ExportJobWorker {
+    fn name(&self) -> &str {
+        "export_jobs"
+    }
+
+    fn schedule(&self) -> &str {
+        "*/10 * * * * *"
+    }
+
+    async fn execute(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let processed = self.service.run_pending().await?;
+        if processed > 0 {
+            tracing::info!(processed, "Processed export jobs");
+        }
+        Ok(())
+    }
+}
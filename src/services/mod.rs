@@ -2,8 +2,10 @@ pub mod account_monitor;
 pub mod backup;
 pub mod circuit_breaker;
 pub mod compliance;
+pub mod export_jobs;
 pub mod feature_flags;
 pub mod lock_manager;
+pub mod payment_matching;
 pub mod pitr;
 pub mod processor;
 pub mod query_cache;
@@ -11,6 +13,7 @@ pub mod reconciliation;
 pub mod resource_limits;
 pub mod scheduler;
 pub mod settlement;
+pub mod settlement_job;
 pub mod transaction_processor;
 pub mod transaction_processor_job;
 pub mod webhook_dispatcher;
@@ -23,8 +26,9 @@ pub use lock_manager::{FairLockConfig, FairLockManager};
 pub use query_cache::{CacheConfig, QueryCache};
 pub use reconciliation::ReconciliationService;
 pub use resource_limits::{ResourceLimiter, TaskLimits};
-pub use scheduler::{AuditLogRetentionJob, Job, JobScheduler, JobStatus};
-pub use settlement::SettlementService;
+pub use scheduler::{AuditLogRetentionJob, Job, JobOutcome, JobRun, JobScheduler, JobStatus};
+pub use settlement::{SettlementSchedule, SettlementService};
+pub use settlement_job::SettlementJob;
 pub use transaction_processor::TransactionProcessor;
 pub use transaction_processor_job::TransactionProcessorJob;
 pub use webhook_dispatcher::WebhookDispatcher;
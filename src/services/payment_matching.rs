@@ -0,0 +1,203 @@
+//! Optional ingest step that matches a callback to its on-chain payment by
+//! account + memo, so a transaction carries the chain's view of the payment
+//! before reconciliation ever runs.
+
+use crate::db::models::Transaction;
+use crate::stellar::client::{HorizonClient, PaymentRecord};
+use serde::{Deserialize, Serialize};
+use sqlx::types::BigDecimal;
+use std::str::FromStr;
+
+/// Feature flag gating [`annotate_with_chain_payment`] on `POST /callback`.
+pub const PAYMENT_MATCHING_FLAG: &str = "callback_payment_matching";
+
+/// Metadata key the matched payment is recorded under.
+pub const CHAIN_PAYMENT_METADATA_KEY: &str = "chain_payment";
+
+/// The on-chain payment a callback was matched to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainPaymentMatch {
+    pub payment_id: String,
+    pub amount: String,
+    pub asset_code: String,
+    pub asset_issuer: Option<String>,
+    /// Fields where the payment disagrees with the callback: `"amount"`
+    /// and/or `"asset"`. Empty for an exact match.
+    pub mismatches: Vec<String>,
+}
+
+impl ChainPaymentMatch {
+    pub fn is_mismatch(&self) -> bool {
+        !self.mismatches.is_empty()
+    }
+}
+
+/// Picks the newest payment to the transaction's account carrying its memo
+/// and compares it against the callback. Returns `None` for a transaction
+/// without a memo or when no payment carries it.
+pub fn match_payment(tx: &Transaction, payments: &[PaymentRecord]) -> Option<ChainPaymentMatch> {
+    let memo = tx.memo.as_deref()?;
+    let payment = payments
+        .iter()
+        .find(|p| p.to == tx.stellar_account && p.memo.as_deref() == Some(memo))?;
+
+    let (asset_code, asset_issuer) = payment.asset();
+    let mut mismatches = Vec::new();
+    let same_amount = BigDecimal::from_str(&payment.amount)
+        .map(|amount| amount == tx.amount)
+        .unwrap_or(false);
+    if !same_amount {
+        mismatches.push("amount".to_string());
+    }
+    let same_asset = asset_code == tx.asset_code
+        && match &tx.asset_issuer {
+            Some(issuer) => asset_issuer.as_ref() == Some(issuer),
+            None => true,
+        };
+    if !same_asset {
+        mismatches.push("asset".to_string());
+    }
+
+    Some(ChainPaymentMatch {
+        payment_id: payment.id.clone(),
+        amount: payment.amount.clone(),
+        asset_code,
+        asset_issuer,
+        mismatches,
+    })
+}
+
+/// Records `found` on `tx`: under [`CHAIN_PAYMENT_METADATA_KEY`] in its
+/// metadata, and as its `anchor_transaction_id` when the callback did not
+/// send one. Metadata that is present but not a JSON object is left alone.
+pub fn apply_match(tx: &mut Transaction, found: &ChainPaymentMatch) {
+    let entry = serde_json::to_value(found).expect("serializing a payment match cannot fail");
+    match tx.metadata.get_or_insert_with(|| serde_json::json!({})) {
+        serde_json::Value::Object(map) => {
+            map.insert(CHAIN_PAYMENT_METADATA_KEY.to_string(), entry);
+        }
+        _ => tracing::warn!(
+            payment_id = %found.payment_id,
+            "Callback metadata is not an object; chain payment not recorded"
+        ),
+    }
+    if tx.anchor_transaction_id.is_none() {
+        tx.anchor_transaction_id = Some(found.payment_id.clone());
+    }
+}
+
+/// Looks up the newest `page_limit` payments of `tx.stellar_account` and
+/// annotates `tx` with the one carrying its memo. Horizon failures are
+/// logged and leave `tx` untouched: matching never blocks ingest.
+pub async fn annotate_with_chain_payment(
+    horizon: &HorizonClient,
+    tx: &mut Transaction,
+    page_limit: u32,
+) -> Option<ChainPaymentMatch> {
+    tx.memo.as_ref()?;
+    let page = match horizon
+        .get_latest_account_payments(&tx.stellar_account, page_limit)
+        .await
+    {
+        Ok(page) => page,
+        Err(e) => {
+            tracing::warn!(
+                stellar_account = %tx.stellar_account,
+                "Payment matching skipped, Horizon lookup failed: {e}"
+            );
+            return None;
+        }
+    };
+
+    let found = match_payment(tx, &page.records)?;
+    if found.is_mismatch() {
+        tracing::warn!(
+            payment_id = %found.payment_id,
+            mismatches = ?found.mismatches,
+            "Callback disagrees with its on-chain payment"
+        );
+    }
+    apply_match(tx, &found);
+    Some(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+    fn tx(amount: &str, memo: Option<&str>) -> Transaction {
+        Transaction::new(
+            ACCOUNT.to_string(),
+            amount.parse().unwrap(),
+            "USDC".to_string(),
+            None,
+            None,
+            None,
+            memo.map(String::from),
+            Some("text".to_string()),
+            None,
+        )
+    }
+
+    fn payment(id: &str, to: &str, amount: &str, memo: Option<&str>) -> PaymentRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "from": "GSENDER",
+            "to": to,
+            "amount": amount,
+            "asset_type": "credit_alphanum4",
+            "asset_code": "USDC",
+            "asset_issuer": "GISSUER",
+            "memo": memo,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_matches_payment_to_account_with_memo() {
+        let payments = [
+            payment("1", ACCOUNT, "10.0000000", Some("other")),
+            payment("2", "GSOMEONEELSE", "10.0000000", Some("order-7")),
+            payment("3", ACCOUNT, "10.0000000", Some("order-7")),
+        ];
+
+        let found = match_payment(&tx("10", Some("order-7")), &payments).unwrap();
+
+        assert_eq!(found.payment_id, "3");
+        assert!(!found.is_mismatch());
+    }
+
+    #[test]
+    fn test_flags_amount_mismatch() {
+        let payments = [payment("3", ACCOUNT, "9.5000000", Some("order-7"))];
+
+        let found = match_payment(&tx("10", Some("order-7")), &payments).unwrap();
+
+        assert_eq!(found.mismatches, ["amount"]);
+    }
+
+    #[test]
+    fn test_no_memo_or_no_payment_is_no_match() {
+        let payments = [payment("3", ACCOUNT, "10", Some("order-7"))];
+
+        assert!(match_payment(&tx("10", None), &payments).is_none());
+        assert!(match_payment(&tx("10", Some("order-8")), &payments).is_none());
+    }
+
+    #[test]
+    fn test_apply_match_keeps_callback_anchor_id_and_metadata() {
+        let mut tx = tx("10", Some("order-7"));
+        tx.anchor_transaction_id = Some("anchor-1".to_string());
+        tx.metadata = Some(serde_json::json!({ "source": "anchor" }));
+        let found = match_payment(&tx, &[payment("3", ACCOUNT, "10", Some("order-7"))]).unwrap();
+
+        apply_match(&mut tx, &found);
+
+        assert_eq!(tx.anchor_transaction_id.as_deref(), Some("anchor-1"));
+        let metadata = tx.metadata.unwrap();
+        assert_eq!(metadata["source"], "anchor");
+        assert_eq!(metadata[CHAIN_PAYMENT_METADATA_KEY]["payment_id"], "3");
+    }
+}
@@ -2,16 +2,28 @@ use sqlx::PgPool;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::watch;
+use tokio::task::JoinSet;
 use tokio::time::{sleep, Duration};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
+use uuid::Uuid;
 
-use crate::db::models::Transaction;
 use crate::services::lock_manager::LeaderElection;
+use crate::services::transaction_processor::RetryPolicy;
+use crate::services::TransactionProcessor;
 use crate::stellar::HorizonClient;
 
 const LEADER_HEARTBEAT_SECS: u64 = 15;
 const POLL_INTERVAL_SECS: u64 = 5;
 
+/// Transactions processed concurrently within a batch unless configured
+/// otherwise (`PROCESSOR_CONCURRENCY`).
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// How long a claim keeps other workers away from a transaction. Claims are
+/// released as soon as the batch finishes; the lease only matters if the
+/// worker dies mid-batch, after which the rows become claimable again.
+const CLAIM_LEASE_SECS: f64 = 300.0;
+
 /// Exponential moving average tracker for adaptive batch sizing.
 pub struct BatchSizer {
     ema: f64,
@@ -47,12 +59,13 @@ impl BatchSizer {
 
 pub struct ProcessorPool {
     pool: PgPool,
-    horizon_client: HorizonClient,
     workers: usize,
     poll_interval_ms: u64,
     min_batch: u32,
     max_batch: u32,
     scaling_factor: f64,
+    concurrency: usize,
+    retry_policy: RetryPolicy,
     /// Shared atomic for current batch size (exposed via /health).
     current_batch_size: Arc<AtomicU64>,
     /// Shared atomic for queue depth (read by back-pressure task).
@@ -63,7 +76,6 @@ impl ProcessorPool {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         pool: PgPool,
-        horizon_client: HorizonClient,
         workers: usize,
         poll_interval_ms: u64,
         min_batch: u32,
@@ -74,17 +86,30 @@ impl ProcessorPool {
     ) -> Self {
         Self {
             pool,
-            horizon_client,
             workers,
             poll_interval_ms,
             min_batch,
             max_batch,
             scaling_factor,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            retry_policy: RetryPolicy::default(),
             current_batch_size,
             pending_queue_depth,
         }
     }
 
+    /// Set how many transactions each worker processes concurrently per batch.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the retry policy workers apply to failed transactions.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Start the processor pool. Returns a shutdown sender; drop or send to it to stop workers.
     pub fn start(self) -> watch::Sender<bool> {
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
@@ -94,16 +119,21 @@ impl ProcessorPool {
         let min_batch = self.min_batch;
         let max_batch = self.max_batch;
         let scaling_factor = self.scaling_factor;
+        let concurrency = self.concurrency;
         let current_batch_size = self.current_batch_size.clone();
         let pending_queue_depth = self.pending_queue_depth.clone();
         let pool = self.pool;
-        let horizon_client = self.horizon_client;
+        let processor =
+            TransactionProcessor::new(pool.clone()).with_retry_policy(self.retry_policy);
 
-        info!("Starting ProcessorPool with {} workers", workers);
+        info!(
+            "Starting ProcessorPool with {} workers ({} concurrent transactions each)",
+            workers, concurrency
+        );
 
         for worker_id in 0..workers {
             let pool = pool.clone();
-            let horizon_client = horizon_client.clone();
+            let processor = processor.clone();
             let mut shutdown_rx = shutdown_rx.clone();
             let current_batch_size = current_batch_size.clone();
             let pending_queue_depth = pending_queue_depth.clone();
@@ -123,7 +153,7 @@ impl ProcessorPool {
                     current_batch_size.store(batch_size as u64, Ordering::Relaxed);
                     debug!(worker_id, batch_size, depth, "adaptive batch size");
 
-                    match process_batch(&pool, &horizon_client, batch_size).await {
+                    match run_batch(&processor, &pool, batch_size, concurrency).await {
                         Ok(processed) => {
                             if processed > 0 {
                                 tracing::info!(
@@ -156,65 +186,128 @@ impl ProcessorPool {
     }
 }
 
+/// Processes one batch of pending transactions with the default concurrency.
 pub async fn process_batch(
     pool: &PgPool,
     _horizon_client: &HorizonClient,
     batch_size: u32,
 ) -> anyhow::Result<usize> {
-    let mut tx = pool.begin().await?;
-
-    let pending: Vec<Transaction> = sqlx::query_as::<_, Transaction>(
-        r#"
-        SELECT id, stellar_account, amount, asset_code, status, created_at, updated_at,
-               anchor_transaction_id, callback_type, callback_status, settlement_id,
-               memo, memo_type, metadata, priority, trace_id
-        FROM transactions
-        WHERE status = 'pending'
-        ORDER BY created_at ASC
-        LIMIT $1
-        FOR UPDATE SKIP LOCKED
-        "#,
-    )
-    .bind(batch_size as i64)
-    .fetch_all(&mut *tx)
-    .await?;
+    let processor = TransactionProcessor::new(pool.clone());
+    run_batch(&processor, pool, batch_size, DEFAULT_BATCH_CONCURRENCY).await
+}
 
-    if pending.is_empty() {
-        tx.commit().await?;
+/// Claims up to `batch_size` due pending transactions and runs them through
+/// `processor`, at most `concurrency` at a time. Returns the number claimed.
+///
+/// Claiming is safe across workers and instances: rows are selected with
+/// `FOR UPDATE SKIP LOCKED` and leased via `claimed_until`, so no transaction
+/// is handed to two workers at once.
+pub async fn run_batch(
+    processor: &TransactionProcessor,
+    pool: &PgPool,
+    batch_size: u32,
+    concurrency: usize,
+) -> anyhow::Result<usize> {
+    let claimed = claim_batch(pool, batch_size).await?;
+    if claimed.is_empty() {
         return Ok(0);
     }
 
-    debug!("Processing {} pending transaction(s)", pending.len());
-
-    let count = pending.len();
-    let mut asset_codes = std::collections::HashSet::new();
-    for transaction in &pending {
-        asset_codes.insert(transaction.asset_code.clone());
-
-        // Create linked span for transaction processing if trace_id exists
-        if let Some(ref trace_id) = transaction.trace_id {
-            let span = tracing::info_span!(
-                "transaction.process",
-                transaction_id = %transaction.id,
-                trace_id = %trace_id,
-            );
-            let _guard = span.enter();
-            debug!("Processing transaction with trace context");
+    debug!("Processing {} pending transaction(s)", claimed.len());
+
+    let mut in_flight = JoinSet::new();
+    for (id, trace_id) in &claimed {
+        if in_flight.len() >= concurrency.max(1) {
+            in_flight.join_next().await;
         }
-    }
 
-    // TODO: per-transaction processing logic
-    for _transaction in pending {
-        // process each transaction
+        // Link the processing span to the originating request when known.
+        let span = match trace_id {
+            Some(trace_id) => {
+                tracing::info_span!("transaction.process", transaction_id = %id, trace_id = %trace_id)
+            }
+            None => tracing::info_span!("transaction.process", transaction_id = %id),
+        };
+        let processor = processor.clone();
+        let id = *id;
+        in_flight.spawn(
+            async move {
+                // Failures are recorded (retry/DLQ) by the processor itself.
+                if let Err(e) = processor.process_transaction(id).await {
+                    debug!(transaction_id = %id, "Transaction processing failed: {}", e);
+                }
+            }
+            .instrument(span),
+        );
     }
+    while in_flight.join_next().await.is_some() {}
 
-    tx.commit().await?;
+    let ids: Vec<Uuid> = claimed.iter().map(|(id, _)| *id).collect();
+    release_claims(pool, &ids).await?;
 
-    for asset_code in asset_codes {
-        crate::db::queries::invalidate_caches_for_asset(&asset_code).await;
-    }
+    Ok(claimed.len())
+}
+
+/// Leases pending transaction `id` the way [`claim_batch`] leases a batch,
+/// for callers that process a single transaction outside the pool (e.g.
+/// synchronous callbacks). Returns `false` when a worker already holds it.
+pub async fn claim_transaction(pool: &PgPool, id: Uuid) -> sqlx::Result<bool> {
+    let claimed: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        UPDATE transactions
+        SET claimed_until = NOW() + make_interval(secs => $2)
+        WHERE id = $1
+          AND status = 'pending'
+          AND deleted_at IS NULL
+          AND (claimed_until IS NULL OR claimed_until <= NOW())
+        RETURNING id
+        "#,
+    )
+    .bind(id)
+    .bind(CLAIM_LEASE_SECS)
+    .fetch_optional(pool)
+    .await?;
+    Ok(claimed.is_some())
+}
 
-    Ok(count)
+/// Drops the leases on `ids` so the rows are claimable again.
+pub async fn release_claims(pool: &PgPool, ids: &[Uuid]) -> sqlx::Result<()> {
+    sqlx::query("UPDATE transactions SET claimed_until = NULL WHERE id = ANY($1)")
+        .bind(ids)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Leases up to `batch_size` pending transactions that are due for an
+/// attempt, oldest first.
+async fn claim_batch(
+    pool: &PgPool,
+    batch_size: u32,
+) -> anyhow::Result<Vec<(Uuid, Option<String>)>> {
+    let claimed = sqlx::query_as(
+        r#"
+        UPDATE transactions
+        SET claimed_until = NOW() + make_interval(secs => $2)
+        WHERE id IN (
+            SELECT id
+            FROM transactions
+            WHERE status = 'pending'
+              AND deleted_at IS NULL
+              AND (next_attempt_at IS NULL OR next_attempt_at <= NOW())
+              AND (claimed_until IS NULL OR claimed_until <= NOW())
+            ORDER BY created_at ASC
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, trace_id
+        "#,
+    )
+    .bind(batch_size as i64)
+    .bind(CLAIM_LEASE_SECS)
+    .fetch_all(pool)
+    .await?;
+    Ok(claimed)
 }
 
 /// Legacy single-worker entry point kept for backward compatibility.
@@ -1,13 +1,41 @@
 use crate::cache::{CacheValidator, ValidationError};
 use crate::middleware::idempotency::RedisCircuitBreaker;
+use crate::services::lock_manager::{Lock, LockManager};
+use crate::utils::retry::retry_redis_with_backoff;
 use lru::LruCache;
 use redis::{aio::ConnectionManager, AsyncCommands, Client};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
 use std::num::NonZeroUsize;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// TTL of the `cache_fill:<key>` lock held while one caller recomputes a
+/// missing entry in [`QueryCache::get_or_compute`]. Waiters give up on the
+/// holder and compute the value themselves once it has elapsed.
+const FILL_LOCK_TTL_SECS: u64 = 5;
+
+/// How often [`QueryCache::get_or_compute`] waiters check whether the lock
+/// holder has published the value.
+const FILL_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Retries of a Redis command that failed because the connection dropped.
+/// The `ConnectionManager` reconnects in the background after such a failure,
+/// so a brief outage is absorbed here rather than surfaced to the caller;
+/// an operation that still fails counts once towards the circuit breaker.
+const RECONNECT_RETRIES: u32 = 3;
+
+/// Base delay before the first reconnect retry, in milliseconds.
+const RECONNECT_BASE_DELAY_MS: u64 = 50;
+
+/// Backoff of the `ConnectionManager`'s own reconnect attempts: delays of
+/// `2^n * RECONNECT_BACKOFF_FACTOR_MS` for `RECONNECT_BACKOFF_ATTEMPTS`
+/// attempts. The driver's default backs off for over ten seconds, stalling
+/// every cache call for that long while Redis is down.
+const RECONNECT_BACKOFF_FACTOR_MS: u64 = 10;
+const RECONNECT_BACKOFF_ATTEMPTS: usize = 3;
+
 #[derive(Clone)]
 #[allow(dead_code)]
 struct CacheEntry<T> {
@@ -65,6 +93,7 @@ pub struct QueryCache {
     memory_hits: Arc<AtomicU64>,
     memory_misses: Arc<AtomicU64>,
     lru: Arc<Mutex<LruCache<String, String>>>,
+    fill_locks: Arc<LockManager>,
 }
 
 impl std::fmt::Debug for QueryCache {
@@ -124,7 +153,14 @@ impl QueryCache {
         let client = Client::open(redis_url)?;
 
         // OPT: Create connection manager for built-in pooling and health checks
-        let pool = ConnectionManager::new(client).await?;
+        let pool = ConnectionManager::new_with_backoff(
+            client,
+            2,
+            RECONNECT_BACKOFF_FACTOR_MS,
+            RECONNECT_BACKOFF_ATTEMPTS,
+        )
+        .await?;
+        let fill_locks = LockManager::new(redis_url, FILL_LOCK_TTL_SECS)?;
 
         let pool_config = RedisPoolConfig::default();
         let cache_size = std::env::var("MEMORY_CACHE_SIZE")
@@ -143,6 +179,7 @@ impl QueryCache {
             lru: Arc::new(Mutex::new(LruCache::new(
                 NonZeroUsize::new(cache_size).unwrap(),
             ))),
+            fill_locks: Arc::new(fill_locks),
         })
     }
 
@@ -162,12 +199,15 @@ impl QueryCache {
     ) -> Result<Option<T>, redis::RedisError> {
         CacheValidator::validate_key(key).map_err(cache_validation_error)?;
 
+        let query_type = query_type_for_key(key);
+
         // Try in-memory cache first
         {
             let mut lru = self.lru.lock().unwrap();
             if let Some(cached) = lru.get(key) {
                 self.memory_hits.fetch_add(1, Ordering::Relaxed);
                 if let Ok(value) = serde_json::from_str::<T>(cached) {
+                    record_lookup(query_type, true);
                     return Ok(Some(value));
                 }
             }
@@ -186,11 +226,21 @@ impl QueryCache {
         self.cb
             .call(|| async move {
                 // OPT: Get connection from pool (cheap clone of internal Arc)
-                let mut conn = pool.clone();
-                let value: Option<String> = conn.get(&key).await?;
+                let value: Option<String> = retry_redis_with_backoff(
+                    "query_cache_get",
+                    RECONNECT_RETRIES,
+                    RECONNECT_BASE_DELAY_MS,
+                    || {
+                        let mut conn = pool.clone();
+                        let key = key.clone();
+                        async move { conn.get(&key).await }
+                    },
+                )
+                .await?;
                 match value {
                     Some(v) => {
                         hits.fetch_add(1, Ordering::Relaxed);
+                        record_lookup(query_type, true);
                         // Populate in-memory cache
                         {
                             let mut lru_cache = lru.lock().unwrap();
@@ -206,6 +256,7 @@ impl QueryCache {
                     }
                     None => {
                         misses.fetch_add(1, Ordering::Relaxed);
+                        record_lookup(query_type, false);
                         Ok(None)
                     }
                 }
@@ -258,8 +309,18 @@ impl QueryCache {
         self.cb
             .call(|| async move {
                 // OPT: Get connection from pool (cheap clone of internal Arc)
-                let mut conn = pool.clone();
-                conn.set_ex(&key, serialized.clone(), ttl_secs).await
+                retry_redis_with_backoff(
+                    "query_cache_set",
+                    RECONNECT_RETRIES,
+                    RECONNECT_BASE_DELAY_MS,
+                    || {
+                        let mut conn = pool.clone();
+                        let key = key.clone();
+                        let serialized = serialized.clone();
+                        async move { conn.set_ex(&key, serialized, ttl_secs).await }
+                    },
+                )
+                .await
             })
             .await
             .map_err(|e| match e {
@@ -270,6 +331,84 @@ impl QueryCache {
             })
     }
 
+    /// Returns the cached value for `key`, computing and caching it for `ttl`
+    /// on a miss.
+    ///
+    /// Misses are single-flight: only the caller holding the short-lived
+    /// `cache_fill:<key>` lock runs `compute`, while concurrent callers — in
+    /// this process or another instance — wait for it to publish the value.
+    /// If the holder fails, the next waiter takes the lock over; if it has
+    /// not published within the lock TTL, or Redis is unavailable, callers
+    /// compute the value themselves instead of failing. Errors from
+    /// `compute` are returned as-is and nothing is cached.
+    pub async fn get_or_compute<T, E, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        compute: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned + Send,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Ok(Some(value)) = self.get::<T>(key).await {
+            return Ok(value);
+        }
+
+        let resource = format!("cache_fill:{key}");
+        let deadline = Instant::now() + Duration::from_secs(FILL_LOCK_TTL_SECS);
+        let lock = loop {
+            match self.fill_locks.acquire(&resource, Duration::ZERO).await {
+                Ok(Some(lock)) => break Some(lock),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(key, error = %e, "Cache fill lock unavailable, computing without it");
+                    break None;
+                }
+            }
+            // Another caller is filling the entry; wait for its result.
+            tokio::time::sleep(FILL_POLL_INTERVAL).await;
+            if let Some(value) = self.peek::<T>(key).await {
+                return Ok(value);
+            }
+            if Instant::now() >= deadline {
+                tracing::warn!(key, "Timed out waiting for cache fill, computing locally");
+                break None;
+            }
+        };
+
+        // The previous holder may have published just before releasing.
+        if lock.is_some() {
+            if let Some(value) = self.peek::<T>(key).await {
+                release_fill_lock(lock).await;
+                return Ok(value);
+            }
+        }
+
+        let result = compute().await;
+        if let Ok(value) = &result {
+            if let Err(e) = self.set(key, value, ttl).await {
+                tracing::warn!(key, error = %e, "Failed to cache computed value");
+            }
+        }
+        release_fill_lock(lock).await;
+        result
+    }
+
+    /// Reads `key` without touching the hit/miss counters, for polling.
+    async fn peek<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let cached = self.lru.lock().unwrap().get(key).cloned();
+        let raw = match cached {
+            Some(raw) => raw,
+            None => {
+                let mut conn = self.pool.clone();
+                conn.get::<_, Option<String>>(key).await.ok()??
+            }
+        };
+        serde_json::from_str(&raw).ok()
+    }
+
     pub async fn invalidate(&self, pattern: &str) -> Result<(), redis::RedisError> {
         CacheValidator::validate_pattern(pattern).map_err(cache_validation_error)?;
 
@@ -299,8 +438,16 @@ impl QueryCache {
         }
 
         // OPT: Use pooled connection for Redis operations
-        let mut conn = self.get_connection().await?;
-        conn.del::<_, ()>(key).await
+        retry_redis_with_backoff(
+            "query_cache_invalidate",
+            RECONNECT_RETRIES,
+            RECONNECT_BASE_DELAY_MS,
+            || {
+                let mut conn = self.pool.clone();
+                async move { conn.del::<_, ()>(key).await }
+            },
+        )
+        .await
     }
 
     /// Verifies the Redis connection pool is healthy by pinging the server.
@@ -396,6 +543,43 @@ impl QueryCache {
         tracing::info!("Cache warming completed");
         Ok(())
     }
+
+    /// Loads the settlement daily totals (last 7 days) and per-asset
+    /// settlement counts into the cache, so the first settlements listing
+    /// after a cold start or partition rotation does not aggregate the
+    /// settlements table on the request path.
+    pub async fn warm_settlement_aggregates(
+        &self,
+        pool: &sqlx::PgPool,
+        config: &CacheConfig,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let daily_totals = crate::db::queries::get_settlement_daily_totals(pool, 7).await?;
+        self.set(
+            &cache_key_settlement_daily_totals(7),
+            &daily_totals,
+            Duration::from_secs(config.daily_totals_ttl),
+        )
+        .await?;
+
+        let asset_counts = crate::db::queries::get_settlement_asset_counts(pool).await?;
+        self.set(
+            &cache_key_settlement_asset_counts(),
+            &asset_counts,
+            Duration::from_secs(config.asset_stats_ttl),
+        )
+        .await?;
+
+        tracing::info!("Settlement aggregate cache warming completed");
+        Ok(())
+    }
+}
+
+async fn release_fill_lock(lock: Option<Lock>) {
+    if let Some(lock) = lock {
+        if let Err(e) = lock.release().await {
+            tracing::warn!(error = %e, "Failed to release cache fill lock");
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -410,6 +594,33 @@ pub struct CacheMetrics {
     pub memory_hit_rate: f64,
 }
 
+/// Query type reported in the `query_type` label of the cache hit/miss
+/// counters: the segment after `query:` in keys built by the `cache_key_*`
+/// helpers, or `"other"` for any other key.
+pub fn query_type_for_key(key: &str) -> &'static str {
+    let Some(rest) = key.strip_prefix("query:") else {
+        return "other";
+    };
+    match rest.split(':').next() {
+        Some("status_counts") => "status_counts",
+        Some("daily_totals") => "daily_totals",
+        Some("asset_stats") => "asset_stats",
+        Some("asset_total") => "asset_total",
+        Some("settlement_daily_totals") => "settlement_daily_totals",
+        Some("settlement_asset_counts") => "settlement_asset_counts",
+        _ => "other",
+    }
+}
+
+fn record_lookup(query_type: &str, hit: bool) {
+    let counter = if hit {
+        crate::metrics::query_cache_hits_total()
+    } else {
+        crate::metrics::query_cache_misses_total()
+    };
+    counter.with_label_values(&[query_type]).inc();
+}
+
 pub fn cache_key_status_counts() -> String {
     "query:status_counts".to_string()
 }
@@ -418,6 +629,17 @@ pub fn cache_key_daily_totals(days: i32) -> String {
     format!("query:daily_totals:{days}")
 }
 
+/// Key of a [`daily_totals_by_asset`](crate::db::queries::daily_totals_by_asset)
+/// series; shares the `daily_totals` prefix so it is invalidated with the
+/// other daily totals.
+pub fn cache_key_daily_totals_by_asset(
+    asset_code: &str,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> String {
+    format!("query:daily_totals:{asset_code}:{from}:{to}")
+}
+
 pub fn cache_key_asset_stats() -> String {
     "query:asset_stats".to_string()
 }
@@ -426,6 +648,14 @@ pub fn cache_key_asset_total(asset_code: &str) -> String {
     format!("query:asset_total:{asset_code}")
 }
 
+pub fn cache_key_settlement_daily_totals(days: i32) -> String {
+    format!("query:settlement_daily_totals:{days}")
+}
+
+pub fn cache_key_settlement_asset_counts() -> String {
+    "query:settlement_asset_counts".to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,8 +678,76 @@ mod tests {
     fn test_cache_key_generation() {
         assert_eq!(cache_key_status_counts(), "query:status_counts");
         assert_eq!(cache_key_daily_totals(7), "query:daily_totals:7");
+        let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 8, d).unwrap();
+        assert_eq!(
+            cache_key_daily_totals_by_asset("USDC", day(1), day(7)),
+            "query:daily_totals:USDC:2026-08-01:2026-08-07"
+        );
+        assert_eq!(
+            query_type_for_key(&cache_key_daily_totals_by_asset("USDC", day(1), day(7))),
+            "daily_totals"
+        );
         assert_eq!(cache_key_asset_stats(), "query:asset_stats");
         assert_eq!(cache_key_asset_total("USD"), "query:asset_total:USD");
+        assert_eq!(
+            cache_key_settlement_daily_totals(7),
+            "query:settlement_daily_totals:7"
+        );
+        assert_eq!(
+            cache_key_settlement_asset_counts(),
+            "query:settlement_asset_counts"
+        );
+    }
+
+    #[test]
+    fn test_query_type_for_key() {
+        assert_eq!(
+            query_type_for_key(&cache_key_status_counts()),
+            "status_counts"
+        );
+        assert_eq!(
+            query_type_for_key(&cache_key_daily_totals(30)),
+            "daily_totals"
+        );
+        assert_eq!(query_type_for_key(&cache_key_asset_stats()), "asset_stats");
+        assert_eq!(
+            query_type_for_key(&cache_key_asset_total("USDC")),
+            "asset_total"
+        );
+        assert_eq!(
+            query_type_for_key(&cache_key_settlement_daily_totals(7)),
+            "settlement_daily_totals"
+        );
+        assert_eq!(
+            query_type_for_key(&cache_key_settlement_asset_counts()),
+            "settlement_asset_counts"
+        );
+        assert_eq!(query_type_for_key("query:unknown"), "other");
+        assert_eq!(query_type_for_key("test:pool:reuse"), "other");
+    }
+
+    #[test]
+    fn test_lookups_are_counted_per_query_type() {
+        let hits = crate::metrics::query_cache_hits_total();
+        let misses = crate::metrics::query_cache_misses_total();
+        let count = |vec: &prometheus::IntCounterVec, query_type: &str| {
+            vec.with_label_values(&[query_type]).get()
+        };
+        let before = [
+            count(hits, "daily_totals"),
+            count(misses, "daily_totals"),
+            count(hits, "asset_total"),
+            count(misses, "asset_total"),
+        ];
+
+        record_lookup(query_type_for_key(&cache_key_daily_totals(7)), true);
+        record_lookup(query_type_for_key(&cache_key_daily_totals(7)), true);
+        record_lookup(query_type_for_key(&cache_key_asset_total("USD")), false);
+
+        assert_eq!(count(hits, "daily_totals"), before[0] + 2);
+        assert_eq!(count(misses, "daily_totals"), before[1]);
+        assert_eq!(count(hits, "asset_total"), before[2]);
+        assert_eq!(count(misses, "asset_total"), before[3] + 1);
     }
 
     #[tokio::test]
@@ -1,10 +1,12 @@
-use crate::stellar::client::HorizonClient;
+use crate::db::models::{normalize_asset, TransactionStatus};
+use crate::stellar::client::{HorizonClient, MAX_PAGE_LIMIT};
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::collections::HashMap;
-use tracing::info;
+use std::time::Instant;
+use tracing::{field::Empty, info, instrument};
 use uuid::Uuid;
 
 // ── Public report types ─────────────────────────────────────────────────────
@@ -42,6 +44,9 @@ pub struct MissingTransaction {
     pub stellar_account: String,
     pub amount: String,
     pub asset_code: String,
+    /// `None` for native XLM and for rows recorded without an issuer.
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
     pub memo: Option<String>,
     pub created_at: DateTime<Utc>,
 }
@@ -53,6 +58,9 @@ pub struct OrphanedPayment {
     pub to: String,
     pub amount: String,
     pub asset_code: String,
+    /// `None` for native XLM and for rows recorded without an issuer.
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
     pub memo: Option<String>,
 }
 
@@ -71,6 +79,9 @@ pub struct AmbiguousTransaction {
     pub stellar_account: String,
     pub amount: String,
     pub asset_code: String,
+    /// `None` for native XLM and for rows recorded without an issuer.
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
     pub memo: Option<String>,
     pub created_at: DateTime<Utc>,
     pub reason: String,
@@ -83,10 +94,222 @@ pub struct AmbiguousPayment {
     pub to: String,
     pub amount: String,
     pub asset_code: String,
+    /// `None` for native XLM and for rows recorded without an issuer.
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
     pub memo: Option<String>,
     pub reason: String,
 }
 
+/// Combined result of [`ReconciliationService::reconcile_accounts`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MultiAccountReconciliationReport {
+    pub generated_at: DateTime<Utc>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Counts summed over every account that reconciled successfully.
+    pub summary: ReconciliationSummary,
+    /// One section per requested account, in request order.
+    pub accounts: Vec<AccountReconciliation>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReconciliationSummary {
+    pub accounts: usize,
+    pub failed_accounts: usize,
+    pub total_db_transactions: usize,
+    pub total_chain_payments: usize,
+    pub matched_count: usize,
+    pub missing_on_chain: usize,
+    pub orphaned_payments: usize,
+    pub amount_mismatches: usize,
+    pub ambiguous_db: usize,
+    pub ambiguous_chain: usize,
+    pub unmatched_no_memo_db: usize,
+    pub unmatched_no_memo_chain: usize,
+}
+
+/// One account's section of a multi-account run. Exactly one of `report`
+/// and `error` is set; a failing account does not abort the others.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountReconciliation {
+    pub account: String,
+    pub report: Option<ReconciliationReport>,
+    pub error: Option<String>,
+}
+
+impl ReconciliationSummary {
+    fn add(&mut self, report: &ReconciliationReport) {
+        self.total_db_transactions += report.total_db_transactions;
+        self.total_chain_payments += report.total_chain_payments;
+        self.matched_count += report.matched_count;
+        self.missing_on_chain += report.missing_on_chain.len();
+        self.orphaned_payments += report.orphaned_payments.len();
+        self.amount_mismatches += report.amount_mismatches.len();
+        self.ambiguous_db += report.ambiguous_db.len();
+        self.ambiguous_chain += report.ambiguous_chain.len();
+        self.unmatched_no_memo_db += report.unmatched_no_memo_db.len();
+        self.unmatched_no_memo_chain += report.unmatched_no_memo_chain.len();
+    }
+}
+
+// ── CSV export ──────────────────────────────────────────────────────────────
+
+/// Section labels emitted by [`ReconciliationReport::to_csv`], in order.
+pub const CSV_SECTIONS: [&str; 3] = ["missing_on_chain", "orphaned_payments", "amount_mismatches"];
+
+impl ReconciliationReport {
+    /// Renders the discrepancies as CSV, one labelled section per type.
+    ///
+    /// Each section is a row holding only its label (see [`CSV_SECTIONS`]),
+    /// then a header row and the records, with a blank line between
+    /// sections. Every field is quoted, so amounts keep their exact decimal
+    /// text and memos containing commas, quotes or newlines round-trip.
+    pub fn to_csv(&self) -> Result<String, csv::Error> {
+        discrepancies_csv(&[(None, self)])
+    }
+}
+
+impl MultiAccountReconciliationReport {
+    /// Like [`ReconciliationReport::to_csv`], with a leading `account` column.
+    /// Accounts that failed to reconcile contribute no rows.
+    pub fn to_csv(&self) -> Result<String, csv::Error> {
+        let reports: Vec<_> = self
+            .accounts
+            .iter()
+            .filter_map(|a| a.report.as_ref().map(|r| (Some(a.account.as_str()), r)))
+            .collect();
+        discrepancies_csv(&reports)
+    }
+}
+
+fn discrepancies_csv(
+    reports: &[(Option<&str>, &ReconciliationReport)],
+) -> Result<String, csv::Error> {
+    let with_account = reports.iter().any(|(account, _)| account.is_some());
+    let row = |account: Option<&str>, fields: Vec<String>| -> Vec<String> {
+        account
+            .map(str::to_string)
+            .into_iter()
+            .chain(fields)
+            .collect()
+    };
+    let memo = |memo: &Option<String>| memo.clone().unwrap_or_default();
+
+    let missing = reports.iter().flat_map(|(account, report)| {
+        report.missing_on_chain.iter().map(move |m| {
+            row(
+                *account,
+                vec![
+                    m.id.to_string(),
+                    m.stellar_account.clone(),
+                    m.amount.clone(),
+                    m.asset_code.clone(),
+                    m.asset_issuer.clone().unwrap_or_default(),
+                    memo(&m.memo),
+                    m.created_at.to_rfc3339(),
+                ],
+            )
+        })
+    });
+    let orphaned = reports.iter().flat_map(|(account, report)| {
+        report.orphaned_payments.iter().map(move |o| {
+            row(
+                *account,
+                vec![
+                    o.payment_id.clone(),
+                    o.from.clone(),
+                    o.to.clone(),
+                    o.amount.clone(),
+                    o.asset_code.clone(),
+                    o.asset_issuer.clone().unwrap_or_default(),
+                    memo(&o.memo),
+                ],
+            )
+        })
+    });
+    let mismatched = reports.iter().flat_map(|(account, report)| {
+        report.amount_mismatches.iter().map(move |m| {
+            row(
+                *account,
+                vec![
+                    m.transaction_id.to_string(),
+                    m.payment_id.clone(),
+                    m.db_amount.clone(),
+                    m.chain_amount.clone(),
+                    memo(&m.memo),
+                ],
+            )
+        })
+    });
+
+    let sections = [
+        csv_section(
+            CSV_SECTIONS[0],
+            &[
+                "id",
+                "stellar_account",
+                "amount",
+                "asset_code",
+                "asset_issuer",
+                "memo",
+                "created_at",
+            ],
+            with_account,
+            missing,
+        )?,
+        csv_section(
+            CSV_SECTIONS[1],
+            &[
+                "payment_id",
+                "from",
+                "to",
+                "amount",
+                "asset_code",
+                "asset_issuer",
+                "memo",
+            ],
+            with_account,
+            orphaned,
+        )?,
+        csv_section(
+            CSV_SECTIONS[2],
+            &[
+                "transaction_id",
+                "payment_id",
+                "db_amount",
+                "chain_amount",
+                "memo",
+            ],
+            with_account,
+            mismatched,
+        )?,
+    ];
+    Ok(sections.join("\n"))
+}
+
+fn csv_section(
+    label: &str,
+    header: &[&str],
+    with_account: bool,
+    rows: impl Iterator<Item = Vec<String>>,
+) -> Result<String, csv::Error> {
+    let mut wtr = csv::WriterBuilder::new()
+        .flexible(true)
+        .quote_style(csv::QuoteStyle::Always)
+        .from_writer(vec![]);
+    wtr.write_record([label])?;
+    let account_column = with_account.then_some("account");
+    wtr.write_record(account_column.iter().chain(header))?;
+    for row in rows {
+        wtr.write_record(row)?;
+    }
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| csv::Error::from(std::io::Error::other(e.to_string())))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 // ── Private internal types ──────────────────────────────────────────────────
 
 #[derive(Debug)]
@@ -95,6 +318,7 @@ struct DbTransaction {
     stellar_account: String,
     amount: String,
     asset_code: String,
+    asset_issuer: Option<String>,
     memo: Option<String>,
     created_at: DateTime<Utc>,
 }
@@ -106,6 +330,7 @@ struct ChainPayment {
     to: String,
     amount: String,
     asset_code: String,
+    asset_issuer: Option<String>,
     memo: Option<String>,
 }
 
@@ -125,9 +350,19 @@ struct MatchAccumulator {
 
 // ── Service ─────────────────────────────────────────────────────────────────
 
+/// Accounts reconciled at once by [`ReconciliationService::reconcile_accounts`].
+pub const DEFAULT_ACCOUNT_CONCURRENCY: usize = 4;
+
+/// Statuses of the database transactions reconciled unless
+/// [`ReconciliationService::with_statuses`] says otherwise.
+pub const DEFAULT_RECONCILE_STATUSES: &[TransactionStatus] = &[TransactionStatus::Completed];
+
 pub struct ReconciliationService {
     horizon_client: HorizonClient,
     pool: PgPool,
+    account_concurrency: usize,
+    page_limit: u32,
+    statuses: Vec<TransactionStatus>,
 }
 
 impl ReconciliationService {
@@ -135,9 +370,90 @@ impl ReconciliationService {
         Self {
             horizon_client,
             pool,
+            account_concurrency: DEFAULT_ACCOUNT_CONCURRENCY,
+            page_limit: MAX_PAGE_LIMIT,
+            statuses: DEFAULT_RECONCILE_STATUSES.to_vec(),
         }
     }
 
+    /// Database transactions in any of `statuses` take part in matching, e.g.
+    /// `pending` ones that should already have settled on-chain. An empty
+    /// list keeps [`DEFAULT_RECONCILE_STATUSES`].
+    pub fn with_statuses(mut self, statuses: Vec<TransactionStatus>) -> Self {
+        if !statuses.is_empty() {
+            self.statuses = statuses;
+        }
+        self
+    }
+
+    pub fn with_account_concurrency(mut self, concurrency: usize) -> Self {
+        self.account_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Records requested per Horizon payments page, clamped to 1–200.
+    pub fn with_page_limit(mut self, limit: u32) -> Self {
+        self.page_limit = limit.clamp(1, MAX_PAGE_LIMIT);
+        self
+    }
+
+    /// Reconciles each account over the same window, at most
+    /// `account_concurrency` at a time, and merges the results.
+    pub async fn reconcile_accounts(
+        &self,
+        accounts: &[String],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> anyhow::Result<MultiAccountReconciliationReport> {
+        use futures::stream::{self, StreamExt};
+
+        if accounts.is_empty() {
+            anyhow::bail!("no accounts given to reconcile");
+        }
+
+        let sections: Vec<AccountReconciliation> = stream::iter(accounts)
+            .map(|account| async move {
+                match self.reconcile(account, start, end).await {
+                    Ok(report) => AccountReconciliation {
+                        account: account.clone(),
+                        report: Some(report),
+                        error: None,
+                    },
+                    Err(e) => {
+                        tracing::warn!(account = %account, "Reconciliation failed: {e:#}");
+                        AccountReconciliation {
+                            account: account.clone(),
+                            report: None,
+                            error: Some(format!("{e:#}")),
+                        }
+                    }
+                }
+            })
+            .buffered(self.account_concurrency)
+            .collect()
+            .await;
+
+        let mut summary = ReconciliationSummary {
+            accounts: sections.len(),
+            ..Default::default()
+        };
+        for section in &sections {
+            match &section.report {
+                Some(report) => summary.add(report),
+                None => summary.failed_accounts += 1,
+            }
+        }
+
+        Ok(MultiAccountReconciliationReport {
+            generated_at: Utc::now(),
+            period_start: start,
+            period_end: end,
+            summary,
+            accounts: sections,
+        })
+    }
+
+    #[instrument(name = "reconciliation.reconcile", skip(self), fields(stellar.account = %account))]
     pub async fn reconcile(
         &self,
         account: &str,
@@ -145,8 +461,11 @@ impl ReconciliationService {
         end: DateTime<Utc>,
     ) -> anyhow::Result<ReconciliationReport> {
         info!(
+            request_id = ?crate::middleware::request_logger::current_request_id(),
             "Starting reconciliation for {} from {} to {}",
-            account, start, end
+            account,
+            start,
+            end
         );
 
         let db_txs = self.fetch_db_transactions(account, start, end).await?;
@@ -174,135 +493,156 @@ impl ReconciliationService {
         Ok(report)
     }
 
+    #[instrument(
+        name = "reconciliation.fetch_db_transactions",
+        skip(self, start, end),
+        fields(stellar.account = %account, db.rows = Empty, duration_ms = Empty)
+    )]
     async fn fetch_db_transactions(
         &self,
         account: &str,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     ) -> anyhow::Result<Vec<DbTransaction>> {
-        let rows =
-            sqlx::query_as::<_, (Uuid, String, String, String, Option<String>, DateTime<Utc>)>(
-                "SELECT id, stellar_account, amount::text, asset_code, memo, created_at
+        let started = Instant::now();
+        let rows = sqlx::query_as::<
+            _,
+            (
+                Uuid,
+                String,
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                DateTime<Utc>,
+            ),
+        >(
+            "SELECT id, stellar_account, amount::text, asset_code, asset_issuer, memo, created_at
              FROM transactions
              WHERE stellar_account = $1
              AND created_at >= $2
              AND created_at <= $3
-             AND status = 'completed'
+             AND status = ANY($4)
              ORDER BY created_at",
-            )
-            .bind(account)
-            .bind(start)
-            .bind(end)
-            .fetch_all(&self.pool)
-            .await?;
+        )
+        .bind(account)
+        .bind(start)
+        .bind(end)
+        .bind(
+            self.statuses
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+        )
+        .fetch_all(&self.pool)
+        .await;
+        let span = tracing::Span::current();
+        span.record("duration_ms", started.elapsed().as_millis() as u64);
+        let rows = rows?;
+        span.record("db.rows", rows.len());
 
         Ok(rows
             .into_iter()
             .map(
-                |(id, stellar_account, amount, asset_code, memo, created_at)| DbTransaction {
-                    id,
-                    stellar_account,
-                    amount,
-                    asset_code,
-                    memo,
-                    created_at,
+                |(id, stellar_account, amount, asset_code, asset_issuer, memo, created_at)| {
+                    let (asset_code, asset_issuer) =
+                        normalize_asset(&asset_code, asset_issuer.as_deref());
+                    DbTransaction {
+                        id,
+                        stellar_account,
+                        amount,
+                        asset_code,
+                        asset_issuer,
+                        memo,
+                        created_at,
+                    }
                 },
             )
             .collect())
     }
 
+    #[instrument(
+        name = "reconciliation.fetch_chain_payments",
+        skip(self, start, end),
+        fields(
+            stellar.account = %account,
+            horizon.pages = Empty,
+            horizon.payments = Empty,
+            duration_ms = Empty
+        )
+    )]
     async fn fetch_chain_payments(
         &self,
         account: &str,
         start: DateTime<Utc>,
         end: DateTime<Utc>,
     ) -> anyhow::Result<Vec<ChainPayment>> {
-        #[derive(Deserialize, Default)]
-        struct Links {
-            next: Option<Link>,
-        }
-
-        #[derive(Deserialize)]
-        struct Link {
-            href: String,
-        }
-
-        #[derive(Deserialize)]
-        struct PaymentsResponse {
-            #[serde(rename = "_links", default)]
-            links: Links,
-            #[serde(rename = "_embedded")]
-            embedded: Embedded,
-        }
-
-        #[derive(Deserialize)]
-        struct Embedded {
-            records: Vec<PaymentRecord>,
-        }
-
-        #[derive(Deserialize)]
-        struct PaymentRecord {
-            id: String,
-            from: String,
-            to: String,
-            amount: String,
-            asset_code: String,
-            #[serde(default)]
-            memo: Option<String>,
-            /// RFC 3339 timestamp; absent in some test fixtures.
-            #[serde(default)]
-            created_at: Option<String>,
-        }
-
-        let base = self.horizon_client.base_url.trim_end_matches('/');
-        let mut url = format!("{}/accounts/{}/payments?order=asc&limit=200", base, account);
         let mut all_payments = Vec::new();
+        let span = tracing::Span::current();
+        let started = Instant::now();
+        let mut pages: u32 = 0;
+        let mut next_page: Option<String> = None;
 
         loop {
-            let response = self.horizon_client.client.get(&url).send().await?;
-            if !response.status().is_success() {
-                return Err(anyhow::anyhow!("Horizon API error: {}", response.status()));
-            }
-
-            let page: PaymentsResponse = response.json().await?;
-            let records = page.embedded.records;
-            let next_url = page.links.next.map(|l| l.href);
+            pages += 1;
+            span.record("horizon.pages", pages);
+            let result = match &next_page {
+                None => {
+                    self.horizon_client
+                        .get_latest_account_payments(account, self.page_limit)
+                        .await
+                }
+                Some(url) => self.horizon_client.get_payments_page(url).await,
+            };
+            let page = match result {
+                Ok(page) => page,
+                Err(e) => {
+                    span.record("duration_ms", started.elapsed().as_millis() as u64);
+                    return Err(e.into());
+                }
+            };
 
-            if records.is_empty() {
+            if page.records.is_empty() {
                 break;
             }
 
-            let mut past_window = false;
-            for r in records {
+            // Pages run newest first, so the first record older than
+            // `start` means every later page is outside the window too.
+            let mut before_window = false;
+            for r in page.records {
                 let created: Option<DateTime<Utc>> =
                     r.created_at.as_deref().and_then(|s| s.parse().ok());
 
                 if let Some(ts) = created {
-                    if ts > end {
-                        past_window = true;
+                    if ts < start {
+                        before_window = true;
                         break;
                     }
-                    if ts < start {
+                    if ts > end {
                         continue;
                     }
                 }
 
+                let (asset_code, asset_issuer) = r.asset();
                 all_payments.push(ChainPayment {
                     id: r.id,
                     from: r.from,
                     to: r.to,
                     amount: r.amount,
-                    asset_code: r.asset_code,
+                    asset_code,
+                    asset_issuer,
                     memo: r.memo,
                 });
             }
 
-            if past_window || next_url.is_none() {
-                break;
+            match page.next {
+                Some(next) if !before_window => next_page = Some(next),
+                _ => break,
             }
-            url = next_url.unwrap();
         }
 
+        span.record("horizon.payments", all_payments.len());
+        span.record("duration_ms", started.elapsed().as_millis() as u64);
         Ok(all_payments)
     }
 }
@@ -383,9 +723,20 @@ fn perform_matching(
     }
 }
 
+/// Whether a DB row and a chain payment are in the same asset. A row that
+/// records an issuer must match the payment's issuer; rows recorded before
+/// issuers were tracked match on asset code alone.
+fn same_asset(tx: &DbTransaction, p: &ChainPayment) -> bool {
+    tx.asset_code == p.asset_code
+        && match &tx.asset_issuer {
+            Some(issuer) => p.asset_issuer.as_ref() == Some(issuer),
+            None => true,
+        }
+}
+
 /// Match one memo group using a two-phase greedy algorithm.
 ///
-/// Phase 1 — exact (amount + asset_code): consumes pairs that agree on both.
+/// Phase 1 — exact (amount + asset): consumes pairs that agree on both.
 /// Phase 2 — asset-only: pairs remaining items that share an asset code,
 ///           recording the amount difference.
 /// Remainder — if only one side has leftover items they go to missing/orphaned;
@@ -409,7 +760,7 @@ fn match_memo_group(
                 continue;
             }
             let p = &chain_payments[chain_idx];
-            if tx.asset_code == p.asset_code && tx.amount == p.amount {
+            if same_asset(tx, p) && tx.amount == p.amount {
                 avail_db[di] = false;
                 avail_chain[ci] = false;
                 acc.matched_count += 1;
@@ -429,7 +780,7 @@ fn match_memo_group(
                 continue;
             }
             let p = &chain_payments[chain_idx];
-            if tx.asset_code == p.asset_code {
+            if same_asset(tx, p) {
                 avail_db[di] = false;
                 avail_chain[ci] = false;
                 acc.matched_count += 1;
@@ -474,6 +825,7 @@ fn match_memo_group(
                 stellar_account: tx.stellar_account.clone(),
                 amount: tx.amount.clone(),
                 asset_code: tx.asset_code.clone(),
+                asset_issuer: tx.asset_issuer.clone(),
                 memo: tx.memo.clone(),
                 created_at: tx.created_at,
                 reason: reason.clone(),
@@ -487,6 +839,7 @@ fn match_memo_group(
                 to: p.to.clone(),
                 amount: p.amount.clone(),
                 asset_code: p.asset_code.clone(),
+                asset_issuer: p.asset_issuer.clone(),
                 memo: p.memo.clone(),
                 reason: reason.clone(),
             });
@@ -499,6 +852,7 @@ fn match_memo_group(
                 stellar_account: tx.stellar_account.clone(),
                 amount: tx.amount.clone(),
                 asset_code: tx.asset_code.clone(),
+                asset_issuer: tx.asset_issuer.clone(),
                 memo: tx.memo.clone(),
                 created_at: tx.created_at,
             });
@@ -511,13 +865,14 @@ fn match_memo_group(
                 to: p.to.clone(),
                 amount: p.amount.clone(),
                 asset_code: p.asset_code.clone(),
+                asset_issuer: p.asset_issuer.clone(),
                 memo: p.memo.clone(),
             });
         }
     }
 }
 
-/// Match memo-less records by destination account + amount + asset_code.
+/// Match memo-less records by destination account + amount + asset.
 fn match_no_memo_records(
     db_indices: &[usize],
     chain_indices: &[usize],
@@ -535,8 +890,7 @@ fn match_no_memo_records(
                 continue;
             }
             let p = &chain_payments[chain_idx];
-            if p.to == tx.stellar_account && p.amount == tx.amount && p.asset_code == tx.asset_code
-            {
+            if p.to == tx.stellar_account && p.amount == tx.amount && same_asset(tx, p) {
                 avail_chain[ci] = false;
                 acc.matched_count += 1;
                 matched = true;
@@ -549,6 +903,7 @@ fn match_no_memo_records(
                 stellar_account: tx.stellar_account.clone(),
                 amount: tx.amount.clone(),
                 asset_code: tx.asset_code.clone(),
+                asset_issuer: tx.asset_issuer.clone(),
                 memo: None,
                 created_at: tx.created_at,
             });
@@ -564,6 +919,7 @@ fn match_no_memo_records(
                 to: p.to.clone(),
                 amount: p.amount.clone(),
                 asset_code: p.asset_code.clone(),
+                asset_issuer: p.asset_issuer.clone(),
                 memo: None,
             });
         }
@@ -609,6 +965,8 @@ pub struct ReconciliationJob {
     pub horizon_client: HorizonClient,
     /// Stellar account to reconcile (from config / env).
     pub stellar_account: String,
+    /// Records requested per Horizon payments page (`HORIZON_PAGE_LIMIT`).
+    pub page_limit: u32,
 }
 
 #[async_trait]
@@ -633,7 +991,8 @@ impl crate::services::scheduler::Job for ReconciliationJob {
             "Running scheduled daily reconciliation"
         );
 
-        let svc = ReconciliationService::new(self.horizon_client.clone(), self.pool.clone());
+        let svc = ReconciliationService::new(self.horizon_client.clone(), self.pool.clone())
+            .with_page_limit(self.page_limit);
         let report = svc.reconcile(&self.stellar_account, start, end).await?;
 
         let has_discrepancies = !report.missing_on_chain.is_empty()
@@ -730,6 +1089,7 @@ mod tests {
             asset_code: asset.to_string(),
             memo: memo.map(str::to_string),
             created_at: fixed_time(),
+            asset_issuer: None,
         }
     }
 
@@ -747,6 +1107,7 @@ mod tests {
             amount: amount.to_string(),
             asset_code: asset.to_string(),
             memo: memo.map(str::to_string),
+            asset_issuer: None,
         }
     }
 
@@ -808,6 +1169,7 @@ mod tests {
             asset_code: "USDC".to_string(),
             memo: Some("memo-xyz".to_string()),
             created_at: now,
+            asset_issuer: None,
         };
 
         assert_eq!(missing.id, id);
@@ -826,6 +1188,7 @@ mod tests {
             amount: "50.00".to_string(),
             asset_code: "USDC".to_string(),
             memo: Some("orphan-memo".to_string()),
+            asset_issuer: None,
         };
 
         assert_eq!(orphan.payment_id, "pay-001");
@@ -869,6 +1232,7 @@ mod tests {
                 asset_code: "XLM".to_string(),
                 memo: Some("m1".to_string()),
                 created_at: start,
+                asset_issuer: None,
             }],
             orphaned_payments: vec![OrphanedPayment {
                 payment_id: "p1".to_string(),
@@ -877,6 +1241,7 @@ mod tests {
                 amount: "5.00".to_string(),
                 asset_code: "XLM".to_string(),
                 memo: None,
+                asset_issuer: None,
             }],
             amount_mismatches: vec![],
             ambiguous_db: vec![],
@@ -896,6 +1261,160 @@ mod tests {
         assert!(deserialized.amount_mismatches.is_empty());
     }
 
+    /// Splits `to_csv` output back into `(label, header, rows)` sections.
+    fn parse_csv_sections(csv: &str) -> Vec<(String, Vec<String>, Vec<Vec<String>>)> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(csv.as_bytes());
+        let mut sections: Vec<(String, Vec<String>, Vec<Vec<String>>)> = Vec::new();
+        let mut expect_header = false;
+        for record in reader.records() {
+            let record: Vec<String> = record.unwrap().iter().map(str::to_string).collect();
+            if record.len() == 1 && CSV_SECTIONS.contains(&record[0].as_str()) {
+                sections.push((record[0].clone(), Vec::new(), Vec::new()));
+                expect_header = true;
+            } else if expect_header {
+                sections.last_mut().unwrap().1 = record;
+                expect_header = false;
+            } else {
+                sections.last_mut().unwrap().2.push(record);
+            }
+        }
+        sections
+    }
+
+    fn report_with_every_discrepancy() -> ReconciliationReport {
+        let (start, end) = make_period();
+        let db = vec![
+            make_db_tx(1, "GACC", "100.00", "USDC", Some(r#"inv, "A""#)),
+            make_db_tx(2, "GACC", "10.0000001", "USDC", Some("multi\nline")),
+        ];
+        let chain = vec![
+            make_chain_payment("cp-1", "GACC", "99.50", "USDC", Some(r#"inv, "A""#)),
+            make_chain_payment("cp-2", "GACC", "5.00", "USDC", Some("orphan, memo")),
+        ];
+        perform_matching(&db, &chain, start, end)
+    }
+
+    #[test]
+    fn test_report_csv_roundtrip() {
+        let report = report_with_every_discrepancy();
+        let csv = report.to_csv().unwrap();
+
+        // Amounts are quoted so spreadsheets keep them as exact text.
+        assert!(csv.contains(r#""10.0000001""#));
+
+        let sections = parse_csv_sections(&csv);
+        let labels: Vec<&str> = sections.iter().map(|(l, _, _)| l.as_str()).collect();
+        assert_eq!(labels, CSV_SECTIONS);
+
+        let (_, header, rows) = &sections[0];
+        assert_eq!(
+            header,
+            &[
+                "id",
+                "stellar_account",
+                "amount",
+                "asset_code",
+                "asset_issuer",
+                "memo",
+                "created_at"
+            ]
+        );
+        assert_eq!(
+            rows,
+            &[vec![
+                Uuid::from_u128(2).to_string(),
+                "GACC".to_string(),
+                "10.0000001".to_string(),
+                "USDC".to_string(),
+                String::new(),
+                "multi\nline".to_string(),
+                fixed_time().to_rfc3339(),
+            ]]
+        );
+
+        let (_, header, rows) = &sections[1];
+        assert_eq!(
+            header,
+            &[
+                "payment_id",
+                "from",
+                "to",
+                "amount",
+                "asset_code",
+                "asset_issuer",
+                "memo"
+            ]
+        );
+        assert_eq!(
+            rows,
+            &[vec![
+                "cp-2",
+                "GSRC",
+                "GACC",
+                "5.00",
+                "USDC",
+                "",
+                "orphan, memo"
+            ]]
+        );
+
+        let (_, header, rows) = &sections[2];
+        assert_eq!(
+            header,
+            &[
+                "transaction_id",
+                "payment_id",
+                "db_amount",
+                "chain_amount",
+                "memo"
+            ]
+        );
+        assert_eq!(
+            rows,
+            &[vec![
+                Uuid::from_u128(1).to_string(),
+                "cp-1".to_string(),
+                "100.00".to_string(),
+                "99.50".to_string(),
+                r#"inv, "A""#.to_string(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_multi_account_csv_has_account_column() {
+        let (start, end) = make_period();
+        let merged = MultiAccountReconciliationReport {
+            generated_at: Utc::now(),
+            period_start: start,
+            period_end: end,
+            summary: ReconciliationSummary::default(),
+            accounts: vec![
+                AccountReconciliation {
+                    account: "GACC".to_string(),
+                    report: Some(report_with_every_discrepancy()),
+                    error: None,
+                },
+                AccountReconciliation {
+                    account: "GFAIL".to_string(),
+                    report: None,
+                    error: Some("horizon unavailable".to_string()),
+                },
+            ],
+        };
+
+        let sections = parse_csv_sections(&merged.to_csv().unwrap());
+        assert_eq!(sections.len(), 3);
+        for (_, header, rows) in &sections {
+            assert_eq!(header[0], "account");
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0][0], "GACC");
+        }
+    }
+
     #[test]
     fn test_report_backward_compatible_deserialization() {
         // Old JSON without the new fields must still deserialize successfully.
@@ -1068,6 +1587,40 @@ mod tests {
         check_conservation(&report);
     }
 
+    #[test]
+    fn test_matching_distinguishes_issuers_of_same_asset_code() {
+        // Two USDC issuers share an asset code; a payment from the wrong
+        // issuer must not reconcile a row that recorded its issuer, while a
+        // legacy row without an issuer still matches on code alone.
+        let (start, end) = make_period();
+        let mut db_a = make_db_tx(1, "GACC", "10.00", "USDC", Some("m1"));
+        db_a.asset_issuer = Some("GISSUERA".to_string());
+        let mut db_b = make_db_tx(2, "GACC", "20.00", "USDC", Some("m2"));
+        db_b.asset_issuer = Some("GISSUERB".to_string());
+        let legacy = make_db_tx(3, "GACC", "30.00", "USDC", Some("m3"));
+
+        let mut chain = vec![
+            make_chain_payment("cp-1", "GACC", "10.00", "USDC", Some("m1")),
+            make_chain_payment("cp-2", "GACC", "20.00", "USDC", Some("m2")),
+            make_chain_payment("cp-3", "GACC", "30.00", "USDC", Some("m3")),
+        ];
+        for p in &mut chain {
+            p.asset_issuer = Some("GISSUERA".to_string());
+        }
+        let report = perform_matching(&[db_a, db_b, legacy], &chain, start, end);
+
+        assert_eq!(report.matched_count, 2);
+        assert_eq!(report.ambiguous_db.len(), 1);
+        assert_eq!(report.ambiguous_db[0].id, Uuid::from_u128(2));
+        assert_eq!(
+            report.ambiguous_db[0].asset_issuer.as_deref(),
+            Some("GISSUERB")
+        );
+        assert_eq!(report.ambiguous_chain.len(), 1);
+        assert_eq!(report.ambiguous_chain[0].payment_id, "cp-2");
+        check_conservation(&report);
+    }
+
     #[test]
     fn test_matching_none_memo_db_matched_by_account_amount() {
         // Memo-less DB row is matched to a memo-less chain payment via
@@ -1177,8 +1730,249 @@ mod tests {
         assert_eq!("0 0 2 * * *", "0 0 2 * * *");
     }
 
+    // ── Tracing spans ─────────────────────────────────────────────────────────
+
+    mod span_capture {
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::Subscriber;
+        use tracing_subscriber::layer::Context;
+        use tracing_subscriber::registry::LookupSpan;
+        use tracing_subscriber::Layer;
+
+        #[derive(Debug, Clone, Default)]
+        pub struct CapturedSpan {
+            pub name: String,
+            pub parent: Option<String>,
+            pub fields: HashMap<String, String>,
+        }
+
+        /// Records every span with its parent name and final field values.
+        #[derive(Clone, Default)]
+        pub struct SpanCapture(pub Arc<Mutex<HashMap<u64, CapturedSpan>>>);
+
+        struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+        impl Visit for FieldVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{value:?}"));
+            }
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+        }
+
+        impl<S> Layer<S> for SpanCapture
+        where
+            S: Subscriber + for<'a> LookupSpan<'a>,
+        {
+            fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+                let mut span = CapturedSpan {
+                    name: attrs.metadata().name().to_string(),
+                    parent: ctx
+                        .span(id)
+                        .and_then(|s| s.parent())
+                        .map(|p| p.name().to_string()),
+                    ..Default::default()
+                };
+                attrs.record(&mut FieldVisitor(&mut span.fields));
+                self.0.lock().unwrap().insert(id.into_u64(), span);
+            }
+
+            fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+                if let Some(span) = self.0.lock().unwrap().get_mut(&id.into_u64()) {
+                    values.record(&mut FieldVisitor(&mut span.fields));
+                }
+            }
+        }
+
+        impl SpanCapture {
+            pub fn find(&self, name: &str) -> Vec<CapturedSpan> {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .filter(|s| s.name == name)
+                    .cloned()
+                    .collect()
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_horizon_and_db_calls_emit_nested_spans() {
+        use span_capture::SpanCapture;
+        use tracing::Instrument;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let capture = SpanCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut server = mockito::Server::new_async().await;
+        let page2 = format!("{}/page2", server.url());
+        let _first = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(r"^/accounts/.*/payments.*".into()),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "_links": { "next": { "href": page2 } },
+                    "_embedded": { "records": [
+                        { "id": "p1", "from": "GA", "to": "GB", "amount": "10", "asset_code": "USD" }
+                    ] }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _second = server
+            .mock("GET", "/page2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(payments_body(&[]))
+            .create_async()
+            .await;
+        let _account = server
+            .mock("GET", mockito::Matcher::Regex(r"^/accounts/[^/]*$".into()))
+            .with_status(404)
+            .create_async()
+            .await;
+
+        // Nothing listens on port 1, so the DB query fails fast.
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_secs(1))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/postgres")
+            .unwrap();
+        let client = HorizonClient::new(server.url());
+        let svc = ReconciliationService::new(client.clone(), pool);
+        let (start, end) = make_period();
+
+        async {
+            let payments = svc.fetch_chain_payments("GACC123", start, end).await;
+            assert_eq!(payments.unwrap().len(), 1);
+            assert!(svc
+                .fetch_db_transactions("GACC123", start, end)
+                .await
+                .is_err());
+            let _ = client.get_account("GACC123").await;
+        }
+        .instrument(tracing::info_span!("http.request"))
+        .await;
+
+        let chain = capture.find("reconciliation.fetch_chain_payments");
+        assert_eq!(chain.len(), 1);
+        let chain = &chain[0];
+        assert_eq!(chain.parent.as_deref(), Some("http.request"));
+        assert_eq!(chain.fields["stellar.account"], "GACC123");
+        assert_eq!(chain.fields["horizon.pages"], "2");
+        assert_eq!(chain.fields["horizon.payments"], "1");
+        assert!(chain.fields.contains_key("duration_ms"));
+
+        let mut pages = capture.find("horizon.get_latest_account_payments");
+        assert_eq!(pages.len(), 1);
+        pages.extend(capture.find("horizon.get_payments_page"));
+        assert_eq!(pages.len(), 2);
+        assert!(pages.iter().all(|p| {
+            p.parent.as_deref() == Some("reconciliation.fetch_chain_payments")
+                && p.fields["http.status_code"] == "200"
+        }));
+
+        let db = capture.find("reconciliation.fetch_db_transactions");
+        assert_eq!(db.len(), 1);
+        assert_eq!(db[0].parent.as_deref(), Some("http.request"));
+        assert_eq!(db[0].fields["stellar.account"], "GACC123");
+        assert!(db[0].fields.contains_key("duration_ms"));
+
+        let account = capture.find("horizon.get_account");
+        assert_eq!(account.len(), 1);
+        assert_eq!(account[0].parent.as_deref(), Some("http.request"));
+        assert_eq!(account[0].fields["http.status_code"], "404");
+        assert!(account[0].fields.contains_key("duration_ms"));
+    }
+
     // ── Horizon HTTP mock tests ───────────────────────────────────────────────
 
+    #[tokio::test]
+    async fn test_fetch_chain_payments_stops_paging_before_window() {
+        // Newest-first pages: page 1 straddles the window, page 2 is entirely
+        // older than `start`, so page 3 must never be requested.
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let (start, end) = make_period();
+        let record = |id: &str, at: DateTime<Utc>| {
+            let mut r = payment_record(id, "GSRC", "GACC", "1.00", "USDC", None);
+            r["created_at"] = serde_json::json!(at.to_rfc3339());
+            r
+        };
+        let page = |records: Vec<serde_json::Value>, next: &str| {
+            serde_json::json!({
+                "_links": { "next": { "href": format!("{base}{next}") } },
+                "_embedded": { "records": records }
+            })
+            .to_string()
+        };
+
+        let first = server
+            .mock("GET", "/accounts/GACC/payments")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("order".into(), "desc".into()),
+                mockito::Matcher::UrlEncoded("limit".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page(
+                vec![
+                    record("after-end", end + Duration::hours(1)),
+                    record("in-window", start + Duration::hours(1)),
+                ],
+                "/page2",
+            ))
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/page2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page(
+                vec![
+                    record("before-start", start - Duration::hours(1)),
+                    record("older", start - Duration::hours(2)),
+                ],
+                "/page3",
+            ))
+            .create_async()
+            .await;
+        let third = server
+            .mock("GET", "/page3")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(payments_body(&[]))
+            .expect(0)
+            .create_async()
+            .await;
+
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://postgres@127.0.0.1:1/postgres")
+            .unwrap();
+        let svc =
+            ReconciliationService::new(HorizonClient::new(server.url()), pool).with_page_limit(2);
+
+        let payments = svc.fetch_chain_payments("GACC", start, end).await.unwrap();
+
+        let ids: Vec<&str> = payments.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["in-window"]);
+        first.assert_async().await;
+        second.assert_async().await;
+        third.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_fetch_chain_payments_horizon_error_returns_err() {
         let mut server = mockito::Server::new_async().await;
@@ -1364,6 +2158,83 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL and migrations"]
+    async fn test_reconcile_with_pending_status_matches_pending_transactions() {
+        let pool = sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let account = "GPENDING_ACCOUNT";
+        let (start, end) = make_period();
+        let settled = format!("settled-memo-{}", Uuid::new_v4());
+        let stuck = format!("stuck-memo-{}", Uuid::new_v4());
+        for (memo, status) in [(&settled, "pending"), (&stuck, "processing")] {
+            sqlx::query(
+                "INSERT INTO transactions (id, stellar_account, amount, asset_code, status, memo, created_at, updated_at)
+                 VALUES ($1, $2, $3::numeric, $4, $5, $6, $7, $7)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(account)
+            .bind("12.50")
+            .bind("USDC")
+            .bind(status)
+            .bind(memo)
+            .bind(start + chrono::Duration::minutes(10))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let record = payment_record(
+            "pay-pending-001",
+            "GSRC",
+            account,
+            "12.50",
+            "USDC",
+            Some(&settled),
+        );
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(r"^/accounts/.*/payments.*".into()),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(payments_body(&[record]))
+            .create_async()
+            .await;
+
+        // By default only completed transactions are reconciled.
+        let default_svc =
+            ReconciliationService::new(HorizonClient::new(server.url()), pool.clone());
+        let report = default_svc.reconcile(account, start, end).await.unwrap();
+        assert_eq!(report.total_db_transactions, 0);
+        assert_eq!(report.orphaned_payments.len(), 1);
+
+        let svc = ReconciliationService::new(HorizonClient::new(server.url()), pool.clone())
+            .with_statuses(vec![
+                TransactionStatus::Pending,
+                TransactionStatus::Processing,
+            ]);
+        let report = svc.reconcile(account, start, end).await.unwrap();
+        assert_eq!(report.total_db_transactions, 2);
+        assert_eq!(report.matched_count, 1);
+        assert!(report.orphaned_payments.is_empty());
+        assert_eq!(report.missing_on_chain.len(), 1);
+        assert_eq!(
+            report.missing_on_chain[0].memo.as_deref(),
+            Some(stuck.as_str())
+        );
+        check_conservation(&report);
+
+        sqlx::query("DELETE FROM transactions WHERE stellar_account = $1")
+            .bind(account)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     #[ignore = "requires DATABASE_URL and migrations"]
     async fn test_reconcile_detects_amount_mismatch() {
@@ -1707,6 +2578,123 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_reconcile_accounts_reports_failures_per_account() {
+        // Nothing listens on port 1, so every account fails at the DB step.
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_secs(1))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/postgres")
+            .unwrap();
+        let svc = ReconciliationService::new(HorizonClient::new("http://127.0.0.1:1".into()), pool)
+            .with_account_concurrency(2);
+        let (start, end) = make_period();
+        let accounts = vec![
+            "GFIRST".to_string(),
+            "GSECOND".to_string(),
+            "GTHIRD".to_string(),
+        ];
+
+        let merged = svc.reconcile_accounts(&accounts, start, end).await.unwrap();
+
+        let order: Vec<_> = merged.accounts.iter().map(|a| a.account.as_str()).collect();
+        assert_eq!(order, ["GFIRST", "GSECOND", "GTHIRD"]);
+        assert!(merged
+            .accounts
+            .iter()
+            .all(|a| a.report.is_none() && a.error.is_some()));
+        assert_eq!(merged.summary.accounts, 3);
+        assert_eq!(merged.summary.failed_accounts, 3);
+        assert_eq!(merged.summary.total_db_transactions, 0);
+
+        assert!(svc.reconcile_accounts(&[], start, end).await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires DATABASE_URL and migrations"]
+    async fn test_reconcile_accounts_merges_each_accounts_discrepancies() {
+        let pool = sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let (start, end) = make_period();
+        let missing_account = format!("GMULTI_MISSING_{}", Uuid::new_v4().simple());
+        let orphan_account = format!("GMULTI_ORPHAN_{}", Uuid::new_v4().simple());
+
+        // First account: a completed DB row that never reached the chain.
+        let missing_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO transactions (id, stellar_account, amount, asset_code, status, memo, created_at, updated_at)
+             VALUES ($1, $2, 40.00, 'USDC', 'completed', 'multi-missing', $3, $3)",
+        )
+        .bind(missing_id)
+        .bind(&missing_account)
+        .bind(start + chrono::Duration::minutes(10))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Second account: a chain payment with no DB row.
+        let mut server = mockito::Server::new_async().await;
+        let _missing_mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(format!(r"^/accounts/{missing_account}/payments.*")),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(payments_body(&[]))
+            .create_async()
+            .await;
+        let _orphan_mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(format!(r"^/accounts/{orphan_account}/payments.*")),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(payments_body(&[payment_record(
+                "pay-multi-orphan",
+                "GSRC",
+                &orphan_account,
+                "12.00",
+                "USDC",
+                Some("multi-orphan"),
+            )]))
+            .create_async()
+            .await;
+
+        let svc = ReconciliationService::new(HorizonClient::new(server.url()), pool);
+        let merged = svc
+            .reconcile_accounts(
+                &[missing_account.clone(), orphan_account.clone()],
+                start,
+                end,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(merged.summary.accounts, 2);
+        assert_eq!(merged.summary.failed_accounts, 0);
+        assert_eq!(merged.summary.missing_on_chain, 1);
+        assert_eq!(merged.summary.orphaned_payments, 1);
+
+        let first = &merged.accounts[0];
+        assert_eq!(first.account, missing_account);
+        let first_report = first.report.as_ref().unwrap();
+        assert_eq!(first_report.missing_on_chain.len(), 1);
+        assert_eq!(first_report.missing_on_chain[0].id, missing_id);
+        assert!(first_report.orphaned_payments.is_empty());
+
+        let second = &merged.accounts[1];
+        assert_eq!(second.account, orphan_account);
+        let second_report = second.report.as_ref().unwrap();
+        assert_eq!(second_report.orphaned_payments.len(), 1);
+        assert_eq!(
+            second_report.orphaned_payments[0].payment_id,
+            "pay-multi-orphan"
+        );
+        assert!(second_report.missing_on_chain.is_empty());
+    }
 } // end mod tests
 
 // ── Property tests ────────────────────────────────────────────────────────────
@@ -1757,6 +2745,7 @@ mod property_tests {
                 asset_code: asset,
                 memo,
                 created_at: fixed(),
+                asset_issuer: None,
             }
         }
     }
@@ -1774,6 +2763,7 @@ mod property_tests {
                 amount,
                 asset_code: asset,
                 memo,
+                asset_issuer: None,
             }
         }
     }
@@ -1806,6 +2796,7 @@ mod property_tests {
                         asset_code: if i % 3 == 0 { "USDC" } else { "XLM" }.to_string(),
                         memo,
                         created_at: t,
+                        asset_issuer: None,
                     }
                 })
                 .collect();
@@ -1825,6 +2816,7 @@ mod property_tests {
                         amount: if i % 2 == 0 { "100.00" } else { "300.00" }.to_string(),
                         asset_code: if i % 3 == 0 { "USDC" } else { "XLM" }.to_string(),
                         memo,
+                        asset_issuer: None,
                     }
                 })
                 .collect();
@@ -1868,6 +2860,7 @@ mod property_tests {
                     asset_code: "USDC".to_string(),
                     memo: Some(format!("memo-{}", i % 3)),
                     created_at: t,
+                    asset_issuer: None,
                 })
                 .collect();
             let chain_payments: Vec<ChainPayment> = (0..chain_count)
@@ -1878,6 +2871,7 @@ mod property_tests {
                     amount: if i % 2 == 0 { "100.00" } else { "999.00" }.to_string(),
                     asset_code: "USDC".to_string(),
                     memo: Some(format!("memo-{}", i % 3)),
+                    asset_issuer: None,
                 })
                 .collect();
 
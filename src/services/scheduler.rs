@@ -1,11 +1,11 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use cron::Schedule;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::str::FromStr;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Represents a scheduled job that can be executed at specific intervals
 #[async_trait]
@@ -20,11 +20,81 @@ pub trait Job: Send + Sync {
     async fn execute(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
 }
 
+/// How long [`JobScheduler::stop`] waits for in-flight executions by default.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Executions kept in [`JobScheduler::job_history`]; older entries are dropped.
+const JOB_HISTORY_CAPACITY: usize = 100;
+
+/// How one execution of a job ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobOutcome {
+    Succeeded,
+    Failed(String),
+    /// Still running when [`JobScheduler::stop`] gave up waiting; the
+    /// execution was aborted.
+    Interrupted,
+}
+
+/// One recorded execution of a job.
+#[derive(Debug, Clone)]
+pub struct JobRun {
+    pub name: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub outcome: JobOutcome,
+}
+
+/// Latest execution times of one job, kept for as long as the scheduler runs
+/// so they survive the job's runs rotating out of the bounded history.
+#[derive(Debug, Clone, Default)]
+struct LastRuns {
+    last_run: Option<DateTime<Utc>>,
+    last_success: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+/// Recent executions plus the ones currently running, shared with job loops.
+#[derive(Default)]
+struct JobHistory {
+    runs: VecDeque<JobRun>,
+    in_flight: HashMap<String, DateTime<Utc>>,
+    last: HashMap<String, LastRuns>,
+}
+
+impl JobHistory {
+    fn record(&mut self, name: &str, started_at: DateTime<Utc>, outcome: JobOutcome) {
+        let finished_at = Utc::now();
+        let last = self.last.entry(name.to_string()).or_default();
+        last.last_run = Some(started_at);
+        last.last_error = match &outcome {
+            JobOutcome::Succeeded => {
+                last.last_success = Some(finished_at);
+                None
+            }
+            JobOutcome::Failed(e) => Some(e.clone()),
+            JobOutcome::Interrupted => Some("interrupted by shutdown".to_string()),
+        };
+
+        if self.runs.len() == JOB_HISTORY_CAPACITY {
+            self.runs.pop_front();
+        }
+        self.runs.push_back(JobRun {
+            name: name.to_string(),
+            started_at,
+            finished_at,
+            outcome,
+        });
+    }
+}
+
 /// A job scheduler that manages cron-based recurring tasks
 pub struct JobScheduler {
     jobs: Arc<Mutex<HashMap<String, Arc<dyn Job>>>>,
     active_handles: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
     shutdown_tx: tokio::sync::broadcast::Sender<()>,
+    history: Arc<Mutex<JobHistory>>,
+    shutdown_timeout: std::time::Duration,
 }
 
 impl Default for JobScheduler {
@@ -41,9 +111,18 @@ impl JobScheduler {
             jobs: Arc::new(Mutex::new(HashMap::new())),
             active_handles: Arc::new(Mutex::new(HashMap::new())),
             shutdown_tx,
+            history: Arc::new(Mutex::new(JobHistory::default())),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
         }
     }
 
+    /// How long [`stop`](Self::stop) waits for in-flight executions before
+    /// aborting them. Defaults to [`DEFAULT_SHUTDOWN_TIMEOUT`].
+    pub fn with_shutdown_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
     /// Register a new job with the scheduler
     pub async fn register_job(
         &self,
@@ -77,6 +156,7 @@ impl JobScheduler {
                 self.shutdown_tx.clone(),
                 shutdown_rx,
                 active_handles_clone,
+                Arc::clone(&self.history),
             ));
 
             active_handles.lock().await.insert(name.clone(), handle);
@@ -87,6 +167,10 @@ impl JobScheduler {
     }
 
     /// Stop the scheduler and all running jobs gracefully
+    ///
+    /// No new executions start once this is called. Executions already
+    /// running get up to the shutdown timeout to finish; any still running
+    /// after that are aborted and recorded as [`JobOutcome::Interrupted`].
     pub async fn stop(&self) -> Result<(), Box<dyn std::error::Error + Sync>> {
         info!("Stopping job scheduler...");
 
@@ -96,29 +180,52 @@ impl JobScheduler {
         // Wait for all active handles to finish
         let handles: Vec<_> = {
             let mut active_handles = self.active_handles.lock().await;
-            active_handles.drain().map(|(_, handle)| handle).collect()
+            active_handles.drain().collect()
         };
 
-        // Wait for all tasks to complete
-        for handle in handles {
-            if let Err(e) = handle.await {
-                error!("Error waiting for job task to finish: {}", e);
+        let deadline = tokio::time::Instant::now() + self.shutdown_timeout;
+        for (name, mut handle) in handles {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => error!("Error waiting for job task to finish: {}", e),
+                Err(_) => {
+                    warn!(
+                        "Job '{}' still running after {:?}, aborting",
+                        name, self.shutdown_timeout
+                    );
+                    handle.abort();
+                    let _ = handle.await;
+                }
             }
         }
 
+        // Whatever is still marked in flight was cut off by the abort above.
+        let mut history = self.history.lock().await;
+        let interrupted: Vec<_> = history.in_flight.drain().collect();
+        for (name, started_at) in interrupted {
+            history.record(&name, started_at, JobOutcome::Interrupted);
+        }
+
         info!("Job scheduler stopped");
         Ok(())
     }
 
+    /// Recent executions of all jobs, oldest first.
+    pub async fn job_history(&self) -> Vec<JobRun> {
+        self.history.lock().await.runs.iter().cloned().collect()
+    }
+
     /// Get status information about all registered jobs
     pub async fn get_job_status(&self) -> HashMap<String, JobStatus> {
         let jobs = self.jobs.lock().await;
         let active_handles = self.active_handles.lock().await;
+        let history = self.history.lock().await;
         let mut status = HashMap::new();
 
         for (name, job) in jobs.iter() {
             // Parse the schedule to get the next run time
             let next_run = Self::get_next_run_time(job.schedule());
+            let last = history.last.get(name).cloned().unwrap_or_default();
 
             status.insert(
                 name.clone(),
@@ -127,6 +234,9 @@ impl JobScheduler {
                     schedule: job.schedule().to_string(),
                     next_run,
                     is_active: active_handles.contains_key(name),
+                    last_run: last.last_run,
+                    last_success: last.last_success,
+                    last_error: last.last_error,
                 },
             );
         }
@@ -141,6 +251,7 @@ impl JobScheduler {
         _shutdown_tx: tokio::sync::broadcast::Sender<()>,
         mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
         active_handles: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+        history: Arc<Mutex<JobHistory>>,
     ) {
         info!("Starting job '{}' with schedule: {}", name, job.schedule());
 
@@ -183,13 +294,20 @@ impl JobScheduler {
             };
 
             // Execute the job
-            match job.execute().await {
+            let started_at = Utc::now();
+            history
+                .lock()
+                .await
+                .in_flight
+                .insert(name.clone(), started_at);
+            let outcome = match job.execute().await {
                 Ok(()) => {
                     info!(
                         "Job '{}' executed successfully at {}",
                         name,
                         next_run_time.format("%Y-%m-%d %H:%M:%S")
                     );
+                    JobOutcome::Succeeded
                 }
                 Err(e) => {
                     error!(
@@ -198,8 +316,12 @@ impl JobScheduler {
                         next_run_time.format("%Y-%m-%d %H:%M:%S"),
                         e
                     );
+                    JobOutcome::Failed(e.to_string())
                 }
-            }
+            };
+            let mut recorded = history.lock().await;
+            recorded.in_flight.remove(&name);
+            recorded.record(&name, started_at, outcome);
         }
     }
 
@@ -216,12 +338,18 @@ impl JobScheduler {
 }
 
 /// Status information for a scheduled job
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct JobStatus {
     pub name: String,
     pub schedule: String,
     pub next_run: Option<DateTime<Utc>>,
     pub is_active: bool,
+    /// When the most recent execution started.
+    pub last_run: Option<DateTime<Utc>>,
+    /// When the most recent successful execution finished.
+    pub last_success: Option<DateTime<Utc>>,
+    /// Why the most recent execution failed; `None` once a later one succeeds.
+    pub last_error: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
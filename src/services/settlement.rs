@@ -1,9 +1,10 @@
-use crate::db::models::{Asset, Settlement};
+use crate::db::audit::{AuditLog, ENTITY_SETTLEMENT};
+use crate::db::models::{Settlement, SettlementConfig, Transaction};
 use crate::db::queries;
 use crate::error::AppError;
 use crate::validation::state_transitions::{is_valid_transition, SETTLEMENT_TRANSITIONS};
 use bigdecimal::BigDecimal;
-use chrono::Utc;
+use chrono::{DateTime, Datelike, Utc, Weekday};
 use opentelemetry::metrics::Histogram;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -22,15 +23,120 @@ fn map_db_err(e: sqlx::Error) -> AppError {
     }
 }
 
-/// Maps update_settlement_status result, converting RowNotFound to StaleTransition
-/// when it indicates a concurrent modification during atomic update.
-fn map_update_settlement_err(e: sqlx::Error) -> AppError {
+/// Maps update_settlement_status result, converting RowNotFound to Conflict:
+/// another writer changed the settlement after `expected_version` was read.
+fn map_update_settlement_err(id: Uuid, expected_version: i32, e: sqlx::Error) -> AppError {
     match e {
-        sqlx::Error::RowNotFound => AppError::StaleTransition,
+        sqlx::Error::RowNotFound => AppError::Conflict(format!(
+            "settlement {id} was modified concurrently (expected version {expected_version})"
+        )),
         other => AppError::DatabaseError(other.to_string()),
     }
 }
 
+/// Splits transactions of one asset code into per-issuer groups, keeping the
+/// query order (issuer, then creation time) within each group.
+fn group_by_issuer(transactions: Vec<Transaction>) -> Vec<(Option<String>, Vec<Transaction>)> {
+    let mut groups: Vec<(Option<String>, Vec<Transaction>)> = Vec::new();
+    for t in transactions {
+        match groups
+            .iter_mut()
+            .find(|(issuer, _)| *issuer == t.asset_issuer)
+        {
+            Some((_, txs)) => txs.push(t),
+            None => groups.push((t.asset_issuer.clone(), vec![t])),
+        }
+    }
+    groups
+}
+
+/// How often an asset may settle, from `settlement_config.schedule`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SettlementSchedule {
+    /// On every settlement run.
+    #[default]
+    Hourly,
+    /// At most once per UTC day.
+    Daily,
+    /// At most once, on Mondays (UTC).
+    Weekly,
+}
+
+impl SettlementSchedule {
+    pub const ALL: [Self; 3] = [Self::Hourly, Self::Daily, Self::Weekly];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|schedule| schedule.as_str() == s)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+
+    /// Whether an asset last settled at `last_settled` may settle at `now`.
+    pub fn is_due(self, last_settled: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        let not_today = last_settled.is_none_or(|last| last.date_naive() < now.date_naive());
+        match self {
+            Self::Hourly => true,
+            Self::Daily => not_today,
+            Self::Weekly => now.weekday() == Weekday::Mon && not_today,
+        }
+    }
+}
+
+/// Settlement settings of one asset after applying service defaults to its
+/// `settlement_config` row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetSettlementRules {
+    pub min_tx_count: usize,
+    pub min_amount: Option<BigDecimal>,
+    pub schedule: SettlementSchedule,
+    pub target_account: Option<String>,
+}
+
+impl AssetSettlementRules {
+    fn resolve(config: Option<SettlementConfig>, default_min_tx_count: usize) -> Self {
+        let Some(config) = config else {
+            return Self {
+                min_tx_count: default_min_tx_count,
+                min_amount: None,
+                schedule: SettlementSchedule::default(),
+                target_account: None,
+            };
+        };
+        Self {
+            min_tx_count: config
+                .min_tx_count
+                .map_or(default_min_tx_count, |n| n.max(1) as usize),
+            min_amount: config.min_amount,
+            schedule: SettlementSchedule::parse(&config.schedule).unwrap_or_default(),
+            target_account: config.target_account,
+        }
+    }
+
+    /// Whether a per-issuer group of unsettled transactions is large enough.
+    fn is_met_by(&self, txs: &[Transaction]) -> bool {
+        if txs.len() < self.min_tx_count {
+            return false;
+        }
+        match &self.min_amount {
+            Some(min_amount) => {
+                txs.iter()
+                    .map(|t| &t.amount)
+                    .fold(BigDecimal::from(0), |acc, x| acc + x)
+                    >= *min_amount
+            }
+            None => true,
+        }
+    }
+}
+
 pub struct SettlementService {
     pool: PgPool,
     max_batch_size: usize,
@@ -149,10 +255,19 @@ impl SettlementService {
         Ok(())
     }
 
+    /// Settlement rules for `asset_code`: its `settlement_config` row, with
+    /// this service's defaults for anything not configured.
+    pub async fn rules_for(&self, asset_code: &str) -> Result<AssetSettlementRules, AppError> {
+        let config = queries::get_settlement_config(&self.pool, asset_code)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(AssetSettlementRules::resolve(config, self.min_tx_count))
+    }
+
     /// Run settlement for all assets with completed, unsettled transactions.
-    /// Respects each asset's `settlement_schedule` — assets configured as
-    /// "hourly" are always eligible; "daily" assets only settle once per day;
-    /// "weekly" assets only settle on Mondays.
+    /// Respects each asset's configured schedule — "hourly" assets (and
+    /// unconfigured ones) are always eligible; "daily" assets only settle
+    /// once per day; "weekly" assets only settle on Mondays.
     pub async fn run_settlements(&self) -> Result<Vec<Settlement>, AppError> {
         let start = std::time::Instant::now();
 
@@ -160,18 +275,23 @@ impl SettlementService {
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        // Load asset configs so we can apply per-asset schedules
-        let assets = Asset::fetch_all(&self.pool)
-            .await
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-        let _asset_map: std::collections::HashMap<String, Asset> = assets
-            .into_iter()
-            .map(|a| (a.asset_code.clone(), a))
-            .collect();
-
-        let _now = Utc::now();
+        let now = Utc::now();
         let mut results = Vec::new();
         for asset_code in &asset_codes {
+            let schedule = self.rules_for(asset_code).await?.schedule;
+            if schedule != SettlementSchedule::Hourly {
+                let last_settled = queries::get_last_settlement_at(&self.pool, asset_code)
+                    .await
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                if !schedule.is_due(last_settled, now) {
+                    tracing::debug!(
+                        asset = %asset_code,
+                        schedule = schedule.as_str(),
+                        "Settlement not due for asset"
+                    );
+                    continue;
+                }
+            }
             match self.settle_asset(asset_code).await {
                 Ok(settlements) => results.extend(settlements),
                 Err(e) => tracing::error!("Failed to settle asset {:?}: {:?}", asset_code, e),
@@ -191,10 +311,18 @@ impl SettlementService {
     /// Settle transactions for a specific asset, splitting into multiple settlements
     /// when the number of transactions exceeds `max_batch_size`.
     ///
-    /// Returns an empty `Vec` when there are fewer than `min_tx_count`
-    /// transactions.  Returns `Err` on any database or domain-level failure.
+    /// Returns an empty `Vec` when there are fewer than the asset's
+    /// `min_tx_count` transactions or their total is below its `min_amount`.
+    /// Settlements record the asset's configured `target_account`. Returns
+    /// `Err` on any database or domain-level failure.
+    ///
+    /// Safe to retry or run concurrently: the run settles inside one database
+    /// transaction, skipping rows another run has locked and only assigning
+    /// rows still without a `settlement_id`, so no transaction is settled
+    /// twice.
     pub async fn settle_asset(&self, asset_code: &str) -> Result<Vec<Settlement>, AppError> {
         let start = std::time::Instant::now();
+        let rules = self.rules_for(asset_code).await?;
 
         let mut tx = self
             .pool
@@ -208,18 +336,30 @@ impl SettlementService {
             .await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        if unsettled.len() < self.min_tx_count {
-            tx.rollback().await.map_err(map_db_err)?;
-            if unsettled.is_empty() {
-                tracing::info!("No transactions to settle for asset {}", asset_code);
-            } else {
+        // Same-code assets from different issuers are different assets; each
+        // issuer settles on its own and must meet the minimums by itself.
+        let (groups, below_minimum): (Vec<_>, Vec<_>) = group_by_issuer(unsettled)
+            .into_iter()
+            .partition(|(_, txs)| rules.is_met_by(txs));
+
+        for (issuer, txs) in &below_minimum {
+            if !txs.is_empty() {
                 tracing::info!(
-                    "Skipping settlement for asset {}: {} transaction(s) below minimum {}",
+                    "Skipping settlement for asset {} (issuer {:?}): {} transaction(s) below minimum {} or total below minimum {:?}",
                     asset_code,
-                    unsettled.len(),
-                    self.min_tx_count
+                    issuer,
+                    txs.len(),
+                    rules.min_tx_count,
+                    rules.min_amount
                 );
             }
+        }
+
+        if groups.is_empty() {
+            tx.rollback().await.map_err(map_db_err)?;
+            if below_minimum.is_empty() {
+                tracing::info!("No transactions to settle for asset {}", asset_code);
+            }
 
             // Record metrics for skipped settlement
             let duration_ms = start.elapsed().as_millis() as f64;
@@ -234,10 +374,14 @@ impl SettlementService {
             return Ok(vec![]);
         }
 
-        let total_tx = unsettled.len();
-        let batch_count = total_tx.div_ceil(self.max_batch_size);
+        let total_tx: usize = groups.iter().map(|(_, txs)| txs.len()).sum();
+        let batch_count: usize = groups
+            .iter()
+            .map(|(_, txs)| txs.len().div_ceil(self.max_batch_size))
+            .sum();
         tracing::info!(
             asset = %asset_code,
+            issuers = groups.len(),
             total_transactions = total_tx,
             batch_size = self.max_batch_size,
             batches = batch_count,
@@ -246,7 +390,11 @@ impl SettlementService {
 
         let mut settlements = Vec::with_capacity(batch_count);
 
-        for (batch_idx, chunk) in unsettled.chunks(self.max_batch_size).enumerate() {
+        let mut batches: Vec<(&Option<String>, &[Transaction])> = Vec::with_capacity(batch_count);
+        for (issuer, txs) in &groups {
+            batches.extend(txs.chunks(self.max_batch_size).map(|chunk| (issuer, chunk)));
+        }
+        for (batch_idx, (issuer, chunk)) in batches.into_iter().enumerate() {
             let tx_count = chunk.len() as i32;
             let total_amount: BigDecimal = chunk
                 .iter()
@@ -270,6 +418,9 @@ impl SettlementService {
                 original_total_amount: None,
                 reviewed_by: None,
                 reviewed_at: None,
+                asset_issuer: issuer.clone(),
+                version: 1,
+                target_account: rules.target_account.clone(),
             };
 
             let saved = queries::insert_settlement(&mut tx, &settlement)
@@ -277,12 +428,41 @@ impl SettlementService {
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
             let tx_ids: Vec<Uuid> = chunk.iter().map(|t| t.id).collect();
-            queries::update_transactions_settlement(&mut tx, &tx_ids, saved.id)
+            let updated = queries::update_transactions_settlement(&mut tx, &tx_ids, saved.id)
                 .await
                 .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            // The rows are locked, so this only trips if another run settled
+            // them regardless; dropping `tx` rolls the whole run back.
+            if updated.len() != tx_ids.len() {
+                return Err(AppError::Conflict(format!(
+                    "{} of {} transactions for asset {} were already settled",
+                    tx_ids.len() - updated.len(),
+                    tx_ids.len(),
+                    asset_code
+                )));
+            }
+
+            AuditLog::log(
+                &mut tx,
+                saved.id,
+                ENTITY_SETTLEMENT,
+                "settlement_created",
+                None,
+                Some(serde_json::json!({
+                    "asset_code": saved.asset_code,
+                    "asset_issuer": saved.asset_issuer,
+                    "total_amount": saved.total_amount.to_string(),
+                    "tx_count": saved.tx_count,
+                    "transaction_ids": updated,
+                })),
+                "system",
+            )
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
             tracing::info!(
                 asset = %asset_code,
+                asset_issuer = ?issuer,
                 settlement_id = %saved.id,
                 batch = batch_idx + 1,
                 total_batches = batch_count,
@@ -299,6 +479,7 @@ impl SettlementService {
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         queries::invalidate_caches_for_asset(asset_code).await;
+        queries::invalidate_settlement_caches().await;
 
         // Record metrics for the settle_asset operation
         let duration_ms = start.elapsed().as_millis() as f64;
@@ -314,9 +495,8 @@ impl SettlementService {
         Ok(settlements)
     }
 
-    /// Change a settlement's status (dispute, adjust, void, etc.).
-    /// Validates the transition before delegating to the query layer which
-    /// handles atomic validation (within the lock), audit logging, and releasing transactions on void.
+    /// Change a settlement's status (dispute, adjust, void, etc.) from
+    /// whatever version is current. See [`Self::update_status_at_version`].
     pub async fn update_status(
         &self,
         id: Uuid,
@@ -325,9 +505,26 @@ impl SettlementService {
         new_total: Option<&BigDecimal>,
         actor: &str,
     ) -> Result<Settlement, AppError> {
-        // Pre-flight validation using unified state machine (no lock yet).
-        // This provides early feedback but is not relied upon for correctness.
-        // The actual correctness check happens inside the locked transaction in the query layer.
+        self.update_status_at_version(id, None, new_status, reason, new_total, actor)
+            .await
+    }
+
+    /// Change a settlement's status, provided it is still at
+    /// `expected_version` (or at the version read here when `None`).
+    /// Validates the transition before delegating to the query layer, which
+    /// applies the update only if the version is unchanged, audit-logs it and
+    /// releases transactions on void.
+    ///
+    /// Returns [`AppError::Conflict`] when another update won the race.
+    pub async fn update_status_at_version(
+        &self,
+        id: Uuid,
+        expected_version: Option<i32>,
+        new_status: &str,
+        reason: Option<&str>,
+        new_total: Option<&BigDecimal>,
+        actor: &str,
+    ) -> Result<Settlement, AppError> {
         let current = queries::get_settlement(&self.pool, id).await.map_err(|e| {
             if matches!(e, sqlx::Error::RowNotFound) {
                 AppError::NotFound(format!("settlement {id}"))
@@ -335,7 +532,20 @@ impl SettlementService {
                 AppError::DatabaseError(e.to_string())
             }
         })?;
+        let expected_version = expected_version.unwrap_or(current.version);
+        if current.version != expected_version {
+            return Err(map_update_settlement_err(
+                id,
+                expected_version,
+                sqlx::Error::RowNotFound,
+            ));
+        }
 
+        if current.status == new_status {
+            return Err(AppError::Conflict(format!(
+                "settlement {id} is already {new_status}"
+            )));
+        }
         if !is_valid_transition(&current.status, new_status, SETTLEMENT_TRANSITIONS) {
             return Err(AppError::BadRequest(format!(
                 "invalid transition: {} -> {}",
@@ -343,17 +553,19 @@ impl SettlementService {
             )));
         }
 
-        queries::update_settlement_status(
+        let updated = queries::update_settlement_status(
             &self.pool,
             id,
-            &current.status,
+            expected_version,
             new_status,
             reason,
             new_total,
             actor,
         )
         .await
-        .map_err(map_update_settlement_err)
+        .map_err(|e| map_update_settlement_err(id, expected_version, e))?;
+        queries::invalidate_settlement_caches().await;
+        Ok(updated)
     }
 }
 
@@ -382,6 +594,9 @@ mod tests {
             memo_type: None,
             metadata: None,
             trace_id: None,
+            asset_issuer: None,
+            deleted_at: None,
+            tenant_id: None,
         }
     }
 
@@ -505,4 +720,49 @@ mod tests {
         // We can't easily test the actual recording in unit tests, but we can verify the method exists
         assert!(std::mem::size_of::<Histogram<f64>>() > 0);
     }
+
+    #[test]
+    fn schedule_is_due() {
+        use chrono::TimeZone;
+        // 2026-10-19 is a Monday.
+        let monday = Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap();
+        let tuesday = monday + chrono::Duration::days(1);
+        let earlier_today = monday - chrono::Duration::hours(2);
+        let yesterday = monday - chrono::Duration::days(1);
+
+        assert!(SettlementSchedule::Hourly.is_due(Some(earlier_today), monday));
+        assert!(SettlementSchedule::Daily.is_due(None, tuesday));
+        assert!(SettlementSchedule::Daily.is_due(Some(yesterday), monday));
+        assert!(!SettlementSchedule::Daily.is_due(Some(earlier_today), monday));
+        assert!(SettlementSchedule::Weekly.is_due(Some(yesterday), monday));
+        assert!(!SettlementSchedule::Weekly.is_due(Some(earlier_today), monday));
+        assert!(!SettlementSchedule::Weekly.is_due(None, tuesday));
+    }
+
+    #[test]
+    fn rules_fall_back_to_service_defaults() {
+        let defaults = AssetSettlementRules::resolve(None, 3);
+        assert_eq!(defaults.min_tx_count, 3);
+        assert_eq!(defaults.min_amount, None);
+        assert_eq!(defaults.schedule, SettlementSchedule::Hourly);
+        assert_eq!(defaults.target_account, None);
+
+        let configured = AssetSettlementRules::resolve(
+            Some(SettlementConfig {
+                asset_code: "USDC".to_string(),
+                min_amount: Some(BigDecimal::from(100)),
+                min_tx_count: None,
+                schedule: "daily".to_string(),
+                target_account: Some("GTARGET".to_string()),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }),
+            3,
+        );
+        assert_eq!(configured.min_tx_count, 3);
+        assert_eq!(configured.schedule, SettlementSchedule::Daily);
+        assert!(!configured.is_met_by(&[make_tx(40.0), make_tx(50.0), make_tx(9.0)]));
+        assert!(configured.is_met_by(&[make_tx(40.0), make_tx(50.0), make_tx(10.0)]));
+        assert!(!configured.is_met_by(&[make_tx(60.0), make_tx(50.0)]));
+    }
 }
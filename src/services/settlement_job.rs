@@ -0,0 +1,82 @@
+use crate::services::resource_limits::ResourceLimiter;
+use crate::services::scheduler::Job;
+use crate::services::SettlementService;
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use opentelemetry::KeyValue;
+use std::error::Error;
+use std::io;
+use tracing::info;
+
+/// Runs [`SettlementService::run_settlements`] on the configured cron schedule
+/// (`SETTLEMENT_SCHEDULE`, hourly by default).
+///
+/// A failed run is logged and counted in `settlement_job_failures_total`; the
+/// scheduler keeps the job registered and tries again at the next tick.
+pub struct SettlementJob {
+    service: SettlementService,
+    schedule: String,
+    limiter: Option<ResourceLimiter>,
+}
+
+impl SettlementJob {
+    pub fn new(service: SettlementService, schedule: impl Into<String>) -> Self {
+        Self {
+            service,
+            schedule: schedule.into(),
+            limiter: None,
+        }
+    }
+
+    /// Bound each run by the given limiter's concurrency and timeout.
+    pub fn with_limiter(mut self, limiter: ResourceLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    async fn run_once(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let run = self.service.run_settlements();
+        let settlements = match &self.limiter {
+            Some(limiter) => limiter.run(run).await?,
+            None => run.await,
+        }
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+        let total = settlements
+            .iter()
+            .fold(BigDecimal::from(0), |acc, s| acc + &s.total_amount);
+        for settlement in &settlements {
+            let asset = [KeyValue::new("asset_code", settlement.asset_code.clone())];
+            crate::metrics::settlements_created_total().add(1, &asset);
+            crate::metrics::settlement_amount_total()
+                .add(settlement.total_amount.to_f64().unwrap_or_default(), &asset);
+        }
+
+        info!(
+            settlements = settlements.len(),
+            total_amount = %total,
+            "Scheduled settlement run completed"
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Job for SettlementJob {
+    fn name(&self) -> &str {
+        "settlement"
+    }
+
+    fn schedule(&self) -> &str {
+        &self.schedule
+    }
+
+    async fn execute(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        // The scheduler logs the error; only the metric is recorded here.
+        let result = self.run_once().await;
+        if result.is_err() {
+            crate::metrics::settlement_job_failures_total().add(1, &[]);
+        }
+        result
+    }
+}
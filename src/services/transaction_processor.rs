@@ -1,5 +1,8 @@
 use crate::services::webhook_dispatcher::WebhookDispatcher;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::instrument;
 
 #[async_trait::async_trait]
@@ -71,26 +74,13 @@ impl CompleteStage {
 #[async_trait::async_trait]
 impl ProcessingStage for CompleteStage {
     async fn execute(&self, tx: &crate::db::models::Transaction) -> Result<(), anyhow::Error> {
-        // Get asset_code before update for cache invalidation
-        let asset_code: String =
-            sqlx::query_scalar("SELECT asset_code FROM transactions WHERE id = $1")
-                .bind(tx.id)
-                .fetch_one(&self.pool)
-                .await?;
-
         // Validate status transition: current status → completed
         crate::validation::state_machine::validate_status_transition(&tx.status, "completed")
             .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-        sqlx::query(
-            "UPDATE transactions SET status = 'completed', updated_at = NOW() WHERE id = $1",
-        )
-        .bind(tx.id)
-        .execute(&self.pool)
-        .await?;
-
-        // Invalidate cache after update
-        crate::db::queries::invalidate_caches_for_asset(&asset_code).await;
+        crate::db::queries::update_transaction_status(&self.pool, tx.id, "completed")
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("transaction {} not found", tx.id))?;
 
         tracing::info!("Completion stage completed for transaction {}", tx.id);
         Ok(())
@@ -101,11 +91,59 @@ impl ProcessingStage for CompleteStage {
     }
 }
 
+/// How often a failing transaction is retried before it is moved to the DLQ.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Failed attempts allowed before the transaction is escalated to the DLQ.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after every further failure.
+    pub base_backoff: Duration,
+    /// Upper bound on the delay between retries.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the next attempt after `attempts` failures.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Result of replaying a single DLQ entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DlqReplayOutcome {
+    /// Processed successfully; the DLQ row was removed.
+    Replayed,
+    /// The transaction had already completed; the stale DLQ row was removed.
+    AlreadyCompleted,
+    /// Processing failed again; the transaction is back in the DLQ.
+    Failed(String),
+    /// No DLQ row with that id (e.g. already replayed).
+    NotFound,
+    /// The transaction was not in the DLQ, e.g. a concurrent replay owns it.
+    Skipped(String),
+}
+
 #[derive(Clone)]
 pub struct TransactionProcessor {
     pool: PgPool,
     webhook_dispatcher: Option<WebhookDispatcher>,
     feature_flags: crate::services::feature_flags::FeatureFlagService,
+    retry_policy: RetryPolicy,
+    extra_stages: Vec<Arc<dyn ProcessingStage>>,
 }
 
 impl TransactionProcessor {
@@ -114,6 +152,8 @@ impl TransactionProcessor {
             pool: pool.clone(),
             webhook_dispatcher: None,
             feature_flags: crate::services::feature_flags::FeatureFlagService::new(pool),
+            retry_policy: RetryPolicy::default(),
+            extra_stages: Vec::new(),
         }
     }
 
@@ -123,6 +163,19 @@ impl TransactionProcessor {
         self
     }
 
+    /// Replace the default retry policy, normally with
+    /// [`Config::retry_policy`](crate::config::Config::retry_policy).
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Run an additional stage after verification and before completion.
+    pub fn with_stage(mut self, stage: Arc<dyn ProcessingStage>) -> Self {
+        self.extra_stages.push(stage);
+        self
+    }
+
     #[instrument(name = "processor.process_transaction", skip(self), fields(transaction.id = %tx_id))]
     pub async fn process_transaction(&self, tx_id: uuid::Uuid) -> anyhow::Result<()> {
         // Fetch the transaction first
@@ -132,11 +185,26 @@ impl TransactionProcessor {
                 .fetch_one(&self.pool)
                 .await?;
 
+        // Transactions in the DLQ are only retried after an explicit requeue,
+        // and failed ones wait out their backoff.
+        if tx.status == "dlq" {
+            anyhow::bail!("transaction {tx_id} is in the DLQ; requeue it to retry");
+        }
+        let (attempts, next_attempt_at): (i32, Option<DateTime<Utc>>) = sqlx::query_as(
+            "SELECT processing_attempts, next_attempt_at FROM transactions WHERE id = $1",
+        )
+        .bind(tx_id)
+        .fetch_one(&self.pool)
+        .await?;
+        if let Some(next) = next_attempt_at.filter(|next| *next > Utc::now()) {
+            anyhow::bail!("transaction {tx_id} is backing off until {next}");
+        }
+
         // Define the pipeline stages
-        let mut stages: Vec<Box<dyn ProcessingStage>> = Vec::new();
+        let mut stages: Vec<Arc<dyn ProcessingStage>> = Vec::new();
 
         // Validate stage - always enabled
-        stages.push(Box::new(ValidateStage));
+        stages.push(Arc::new(ValidateStage));
 
         // Enrich stage - feature flagged
         if self
@@ -145,7 +213,7 @@ impl TransactionProcessor {
             .await
             .unwrap_or(false)
         {
-            stages.push(Box::new(EnrichStage));
+            stages.push(Arc::new(EnrichStage));
         }
 
         // Verify stage - feature flagged
@@ -155,11 +223,13 @@ impl TransactionProcessor {
             .await
             .unwrap_or(false)
         {
-            stages.push(Box::new(VerifyStage));
+            stages.push(Arc::new(VerifyStage));
         }
 
+        stages.extend(self.extra_stages.iter().cloned());
+
         // Complete stage - always enabled
-        stages.push(Box::new(CompleteStage::new(self.pool.clone())));
+        stages.push(Arc::new(CompleteStage::new(self.pool.clone())));
 
         // Execute the pipeline
         for stage in stages {
@@ -184,9 +254,12 @@ impl TransactionProcessor {
                         tx_id,
                         e
                     );
-                    // Move to DLQ on failure
-                    self.move_to_dlq(tx_id, &format!("{stage_name} stage failed: {e}"))
-                        .await?;
+                    self.record_failure(
+                        &tx,
+                        attempts + 1,
+                        &format!("{stage_name} stage failed: {e}"),
+                    )
+                    .await?;
                     return Err(e);
                 }
             }
@@ -195,14 +268,218 @@ impl TransactionProcessor {
         Ok(())
     }
 
-    async fn move_to_dlq(&self, tx_id: uuid::Uuid, reason: &str) -> anyhow::Result<()> {
+    /// Record a failed attempt: schedule a retry after the policy's backoff,
+    /// or move the transaction to the DLQ once `max_attempts` is reached.
+    async fn record_failure(
+        &self,
+        tx: &crate::db::models::Transaction,
+        attempts: i32,
+        reason: &str,
+    ) -> anyhow::Result<()> {
+        if attempts as u32 >= self.retry_policy.max_attempts {
+            return self.move_to_dlq(tx, attempts, reason).await;
+        }
+
+        let delay = self.retry_policy.backoff(attempts as u32);
         sqlx::query(
-            "INSERT INTO transaction_dlq (transaction_id, reason, created_at) VALUES ($1, $2, NOW())",
+            "UPDATE transactions SET processing_attempts = $2, last_processing_error = $3, \
+             next_attempt_at = NOW() + make_interval(secs => $4), updated_at = NOW() \
+             WHERE id = $1",
         )
-        .bind(tx_id)
+        .bind(tx.id)
+        .bind(attempts)
         .bind(reason)
+        .bind(delay.as_secs_f64())
         .execute(&self.pool)
         .await?;
+
+        tracing::warn!(
+            transaction.id = %tx.id,
+            attempts,
+            max_attempts = self.retry_policy.max_attempts,
+            retry_in_ms = delay.as_millis() as u64,
+            "Transaction processing failed; will retry"
+        );
+        Ok(())
+    }
+
+    async fn move_to_dlq(
+        &self,
+        tx: &crate::db::models::Transaction,
+        attempts: i32,
+        reason: &str,
+    ) -> anyhow::Result<()> {
+        crate::validation::state_machine::validate_status_transition(&tx.status, "dlq")
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+        let mut db_tx = self.pool.begin().await?;
+        // A transaction keeps a single DLQ entry: a failed replay updates it
+        // rather than adding another.
+        let updated = sqlx::query(
+            "UPDATE transaction_dlq SET error_reason = $2, retry_count = $3, \
+             last_retry_at = NOW() WHERE transaction_id = $1",
+        )
+        .bind(tx.id)
+        .bind(reason)
+        .bind(attempts)
+        .execute(&mut *db_tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            sqlx::query(
+                "INSERT INTO transaction_dlq (transaction_id, stellar_account, amount, asset_code, \
+                 anchor_transaction_id, error_reason, retry_count, original_created_at) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            )
+            .bind(tx.id)
+            .bind(&tx.stellar_account)
+            .bind(&tx.amount)
+            .bind(&tx.asset_code)
+            .bind(&tx.anchor_transaction_id)
+            .bind(reason)
+            .bind(attempts)
+            .bind(tx.created_at)
+            .execute(&mut *db_tx)
+            .await?;
+        }
+        sqlx::query(
+            "UPDATE transactions SET status = 'dlq', processing_attempts = $2, \
+             last_processing_error = $3, next_attempt_at = NULL, updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(tx.id)
+        .bind(attempts)
+        .bind(reason)
+        .execute(&mut *db_tx)
+        .await?;
+        db_tx.commit().await?;
+
+        crate::db::queries::invalidate_caches_for_asset(&tx.asset_code).await;
+
+        tracing::error!(
+            transaction.id = %tx.id,
+            attempts,
+            "Transaction exhausted its retries and was moved to the DLQ"
+        );
+        Ok(())
+    }
+
+    /// Replay a DLQ entry with exactly one more processing attempt.
+    ///
+    /// On success the DLQ row is removed; on failure the transaction goes
+    /// straight back to the DLQ with its entry's `retry_count` bumped. Both
+    /// outcomes are audited under `actor`, with the caller-supplied
+    /// `requested_by` stored alongside as unverified metadata. Replaying an
+    /// entry that is gone, already being replayed, or whose transaction has
+    /// since completed is a no-op, so the call is safe to repeat.
+    #[instrument(
+        name = "processor.replay_dlq",
+        skip(self, actor, requested_by),
+        fields(dlq.id = %dlq_id)
+    )]
+    pub async fn replay_dlq_entry(
+        &self,
+        dlq_id: uuid::Uuid,
+        actor: &str,
+        requested_by: Option<&str>,
+    ) -> anyhow::Result<DlqReplayOutcome> {
+        let entry: Option<crate::db::models::TransactionDlq> =
+            sqlx::query_as("SELECT * FROM transaction_dlq WHERE id = $1")
+                .bind(dlq_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        let Some(entry) = entry else {
+            return Ok(DlqReplayOutcome::NotFound);
+        };
+        let tx_id = entry.transaction_id;
+
+        // Only one replay can move the transaction out of the DLQ.
+        let attempts: Option<i32> = sqlx::query_scalar(
+            "UPDATE transactions SET status = 'pending', next_attempt_at = NULL, \
+             updated_at = NOW() WHERE id = $1 AND status = 'dlq' RETURNING processing_attempts",
+        )
+        .bind(tx_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        let Some(attempts) = attempts else {
+            let status: Option<String> =
+                sqlx::query_scalar("SELECT status FROM transactions WHERE id = $1")
+                    .bind(tx_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if status.as_deref() == Some("completed") {
+                self.finish_replay(&entry, actor, requested_by, "completed")
+                    .await?;
+                return Ok(DlqReplayOutcome::AlreadyCompleted);
+            }
+            return Ok(DlqReplayOutcome::Skipped(format!(
+                "transaction is {}, not in the DLQ",
+                status.as_deref().unwrap_or("missing")
+            )));
+        };
+        crate::db::queries::invalidate_caches_for_asset(&entry.asset_code).await;
+
+        let one_shot = self.clone().with_retry_policy(RetryPolicy {
+            max_attempts: attempts.max(0) as u32 + 1,
+            ..self.retry_policy.clone()
+        });
+        match one_shot.process_transaction(tx_id).await {
+            Ok(()) => {
+                self.finish_replay(&entry, actor, requested_by, "completed")
+                    .await?;
+                Ok(DlqReplayOutcome::Replayed)
+            }
+            Err(e) => {
+                let mut db_tx = self.pool.begin().await?;
+                crate::db::audit::AuditLog::log(
+                    &mut db_tx,
+                    tx_id,
+                    crate::db::audit::ENTITY_TRANSACTION,
+                    "dlq_replay_failed",
+                    Some(
+                        serde_json::json!({ "dlq_id": dlq_id, "error_reason": entry.error_reason }),
+                    ),
+                    Some(serde_json::json!({
+                        "status": "dlq",
+                        "error": e.to_string(),
+                        "requested_by": requested_by,
+                    })),
+                    actor,
+                )
+                .await?;
+                db_tx.commit().await?;
+                Ok(DlqReplayOutcome::Failed(e.to_string()))
+            }
+        }
+    }
+
+    /// Remove a replayed DLQ row and audit the replay.
+    async fn finish_replay(
+        &self,
+        entry: &crate::db::models::TransactionDlq,
+        actor: &str,
+        requested_by: Option<&str>,
+        status: &str,
+    ) -> anyhow::Result<()> {
+        let mut db_tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM transaction_dlq WHERE id = $1")
+            .bind(entry.id)
+            .execute(&mut *db_tx)
+            .await?;
+        crate::db::audit::AuditLog::log(
+            &mut db_tx,
+            entry.transaction_id,
+            crate::db::audit::ENTITY_TRANSACTION,
+            "dlq_replayed",
+            Some(serde_json::json!({
+                "dlq_id": entry.id,
+                "error_reason": entry.error_reason,
+                "retry_count": entry.retry_count,
+            })),
+            Some(serde_json::json!({ "status": status, "requested_by": requested_by })),
+            actor,
+        )
+        .await?;
+        db_tx.commit().await?;
         Ok(())
     }
 
@@ -225,10 +502,15 @@ impl TransactionProcessor {
         crate::validation::state_machine::validate_status_transition(&current_status, "pending")
             .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-        sqlx::query("UPDATE transactions SET status = 'pending', updated_at = NOW() WHERE id = $1")
-            .bind(tx_id)
-            .execute(&self.pool)
-            .await?;
+        // A requeued transaction starts over with a fresh retry budget.
+        sqlx::query(
+            "UPDATE transactions SET status = 'pending', processing_attempts = 0, \
+             last_processing_error = NULL, next_attempt_at = NULL, updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(tx_id)
+        .execute(&self.pool)
+        .await?;
 
         sqlx::query("DELETE FROM transaction_dlq WHERE id = $1")
             .bind(dlq_id)
@@ -241,3 +523,29 @@ impl TransactionProcessor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_per_attempt_and_is_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(64), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn default_policy_is_bounded() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_attempts, 5);
+        assert!(policy.backoff(policy.max_attempts) <= policy.max_backoff);
+    }
+}
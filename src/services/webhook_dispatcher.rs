@@ -428,14 +428,22 @@ impl WebhookDispatcher {
 
         let signature = sign_payload_with_version(&endpoint.secret, &timestamp, &body);
 
-        // Get trace_id from transaction if available
-        let trace_id: Option<String> =
-            sqlx::query_scalar("SELECT trace_id FROM transactions WHERE id = $1")
-                .bind(delivery.transaction_id)
-                .fetch_optional(&self.pool)
-                .await
-                .ok()
-                .flatten();
+        // Get trace_id and the owning tenant's webhook secret from the
+        // transaction if available
+        let (trace_id, tenant_secret): (Option<String>, Option<String>) = sqlx::query_as(
+            r#"
+            SELECT t.trace_id, tn.webhook_secret
+            FROM transactions t
+            LEFT JOIN tenants tn ON tn.tenant_id = t.tenant_id AND tn.is_active
+            WHERE t.id = $1
+            "#,
+        )
+        .bind(delivery.transaction_id)
+        .fetch_optional(&self.pool)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
 
         let mut request = self
             .http
@@ -449,6 +457,23 @@ impl WebhookDispatcher {
             request = request.header("X-Trace-Id", trace_id);
         }
 
+        // Signed per attempt, so the timestamp reflects when this request was sent.
+        match tenant_secret.filter(|secret| !secret.is_empty()) {
+            Some(secret) => {
+                let sent_at = Utc::now().timestamp();
+                request = request
+                    .header(
+                        SYNAPSE_SIGNATURE_HEADER,
+                        sign_synapse(&secret, sent_at, &body),
+                    )
+                    .header(SYNAPSE_TIMESTAMP_HEADER, sent_at.to_string());
+            }
+            None => tracing::debug!(
+                delivery_id = %delivery.id,
+                "No tenant webhook secret for delivery, sending without X-Synapse-Signature"
+            ),
+        }
+
         let response = request.body(body).send().await;
 
         let new_attempt_count = delivery.attempt_count + 1;
@@ -1090,6 +1115,26 @@ fn sign_payload_with_version(secret: &str, timestamp: &str, body: &str) -> Strin
     format!("{SIGNATURE_VERSION}={signature_hex}")
 }
 
+/// Header carrying the tenant signature of an outbound delivery.
+pub const SYNAPSE_SIGNATURE_HEADER: &str = "X-Synapse-Signature";
+/// Header carrying the Unix time (seconds) the tenant signature was made at.
+pub const SYNAPSE_TIMESTAMP_HEADER: &str = "X-Synapse-Timestamp";
+
+/// Signs an outbound delivery with the tenant's `webhook_secret`.
+///
+/// # Signature Format
+/// Returns: `v1=sha256_hex_value`
+///
+/// # Signed Content
+/// The canonical string is `{timestamp}.{body}`: the decimal Unix timestamp
+/// sent in `X-Synapse-Timestamp`, a literal `.`, then the raw request body.
+/// Receivers should recompute the HMAC over exactly these bytes and reject
+/// timestamps outside their replay window.
+pub fn sign_synapse(secret: &str, timestamp: i64, body: &str) -> String {
+    let signature_hex = sign_payload_v1(secret, &format!("{timestamp}.{body}"));
+    format!("{SIGNATURE_VERSION}={signature_hex}")
+}
+
 /// Compute HMAC-SHA256 hex signature (v1).
 fn sign_payload_v1(secret: &str, signed_content: &str) -> String {
     let mut mac =
@@ -1122,6 +1167,23 @@ fn sign_payload(secret: &str, body: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_synapse_signature_signs_timestamp_dot_body() {
+        let body = r#"{"event_type":"transaction.completed"}"#;
+        let signature = sign_synapse("tenant-secret", 1_700_000_000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"tenant-secret").unwrap();
+        mac.update(format!("1700000000.{body}").as_bytes());
+        let expected = format!("v1={}", hex::encode(mac.finalize().into_bytes()));
+        assert_eq!(signature, expected);
+
+        assert_ne!(
+            signature,
+            sign_synapse("tenant-secret", 1_700_000_001, body)
+        );
+        assert_ne!(signature, sign_synapse("other-secret", 1_700_000_000, body));
+    }
+
     #[test]
     fn test_v1_signature_includes_timestamp() {
         let secret = "test-secret";
@@ -1,6 +1,9 @@
 use crate::config::Config;
+use crate::stellar::{HorizonClient, StellarNetwork};
 use anyhow::{Context, Result};
+use sqlx::migrate::Migrator;
 use sqlx::PgPool;
+use std::collections::HashSet;
 use std::time::Duration;
 
 pub struct ValidationReport {
@@ -103,6 +106,11 @@ fn validate_env_vars(config: &Config) -> Result<()> {
     // Validate URL formats
     url::Url::parse(&config.stellar_horizon_url)
         .context("STELLAR_HORIZON_URL is not a valid URL")?;
+    for fallback in &config.stellar_horizon_fallback_urls {
+        url::Url::parse(fallback).with_context(|| {
+            format!("STELLAR_HORIZON_FALLBACK_URLS entry '{fallback}' is not a valid URL")
+        })?;
+    }
 
     Ok(())
 }
@@ -160,6 +168,103 @@ async fn validate_horizon(horizon_url: &str) -> Result<()> {
     Ok(())
 }
 
+/// Fails when Horizon reports a network passphrase other than `network`'s,
+/// e.g. a pubnet `STELLAR_HORIZON_URL` with `STELLAR_NETWORK=testnet`. An
+/// unreachable Horizon is only logged: availability is the readiness
+/// probe's concern, and the check runs again on the next start.
+pub async fn ensure_horizon_network(
+    client: &HorizonClient,
+    network: &StellarNetwork,
+) -> Result<()> {
+    let root = match client.get_root().await {
+        Ok(root) => root,
+        Err(e) => {
+            tracing::warn!(
+                horizon_url = %client.base_url(),
+                error = %e,
+                "Could not verify the Horizon network passphrase"
+            );
+            return Ok(());
+        }
+    };
+
+    if root.network_passphrase != network.passphrase() {
+        anyhow::bail!(
+            "Horizon at {} serves network '{}', but STELLAR_NETWORK is {} ('{}'). \
+             Point STELLAR_HORIZON_URL at a {} Horizon or fix STELLAR_NETWORK.",
+            client.base_url(),
+            root.network_passphrase,
+            network.as_str(),
+            network.passphrase(),
+            network.as_str()
+        );
+    }
+
+    Ok(())
+}
+
+/// A migration in the migrations directory that the database has not applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    pub version: i64,
+    pub description: String,
+}
+
+/// Lists the up migrations of `migrator` missing from `_sqlx_migrations`,
+/// oldest first. A database that was never migrated has all of them pending.
+pub async fn pending_migrations(
+    pool: &PgPool,
+    migrator: &Migrator,
+) -> Result<Vec<PendingMigration>> {
+    let has_table: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await
+        .context("Failed to look up the migrations table")?;
+    let applied: HashSet<i64> = if has_table {
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(pool)
+            .await
+            .context("Failed to read applied migrations")?
+            .into_iter()
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    Ok(migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && !applied.contains(&m.version))
+        .map(|m| PendingMigration {
+            version: m.version,
+            description: m.description.to_string(),
+        })
+        .collect())
+}
+
+/// Fails when the database schema is behind the migrations in
+/// `migrations_dir`. Used at startup when migrations are not run, so the
+/// server never serves against a schema it does not expect.
+pub async fn ensure_no_pending_migrations(pool: &PgPool, migrations_dir: &str) -> Result<()> {
+    let migrator = Migrator::new(std::path::Path::new(migrations_dir))
+        .await
+        .with_context(|| format!("Failed to read migrations from {migrations_dir}"))?;
+    let pending = pending_migrations(pool, &migrator).await?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<String> = pending
+        .iter()
+        .map(|m| format!("{} ({})", m.version, m.description))
+        .collect();
+    anyhow::bail!(
+        "Database schema is behind: {} pending migration(s) in {migrations_dir}: {}. \
+         Run `synapse-core db migrate` or start without --skip-migrate.",
+        pending.len(),
+        names.join(", ")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,11 +272,15 @@ mod tests {
     fn test_config_base() -> Config {
         Config {
             app_env: crate::config::AppEnv::Development,
+            profile_overrides: Vec::new(),
             server_port: 3000,
             database_url: "postgres://localhost:5432/test".to_string(),
             database_replica_url: None,
             stellar_horizon_url: "https://horizon-testnet.stellar.org".to_string(),
+            stellar_horizon_fallback_urls: vec![],
+            stellar_network: crate::stellar::StellarNetwork::Testnet,
             anchor_webhook_secret: "test".to_string(),
+            jwt_secret: None,
             redis_url: "redis://localhost:6379".to_string(),
             default_rate_limit: 100,
             whitelist_rate_limit: 1000,
@@ -180,10 +289,29 @@ mod tests {
             allowed_ips: crate::config::AllowedIps::Any,
             backup_dir: "/tmp".to_string(),
             backup_encryption_key: None,
+            backup_max_age_hours: 25,
+            migrations_dir: "./migrations".to_string(),
             db_timeouts: crate::config::DbTimeoutConfig::default(),
             otlp_endpoint: None,
             cors_allowed_origins: vec![],
+            log_sensitive_keys: vec![],
             max_pending_queue: 10000,
+            webhook_max_concurrency: 64,
+            profiling_retention_hours: 168,
+            profiling_min_start_interval_secs: 60,
+            profiling_max_sample_rate_hz: 1000,
+            webhook_replay_max_batch: 500,
+            webhook_replay_concurrency: 8,
+            max_body_bytes: 1024 * 1024,
+            request_timeout_secs: 30,
+            search_default_window_days: 90,
+            export_jobs_dir: "/tmp/synapse_exports".to_string(),
+            broadcast_capacity: 100,
+            horizon_page_limit: 200,
+            health_latency_threshold_ms: 1000,
+            idempotency_failure_mode: Default::default(),
+            readiness_required_dependencies: Vec::new(),
+            feature_flag_defaults: Default::default(),
             db_min_connections: 5,
             db_max_connections: 50,
             db_statement_timeout_ms: 30000,
@@ -192,12 +320,22 @@ mod tests {
             processor_workers: 4,
             processor_batch_size: 50,
             processor_poll_interval_ms: 1000,
+            processor_concurrency: 8,
             processor_min_batch: 10,
             processor_max_batch: 500,
             processor_scaling_factor: 0.5,
+            processor_max_attempts: 5,
+            processor_retry_backoff_ms: 1000,
+            processor_retry_max_backoff_ms: 300_000,
             slow_query_threshold_ms: 500,
             settlement_max_batch_size: 10_000,
             settlement_min_tx_count: 1,
+            settlement_schedule: "0 0 * * * *".to_string(),
+            partition_strategy: crate::db::partition::PartitionStrategy::Monthly,
+            partition_months_ahead: 3,
+            partition_days_ahead: 7,
+            partition_retention_months: 12,
+            partition_drop_after_archive: false,
         }
     }
 
@@ -211,6 +349,21 @@ mod tests {
         assert!(validate_env_vars(&config).is_err());
     }
 
+    #[test]
+    fn test_validate_env_vars_invalid_fallback_url() {
+        let config = Config {
+            stellar_horizon_fallback_urls: vec!["https://horizon.example.org".to_string()],
+            ..test_config_base()
+        };
+        assert!(validate_env_vars(&config).is_ok());
+
+        let config = Config {
+            stellar_horizon_fallback_urls: vec!["not-a-url".to_string()],
+            ..test_config_base()
+        };
+        assert!(validate_env_vars(&config).is_err());
+    }
+
     #[test]
     fn test_validate_env_vars_invalid_url() {
         let config = Config {
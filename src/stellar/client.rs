@@ -5,8 +5,9 @@ use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::instrument;
@@ -17,10 +18,14 @@ pub enum HorizonError {
     RequestError(#[from] reqwest::Error),
     #[error("Account not found: {0}")]
     AccountNotFound(String),
+    #[error("Transaction not found: {0}")]
+    TransactionNotFound(String),
     #[error("Invalid response from Horizon: {0}")]
     InvalidResponse(String),
     #[error("Circuit breaker open: {0}")]
     CircuitBreakerOpen(String),
+    #[error("Rate limited by Horizon: {0}")]
+    RateLimited(String),
 }
 
 impl Clone for HorizonError {
@@ -28,8 +33,10 @@ impl Clone for HorizonError {
         match self {
             Self::RequestError(e) => Self::InvalidResponse(e.to_string()),
             Self::AccountNotFound(s) => Self::AccountNotFound(s.clone()),
+            Self::TransactionNotFound(s) => Self::TransactionNotFound(s.clone()),
             Self::InvalidResponse(s) => Self::InvalidResponse(s.clone()),
             Self::CircuitBreakerOpen(s) => Self::CircuitBreakerOpen(s.clone()),
+            Self::RateLimited(s) => Self::RateLimited(s.clone()),
         }
     }
 }
@@ -56,6 +63,227 @@ pub struct Balance {
     pub asset_issuer: Option<String>,
 }
 
+/// A payment operation from Horizon's `/accounts/{id}/payments` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRecord {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+    /// `"native"` for XLM, otherwise `"credit_alphanum4"`/`"credit_alphanum12"`.
+    #[serde(default)]
+    pub asset_type: Option<String>,
+    /// Empty for native payments; see `db::models::normalize_asset`.
+    #[serde(default)]
+    pub asset_code: String,
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default)]
+    pub memo_type: Option<String>,
+    /// RFC 3339 timestamp; absent in some fixtures.
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// One page of payments plus the link to the next page, if any.
+#[derive(Debug, Clone, Default)]
+pub struct PaymentsPage {
+    pub records: Vec<PaymentRecord>,
+    /// Absolute URL of the next page; pass it to
+    /// [`HorizonClient::get_payments_page`].
+    pub next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PaymentsResponse {
+    #[serde(rename = "_links", default)]
+    links: PageLinks,
+    #[serde(rename = "_embedded")]
+    embedded: Embedded<PaymentRecord>,
+}
+
+#[derive(Deserialize, Default)]
+struct PageLinks {
+    next: Option<Link>,
+}
+
+#[derive(Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Deserialize)]
+struct Embedded<T> {
+    records: Vec<T>,
+}
+
+/// Response from Horizon's `/transactions/{hash}` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionResponse {
+    pub id: String,
+    pub hash: String,
+    pub ledger: i64,
+    pub created_at: String,
+    pub source_account: String,
+    pub successful: bool,
+    #[serde(default)]
+    pub memo: Option<String>,
+    #[serde(default)]
+    pub memo_type: Option<String>,
+    #[serde(default)]
+    pub fee_charged: Option<String>,
+    #[serde(default)]
+    pub operation_count: Option<i32>,
+}
+
+/// Response from Horizon's root endpoint (`/`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootResponse {
+    pub network_passphrase: String,
+    #[serde(default)]
+    pub horizon_version: Option<String>,
+    #[serde(default)]
+    pub core_version: Option<String>,
+}
+
+/// Page size requested from Horizon's paginated endpoints (its maximum).
+pub const MAX_PAGE_LIMIT: u32 = 200;
+
+/// Retries of a request answered `429 Too Many Requests` before giving up
+/// with [`HorizonError::RateLimited`].
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Wait used when a `429` carries no usable `Retry-After`.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Upper bound on a single `Retry-After` wait, so a misbehaving proxy cannot
+/// park a reconciliation run indefinitely.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Wait requested by a `429` response. Only the delay-seconds form of
+/// `Retry-After` is honoured; anything else falls back to
+/// [`DEFAULT_RETRY_AFTER`].
+fn retry_after(response: &reqwest::Response) -> Duration {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+        .min(MAX_RETRY_AFTER)
+}
+
+/// Normalizes a Horizon base URL: surrounding whitespace and trailing slashes
+/// are dropped and `https://` is assumed when no scheme is given.
+pub fn normalize_base_url(raw: &str) -> String {
+    let trimmed = raw.trim().trim_end_matches('/');
+    if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("https://{trimmed}")
+    }
+}
+
+/// How long a client that failed over keeps using the fallback before trying
+/// the primary endpoint again.
+pub const PRIMARY_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Horizon base URLs in priority order, shared by all clones of one
+/// [`HorizonClient`] so a failover is seen by every holder.
+struct Endpoints {
+    urls: Vec<String>,
+    /// Index into `urls` of the endpoint requests currently go to.
+    active: AtomicUsize,
+    /// When the primary last failed while a fallback was active.
+    primary_failed_at: Mutex<Option<Instant>>,
+}
+
+impl Endpoints {
+    fn new(urls: Vec<String>) -> Self {
+        assert!(
+            !urls.is_empty(),
+            "HorizonClient needs at least one base URL"
+        );
+        Self {
+            urls: urls.iter().map(|url| normalize_base_url(url)).collect(),
+            active: AtomicUsize::new(0),
+            primary_failed_at: Mutex::new(None),
+        }
+    }
+
+    fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Endpoint indices in the order a request should try them: the active
+    /// endpoint first, or the primary once `recheck` has passed since it last
+    /// failed, then the rest by priority.
+    fn order(&self, recheck: Duration) -> Vec<usize> {
+        let active = self.active();
+        let recheck_primary = active != 0
+            && self
+                .primary_failed_at
+                .lock()
+                .unwrap()
+                .is_none_or(|at| at.elapsed() >= recheck);
+        let first = if recheck_primary { 0 } else { active };
+        std::iter::once(first)
+            .chain((0..self.urls.len()).filter(|&i| i != first))
+            .collect()
+    }
+
+    /// The path of `url` relative to whichever endpoint it points at, so a
+    /// link returned by one endpoint can be followed on another.
+    fn relative<'a>(&self, url: &'a str) -> Option<&'a str> {
+        self.urls.iter().find_map(|base| {
+            url.strip_prefix(base.as_str())
+                .filter(|rest| rest.is_empty() || rest.starts_with(['/', '?']))
+        })
+    }
+
+    fn record_failure(&self, index: usize) {
+        if index == 0 {
+            *self.primary_failed_at.lock().unwrap() = Some(Instant::now());
+        }
+    }
+
+    fn record_success(&self, index: usize) {
+        let previous = self.active.swap(index, Ordering::Relaxed);
+        if previous == index {
+            return;
+        }
+        if index == 0 {
+            tracing::info!(horizon_url = %self.urls[0], "Horizon primary healthy again, switching back");
+        } else {
+            tracing::warn!(
+                from = %self.urls[previous],
+                to = %self.urls[index],
+                "Horizon endpoint unavailable, failed over"
+            );
+        }
+    }
+}
+
+/// Whether `error` means the endpoint itself is unreachable, so the request
+/// should move on to the next one.
+fn is_endpoint_failure(error: &HorizonError) -> bool {
+    matches!(error, HorizonError::RequestError(e) if e.is_connect() || e.is_timeout())
+}
+
+/// HTTP client shared by all clones of one [`HorizonClient`], keeping a pool
+/// of idle connections to Horizon.
+fn build_http_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(30))
+        .pool_idle_timeout(Duration::from_secs(90))
+        .pool_max_idle_per_host(16)
+        .build()
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamPayment {
     pub id: String,
@@ -76,43 +304,66 @@ pub struct StreamMetrics {
 }
 
 /// HTTP client for interacting with the Stellar Horizon API
+///
+/// Holds a prioritized list of Horizon base URLs. A request that cannot
+/// connect, times out or gets a `5xx` moves on to the next URL, which then
+/// serves later requests until the primary answers again.
 #[derive(Clone)]
 pub struct HorizonClient {
     pub(crate) client: Client,
-    pub(crate) base_url: String,
+    endpoints: Arc<Endpoints>,
+    primary_recheck: Duration,
     circuit_breaker: StateMachine<failure_policy::ConsecutiveFailures<backoff::EqualJittered>, ()>,
 }
 
+impl PaymentRecord {
+    /// Canonical `(asset_code, asset_issuer)` of the payment; native XLM is
+    /// `("XLM", None)`.
+    pub fn asset(&self) -> (String, Option<String>) {
+        if self.asset_type.as_deref() == Some("native") {
+            return crate::db::models::normalize_asset("native", None);
+        }
+        crate::db::models::normalize_asset(&self.asset_code, self.asset_issuer.as_deref())
+    }
+}
+
 impl HorizonClient {
     /// Creates a new HorizonClient with the specified base URL and circuit breaker
     pub fn new(base_url: String) -> Self {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .unwrap_or_default();
+        Self::with_endpoints(vec![base_url])
+    }
 
+    /// Creates a client that fails over across `base_urls`, tried in order.
+    /// The first URL is the primary.
+    ///
+    /// # Panics
+    ///
+    /// If `base_urls` is empty.
+    pub fn with_endpoints(base_urls: Vec<String>) -> Self {
         let backoff = backoff::equal_jittered(Duration::from_secs(60), Duration::from_secs(120));
         let policy = failure_policy::consecutive_failures(3, backoff);
         let circuit_breaker = Config::new().failure_policy(policy).build();
 
         HorizonClient {
-            client,
-            base_url,
+            client: build_http_client(),
+            endpoints: Arc::new(Endpoints::new(base_urls)),
+            primary_recheck: PRIMARY_RECHECK_INTERVAL,
             circuit_breaker,
         }
     }
 
+    /// Sets how long to stay on a fallback before trying the primary again.
+    pub fn with_primary_recheck(mut self, interval: Duration) -> Self {
+        self.primary_recheck = interval;
+        self
+    }
+
     /// Creates a new HorizonClient with custom circuit breaker configuration
     pub fn with_circuit_breaker(
         base_url: String,
         failure_threshold: u32,
         reset_timeout_secs: u64,
     ) -> Self {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .unwrap_or_default();
-
         let backoff = backoff::equal_jittered(
             Duration::from_secs(reset_timeout_secs),
             Duration::from_secs(reset_timeout_secs * 2),
@@ -121,12 +372,24 @@ impl HorizonClient {
         let circuit_breaker = Config::new().failure_policy(policy).build();
 
         HorizonClient {
-            client,
-            base_url,
+            client: build_http_client(),
+            endpoints: Arc::new(Endpoints::new(vec![base_url])),
+            primary_recheck: PRIMARY_RECHECK_INTERVAL,
             circuit_breaker,
         }
     }
 
+    /// Normalized base URL of the endpoint currently in use, without a
+    /// trailing slash.
+    pub fn base_url(&self) -> &str {
+        &self.endpoints.urls[self.endpoints.active()]
+    }
+
+    /// All configured base URLs, primary first.
+    pub fn base_urls(&self) -> &[String] {
+        &self.endpoints.urls
+    }
+
     /// Returns the current state of the circuit breaker
     pub fn circuit_state(&self) -> String {
         if self.circuit_breaker.is_call_permitted() {
@@ -136,36 +399,211 @@ impl HorizonClient {
         }
     }
 
-    /// Fetches account details from the Horizon API.
-    /// The current trace context is propagated via W3C `traceparent` headers.
-    #[instrument(name = "horizon.get_account", skip(self), fields(stellar.account = %address))]
-    pub async fn get_account(&self, address: &str) -> Result<AccountResponse, HorizonError> {
-        let url = format!(
-            "{}/accounts/{}",
-            self.base_url.trim_end_matches('/'),
-            address
-        );
-        let client = self.client.clone();
-        let addr = address.to_string();
-
-        // Inject W3C traceparent / tracestate into outgoing request headers.
+    /// Headers forwarded on every outbound Horizon call: the W3C
+    /// `traceparent`/`tracestate` of the current span and, inside a request,
+    /// its `X-Request-ID`.
+    pub(crate) fn propagation_headers() -> std::collections::HashMap<String, String> {
         let mut headers = std::collections::HashMap::new();
         let propagator = TraceContextPropagator::new();
         let cx = opentelemetry::Context::current();
         propagator.inject_context(&cx, &mut headers);
+        if let Some(request_id) = crate::middleware::request_logger::current_request_id() {
+            headers.insert("x-request-id".to_string(), request_id);
+        }
+        headers
+    }
+
+    /// Starts a GET request to `url` carrying [`Self::propagation_headers`].
+    pub(crate) fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        Self::propagation_headers()
+            .iter()
+            .fold(self.client.get(url), |req, (k, v)| {
+                req.header(k.as_str(), v.as_str())
+            })
+    }
+
+    /// Fetches account details from the Horizon API.
+    /// The current trace context is propagated via W3C `traceparent` headers,
+    /// along with the caller's `X-Request-ID`.
+    #[instrument(
+        name = "horizon.get_account",
+        skip(self),
+        fields(stellar.account = %address, http.status_code = tracing::field::Empty, duration_ms = tracing::field::Empty)
+    )]
+    pub async fn get_account(&self, address: &str) -> Result<AccountResponse, HorizonError> {
+        let path = format!("/accounts/{address}");
+        let address = address.to_string();
+        self.get_json(&path, move || HorizonError::AccountNotFound(address))
+            .await
+    }
+
+    /// Fetches the first page of payments for `account`, oldest first,
+    /// starting after `cursor` (a payment paging token) when given.
+    #[instrument(
+        name = "horizon.get_account_payments",
+        skip(self),
+        fields(stellar.account = %account, http.status_code = tracing::field::Empty, duration_ms = tracing::field::Empty)
+    )]
+    pub async fn get_account_payments(
+        &self,
+        account: &str,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<PaymentsPage, HorizonError> {
+        let mut path = format!(
+            "/accounts/{}/payments?order=asc&limit={}",
+            account,
+            limit.clamp(1, MAX_PAGE_LIMIT)
+        );
+        if let Some(cursor) = cursor {
+            path.push_str(&format!("&cursor={cursor}"));
+        }
+        let account = account.to_string();
+        self.fetch_payments_page(&path, move || HorizonError::AccountNotFound(account))
+            .await
+    }
+
+    /// Fetches the first page of payments for `account`, newest first.
+    /// Following [`PaymentsPage::next`] walks further back in time.
+    #[instrument(
+        name = "horizon.get_latest_account_payments",
+        skip(self),
+        fields(stellar.account = %account, http.status_code = tracing::field::Empty, duration_ms = tracing::field::Empty)
+    )]
+    pub async fn get_latest_account_payments(
+        &self,
+        account: &str,
+        limit: u32,
+    ) -> Result<PaymentsPage, HorizonError> {
+        let path = format!(
+            "/accounts/{}/payments?order=desc&limit={}",
+            account,
+            limit.clamp(1, MAX_PAGE_LIMIT)
+        );
+        let account = account.to_string();
+        self.fetch_payments_page(&path, move || HorizonError::AccountNotFound(account))
+            .await
+    }
+
+    /// Fetches the page at `url`, a [`PaymentsPage::next`] link returned by
+    /// a previous call.
+    #[instrument(
+        name = "horizon.get_payments_page",
+        skip(self),
+        fields(http.status_code = tracing::field::Empty, duration_ms = tracing::field::Empty)
+    )]
+    pub async fn get_payments_page(&self, url: &str) -> Result<PaymentsPage, HorizonError> {
+        let missing = format!("Payments page not found: {url}");
+        self.fetch_payments_page(url, move || HorizonError::InvalidResponse(missing))
+            .await
+    }
+
+    async fn fetch_payments_page(
+        &self,
+        target: &str,
+        not_found: impl FnOnce() -> HorizonError,
+    ) -> Result<PaymentsPage, HorizonError> {
+        let page: PaymentsResponse = self.get_json(target, not_found).await?;
+        Ok(PaymentsPage {
+            records: page.embedded.records,
+            next: page.links.next.map(|l| l.href),
+        })
+    }
+
+    /// Fetches a transaction by hash.
+    #[instrument(
+        name = "horizon.get_transaction",
+        skip(self),
+        fields(stellar.tx_hash = %hash, http.status_code = tracing::field::Empty, duration_ms = tracing::field::Empty)
+    )]
+    pub async fn get_transaction(&self, hash: &str) -> Result<TransactionResponse, HorizonError> {
+        let path = format!("/transactions/{hash}");
+        let hash = hash.to_string();
+        self.get_json(&path, move || HorizonError::TransactionNotFound(hash))
+            .await
+    }
+
+    /// Fetches Horizon's root info, which names the network it serves.
+    #[instrument(
+        name = "horizon.get_root",
+        skip(self),
+        fields(http.status_code = tracing::field::Empty, duration_ms = tracing::field::Empty)
+    )]
+    pub async fn get_root(&self) -> Result<RootResponse, HorizonError> {
+        self.get_json("/", || {
+            HorizonError::InvalidResponse("Horizon root endpoint not found".to_string())
+        })
+        .await
+    }
+
+    /// Full URLs to try for `target`, in failover order, each with the index
+    /// of its endpoint. `target` is a path, or an absolute URL such as a
+    /// paging link; one that matches no endpoint is requested as is.
+    fn candidates(&self, target: &str) -> Vec<(Option<usize>, String)> {
+        let path = if target.starts_with('/') {
+            Some(target)
+        } else {
+            self.endpoints.relative(target)
+        };
+        match path {
+            Some(path) => self
+                .endpoints
+                .order(self.primary_recheck)
+                .into_iter()
+                .map(|i| (Some(i), format!("{}{}", self.endpoints.urls[i], path)))
+                .collect(),
+            None => vec![(None, target.to_string())],
+        }
+    }
+
+    /// GETs `target` (see [`Self::candidates`]) through the circuit breaker
+    /// with [`Self::propagation_headers`] and decodes the JSON body,
+    /// recording `http.status_code` and `duration_ms` on the current span. A
+    /// 404 maps to `not_found()`.
+    ///
+    /// A `429` is retried after its `Retry-After` delay, up to
+    /// [`MAX_RATE_LIMIT_RETRIES`] times. Connection errors, timeouts and
+    /// `5xx` responses fail over to the next endpoint. Retries and failovers
+    /// count as one call towards the circuit breaker.
+    async fn get_json<T>(
+        &self,
+        target: &str,
+        not_found: impl FnOnce() -> HorizonError,
+    ) -> Result<T, HorizonError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let span = tracing::Span::current();
+        let started = std::time::Instant::now();
 
         let result = self
             .circuit_breaker
-            .call(async move {
-                let mut req = client.get(&url);
-                for (k, v) in &headers {
-                    req = req.header(k.as_str(), v.as_str());
-                }
-                let response = req.send().await?;
+            .call(async {
+                let mut candidates = self.candidates(target).into_iter().peekable();
+                let response = loop {
+                    let (endpoint, url) = candidates.next().expect("at least one endpoint");
+                    let has_fallback = candidates.peek().is_some();
+                    let failure = match self.send_retrying_rate_limits(&url, &span).await {
+                        Ok(response) if !response.status().is_server_error() => {
+                            if let Some(i) = endpoint {
+                                self.endpoints.record_success(i);
+                            }
+                            break response;
+                        }
+                        Ok(response) if !has_fallback => break response,
+                        Ok(response) => format!("Horizon API error: {}", response.status()),
+                        Err(e) if has_fallback && is_endpoint_failure(&e) => e.to_string(),
+                        Err(e) => return Err(e),
+                    };
+                    tracing::warn!(url = %url, error = %failure, "Horizon endpoint failed, trying next");
+                    if let Some(i) = endpoint {
+                        self.endpoints.record_failure(i);
+                    }
+                };
 
                 if !response.status().is_success() {
                     if response.status() == 404 {
-                        return Err(HorizonError::AccountNotFound(addr));
+                        return Err(not_found());
                     }
                     return Err(HorizonError::InvalidResponse(format!(
                         "Horizon API error: {}",
@@ -173,13 +611,13 @@ impl HorizonClient {
                     )));
                 }
 
-                let account = response.json::<AccountResponse>().await?;
-                Ok(account)
+                Ok(response.json::<T>().await?)
             })
             .await;
+        span.record("duration_ms", started.elapsed().as_millis() as u64);
 
         match result {
-            Ok(account) => Ok(account),
+            Ok(value) => Ok(value),
             Err(FailsafeError::Rejected) => Err(HorizonError::CircuitBreakerOpen(
                 "Horizon API circuit breaker is open".to_string(),
             )),
@@ -187,6 +625,37 @@ impl HorizonClient {
         }
     }
 
+    /// Sends a GET to `url`, retrying a `429` after its `Retry-After` delay
+    /// up to [`MAX_RATE_LIMIT_RETRIES`] times.
+    async fn send_retrying_rate_limits(
+        &self,
+        url: &str,
+        span: &tracing::Span,
+    ) -> Result<reqwest::Response, HorizonError> {
+        let mut retries = 0;
+        loop {
+            let response = self.get(url).send().await?;
+            span.record("http.status_code", response.status().as_u16());
+            if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+            if retries == MAX_RATE_LIMIT_RETRIES {
+                return Err(HorizonError::RateLimited(format!(
+                    "still rate limited after {MAX_RATE_LIMIT_RETRIES} retries"
+                )));
+            }
+            retries += 1;
+            let wait = retry_after(&response);
+            tracing::warn!(
+                url = %url,
+                retry = retries,
+                wait_ms = wait.as_millis() as u64,
+                "Horizon rate limited request, retrying"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
     /// Stream payments for an account via SSE with automatic reconnection.
     ///
     /// Resumes from `initial_cursor` (the Horizon paging token of the last
@@ -217,7 +686,7 @@ impl HorizonClient {
         loop {
             let mut url = format!(
                 "{}/accounts/{}/payments?order=asc&stream=true",
-                self.base_url.trim_end_matches('/'),
+                self.base_url(),
                 account
             );
             if let Some(ref cursor) = last_cursor {
@@ -365,7 +834,7 @@ mod tests {
     #[test]
     fn test_horizon_client_creation() {
         let client = HorizonClient::new("https://horizon-testnet.stellar.org".to_string());
-        assert_eq!(client.base_url, "https://horizon-testnet.stellar.org");
+        assert_eq!(client.base_url(), "https://horizon-testnet.stellar.org");
     }
 
     #[tokio::test]
@@ -432,6 +901,166 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[test]
+    fn test_payment_record_asset_normalizes_native() {
+        let native: PaymentRecord = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "from": "GFROM",
+            "to": "GACC",
+            "amount": "5.0000000",
+            "asset_type": "native",
+            "created_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(native.asset(), ("XLM".to_string(), None));
+
+        let credit: PaymentRecord = serde_json::from_value(serde_json::json!({
+            "id": "2",
+            "from": "GFROM",
+            "to": "GACC",
+            "amount": "5.0000000",
+            "asset_type": "credit_alphanum4",
+            "asset_code": "USDC",
+            "asset_issuer": "GISSUER",
+            "created_at": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(
+            credit.asset(),
+            ("USDC".to_string(), Some("GISSUER".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(
+            normalize_base_url("https://horizon-testnet.stellar.org/"),
+            "https://horizon-testnet.stellar.org"
+        );
+        assert_eq!(
+            normalize_base_url("horizon-testnet.stellar.org"),
+            "https://horizon-testnet.stellar.org"
+        );
+        assert_eq!(
+            normalize_base_url("  http://localhost:8000//  "),
+            "http://localhost:8000"
+        );
+    }
+
+    #[test]
+    fn test_constructor_normalizes_base_url() {
+        let client = HorizonClient::new("horizon-testnet.stellar.org/".to_string());
+        assert_eq!(client.base_url(), "https://horizon-testnet.stellar.org");
+    }
+
+    #[tokio::test]
+    async fn test_get_account_payments_with_mock() {
+        let mut server = mockito::Server::new_async().await;
+        let next = format!(
+            "{}/accounts/GACC/payments?cursor=124&limit=200&order=asc",
+            server.url()
+        );
+        let body = serde_json::json!({
+            "_links": { "next": { "href": next } },
+            "_embedded": { "records": [{
+                "id": "124",
+                "from": "GFROM",
+                "to": "GACC",
+                "amount": "10.0000000",
+                "asset_code": "USDC",
+                "memo": "m-1",
+                "memo_type": "text",
+                "created_at": "2024-01-01T00:00:00Z"
+            }]}
+        });
+
+        let mock = server
+            .mock("GET", "/accounts/GACC/payments")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("order".into(), "asc".into()),
+                mockito::Matcher::UrlEncoded("limit".into(), "200".into()),
+                mockito::Matcher::UrlEncoded("cursor".into(), "123".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await;
+
+        let client = HorizonClient::new(format!("{}/", server.url()));
+        let page = client
+            .get_account_payments("GACC", Some("123"), MAX_PAGE_LIMIT)
+            .await
+            .unwrap();
+
+        assert_eq!(page.records.len(), 1);
+        let record = &page.records[0];
+        assert_eq!(record.id, "124");
+        assert_eq!(record.amount, "10.0000000");
+        assert_eq!(record.memo.as_deref(), Some("m-1"));
+        assert_eq!(page.next.as_deref(), Some(next.as_str()));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_not_found() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/transactions/abc123")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = HorizonClient::new(server.url());
+        let result = client.get_transaction("abc123").await;
+
+        assert!(matches!(result, Err(HorizonError::TransactionNotFound(_))));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_account_forwards_incoming_request_id() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", mockito::Matcher::Regex(r"^/accounts/.*".into()))
+            .match_header("x-request-id", "req-abc-123")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let client = HorizonClient::new(server.url());
+        let app = Router::new()
+            .route(
+                "/lookup",
+                get(move || async move {
+                    let _ = client
+                        .get_account("GBBD47UZQ5CSKQPV456PYYH4FSYJHBWGQJUVNMCNWZ2NBEHKQPW3KXKJ")
+                        .await;
+                    "done"
+                }),
+            )
+            .layer(axum::middleware::from_fn(
+                crate::middleware::request_logger::request_logger_middleware,
+            ));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/lookup")
+                    .header("x-request-id", "req-abc-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["x-request-id"], "req-abc-123");
+        mock.assert_async().await;
+    }
+
     #[test]
     fn test_circuit_breaker_state() {
         let client = HorizonClient::new("https://horizon-testnet.stellar.org".to_string());
@@ -583,6 +1212,150 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_rate_limited_request_is_retried_after_retry_after() {
+        let mut server = mockito::Server::new_async().await;
+        let path = mockito::Matcher::Regex(r"^/transactions/.*".into());
+
+        let limited = server
+            .mock("GET", path.clone())
+            .with_status(429)
+            .with_header("retry-after", "1")
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("GET", path)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"id":"abc","hash":"abc","ledger":1,"created_at":"2024-01-01T00:00:00Z",
+                    "source_account":"GABC","successful":true}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = HorizonClient::new(server.url());
+        let started = std::time::Instant::now();
+        let tx = client.get_transaction("abc").await.unwrap();
+
+        assert_eq!(tx.hash, "abc");
+        assert!(
+            started.elapsed() >= Duration::from_secs(1),
+            "waited for Retry-After before retrying"
+        );
+        limited.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_surfaces_error_after_retries_exhausted() {
+        let mut server = mockito::Server::new_async().await;
+
+        let mock = server
+            .mock("GET", mockito::Matcher::Regex(r"^/transactions/.*".into()))
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(MAX_RATE_LIMIT_RETRIES as usize + 1)
+            .create_async()
+            .await;
+
+        let client = HorizonClient::new(server.url());
+        let result = client.get_transaction("abc").await;
+
+        assert!(
+            matches!(result, Err(HorizonError::RateLimited(_))),
+            "Expected RateLimited, got: {:?}",
+            result
+        );
+        mock.assert_async().await;
+    }
+
+    const ROOT_BODY: &str = r#"{"network_passphrase":"Test SDF Network ; September 2015"}"#;
+
+    #[tokio::test]
+    async fn test_fails_over_to_secondary_and_returns_to_primary() {
+        let mut primary = mockito::Server::new_async().await;
+        let mut secondary = mockito::Server::new_async().await;
+
+        let down = primary
+            .mock("GET", "/")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let fallback = secondary
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(ROOT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = HorizonClient::with_endpoints(vec![primary.url(), secondary.url()])
+            .with_primary_recheck(Duration::ZERO);
+        client.get_root().await.unwrap();
+        assert_eq!(client.base_url(), secondary.url());
+        down.assert_async().await;
+        fallback.assert_async().await;
+
+        down.remove_async().await;
+        let healthy = primary
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(ROOT_BODY)
+            .expect(1)
+            .create_async()
+            .await;
+        client.get_root().await.unwrap();
+        assert_eq!(client.base_url(), primary.url());
+        healthy.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_primary_stays_failed_over_until_recheck() {
+        let mut secondary = mockito::Server::new_async().await;
+        let mock = secondary
+            .mock("GET", "/transactions/abc")
+            .with_status(404)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client =
+            HorizonClient::with_endpoints(vec!["http://127.0.0.1:1".to_string(), secondary.url()]);
+        for _ in 0..2 {
+            let result = client.get_transaction("abc").await;
+            assert!(matches!(result, Err(HorizonError::TransactionNotFound(_))));
+            assert_eq!(client.base_url(), secondary.url());
+        }
+        mock.assert_async().await;
+        assert_eq!(client.endpoints.order(PRIMARY_RECHECK_INTERVAL), vec![1, 0]);
+        assert_eq!(client.endpoints.order(Duration::ZERO), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_paging_link_follows_active_endpoint() {
+        let mut secondary = mockito::Server::new_async().await;
+        let mock = secondary
+            .mock("GET", "/accounts/GACC/payments")
+            .match_query(mockito::Matcher::UrlEncoded("cursor".into(), "9".into()))
+            .with_status(200)
+            .with_body(r#"{"_embedded":{"records":[]}}"#)
+            .create_async()
+            .await;
+
+        let primary = "http://127.0.0.1:1".to_string();
+        let client = HorizonClient::with_endpoints(vec![primary.clone(), secondary.url()]);
+        let page = client
+            .get_payments_page(&format!("{primary}/accounts/GACC/payments?cursor=9"))
+            .await
+            .unwrap();
+        assert!(page.records.is_empty());
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker_opens_after_failures() {
         let mut server = mockito::Server::new_async().await;
@@ -1,4 +1,6 @@
 pub mod client;
+pub mod network;
 
 pub use client::HorizonClient;
-pub use client::{AccountResponse, Balance, HorizonError};
+pub use client::{AccountResponse, Balance, HorizonError, RootResponse};
+pub use network::StellarNetwork;
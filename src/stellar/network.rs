@@ -0,0 +1,85 @@
+//! The Stellar network this instance expects its Horizon to serve.
+
+/// Passphrase of the SDF test network.
+pub const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+/// Passphrase of the public network.
+pub const PUBNET_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+
+/// Configured with `STELLAR_NETWORK` (`testnet`, `pubnet` or `custom`); a
+/// custom network takes its passphrase from `STELLAR_NETWORK_PASSPHRASE`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StellarNetwork {
+    #[default]
+    Testnet,
+    Pubnet,
+    Custom(String),
+}
+
+impl StellarNetwork {
+    /// Parses `STELLAR_NETWORK`, taking the passphrase of a `custom` network
+    /// from `custom_passphrase`.
+    pub fn parse(raw: &str, custom_passphrase: Option<&str>) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "testnet" => Ok(Self::Testnet),
+            "pubnet" | "public" | "mainnet" => Ok(Self::Pubnet),
+            "custom" => match custom_passphrase.map(str::trim) {
+                Some(passphrase) if !passphrase.is_empty() => {
+                    Ok(Self::Custom(passphrase.to_string()))
+                }
+                _ => anyhow::bail!(
+                    "STELLAR_NETWORK_PASSPHRASE must be set when STELLAR_NETWORK is 'custom'"
+                ),
+            },
+            _ => anyhow::bail!("STELLAR_NETWORK must be 'testnet', 'pubnet' or 'custom'"),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Testnet => "testnet",
+            Self::Pubnet => "pubnet",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Network passphrase Horizon must report for this network.
+    pub fn passphrase(&self) -> &str {
+        match self {
+            Self::Testnet => TESTNET_PASSPHRASE,
+            Self::Pubnet => PUBNET_PASSPHRASE,
+            Self::Custom(passphrase) => passphrase,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_known_networks() {
+        assert_eq!(
+            StellarNetwork::parse("testnet", None).unwrap(),
+            StellarNetwork::Testnet
+        );
+        assert_eq!(
+            StellarNetwork::parse(" PUBNET ", Some("ignored")).unwrap(),
+            StellarNetwork::Pubnet
+        );
+        assert_eq!(
+            StellarNetwork::Pubnet.passphrase(),
+            "Public Global Stellar Network ; September 2015"
+        );
+    }
+
+    #[test]
+    fn test_custom_network_requires_passphrase() {
+        assert!(StellarNetwork::parse("custom", None).is_err());
+        assert!(StellarNetwork::parse("custom", Some("  ")).is_err());
+        let network =
+            StellarNetwork::parse("custom", Some("Standalone Network ; February 2017")).unwrap();
+        assert_eq!(network.passphrase(), "Standalone Network ; February 2017");
+        assert!(StellarNetwork::parse("futurenet", None).is_err());
+    }
+}
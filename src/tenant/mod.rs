@@ -1,10 +1,11 @@
 use axum::{
     async_trait,
-    extract::{FromRequestParts, Path},
+    extract::{FromRef, FromRequestParts, Path},
     http::{request::Parts, HeaderMap},
     RequestPartsExt,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{error::AppError, AppState};
@@ -17,6 +18,11 @@ pub struct TenantConfig {
     pub stellar_account: String,
     pub rate_limit_per_minute: i32,
     pub is_active: bool,
+    /// Browser origins allowed for this tenant's requests; empty falls back
+    /// to the global `CORS_ALLOWED_ORIGINS`.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub cors_allowed_origins: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -32,14 +38,27 @@ impl TenantContext {
 }
 
 #[async_trait]
-impl FromRequestParts<AppState> for TenantContext {
+impl<S> FromRequestParts<S> for TenantContext
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
     type Rejection = AppError;
 
     async fn from_request_parts(
         parts: &mut Parts,
-        state: &AppState,
+        state: &S,
     ) -> std::result::Result<Self, AppError> {
-        let tenant_id = resolve_tenant_id(parts, state).await?;
+        let state = &AppState::from_ref(state);
+        let tenant_id = match path_tenant_id(parts).await {
+            Some(tenant_id) => tenant_id?,
+            // Already resolved from the same headers by
+            // `tenant_context_middleware`.
+            None => match parts.extensions.get::<TenantContext>() {
+                Some(tenant) => return Ok(tenant.clone()),
+                None => resolve_header_tenant_id(&parts.headers, state).await?,
+            },
+        };
 
         let config = state
             .get_tenant_config(tenant_id)
@@ -54,16 +73,51 @@ impl FromRequestParts<AppState> for TenantContext {
     }
 }
 
-async fn resolve_tenant_id(
-    parts: &mut Parts,
-    state: &AppState,
-) -> std::result::Result<Uuid, AppError> {
-    if let Ok(Path(tenant_id)) = parts.extract::<Path<Uuid>>().await {
-        return Ok(tenant_id);
+/// Tenant of a request that may or may not identify one. Requests without an
+/// `X-API-Key` or `X-Tenant-ID` header resolve to `None`; requests carrying
+/// either must resolve to an active tenant or are rejected, so a mistyped key
+/// never widens what the caller can see.
+#[derive(Debug, Clone)]
+pub struct OptionalTenant(pub Option<TenantContext>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OptionalTenant
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> std::result::Result<Self, AppError> {
+        if !parts.headers.contains_key("X-API-Key") && !parts.headers.contains_key("X-Tenant-ID") {
+            return Ok(Self(None));
+        }
+        TenantContext::from_request_parts(parts, state)
+            .await
+            .map(|tenant| Self(Some(tenant)))
     }
+}
 
-    let headers = &parts.headers;
+/// Tenant named by a `:tenant_id` path segment, if the route has one. Other
+/// ids in the path (e.g. `/transactions/:id`) identify the resource being
+/// requested.
+async fn path_tenant_id(parts: &mut Parts) -> Option<std::result::Result<Uuid, AppError>> {
+    let Path(params) = parts
+        .extract::<Path<HashMap<String, String>>>()
+        .await
+        .ok()?;
+    params
+        .get("tenant_id")
+        .map(|tenant_id| Uuid::parse_str(tenant_id).map_err(|_| AppError::TenantNotFound))
+}
 
+async fn resolve_header_tenant_id(
+    headers: &HeaderMap,
+    state: &AppState,
+) -> std::result::Result<Uuid, AppError> {
     if let Some(api_key) = extract_api_key(headers) {
         return resolve_tenant_by_api_key(&state.db, &api_key).await;
     }
@@ -82,6 +136,26 @@ async fn resolve_tenant_id(
     Err(AppError::InvalidApiKey)
 }
 
+/// Active tenant named by the request's `X-API-Key` or `X-Tenant-ID` header.
+///
+/// Unlike [`OptionalTenant`], a header that does not resolve to an active
+/// tenant yields `None` rather than an error, so callers that only observe
+/// the tenant (metrics, CORS) never reject a request on their own.
+pub async fn resolve_request_tenant(
+    headers: &HeaderMap,
+    state: &AppState,
+) -> Option<TenantContext> {
+    if !headers.contains_key("X-API-Key") && !headers.contains_key("X-Tenant-ID") {
+        return None;
+    }
+    let tenant_id = resolve_header_tenant_id(headers, state).await.ok()?;
+    let config = state
+        .get_tenant_config(tenant_id)
+        .await
+        .filter(|config| config.is_active)?;
+    Some(TenantContext::new(tenant_id, config))
+}
+
 fn extract_api_key(headers: &HeaderMap) -> Option<String> {
     headers
         .get("X-API-Key")
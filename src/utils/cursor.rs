@@ -28,6 +28,41 @@ pub fn decode(cursor: &str) -> Result<(DateTime<Utc>, Uuid), String> {
     Ok((ts, id))
 }
 
+/// Encodes a keyset position for a listing ordered by `sort`: the sort key's
+/// value and the row id, tagged with the sort so the cursor cannot be
+/// replayed under a different ordering.
+/// Format used internally: "{sort}|{value}|{uuid}" then base64 encoded.
+pub fn encode_sorted(sort: &str, value: &str, id: Uuid) -> String {
+    let s = format!("{}|{}|{}", sort, value, id);
+    general_purpose::STANDARD.encode(s)
+}
+
+/// Decodes a cursor from [`encode_sorted`], returning the sort key's value and
+/// the id. Fails if the cursor was issued for a sort other than `sort`.
+pub fn decode_sorted(cursor: &str, sort: &str) -> Result<(String, Uuid), String> {
+    let decoded = general_purpose::STANDARD
+        .decode(cursor)
+        .map_err(|e| format!("base64 decode error: {e}"))?;
+    let s = String::from_utf8(decoded).map_err(|e| format!("utf8 error: {e}"))?;
+    let mut parts = s.splitn(3, '|');
+    let cursor_sort = parts
+        .next()
+        .ok_or_else(|| "missing sort in cursor".to_string())?;
+    let value = parts
+        .next()
+        .ok_or_else(|| "missing sort value in cursor".to_string())?;
+    let id_str = parts
+        .next()
+        .ok_or_else(|| "missing id in cursor".to_string())?;
+    if cursor_sort != sort {
+        return Err(format!(
+            "cursor was issued for sort '{cursor_sort}' but the request sorts by '{sort}'"
+        ));
+    }
+    let id = Uuid::parse_str(id_str).map_err(|e| format!("uuid parse error: {e}"))?;
+    Ok((value.to_string(), id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +113,27 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("timestamp parse error"));
     }
+
+    #[test]
+    fn test_sorted_cursor_roundtrip() {
+        let id = Uuid::new_v4();
+        let cursor = encode_sorted("amount", "250.50", id);
+        let (value, decoded_id) = decode_sorted(&cursor, "amount").unwrap();
+        assert_eq!(value, "250.50");
+        assert_eq!(decoded_id, id);
+    }
+
+    #[test]
+    fn test_sorted_cursor_rejects_other_sort() {
+        let cursor = encode_sorted("created_at", &Utc::now().to_rfc3339(), Uuid::new_v4());
+        let err = decode_sorted(&cursor, "amount").unwrap_err();
+        assert!(err.contains("issued for sort 'created_at'"), "{err}");
+    }
+
+    #[test]
+    fn test_sorted_cursor_rejects_unsorted_cursor() {
+        // A plain (created_at, id) cursor carries no sort tag.
+        let cursor = encode(Utc::now(), Uuid::new_v4());
+        assert!(decode_sorted(&cursor, "created_at").is_err());
+    }
 }
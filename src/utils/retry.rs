@@ -148,6 +148,69 @@ where
     }
 }
 
+/// Classifies whether a Redis error is transient: the connection dropped,
+/// was refused or timed out, as happens while Redis restarts or fails over.
+/// `redis::aio::ConnectionManager` reconnects after such an error, so the
+/// next attempt can succeed.
+pub fn is_transient_redis_error(err: &redis::RedisError) -> bool {
+    err.is_io_error()
+        || err.is_connection_dropped()
+        || err.is_connection_refusal()
+        || err.is_timeout()
+}
+
+/// Like [`retry_with_backoff`], for Redis operations: retries while
+/// [`is_transient_redis_error`] holds, giving a reconnecting client time to
+/// come back before the error reaches the caller. The same idempotency
+/// requirement applies.
+pub async fn retry_redis_with_backoff<F, Fut, T>(
+    operation_name: &str,
+    max_retries: u32,
+    base_delay_ms: u64,
+    mut f: F,
+) -> Result<T, redis::RedisError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, redis::RedisError>>,
+{
+    let mut attempt = 0u32;
+    let mut prev_delay_ms = base_delay_ms;
+    loop {
+        match f().await {
+            Ok(val) => return Ok(val),
+            Err(err) if attempt < max_retries && is_transient_redis_error(&err) => {
+                attempt += 1;
+                let delay_ms = decorrelated_jitter_delay_ms(
+                    prev_delay_ms,
+                    base_delay_ms,
+                    MAX_DELAY_MS,
+                    &mut rand::thread_rng(),
+                );
+                prev_delay_ms = delay_ms;
+
+                warn!(
+                    operation = operation_name,
+                    attempt,
+                    delay_ms,
+                    error = %err,
+                    "Transient Redis error, retrying"
+                );
+
+                sleep(Duration::from_millis(delay_ms)).await;
+            }
+            Err(err) => {
+                debug!(
+                    operation = operation_name,
+                    attempt,
+                    error = %err,
+                    "Redis error is permanent or max retries exceeded"
+                );
+                return Err(err);
+            }
+        }
+    }
+}
+
 fn classify_error_kind(err: &sqlx::Error) -> &'static str {
     match err {
         sqlx::Error::Io(_) => "io",
@@ -317,4 +380,41 @@ mod tests {
             "two independent callers produced identical jitter sequences"
         );
     }
+
+    #[tokio::test]
+    async fn test_redis_retry_recovers_from_dropped_connection_only() {
+        let dropped =
+            || redis::RedisError::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        let calls = Arc::new(AtomicU32::new(0));
+        let c = calls.clone();
+        let result = retry_redis_with_backoff("test_redis_op", 3, 1, || {
+            let c = c.clone();
+            async move {
+                if c.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(dropped())
+                } else {
+                    Ok("PONG")
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "PONG");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Errors Redis itself returned are not retried.
+        let calls = Arc::new(AtomicU32::new(0));
+        let c = calls.clone();
+        let result: Result<(), _> = retry_redis_with_backoff("test_redis_op", 3, 1, || {
+            c.fetch_add(1, Ordering::SeqCst);
+            async {
+                Err(redis::RedisError::from((
+                    redis::ErrorKind::TypeError,
+                    "WRONGTYPE",
+                )))
+            }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -1,23 +1,111 @@
 use serde_json::Value;
 
+/// Extra sensitive-key patterns applied by [`sanitize_json`], registered at
+/// startup from `Config::log_sensitive_keys`.
+static CONFIGURED_KEYS: std::sync::RwLock<Option<SensitiveKeys>> = std::sync::RwLock::new(None);
+
+/// Registers the configured sensitive-key patterns. They are redacted in
+/// addition to the built-in fields, never instead of them.
+pub fn set_sensitive_keys<S: AsRef<str>>(patterns: &[S]) {
+    *CONFIGURED_KEYS.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(SensitiveKeys::new(patterns));
+}
+
 /// Sanitizes sensitive fields in JSON payloads for logging
 pub fn sanitize_json(value: &Value) -> Value {
-    match value {
-        Value::Object(map) => {
-            let mut sanitized = serde_json::Map::new();
-            for (key, val) in map {
-                let sanitized_val = if is_sensitive_field(key) {
-                    mask_value(val)
-                } else {
-                    sanitize_json(val)
-                };
-                sanitized.insert(key.clone(), sanitized_val);
+    match CONFIGURED_KEYS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        Some(keys) => keys.sanitize(value),
+        None => SensitiveKeys::default().sanitize(value),
+    }
+}
+
+/// Key patterns to redact on top of the built-in sensitive fields.
+///
+/// A pattern is a dot-separated key path matched case-insensitively against
+/// the trailing keys of a field's path (array indices are not part of the
+/// path). `*` inside a segment matches any run of characters, so `ssn`
+/// redacts every `ssn` key, `*.secret` redacts any nested `secret`, and
+/// `card.*` redacts every field directly under a `card` object.
+#[derive(Debug, Clone, Default)]
+pub struct SensitiveKeys {
+    patterns: Vec<Vec<String>>,
+}
+
+impl SensitiveKeys {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let patterns = patterns
+            .iter()
+            .map(|p| p.as_ref().trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .map(|p| p.split('.').map(String::from).collect())
+            .collect();
+        Self { patterns }
+    }
+
+    /// Masks built-in and configured sensitive fields in `value`.
+    pub fn sanitize(&self, value: &Value) -> Value {
+        self.sanitize_at(value, &mut Vec::new())
+    }
+
+    fn sanitize_at(&self, value: &Value, path: &mut Vec<String>) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut sanitized = serde_json::Map::new();
+                for (key, val) in map {
+                    path.push(key.to_lowercase());
+                    let sanitized_val = if is_sensitive_field(key) || self.matches(path) {
+                        mask_value(val)
+                    } else {
+                        self.sanitize_at(val, path)
+                    };
+                    path.pop();
+                    sanitized.insert(key.clone(), sanitized_val);
+                }
+                Value::Object(sanitized)
             }
-            Value::Object(sanitized)
+            Value::Array(arr) => Value::Array(
+                arr.iter()
+                    .map(|item| self.sanitize_at(item, path))
+                    .collect(),
+            ),
+            _ => value.clone(),
         }
-        Value::Array(arr) => Value::Array(arr.iter().map(sanitize_json).collect()),
-        _ => value.clone(),
     }
+
+    fn matches(&self, path: &[String]) -> bool {
+        self.patterns.iter().any(|pattern| {
+            pattern.len() <= path.len()
+                && pattern
+                    .iter()
+                    .zip(&path[path.len() - pattern.len()..])
+                    .all(|(segment, key)| segment_matches(segment, key))
+        })
+    }
+}
+
+/// Matches one path segment, where each `*` stands for any run of characters.
+fn segment_matches(pattern: &str, key: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = key.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` in the pattern: exact match.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
 }
 
 fn is_sensitive_field(key: &str) -> bool {
@@ -223,6 +311,81 @@ mod tests {
         assert!(sanitized["nested"]["data"].is_null());
     }
 
+    #[test]
+    fn test_custom_key_is_redacted() {
+        let keys = SensitiveKeys::new(&["iban", "Tax_ID"]);
+        let input = json!({
+            "iban": "DE89370400440532013000",
+            "customer": {"tax_id": "123-45-6789", "name": "Ada"},
+            "amount": "10.00"
+        });
+
+        let sanitized = keys.sanitize(&input);
+
+        assert_eq!(sanitized["iban"], "DE89****3000");
+        assert!(sanitized["customer"]["tax_id"]
+            .as_str()
+            .unwrap()
+            .contains("****"));
+        assert_eq!(sanitized["customer"]["name"], "Ada");
+        assert_eq!(sanitized["amount"], "10.00");
+    }
+
+    #[test]
+    fn test_builtin_keys_still_redacted_with_custom_keys() {
+        let keys = SensitiveKeys::new(&["iban"]);
+        let input = json!({
+            "password": "hunter22",
+            "api_key": "sk_live_1234567890",
+            "user_token": "abc",
+            "name": "Ada"
+        });
+
+        let sanitized = keys.sanitize(&input);
+
+        assert_eq!(sanitized["password"], "****");
+        assert!(sanitized["api_key"].as_str().unwrap().contains("****"));
+        assert_eq!(sanitized["user_token"], "****");
+        assert_eq!(sanitized["name"], "Ada");
+    }
+
+    #[test]
+    fn test_nested_wildcard_patterns() {
+        let keys = SensitiveKeys::new(&["*.pin", "card.*", "bank.*_number"]);
+        let input = json!({
+            "pin": "top-level",
+            "wallet": {"pin": "1234", "label": "main"},
+            "card": {"last4": "4242", "holder": "Ada"},
+            "bank": {"account_number": "000123", "routing_number": "110000", "name": "Acme"},
+            "items": [{"card": {"cvc": "123"}}]
+        });
+
+        let sanitized = keys.sanitize(&input);
+
+        // `*.pin` needs a parent key, so the top-level `pin` is left alone.
+        assert_eq!(sanitized["pin"], "top-level");
+        assert_eq!(sanitized["wallet"]["pin"], "****");
+        assert_eq!(sanitized["wallet"]["label"], "main");
+        assert_eq!(sanitized["card"]["last4"], "****");
+        assert_eq!(sanitized["card"]["holder"], "****");
+        assert_eq!(sanitized["bank"]["account_number"], "****");
+        assert_eq!(sanitized["bank"]["routing_number"], "****");
+        assert_eq!(sanitized["bank"]["name"], "Acme");
+        assert_eq!(sanitized["items"][0]["card"]["cvc"], "****");
+    }
+
+    #[test]
+    fn test_segment_matches() {
+        assert!(segment_matches("secret", "secret"));
+        assert!(!segment_matches("secret", "secrets"));
+        assert!(segment_matches("*", "anything"));
+        assert!(segment_matches("card_*", "card_number"));
+        assert!(segment_matches("*_number", "routing_number"));
+        assert!(segment_matches("a*b*c", "axxbyyc"));
+        assert!(!segment_matches("a*b*c", "axxbyy"));
+        assert!(!segment_matches("ab*ba", "aba"));
+    }
+
     #[test]
     fn test_sanitize_large_payload_performance() {
         use std::time::Instant;
@@ -13,6 +13,9 @@ pub const CALLBACK_TYPE_MAX_LEN: usize = 20;
 pub const CALLBACK_STATUS_MAX_LEN: usize = 20;
 pub const AMOUNT_INPUT_MAX_LEN: usize = 64;
 pub const ALLOWED_ASSET_CODES: &[&str] = &["USD"];
+pub const METADATA_MAX_PROPERTIES: usize = 50;
+/// Largest body the schema-validation middleware will parse.
+pub const SCHEMA_PAYLOAD_MAX_BYTES: usize = 32 * 1024;
 
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -131,10 +134,29 @@ pub fn validate_stellar_account(account: &str) -> ValidationResult {
     validate_stellar_address(account)
 }
 
+/// Canonical form of an inbound asset code: trimmed and uppercased, so `usd`
+/// and ` USD ` are stored (and settled) as `USD`. Codes that are empty after
+/// trimming are rejected; the result still needs [`validate_asset_code`].
+pub fn normalize_asset_code(asset_code: &str) -> Result<String, ValidationError> {
+    let asset_code = sanitize_string(asset_code).to_ascii_uppercase();
+    validate_required("asset_code", &asset_code)?;
+    Ok(asset_code)
+}
+
 pub fn validate_asset_code(asset_code: &str) -> ValidationResult {
     let asset_code = sanitize_string(asset_code);
-    validate_required("asset_code", &asset_code)?;
-    validate_max_len("asset_code", &asset_code, ASSET_CODE_MAX_LEN)?;
+    validate_asset_code_format(&asset_code)?;
+    validate_enum("asset_code", &asset_code, ALLOWED_ASSET_CODES)?;
+
+    Ok(())
+}
+
+/// Checks that `asset_code` is 1–[`ASSET_CODE_MAX_LEN`] uppercase letters or
+/// digits, without restricting it to [`ALLOWED_ASSET_CODES`]. For lookups
+/// by asset (stats, settlement configuration) rather than new payments.
+pub fn validate_asset_code_format(asset_code: &str) -> ValidationResult {
+    validate_required("asset_code", asset_code)?;
+    validate_max_len("asset_code", asset_code, ASSET_CODE_MAX_LEN)?;
 
     if !asset_code
         .chars()
@@ -146,8 +168,6 @@ pub fn validate_asset_code(asset_code: &str) -> ValidationResult {
         ));
     }
 
-    validate_enum("asset_code", &asset_code, ALLOWED_ASSET_CODES)?;
-
     Ok(())
 }
 
@@ -237,6 +257,16 @@ mod tests {
         assert!(validate_asset_code("").is_err());
     }
 
+    #[test]
+    fn normalizes_asset_code() {
+        assert_eq!(normalize_asset_code("usd").unwrap(), "USD");
+        assert_eq!(normalize_asset_code("  uSd\t").unwrap(), "USD");
+        assert_eq!(normalize_asset_code("USD").unwrap(), "USD");
+        assert!(normalize_asset_code("").is_err());
+        assert!(normalize_asset_code(" \t\n ").is_err());
+        assert!(validate_asset_code(&normalize_asset_code(" usd ").unwrap()).is_ok());
+    }
+
     #[test]
     fn validates_positive_amount() {
         let positive = BigDecimal::from_str("1.23").expect("valid decimal");
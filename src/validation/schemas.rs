@@ -1,3 +1,5 @@
+use crate::middleware::versioning::ApiVersion;
+use crate::validation::METADATA_MAX_PROPERTIES;
 use jsonschema::JSONSchema;
 use once_cell::sync::Lazy;
 use serde_json::json;
@@ -5,6 +7,7 @@ use serde_json::json;
 /// Compiled JSON schemas for webhook payloads
 pub struct SchemaRegistry {
     pub callback_v1: JSONSchema,
+    pub callback_v2: JSONSchema,
     pub webhook_v1: JSONSchema,
 }
 
@@ -13,10 +16,20 @@ impl SchemaRegistry {
         Self {
             callback_v1: JSONSchema::compile(&callback_schema_v1())
                 .expect("Failed to compile callback schema"),
+            callback_v2: JSONSchema::compile(&callback_schema_v2())
+                .expect("Failed to compile callback v2 schema"),
             webhook_v1: JSONSchema::compile(&webhook_schema_v1())
                 .expect("Failed to compile webhook schema"),
         }
     }
+
+    /// Callback schema for the resolved API version.
+    pub fn callback(&self, version: ApiVersion) -> &JSONSchema {
+        match version {
+            ApiVersion::V1 => &self.callback_v1,
+            ApiVersion::V2 => &self.callback_v2,
+        }
+    }
 }
 
 /// Global schema registry with cached compiled schemas
@@ -47,6 +60,11 @@ fn callback_schema_v1() -> serde_json::Value {
                 "maxLength": 12,
                 "description": "Asset code (uppercase alphanumeric)"
             },
+            "asset_issuer": {
+                "type": "string",
+                "pattern": "^G[A-Z2-7]{55}$",
+                "description": "Issuing account of the asset; omit for native XLM"
+            },
             "callback_type": {
                 "type": "string",
                 "maxLength": 20,
@@ -74,12 +92,42 @@ fn callback_schema_v1() -> serde_json::Value {
             },
             "metadata": {
                 "type": "object",
+                "maxProperties": METADATA_MAX_PROPERTIES,
                 "description": "Additional metadata as JSON object"
             }
         }
     })
 }
 
+/// JSON schema for callback payload (v2)
+///
+/// Same as v1, plus the optional `idempotency_key` and `tenant_ref` fields.
+fn callback_schema_v2() -> serde_json::Value {
+    let mut schema = callback_schema_v1();
+    let properties = schema["properties"]
+        .as_object_mut()
+        .expect("callback v1 schema has properties");
+    properties.insert(
+        "idempotency_key".to_string(),
+        json!({
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 255,
+            "description": "Client-supplied key used to deduplicate retries"
+        }),
+    );
+    properties.insert(
+        "tenant_ref".to_string(),
+        json!({
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 255,
+            "description": "Caller's reference for the tenant the callback belongs to"
+        }),
+    );
+    schema
+}
+
 /// JSON schema for webhook payload (v1)
 fn webhook_schema_v1() -> serde_json::Value {
     json!({
@@ -132,6 +180,24 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_callback_schema_caps_metadata_properties() {
+        let metadata = |count: usize| -> serde_json::Map<String, serde_json::Value> {
+            (0..count).map(|i| (format!("k{i}"), json!(i))).collect()
+        };
+        let mut payload = json!({
+            "stellar_account": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "amount": "100.50",
+            "asset_code": "USD",
+            "metadata": metadata(METADATA_MAX_PROPERTIES)
+        });
+        assert!(SCHEMAS.callback_v1.is_valid(&payload));
+
+        payload["metadata"] = metadata(METADATA_MAX_PROPERTIES + 1).into();
+        assert!(!SCHEMAS.callback_v1.is_valid(&payload));
+        assert!(!SCHEMAS.callback_v2.is_valid(&payload));
+    }
+
     #[test]
     fn test_callback_schema_missing_required() {
         let invalid = json!({
@@ -205,6 +271,50 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn v2_payload() -> serde_json::Value {
+        json!({
+            "stellar_account": "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "amount": "100.50",
+            "asset_code": "USD",
+            "idempotency_key": "idem-123",
+            "tenant_ref": "acme"
+        })
+    }
+
+    #[test]
+    fn test_callback_v2_accepts_new_fields() {
+        assert!(SCHEMAS.callback_v2.validate(&v2_payload()).is_ok());
+    }
+
+    #[test]
+    fn test_callback_v1_rejects_v2_fields() {
+        let payload = v2_payload();
+        let errors: Vec<String> = SCHEMAS
+            .callback_v1
+            .validate(&payload)
+            .unwrap_err()
+            .map(|e| e.to_string())
+            .collect();
+        assert!(
+            errors.iter().any(|e| e.contains("Additional properties")),
+            "unexpected errors: {errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_callback_v2_still_rejects_unknown_fields() {
+        let mut payload = v2_payload();
+        payload["unknown_field"] = json!("value");
+        assert!(SCHEMAS.callback_v2.validate(&payload).is_err());
+    }
+
+    #[test]
+    fn test_registry_selects_callback_schema_by_version() {
+        let payload = v2_payload();
+        assert!(SCHEMAS.callback(ApiVersion::V2).is_valid(&payload));
+        assert!(!SCHEMAS.callback(ApiVersion::V1).is_valid(&payload));
+    }
+
     #[test]
     fn test_webhook_schema_valid() {
         let valid = json!({
@@ -1,3 +1,4 @@
+use crate::db::models::TransactionStatus;
 use crate::error::AppError;
 use crate::validation::state_transitions::{is_valid_transition, TRANSACTION_TRANSITIONS};
 
@@ -15,6 +16,14 @@ pub fn validate_status_transition(from: &str, to: &str) -> Result<(), AppError>
     }
 }
 
+/// Whether a transaction may move from `from` to `to`.
+///
+/// Typed counterpart of [`validate_status_transition`], backed by the same
+/// `TRANSACTION_TRANSITIONS` table.
+pub fn can_transition(from: TransactionStatus, to: TransactionStatus) -> bool {
+    is_valid_transition(&from.to_string(), &to.to_string(), TRANSACTION_TRANSITIONS)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -66,4 +75,30 @@ mod tests {
         assert!(err.to_string().contains("completed"));
         assert!(err.to_string().contains("pending"));
     }
+
+    #[test]
+    fn test_can_transition_allows_every_edge_in_the_graph() {
+        use TransactionStatus::*;
+        for (from, to) in [
+            (Pending, Processing),
+            (Pending, Completed),
+            (Pending, Failed),
+            (Pending, Dlq),
+            (Processing, Completed),
+            (Processing, Failed),
+            (Failed, Pending),
+            (Dlq, Pending),
+        ] {
+            assert!(can_transition(from, to), "{from} -> {to} should be allowed");
+        }
+    }
+
+    #[test]
+    fn test_can_transition_rejects_leaving_completed() {
+        use TransactionStatus::*;
+        for to in [Pending, Processing, Failed, Dlq] {
+            assert!(!can_transition(Completed, to), "completed -> {to}");
+        }
+        assert!(can_transition(Completed, Completed));
+    }
 }
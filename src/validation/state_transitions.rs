@@ -24,6 +24,11 @@ pub const TRANSACTION_TRANSITIONS: &[Transition] = &[
         from: "pending",
         to: "failed",
     },
+    // Retries exhausted
+    Transition {
+        from: "pending",
+        to: "dlq",
+    },
     // From processing
     Transition {
         from: "processing",
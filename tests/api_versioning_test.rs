@@ -60,6 +60,21 @@ async fn test_api_versioning_headers() {
         secrets_store: None,
         metrics_handle: synapse_core::metrics::init_metrics().unwrap(),
         ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        cors_allowed_origins: Vec::new(),
+        horizon_page_limit: 200,
+        health_latency_threshold_ms: 1000,
+        idempotency_failure_mode: Default::default(),
+        webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(64)),
+        app_env: Default::default(),
+        backup_checker: None,
+        request_timeout: std::time::Duration::from_secs(30),
+        search_default_window_days: 90,
+        export_jobs_dir: "/tmp/synapse_exports".into(),
+        config_info: Default::default(),
+        job_scheduler: Default::default(),
+        jwt_secret: Default::default(),
+        retry_policy: Default::default(),
+        batch_replay_limits: Default::default(),
     };
     let app = create_app(app_state);
 
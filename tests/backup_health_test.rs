@@ -0,0 +1,83 @@
+//! Backup freshness check reported under `backups` by `/health`.
+
+use chrono::{Duration as ChronoDuration, Utc};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use synapse_core::health::{BackupChecker, DependencyChecker, DependencyStatus};
+use synapse_core::services::backup::{BackupMetadata, BackupService, BackupType};
+
+const MAX_AGE: Duration = Duration::from_secs(25 * 3600);
+
+/// Writes the `.meta` sidecar `BackupService::list_backups` reads for a
+/// backup taken `age` ago.
+fn write_backup_metadata(dir: &Path, name: &str, age: ChronoDuration) {
+    let metadata = BackupMetadata {
+        filename: format!("{name}.sql.gz"),
+        backup_type: BackupType::Daily,
+        timestamp: Utc::now() - age,
+        size_bytes: 1024,
+        compressed: true,
+        encrypted: false,
+        checksum: "0".repeat(64),
+    };
+    std::fs::write(
+        dir.join(format!("{name}.meta")),
+        serde_json::to_string(&metadata).unwrap(),
+    )
+    .unwrap();
+}
+
+fn checker(dir: &Path) -> BackupChecker {
+    let service = BackupService::new("postgres://unused".to_string(), dir.to_path_buf(), None);
+    BackupChecker::new(Arc::new(service), MAX_AGE)
+}
+
+#[tokio::test]
+async fn test_fresh_backup_is_healthy() {
+    let dir = tempfile::tempdir().unwrap();
+    write_backup_metadata(dir.path(), "old", ChronoDuration::days(3));
+    write_backup_metadata(dir.path(), "fresh", ChronoDuration::hours(1));
+
+    let status = checker(dir.path()).check().await;
+
+    assert!(
+        matches!(status, DependencyStatus::Healthy { .. }),
+        "got {status:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_old_backup_is_reported_degraded() {
+    let dir = tempfile::tempdir().unwrap();
+    write_backup_metadata(dir.path(), "old", ChronoDuration::hours(30));
+
+    let status = checker(dir.path()).check().await;
+
+    assert!(
+        matches!(
+            status,
+            DependencyStatus::Stale { age_secs, max_age_secs, .. }
+                if age_secs >= 30 * 3600 && max_age_secs == MAX_AGE.as_secs()
+        ),
+        "got {status:?}"
+    );
+    let json = serde_json::to_value(&status).unwrap();
+    assert_eq!(json["status"], "degraded");
+
+    // Round-trips as stale rather than collapsing into another variant.
+    let back: DependencyStatus = serde_json::from_value(json).unwrap();
+    assert!(matches!(back, DependencyStatus::Stale { .. }));
+}
+
+#[tokio::test]
+async fn test_missing_backups_are_reported_unhealthy() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let status = checker(&dir.path().join("never-created")).check().await;
+
+    assert!(
+        matches!(&status, DependencyStatus::Unhealthy { error, .. } if error == "no backups found"),
+        "got {status:?}"
+    );
+}
@@ -48,6 +48,9 @@ const DEFAULT_REDIS_URL: &str = "redis://127.0.0.1:6379";
 const COMPOSE_UP_CMD: &str = "docker compose up -d postgres redis";
 const DOCKER_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
 
+/// Key the served app verifies bearer tokens against.
+pub const TEST_JWT_SECRET: &str = "common-test-jwt-secret-of-32-bytes-or-more";
+
 /// Test application with automatic database and HTTP server setup.
 pub struct TestApp {
     pub base_url: String,
@@ -111,6 +114,21 @@ impl TestApp {
             secrets_store: None,
             metrics_handle: synapse_core::metrics::init_metrics().unwrap(),
             ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            cors_allowed_origins: Vec::new(),
+            horizon_page_limit: 200,
+            health_latency_threshold_ms: 1000,
+            idempotency_failure_mode: Default::default(),
+            webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(64)),
+            app_env: Default::default(),
+            backup_checker: None,
+            request_timeout: std::time::Duration::from_secs(30),
+            search_default_window_days: 90,
+            export_jobs_dir: "/tmp/synapse_exports".into(),
+            config_info: Default::default(),
+            job_scheduler: Default::default(),
+            jwt_secret: synapse_core::middleware::auth::JwtSecret::new(Some(TEST_JWT_SECRET)),
+            retry_policy: Default::default(),
+            batch_replay_limits: Default::default(),
         };
 
         // Clone readiness before app_state is moved into create_app
@@ -2,11 +2,37 @@ use bigdecimal::BigDecimal;
 use reqwest::StatusCode;
 use sqlx::{migrate::Migrator, PgPool};
 use std::path::Path;
+use synapse_core::tenant::TenantConfig;
 use synapse_core::{create_app, AppState};
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::postgres::Postgres;
 
+const ADMIN_KEY: &str = "export-test-admin-key";
+
+/// `/export` sits behind admin auth.
+fn admin_client() -> reqwest::Client {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        reqwest::header::AUTHORIZATION,
+        format!("Bearer {ADMIN_KEY}").parse().unwrap(),
+    );
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap()
+}
+
 async fn setup_test_app() -> (String, PgPool, impl std::any::Any) {
+    setup_test_app_with_tenants(&[]).await
+}
+
+/// Like [`setup_test_app`], with `tenants` registered so requests carrying
+/// their `X-API-Key` resolve to them.
+async fn setup_test_app_with_tenants(
+    tenants: &[TenantConfig],
+) -> (String, PgPool, impl std::any::Any) {
+    std::env::set_var("ADMIN_API_KEY", ADMIN_KEY);
+
     let container = Postgres::default().start().await.unwrap();
     let host_port = container.get_host_port_ipv4(5432).await.unwrap();
     let database_url = format!(
@@ -50,6 +76,16 @@ async fn setup_test_app() -> (String, PgPool, impl std::any::Any) {
     .execute(&pool)
     .await;
 
+    for tenant in tenants {
+        sqlx::query("INSERT INTO tenants (tenant_id, name, api_key) VALUES ($1, $2, $3)")
+            .bind(tenant.tenant_id)
+            .bind(&tenant.name)
+            .bind(tenant_api_key(tenant))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
     let (tx, _rx) = tokio::sync::broadcast::channel(100);
     let _query_cache = synapse_core::services::QueryCache::new("redis://localhost:6379")
         .await
@@ -73,13 +109,31 @@ async fn setup_test_app() -> (String, PgPool, impl std::any::Any) {
             .unwrap(),
         profiling_manager: synapse_core::handlers::profiling::ProfilingManager::new(),
         tenant_configs: std::sync::Arc::new(tokio::sync::RwLock::new(
-            std::collections::HashMap::new(),
+            tenants
+                .iter()
+                .map(|tenant| (tenant.tenant_id, tenant.clone()))
+                .collect(),
         )),
         pending_queue_depth: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
         current_batch_size: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(10)),
         secrets_store: None,
         metrics_handle: synapse_core::metrics::init_metrics().unwrap(),
         ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        cors_allowed_origins: Vec::new(),
+        horizon_page_limit: 200,
+        health_latency_threshold_ms: 1000,
+        idempotency_failure_mode: Default::default(),
+        webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(64)),
+        app_env: Default::default(),
+        backup_checker: None,
+        request_timeout: std::time::Duration::from_secs(30),
+        search_default_window_days: 90,
+        export_jobs_dir: "/tmp/synapse_exports".into(),
+        config_info: Default::default(),
+        job_scheduler: Default::default(),
+        jwt_secret: Default::default(),
+        retry_policy: Default::default(),
+        batch_replay_limits: Default::default(),
     };
     let app = create_app(app_state);
 
@@ -123,11 +177,54 @@ async fn insert_test_transaction(
     id
 }
 
+fn tenant(name: &str) -> TenantConfig {
+    TenantConfig {
+        tenant_id: uuid::Uuid::new_v4(),
+        name: name.to_string(),
+        webhook_secret: String::new(),
+        stellar_account: String::new(),
+        rate_limit_per_minute: 60,
+        is_active: true,
+        cors_allowed_origins: Vec::new(),
+    }
+}
+
+fn tenant_api_key(tenant: &TenantConfig) -> String {
+    format!("{}-api-key", tenant.name)
+}
+
+/// Ingests a transaction owned by `tenant` through the same insert path the
+/// callback handlers use.
+async fn insert_tenant_transaction(
+    pool: &PgPool,
+    stellar_account: &str,
+    amount: &str,
+    tenant: &TenantConfig,
+) -> uuid::Uuid {
+    let tx = synapse_core::db::models::Transaction::new(
+        stellar_account.to_string(),
+        amount.parse().unwrap(),
+        "USD".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .with_tenant_id(Some(tenant.tenant_id));
+    synapse_core::db::queries::insert_transaction(pool, &tx)
+        .await
+        .unwrap()
+        .0
+        .id
+}
+
 #[ignore = "Requires Docker/external services"]
 #[tokio::test]
 async fn test_export_csv_with_filters() {
     let (base_url, pool, _container) = setup_test_app().await;
-    let client = reqwest::Client::new();
+    let client = admin_client();
 
     insert_test_transaction(&pool, "GABC123", "100.50", "USD", "pending").await;
     insert_test_transaction(&pool, "GDEF456", "200.00", "USD", "completed").await;
@@ -160,7 +257,7 @@ async fn test_export_csv_with_filters() {
 #[tokio::test]
 async fn test_export_json_with_filters() {
     let (base_url, pool, _container) = setup_test_app().await;
-    let client = reqwest::Client::new();
+    let client = admin_client();
 
     insert_test_transaction(&pool, "GABC123", "100.50", "USD", "pending").await;
     insert_test_transaction(&pool, "GDEF456", "200.00", "USDC", "completed").await;
@@ -183,11 +280,38 @@ async fn test_export_json_with_filters() {
     assert!(!body.contains("GABC123"));
 }
 
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_export_excludes_soft_deleted_transactions() {
+    let (base_url, pool, _container) = setup_test_app().await;
+    let client = admin_client();
+
+    insert_test_transaction(&pool, "GKEPT001", "100.00", "USD", "pending").await;
+    let deleted = insert_test_transaction(&pool, "GDELETED1", "200.00", "USD", "pending").await;
+    synapse_core::db::queries::soft_delete_transaction(&pool, deleted, "test")
+        .await
+        .unwrap()
+        .expect("transaction was deleted");
+
+    for format in ["csv", "json", "ndjson"] {
+        let body = client
+            .get(format!("{base_url}/export?format={format}"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(body.contains("GKEPT001"), "{format}: {body}");
+        assert!(!body.contains("GDELETED1"), "{format}: {body}");
+    }
+}
+
 #[ignore = "Requires Docker/external services"]
 #[tokio::test]
 async fn test_export_date_range() {
     let (base_url, pool, _container) = setup_test_app().await;
-    let client = reqwest::Client::new();
+    let client = admin_client();
 
     let id1 = uuid::Uuid::new_v4();
     let id2 = uuid::Uuid::new_v4();
@@ -246,7 +370,7 @@ async fn test_export_date_range() {
 #[tokio::test]
 async fn test_export_large_dataset_streaming() {
     let (base_url, pool, _container) = setup_test_app().await;
-    let client = reqwest::Client::new();
+    let client = admin_client();
 
     for i in 0..2500 {
         insert_test_transaction(
@@ -275,7 +399,7 @@ async fn test_export_large_dataset_streaming() {
 #[tokio::test]
 async fn test_export_empty_results() {
     let (base_url, _pool, _container) = setup_test_app().await;
-    let client = reqwest::Client::new();
+    let client = admin_client();
 
     let res = client
         .get(format!("{}/export?format=csv&status=nonexistent", base_url))
@@ -293,7 +417,7 @@ async fn test_export_empty_results() {
 #[tokio::test]
 async fn test_export_headers_and_filename() {
     let (base_url, pool, _container) = setup_test_app().await;
-    let client = reqwest::Client::new();
+    let client = admin_client();
 
     insert_test_transaction(&pool, "GABC123", "100.50", "USD", "pending").await;
 
@@ -336,3 +460,163 @@ async fn test_export_headers_and_filename() {
     assert!(content_disposition.starts_with("attachment; filename=\"transactions_"));
     assert!(content_disposition.ends_with(".json\""));
 }
+
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_export_json_envelope_and_schema_version() {
+    let (base_url, pool, _container) = setup_test_app().await;
+    let client = admin_client();
+
+    insert_test_transaction(&pool, "GABC123", "100.50", "USD", "pending").await;
+    insert_test_transaction(&pool, "GDEF456", "200.00", "USDC", "completed").await;
+
+    let res = client
+        .get(format!("{}/export?format=json", base_url))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get("x-export-schema-version").unwrap(), "1");
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["schema_version"], 1);
+    assert_eq!(body["count"], 2);
+    let items = body["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    let mut accounts: Vec<&str> = items
+        .iter()
+        .map(|item| item["stellar_account"].as_str().unwrap())
+        .collect();
+    accounts.sort();
+    assert_eq!(accounts, ["GABC123", "GDEF456"]);
+}
+
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_export_ndjson_is_header_only() {
+    let (base_url, pool, _container) = setup_test_app().await;
+    let client = admin_client();
+
+    insert_test_transaction(&pool, "GABC123", "100.50", "USD", "pending").await;
+    insert_test_transaction(&pool, "GDEF456", "200.00", "USDC", "completed").await;
+
+    let res = client
+        .get(format!("{}/export?format=ndjson", base_url))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get("x-export-schema-version").unwrap(), "1");
+    assert_eq!(
+        res.headers().get("content-type").unwrap(),
+        "application/x-ndjson"
+    );
+
+    // One bare record per line, no envelope.
+    let body = res.text().await.unwrap();
+    let records: Vec<serde_json::Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.get("schema_version").is_none()));
+    assert!(records.iter().all(|r| r["stellar_account"].is_string()));
+}
+
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_export_is_scoped_to_requesting_tenant() {
+    let (acme, globex) = (tenant("acme"), tenant("globex"));
+    let (base_url, pool, _container) =
+        setup_test_app_with_tenants(&[acme.clone(), globex.clone()]).await;
+    let client = admin_client();
+
+    insert_tenant_transaction(&pool, "GACME001", "100.00", &acme).await;
+    insert_tenant_transaction(&pool, "GGLOBEX01", "200.00", &globex).await;
+    insert_test_transaction(&pool, "GLEGACY01", "300.00", "USD", "pending").await;
+
+    for format in ["csv", "json", "ndjson"] {
+        let res = client
+            .get(format!("{base_url}/export?format={format}"))
+            .header("X-API-Key", tenant_api_key(&acme))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = res.text().await.unwrap();
+        assert!(body.contains("GACME001"), "{format}: {body}");
+        assert!(!body.contains("GGLOBEX01"), "{format}: {body}");
+        assert!(!body.contains("GLEGACY01"), "{format}: {body}");
+    }
+
+    // Without a tenant, the export covers everyone as before.
+    let body = client
+        .get(format!("{base_url}/export?format=csv"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    for account in ["GACME001", "GGLOBEX01", "GLEGACY01"] {
+        assert!(body.contains(account), "{account} missing: {body}");
+    }
+}
+
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_export_job_is_scoped_to_requesting_tenant() {
+    let (acme, globex) = (tenant("acme"), tenant("globex"));
+    let (base_url, pool, _container) =
+        setup_test_app_with_tenants(&[acme.clone(), globex.clone()]).await;
+    let client = admin_client();
+
+    insert_tenant_transaction(&pool, "GACME001", "100.00", &acme).await;
+    insert_tenant_transaction(&pool, "GGLOBEX01", "200.00", &globex).await;
+    insert_test_transaction(&pool, "GLEGACY01", "300.00", "USD", "pending").await;
+
+    let res = client
+        .post(format!("{base_url}/export/jobs"))
+        .header("X-API-Key", tenant_api_key(&acme))
+        .json(&serde_json::json!({ "format": "csv" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+    let job: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(job["tenant_id"], acme.tenant_id.to_string());
+    let job_url = format!("{base_url}/export/jobs/{}", job["id"].as_str().unwrap());
+
+    let output_dir = std::env::temp_dir().join(format!("export_jobs_{}", uuid::Uuid::new_v4()));
+    synapse_core::services::export_jobs::ExportJobService::new(pool.clone(), &output_dir)
+        .run_pending()
+        .await
+        .unwrap();
+
+    let body = client
+        .get(format!("{job_url}/download"))
+        .header("X-API-Key", tenant_api_key(&acme))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("GACME001"), "{body}");
+    assert!(!body.contains("GGLOBEX01"), "{body}");
+    assert!(!body.contains("GLEGACY01"), "{body}");
+
+    // Another tenant cannot see the job at all.
+    for url in [job_url.clone(), format!("{job_url}/download")] {
+        let res = client
+            .get(&url)
+            .header("X-API-Key", tenant_api_key(&globex))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND, "{url}");
+    }
+
+    let _ = std::fs::remove_dir_all(output_dir);
+}
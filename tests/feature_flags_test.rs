@@ -80,7 +80,7 @@ async fn test_flag_update_via_api() {
     let service = FeatureFlagService::new(pool.clone());
 
     let result = service
-        .update("experimental_processor", true)
+        .update("experimental_processor", true, "admin")
         .await
         .unwrap();
     assert_eq!(result.name, "experimental_processor");
@@ -90,7 +90,7 @@ async fn test_flag_update_via_api() {
     assert!(is_enabled);
 
     let result = service
-        .update("experimental_processor", false)
+        .update("experimental_processor", false, "admin")
         .await
         .unwrap();
     assert!(!result.enabled);
@@ -131,3 +131,71 @@ async fn test_flag_default_values() {
     assert!(flags.contains_key("experimental_processor"));
     assert!(flags.contains_key("new_asset_support"));
 }
+
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_flag_update_is_audited_and_broadcast() {
+    let (pool, _container) = setup_test_db().await;
+    let service = FeatureFlagService::new(pool.clone());
+    let before = service.is_enabled("experimental_processor").await.unwrap();
+    let mut changes = service.subscribe();
+
+    service
+        .update("experimental_processor", !before, "ops@example.com")
+        .await
+        .unwrap();
+
+    let event = changes.try_recv().expect("update should publish an event");
+    assert_eq!(event.flag_name, "experimental_processor");
+    assert_eq!(event.actor, "ops@example.com");
+    assert_eq!(
+        event.old_value,
+        Some(serde_json::json!({ "enabled": before }))
+    );
+    assert_eq!(
+        event.new_value,
+        Some(serde_json::json!({ "enabled": !before }))
+    );
+
+    let history = service
+        .get_audit_history(Some("experimental_processor"), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].id, event.id);
+    assert_eq!(history[0].old_value, event.old_value);
+    assert_eq!(history[0].new_value, event.new_value);
+    assert_eq!(history[0].actor, "ops@example.com");
+}
+
+#[ignore = "Requires Docker/external services"]
+#[tokio::test]
+async fn test_rollout_change_is_broadcast_and_unknown_flag_is_not() {
+    let (pool, _container) = setup_test_db().await;
+    let service = FeatureFlagService::new(pool.clone());
+    let mut changes = service.subscribe();
+
+    service
+        .update_rollout_percentage("new_asset_support", 25, "admin")
+        .await
+        .unwrap();
+    let event = changes.try_recv().unwrap();
+    assert_eq!(
+        event.new_value,
+        Some(serde_json::json!({ "rollout_percentage": 25 }))
+    );
+
+    assert!(service
+        .update("nonexistent_flag", true, "admin")
+        .await
+        .is_err());
+    assert!(
+        changes.try_recv().is_err(),
+        "failed update must not publish"
+    );
+    let history = service
+        .get_audit_history(Some("nonexistent_flag"), 10, 0)
+        .await
+        .unwrap();
+    assert!(history.is_empty());
+}
@@ -33,6 +33,7 @@ pub struct TransactionFixture {
     memo: Option<String>,
     memo_type: Option<String>,
     metadata: Option<serde_json::Value>,
+    asset_issuer: Option<String>,
 }
 
 impl Default for TransactionFixture {
@@ -50,6 +51,7 @@ impl Default for TransactionFixture {
             memo: None,
             memo_type: None,
             metadata: None,
+            asset_issuer: None,
         }
     }
 }
@@ -80,6 +82,11 @@ impl TransactionFixture {
         self
     }
 
+    pub fn with_asset_issuer(mut self, issuer: &str) -> Self {
+        self.asset_issuer = Some(issuer.to_string());
+        self
+    }
+
     pub fn with_status(mut self, status: &str) -> Self {
         self.status = status.to_string();
         self
@@ -135,6 +142,9 @@ impl TransactionFixture {
             memo_type: self.memo_type,
             metadata: self.metadata,
             trace_id: None,
+            asset_issuer: self.asset_issuer,
+            deleted_at: None,
+            tenant_id: None,
         }
     }
 
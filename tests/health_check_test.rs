@@ -149,6 +149,35 @@ async fn test_health_content_type_is_json() {
     );
 }
 
+/// Every dependency reports its latency; the database is reachable, so it
+/// is healthy or (on a slow runner) degraded, never unhealthy.
+#[tokio::test]
+async fn test_health_reports_dependency_latency() {
+    use synapse_core::health::DependencyStatus;
+
+    let app = TestApp::new().await;
+    let client = reqwest::Client::new();
+
+    let body: HealthStatus = client
+        .get(format!("{}/health", app.base_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    for name in ["postgres", "redis", "horizon"] {
+        assert!(body.dependencies.contains_key(name), "missing {name}");
+    }
+    assert!(matches!(
+        body.dependencies["postgres"],
+        DependencyStatus::Healthy { .. } | DependencyStatus::Degraded { .. }
+    ));
+    let postgres = serde_json::to_value(&body.dependencies["postgres"]).unwrap();
+    assert!(postgres["latency_ms"].is_u64(), "got {postgres}");
+}
+
 /// Multiple concurrent requests all succeed (no race conditions on shared state).
 #[tokio::test]
 async fn test_health_concurrent_requests() {
@@ -189,6 +218,11 @@ async fn test_ready_returns_503_when_not_ready() {
 
     let body: ReadinessResponse = res.json().await.unwrap();
     assert_eq!(body.status, "not_ready");
+    assert!(
+        body.reasons.iter().any(|r| r.starts_with("database:")),
+        "uninitialized subsystem missing from {:?}",
+        body.reasons
+    );
 }
 
 /// After marking the app ready, /ready returns 200.
@@ -212,6 +246,7 @@ async fn test_ready_returns_200_when_ready() {
     let body: ReadinessResponse = res.json().await.unwrap();
     assert_eq!(body.status, "ready");
     assert!(!body.draining);
+    assert!(body.reasons.is_empty());
 }
 
 /// Draining state: not_ready + draining=true.
@@ -234,6 +269,7 @@ async fn test_ready_draining_state() {
     let body: ReadinessResponse = res.json().await.unwrap();
     assert_eq!(body.status, "not_ready");
     assert!(body.draining, "should be draining");
+    assert!(body.reasons.iter().any(|r| r.contains("draining")));
 }
 
 /// Content-Type header is application/json.
@@ -397,3 +433,60 @@ fn test_dependency_severity_variants() {
         serde_json::json!("noncritical")
     );
 }
+
+/// Reachable stand-in that takes `delay` to answer.
+struct SlowChecker {
+    delay: std::time::Duration,
+}
+
+#[async_trait::async_trait]
+impl synapse_core::health::DependencyChecker for SlowChecker {
+    async fn check(&self) -> synapse_core::health::DependencyStatus {
+        let start = std::time::Instant::now();
+        tokio::time::sleep(self.delay).await;
+        synapse_core::health::DependencyStatus::Healthy {
+            status: "healthy".to_string(),
+            severity: synapse_core::health::DependencySeverity::NonCritical,
+            latency_ms: start.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_slow_dependency_is_reported_degraded() {
+    use std::time::Duration;
+    use synapse_core::health::{run_check, DependencyStatus};
+
+    let checker = SlowChecker {
+        delay: Duration::from_millis(50),
+    };
+    let status = run_check(&checker, Duration::from_millis(10)).await;
+
+    assert!(
+        matches!(status, DependencyStatus::Degraded { latency_ms, threshold_ms: 10, .. } if latency_ms >= 50),
+        "got {status:?}"
+    );
+    let json = serde_json::to_value(&status).unwrap();
+    assert_eq!(json["status"], "degraded");
+    assert!(json["latency_ms"].as_u64().unwrap() >= 50);
+
+    // Round-trips as degraded rather than collapsing into `Healthy`.
+    let back: DependencyStatus = serde_json::from_value(json).unwrap();
+    assert!(matches!(back, DependencyStatus::Degraded { .. }));
+}
+
+#[tokio::test]
+async fn test_fast_dependency_stays_healthy() {
+    use std::time::Duration;
+    use synapse_core::health::{run_check, DependencyStatus};
+
+    let checker = SlowChecker {
+        delay: Duration::ZERO,
+    };
+    let status = run_check(&checker, Duration::from_secs(1)).await;
+
+    assert!(
+        matches!(status, DependencyStatus::Healthy { .. }),
+        "got {status:?}"
+    );
+}
@@ -8,11 +8,12 @@ use axum::{
 };
 use redis::Client;
 use serde_json::json;
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use synapse_core::middleware::idempotency::{
-    idempotency_middleware, BodyEncoding, CachedResponse, IdempotencyService, IdempotencyStatus,
+    idempotency_middleware, BodyEncoding, CachedResponse, IdempotencyFailureMode,
+    IdempotencyService, IdempotencyStatus,
 };
 use tokio::time::sleep;
 use tower::ServiceExt;
@@ -345,7 +346,8 @@ async fn test_different_payload_same_key_rejected() {
 async fn test_redis_failure_fallback() {
     // Use invalid Redis URL to simulate connection failure
     let invalid_redis_url = "redis://invalid-host:9999";
-    let service = create_idempotency_service(invalid_redis_url);
+    let service = create_idempotency_service(invalid_redis_url)
+        .with_failure_mode(IdempotencyFailureMode::FailOpen);
     let app = create_test_app(service);
 
     let req = Request::builder()
@@ -466,6 +468,111 @@ async fn test_no_tenant_id_uses_default_scope() {
     assert_eq!(resp2.status(), StatusCode::OK);
 }
 
+// ── Per-route namespaces and TTLs ────────────────────────────────────────────
+
+/// Mounts `/callback` and `/webhook` behind their own namespaced services and
+/// counts how often each handler runs.
+fn create_namespaced_app(redis_url: &str) -> (Router, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+    let callback_calls = Arc::new(AtomicUsize::new(0));
+    let webhook_calls = Arc::new(AtomicUsize::new(0));
+    let (cb, wh) = (callback_calls.clone(), webhook_calls.clone());
+    let callback = Router::new()
+        .route(
+            "/callback",
+            post(move || {
+                cb.fetch_add(1, Ordering::SeqCst);
+                test_handler()
+            }),
+        )
+        .layer(middleware::from_fn_with_state(
+            create_idempotency_service(redis_url).with_namespace("callback"),
+            idempotency_middleware,
+        ));
+    let webhook = Router::new()
+        .route(
+            "/webhook",
+            post(move || {
+                wh.fetch_add(1, Ordering::SeqCst);
+                test_handler()
+            }),
+        )
+        .layer(middleware::from_fn_with_state(
+            create_idempotency_service(redis_url).with_namespace("webhook"),
+            idempotency_middleware,
+        ));
+    (callback.merge(webhook), callback_calls, webhook_calls)
+}
+
+#[ignore = "Requires Redis"]
+#[tokio::test]
+async fn test_same_key_on_namespaced_routes_is_processed_independently() {
+    let (_client, redis_url) = setup_redis().await;
+    let (app, callback_calls, webhook_calls) = create_namespaced_app(&redis_url);
+    let key = uuid::Uuid::new_v4().to_string();
+    let request = |uri: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("idempotency-key", &key)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let callback = app.clone().oneshot(request("/callback")).await.unwrap();
+    assert_eq!(callback.status(), StatusCode::OK);
+
+    // The webhook has never seen this key, so it runs instead of replaying.
+    let webhook = app.clone().oneshot(request("/webhook")).await.unwrap();
+    assert_eq!(webhook.status(), StatusCode::OK);
+    assert!(webhook.headers().get("x-idempotent-replayed").is_none());
+
+    // Within each namespace the key still deduplicates.
+    let replay = app.clone().oneshot(request("/callback")).await.unwrap();
+    assert_eq!(replay.headers()["x-idempotent-replayed"], "true");
+    let replay = app.oneshot(request("/webhook")).await.unwrap();
+    assert_eq!(replay.headers()["x-idempotent-replayed"], "true");
+
+    assert_eq!(callback_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(webhook_calls.load(Ordering::SeqCst), 1);
+}
+
+#[ignore = "Requires Redis"]
+#[tokio::test]
+async fn test_route_ttl_applies_to_cached_response() {
+    let (client, redis_url) = setup_redis().await;
+    let service = create_idempotency_service(&redis_url)
+        .with_namespace("short")
+        .with_ttl(Duration::from_secs(60));
+    let key = uuid::Uuid::new_v4().to_string();
+
+    let IdempotencyStatus::New { lock_token } =
+        service.check_idempotency("default", &key).await.unwrap()
+    else {
+        panic!("expected a new key");
+    };
+    service
+        .store_response(
+            "default",
+            &key,
+            CachedResponse {
+                status: 200,
+                body: "{}".to_string(),
+                content_type: None,
+                encoding: BodyEncoding::Utf8,
+            },
+            lock_token.as_deref(),
+        )
+        .await
+        .unwrap();
+
+    let mut conn = client.get_connection().unwrap();
+    let ttl: i64 = redis::cmd("TTL")
+        .arg(format!("idempotency:short:default:{key}"))
+        .query(&mut conn)
+        .unwrap();
+    assert!((1..=60).contains(&ttl), "unexpected TTL {ttl}");
+}
+
 // ── Issue 4: Stale lock recovery tests ───────────────────────────────────────
 
 #[ignore = "Requires Redis"]
@@ -664,3 +771,90 @@ async fn test_replays_preserve_text_json_and_binary_bytes() {
     assert_replay_is_identical(&app, "/json").await;
     assert_replay_is_identical(&app, "/binary").await;
 }
+
+/// A service whose Redis and database fallback both refuse connections.
+fn unreachable_idempotency_service(mode: IdempotencyFailureMode) -> IdempotencyService {
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(500))
+        .connect_lazy("postgres://postgres@127.0.0.1:1/postgres")
+        .unwrap();
+    IdempotencyService::new(
+        "redis://127.0.0.1:1",
+        pool,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    )
+    .unwrap()
+    .with_failure_mode(mode)
+}
+
+/// Sends one keyed request and returns its status and how often the handler ran.
+async fn send_during_outage(mode: IdempotencyFailureMode) -> (Response, usize) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let handler_calls = calls.clone();
+    let app = Router::new()
+        .route(
+            "/webhook",
+            post(move || {
+                handler_calls.fetch_add(1, Ordering::SeqCst);
+                test_handler()
+            }),
+        )
+        .layer(middleware::from_fn_with_state(
+            unreachable_idempotency_service(mode),
+            idempotency_middleware,
+        ));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/webhook")
+        .header("idempotency-key", "outage-key")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    (response, calls.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn test_fail_closed_rejects_request_when_store_is_down() {
+    let (response, calls) = send_during_outage(IdempotencyFailureMode::FailClosed).await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()["retry-after"], "5");
+    assert_eq!(calls, 0, "the handler must not run without deduplication");
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"], "Idempotency store unavailable");
+}
+
+#[tokio::test]
+async fn test_fail_open_processes_request_when_store_is_down() {
+    let (response, calls) = send_during_outage(IdempotencyFailureMode::FailOpen).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(calls, 1);
+    assert!(response.headers().get("x-idempotent-replayed").is_none());
+}
+
+#[tokio::test]
+async fn test_service_defaults_to_fail_closed() {
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .connect_lazy("postgres://dummy")
+        .unwrap();
+    let service = IdempotencyService::new(
+        "redis://127.0.0.1:1",
+        pool,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(AtomicU64::new(0)),
+    )
+    .unwrap();
+    assert_eq!(service.failure_mode(), IdempotencyFailureMode::FailClosed);
+}
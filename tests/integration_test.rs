@@ -80,6 +80,21 @@ async fn setup_test_app() -> (String, PgPool, impl std::any::Any) {
         secrets_store: None,
         metrics_handle: synapse_core::metrics::init_metrics().unwrap(),
         ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        cors_allowed_origins: Vec::new(),
+        horizon_page_limit: 200,
+        health_latency_threshold_ms: 1000,
+        idempotency_failure_mode: Default::default(),
+        webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(64)),
+        app_env: Default::default(),
+        backup_checker: None,
+        request_timeout: std::time::Duration::from_secs(30),
+        search_default_window_days: 90,
+        export_jobs_dir: "/tmp/synapse_exports".into(),
+        config_info: Default::default(),
+        job_scheduler: Default::default(),
+        jwt_secret: Default::default(),
+        retry_policy: Default::default(),
+        batch_replay_limits: Default::default(),
     };
     let app = create_app(app_state);
 
@@ -117,7 +132,7 @@ async fn test_valid_deposit_flow() {
         .await
         .unwrap();
 
-    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
     let transaction: serde_json::Value = res.json().await.unwrap();
     let tx_id = transaction["id"].as_str().unwrap();
 
@@ -164,7 +179,7 @@ async fn test_callback_with_memo_and_metadata() {
         .await
         .unwrap();
 
-    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
     let transaction: serde_json::Value = res.json().await.unwrap();
     let tx_id = transaction["id"].as_str().unwrap();
 
@@ -212,7 +227,7 @@ async fn test_callback_with_hash_memo_type() {
         .await
         .unwrap();
 
-    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
     let transaction: serde_json::Value = res.json().await.unwrap();
     assert_eq!(transaction["memo"], "abc123def456");
     assert_eq!(transaction["memo_type"], "hash");
@@ -267,7 +282,7 @@ async fn test_callback_with_metadata_only() {
         .await
         .unwrap();
 
-    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
     let transaction: serde_json::Value = res.json().await.unwrap();
     assert!(transaction["memo"].is_null());
     assert!(transaction["memo_type"].is_null());
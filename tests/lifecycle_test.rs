@@ -85,8 +85,8 @@ async fn test_full_transaction_lifecycle() {
 
     assert_eq!(
         res.status(),
-        StatusCode::CREATED,
-        "callback should return 201"
+        StatusCode::ACCEPTED,
+        "callback should return 202"
     );
 
     let tx_body: Value = res.json().await.unwrap();
@@ -241,7 +241,7 @@ async fn test_full_transaction_lifecycle() {
 
 #[ignore = "Requires Docker for testcontainers"]
 #[tokio::test]
-async fn test_callback_returns_201_and_persists() {
+async fn test_callback_returns_202_and_persists() {
     let app = common::TestApp::new().await;
     let client = reqwest::Client::new();
 
@@ -256,7 +256,7 @@ async fn test_callback_returns_201_and_persists() {
         .await
         .unwrap();
 
-    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
     let body: Value = res.json().await.unwrap();
     let tx_id: Uuid = body["id"].as_str().unwrap().parse().unwrap();
 
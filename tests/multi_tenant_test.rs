@@ -52,6 +52,7 @@ fn make_tenant_config(tenant_id: Uuid, name: &str) -> TenantConfig {
         stellar_account: "account".to_string(),
         rate_limit_per_minute: 100,
         is_active: true,
+        cors_allowed_origins: Vec::new(),
     }
 }
 
@@ -66,7 +67,8 @@ async fn ensure_schema(pool: &PgPool) {
             webhook_secret VARCHAR(255) NOT NULL DEFAULT '',
             stellar_account VARCHAR(56) NOT NULL DEFAULT '',
             rate_limit_per_minute INTEGER NOT NULL DEFAULT 60,
-            is_active BOOLEAN NOT NULL DEFAULT true
+            is_active BOOLEAN NOT NULL DEFAULT true,
+            cors_allowed_origins TEXT[] NOT NULL DEFAULT '{}'
         )",
     )
     .execute(pool)
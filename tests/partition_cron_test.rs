@@ -1,8 +1,16 @@
-use chrono::{Datelike, Utc};
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
 use sqlx::{migrate::Migrator, PgPool, Row};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use synapse_core::db::cron::{
-    create_month_partition, detach_and_archive_old_partitions, ensure_future_partitions,
+    create_day_partition, create_month_partition, daily_partition_name,
+    detach_and_archive_old_partitions, ensure_future_partitions, monthly_partition_name,
+    parse_partition_name,
+};
+use synapse_core::db::partition::{
+    PartitionBackup, PartitionConfig, PartitionManager, PartitionStrategy,
 };
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::postgres::Postgres;
@@ -205,3 +213,407 @@ async fn test_partition_retention_boundary() {
     let partition_name = format!("transactions_y{}m{:02}", current_year, current_month);
     assert!(partition_exists(&pool, &partition_name).await);
 }
+
+#[test]
+fn test_partition_settings_validation() {
+    use synapse_core::config::validate_partition_settings;
+
+    assert!(validate_partition_settings(3, 7, 12).is_ok());
+    assert!(validate_partition_settings(1, 1, 1).is_ok());
+    assert!(validate_partition_settings(0, 7, 12).is_err());
+    assert!(validate_partition_settings(3, 0, 12).is_err());
+    assert!(validate_partition_settings(3, 7, 0).is_err());
+    assert!(validate_partition_settings(3, 7, -1).is_err());
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_custom_months_ahead() {
+    let (pool, _container) = setup_test_db().await;
+
+    let manager = PartitionManager::with_config(
+        pool.clone(),
+        24,
+        None,
+        PartitionConfig {
+            months_ahead: 6,
+            retention_months: 12,
+            drop_after_archive: false,
+            ..PartitionConfig::default()
+        },
+    );
+    manager.run_maintenance().await.unwrap();
+
+    let now = Utc::now();
+    let (mut y, mut m) = (now.year(), now.month());
+    for _ in 0..6 {
+        let partition_name = format!("transactions_y{}m{:02}", y, m);
+        assert!(
+            partition_exists(&pool, &partition_name).await,
+            "{partition_name} should exist"
+        );
+        if m == 12 {
+            m = 1;
+            y += 1;
+        } else {
+            m += 1;
+        }
+    }
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_custom_retention() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2023, 1).await.unwrap();
+
+    let manager = PartitionManager::with_config(
+        pool.clone(),
+        24,
+        None,
+        PartitionConfig {
+            months_ahead: 1,
+            retention_months: 2,
+            drop_after_archive: false,
+            ..PartitionConfig::default()
+        },
+    );
+    manager.run_maintenance().await.unwrap();
+
+    let archived = sqlx::query(
+        "SELECT c.relname AS name FROM pg_class c
+         JOIN pg_namespace n ON c.relnamespace = n.oid
+         WHERE n.nspname = 'archive' AND c.relname LIKE 'transactions_y%'",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    let archived: Vec<String> = archived.iter().map(|r| r.get("name")).collect();
+    assert!(archived.contains(&"transactions_y2023m01".to_string()));
+
+    // The current month is inside the retention window and stays attached.
+    let now = Utc::now();
+    let current = format!("transactions_y{}m{:02}", now.year(), now.month());
+    assert!(!archived.contains(&current));
+    assert!(partition_exists(&pool, &current).await);
+}
+
+/// Records the state of the partition at the moment it is backed up, so the
+/// test can assert it was already detached but not yet dropped.
+struct RecordingBackup {
+    pool: PgPool,
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl PartitionBackup for RecordingBackup {
+    async fn backup_partition(&self, table: &str) -> anyhow::Result<String> {
+        let still_attached: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM pg_inherits i
+             JOIN pg_class c ON i.inhrelid = c.oid
+             JOIN pg_class p ON i.inhparent = p.oid
+             WHERE p.relname = 'transactions' AND c.oid = to_regclass($1))",
+        )
+        .bind(table)
+        .fetch_one(&self.pool)
+        .await?;
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+            .bind(table)
+            .fetch_one(&self.pool)
+            .await?;
+
+        assert!(!still_attached, "{table} must be detached before backup");
+        assert!(exists, "{table} must not be dropped before backup");
+
+        self.events.lock().unwrap().push(format!("backup:{table}"));
+        Ok(format!("partition_{}.sql.gz", table.replace('.', "_")))
+    }
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_drops_after_backup() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2023, 1).await.unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let manager = PartitionManager::with_config(
+        pool.clone(),
+        24,
+        None,
+        PartitionConfig {
+            months_ahead: 1,
+            retention_months: 12,
+            drop_after_archive: true,
+            ..PartitionConfig::default()
+        },
+    )
+    .with_backup(Arc::new(RecordingBackup {
+        pool: pool.clone(),
+        events: events.clone(),
+    }));
+    manager.run_maintenance().await.unwrap();
+
+    let events = events.lock().unwrap().clone();
+    assert!(events.contains(&"backup:archive.transactions_y2023m01".to_string()));
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('archive.transactions_y2023m01') IS NOT NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(!exists, "archived partition should be dropped after backup");
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_keeps_archive_without_backup() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2023, 1).await.unwrap();
+
+    let manager = PartitionManager::with_config(
+        pool.clone(),
+        24,
+        None,
+        PartitionConfig {
+            months_ahead: 1,
+            retention_months: 12,
+            drop_after_archive: true,
+            ..PartitionConfig::default()
+        },
+    );
+    manager.run_maintenance().await.unwrap();
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('archive.transactions_y2023m01') IS NOT NULL")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(
+        exists,
+        "partition must not be dropped when no backup is configured"
+    );
+}
+
+/// Fails the first backup, then succeeds.
+#[derive(Default)]
+struct FlakyBackup {
+    attempts: AtomicUsize,
+}
+
+#[async_trait]
+impl PartitionBackup for FlakyBackup {
+    async fn backup_partition(&self, table: &str) -> anyhow::Result<String> {
+        if self.attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            anyhow::bail!("pg_dump exited with status 1");
+        }
+        Ok(format!("partition_{}.sql.gz", table.replace('.', "_")))
+    }
+}
+
+async fn archived_exists(pool: &PgPool, table: &str) -> bool {
+    sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+        .bind(format!("archive.{table}"))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+fn dropping_config() -> PartitionConfig {
+    PartitionConfig {
+        months_ahead: 1,
+        retention_months: 12,
+        drop_after_archive: true,
+        ..PartitionConfig::default()
+    }
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_retries_drop_after_failed_backup() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2023, 1).await.unwrap();
+
+    let backup = Arc::new(FlakyBackup::default());
+    let manager = PartitionManager::with_config(pool.clone(), 24, None, dropping_config())
+        .with_backup(backup.clone());
+
+    assert!(manager.run_maintenance().await.is_err());
+    assert!(
+        archived_exists(&pool, "transactions_y2023m01").await,
+        "partition must be kept when its backup fails"
+    );
+
+    // The partition is already archived, so only the sweep can pick it up.
+    manager.run_maintenance().await.unwrap();
+    assert!(!archived_exists(&pool, "transactions_y2023m01").await);
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_sweeps_partitions_archived_without_backup() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2023, 1).await.unwrap();
+
+    PartitionManager::with_config(pool.clone(), 24, None, dropping_config())
+        .run_maintenance()
+        .await
+        .unwrap();
+    assert!(archived_exists(&pool, "transactions_y2023m01").await);
+
+    let backup = Arc::new(FlakyBackup {
+        attempts: AtomicUsize::new(1),
+    });
+    PartitionManager::with_config(pool.clone(), 24, None, dropping_config())
+        .with_backup(backup)
+        .run_maintenance()
+        .await
+        .unwrap();
+    assert!(!archived_exists(&pool, "transactions_y2023m01").await);
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_list_partitions_reports_ranges_and_sizes() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2030, 1).await.unwrap();
+    create_month_partition(&pool, 2030, 2).await.unwrap();
+
+    let partitions = PartitionManager::list_partitions(&pool).await.unwrap();
+
+    let jan = partitions
+        .iter()
+        .find(|p| p.name == "transactions_y2030m01")
+        .expect("transactions_y2030m01 should be listed");
+    assert_eq!(
+        jan.range_start,
+        Some(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap())
+    );
+    assert_eq!(
+        jan.range_end,
+        Some(Utc.with_ymd_and_hms(2030, 2, 1, 0, 0, 0).unwrap())
+    );
+    assert!(jan.row_estimate >= 0);
+    // Even an empty partition has index pages on disk.
+    assert!(jan.total_bytes > 0);
+
+    let feb = partitions
+        .iter()
+        .find(|p| p.name == "transactions_y2030m02")
+        .expect("transactions_y2030m02 should be listed");
+    assert_eq!(feb.range_start, jan.range_end);
+
+    // Oldest first.
+    let jan_pos = partitions.iter().position(|p| p.name == jan.name).unwrap();
+    let feb_pos = partitions.iter().position(|p| p.name == feb.name).unwrap();
+    assert!(jan_pos < feb_pos);
+}
+
+#[test]
+fn test_partition_name_formatting() {
+    let date = NaiveDate::from_ymd_opt(2025, 2, 3).unwrap();
+    assert_eq!(daily_partition_name(date), "transactions_y2025m02d03");
+    assert_eq!(monthly_partition_name(2025, 2), "transactions_y2025m02");
+    assert_eq!(
+        daily_partition_name(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
+        "transactions_y2025m12d31"
+    );
+}
+
+#[test]
+fn test_parse_partition_name_monthly_and_daily() {
+    assert_eq!(
+        parse_partition_name("transactions_y2025m02"),
+        NaiveDate::from_ymd_opt(2025, 2, 1)
+    );
+    assert_eq!(
+        parse_partition_name("transactions_y2025m02d03"),
+        NaiveDate::from_ymd_opt(2025, 2, 3)
+    );
+    assert_eq!(parse_partition_name("transactions_y2025m02d31"), None);
+    assert_eq!(parse_partition_name("transactions_old"), None);
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_create_day_partition() {
+    let (pool, _container) = setup_test_db().await;
+
+    // Far enough ahead that no monthly partition already covers it.
+    let date = NaiveDate::from_ymd_opt(2031, 3, 15).unwrap();
+    create_day_partition(&pool, date).await.unwrap();
+
+    let partition_name = daily_partition_name(date);
+    assert!(partition_exists(&pool, &partition_name).await);
+    assert!(partition_exists(&pool, &format!("idx_{}_status", partition_name)).await);
+    assert!(partition_exists(&pool, &format!("idx_{}_stellar_account", partition_name)).await);
+
+    // Idempotent.
+    create_day_partition(&pool, date).await.unwrap();
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_create_day_partition_skips_day_covered_by_month() {
+    let (pool, _container) = setup_test_db().await;
+
+    create_month_partition(&pool, 2031, 4).await.unwrap();
+    let date = NaiveDate::from_ymd_opt(2031, 4, 10).unwrap();
+    create_day_partition(&pool, date).await.unwrap();
+
+    assert!(!partition_exists(&pool, &daily_partition_name(date)).await);
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_run_maintenance_daily_creates_days_ahead() {
+    let (pool, _container) = setup_test_db().await;
+
+    // Drop any monthly partitions covering the window so daily ones can be created.
+    let today = Utc::now().date_naive();
+    for offset in 0..40 {
+        let d = today + chrono::Duration::days(offset);
+        let monthly = monthly_partition_name(d.year(), d.month());
+        sqlx::query(&format!("DROP TABLE IF EXISTS \"{monthly}\""))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let manager = PartitionManager::with_config(
+        pool.clone(),
+        24,
+        None,
+        PartitionConfig {
+            strategy: PartitionStrategy::Daily,
+            days_ahead: 5,
+            ..PartitionConfig::default()
+        },
+    );
+    manager.run_maintenance().await.unwrap();
+
+    for offset in 0..5 {
+        let name = daily_partition_name(today + chrono::Duration::days(offset));
+        assert!(partition_exists(&pool, &name).await, "{name} should exist");
+    }
+    let beyond = daily_partition_name(today + chrono::Duration::days(5));
+    assert!(!partition_exists(&pool, &beyond).await);
+}
+
+#[ignore = "Requires Docker"]
+#[tokio::test]
+async fn test_detach_old_daily_partitions() {
+    let (pool, _container) = setup_test_db().await;
+
+    let old_day = NaiveDate::from_ymd_opt(2022, 6, 15).unwrap();
+    create_day_partition(&pool, old_day).await.unwrap();
+
+    let archived = detach_and_archive_old_partitions(&pool, 12).await.unwrap();
+    assert!(archived.contains(&daily_partition_name(old_day)));
+}
@@ -0,0 +1,92 @@
+//! The profiling session endpoints only accept JWTs carrying the
+//! `admin:profiling` scope.
+
+mod common;
+
+use chrono::Utc;
+use common::{TestApp, TEST_JWT_SECRET};
+use serde_json::{json, Value};
+use synapse_core::middleware::auth::{encode_jwt, JwtClaims, PROFILING_SCOPE};
+use uuid::Uuid;
+
+fn token(scopes: &[&str]) -> String {
+    encode_jwt(
+        &JwtClaims {
+            sub: "ops".to_string(),
+            tenant_id: Uuid::new_v4(),
+            exp: Utc::now().timestamp() + 3600,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        },
+        TEST_JWT_SECRET.as_bytes(),
+    )
+}
+
+async fn start(app: &TestApp, bearer: Option<&str>, body: Value) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .post(format!("{}/admin/profiling/start", app.base_url))
+        .json(&body);
+    if let Some(bearer) = bearer {
+        request = request.bearer_auth(bearer);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_profiling_endpoints_reject_unauthorized_callers() {
+    let app = TestApp::new().await;
+    let body = json!({ "profile_type": "cpu", "duration_secs": 1 });
+
+    let response = start(&app, None, body.clone()).await;
+    assert_eq!(response.status(), 401);
+
+    let response = start(&app, Some("not-a-jwt"), body.clone()).await;
+    assert_eq!(response.status(), 401);
+
+    // Signed, but without the profiling scope.
+    let response = start(&app, Some(&token(&["admin:read"])), body).await;
+    assert_eq!(response.status(), 403);
+
+    let client = reqwest::Client::new();
+    for path in ["status", "flamegraph/profile-cpu-1"] {
+        let response = client
+            .get(format!("{}/admin/profiling/{path}", app.base_url))
+            .bearer_auth(token(&[]))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 403, "GET /admin/profiling/{path}");
+    }
+    let response = client
+        .post(format!("{}/admin/profiling/stop", app.base_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    let response = client
+        .get(format!("{}/admin/profiling/status", app.base_url))
+        .bearer_auth(token(&[PROFILING_SCOPE]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: Value = response.json().await.unwrap();
+    assert_eq!(status["is_profiling"], false);
+}
+
+#[cfg(not(feature = "heap-profiling"))]
+#[tokio::test]
+async fn test_memory_profile_without_backend_is_unsupported() {
+    let app = TestApp::new().await;
+
+    let response = start(
+        &app,
+        Some(&token(&[PROFILING_SCOPE])),
+        json!({ "profile_type": "memory", "duration_secs": 1 }),
+    )
+    .await;
+
+    assert_eq!(response.status(), 501);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "PROFILING_BACKEND_UNAVAILABLE");
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+use synapse_core::metrics::{query_cache_hits_total, query_cache_misses_total, render_prometheus};
+use synapse_core::services::query_cache::{
+    cache_key_asset_stats, cache_key_status_counts, QueryCache,
+};
+use testcontainers::runners::AsyncRunner;
+use testcontainers_modules::redis::Redis;
+
+fn counts(query_type: &str) -> (u64, u64) {
+    (
+        query_cache_hits_total()
+            .with_label_values(&[query_type])
+            .get(),
+        query_cache_misses_total()
+            .with_label_values(&[query_type])
+            .get(),
+    )
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_hits_and_misses_are_counted_per_query_type() {
+    let redis = Redis::default().start().await.unwrap();
+    let port = redis.get_host_port_ipv4(6379).await.unwrap();
+    let cache = QueryCache::new(&format!("redis://127.0.0.1:{port}"))
+        .await
+        .unwrap();
+
+    let status_before = counts("status_counts");
+    let assets_before = counts("asset_stats");
+
+    // status_counts: miss, then populate and hit twice.
+    let key = cache_key_status_counts();
+    assert!(cache.get::<Vec<i64>>(&key).await.unwrap().is_none());
+    cache
+        .set(&key, &vec![1_i64, 2], Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(cache.get::<Vec<i64>>(&key).await.unwrap().is_some());
+    assert!(cache.get::<Vec<i64>>(&key).await.unwrap().is_some());
+
+    // asset_stats: two misses, never populated.
+    let key = cache_key_asset_stats();
+    assert!(cache.get::<Vec<i64>>(&key).await.unwrap().is_none());
+    assert!(cache.get::<Vec<i64>>(&key).await.unwrap().is_none());
+
+    let status_after = counts("status_counts");
+    let assets_after = counts("asset_stats");
+    assert_eq!(status_after.0 - status_before.0, 2);
+    assert_eq!(status_after.1 - status_before.1, 1);
+    assert_eq!(assets_after.0 - assets_before.0, 0);
+    assert_eq!(assets_after.1 - assets_before.1, 2);
+
+    let text = render_prometheus();
+    assert!(text.contains(r#"cache_hits_total{query_type="status_counts"}"#));
+    assert!(text.contains(r#"cache_misses_total{query_type="asset_stats"}"#));
+}
@@ -81,3 +81,107 @@ fn test_cache_config_defaults() {
     assert_eq!(config.daily_totals_ttl, 3600);
     assert_eq!(config.asset_stats_ttl, 600);
 }
+
+#[ignore = "Requires Redis"]
+#[tokio::test]
+async fn test_get_or_compute_runs_compute_once_for_concurrent_misses() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    // Separate instances stand in for replicas sharing one Redis.
+    let mut caches = Vec::new();
+    for _ in 0..4 {
+        caches.push(QueryCache::new("redis://localhost:6379").await.unwrap());
+    }
+    let key = format!("test:stampede:{}", uuid::Uuid::new_v4());
+    let computed = Arc::new(AtomicUsize::new(0));
+
+    let calls = (0..40).map(|i| {
+        let cache = caches[i % caches.len()].clone();
+        let key = key.clone();
+        let computed = computed.clone();
+        async move {
+            cache
+                .get_or_compute(&key, Duration::from_secs(60), || async move {
+                    computed.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    Ok::<_, std::convert::Infallible>(vec![42_i64, 7])
+                })
+                .await
+                .unwrap()
+        }
+    });
+    let results = futures::future::join_all(calls).await;
+
+    assert_eq!(computed.load(Ordering::SeqCst), 1);
+    assert!(results.iter().all(|value| value == &vec![42_i64, 7]));
+
+    caches[0].invalidate_exact(&key).await.unwrap();
+}
+
+/// TCP proxy in front of Redis whose connections can all be severed at once,
+/// simulating Redis going away and coming back on the same address.
+struct RedisProxy {
+    addr: std::net::SocketAddr,
+    tasks: std::sync::Arc<std::sync::Mutex<Vec<tokio::task::JoinHandle<()>>>>,
+}
+
+impl RedisProxy {
+    async fn start(addr: &str) -> Self {
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let tasks = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let conn_tasks = tasks.clone();
+        let accept = tokio::spawn(async move {
+            while let Ok((mut inbound, _)) = listener.accept().await {
+                let handle = tokio::spawn(async move {
+                    if let Ok(mut upstream) = tokio::net::TcpStream::connect("localhost:6379").await
+                    {
+                        let _ = tokio::io::copy_bidirectional(&mut inbound, &mut upstream).await;
+                    }
+                });
+                conn_tasks.lock().unwrap().push(handle);
+            }
+        });
+        tasks.lock().unwrap().push(accept);
+        Self { addr, tasks }
+    }
+
+    fn stop(&self) {
+        for task in self.tasks.lock().unwrap().drain(..) {
+            task.abort();
+        }
+    }
+}
+
+#[ignore = "Requires Redis"]
+#[tokio::test]
+async fn test_query_cache_recovers_after_dropped_connection() {
+    let proxy = RedisProxy::start("127.0.0.1:0").await;
+    let cache = QueryCache::new(&format!("redis://{}", proxy.addr))
+        .await
+        .unwrap();
+    let ttl = std::time::Duration::from_secs(60);
+    let key = format!("reconnect:{}", uuid::Uuid::new_v4());
+
+    cache.set(&key, &1u32, ttl).await.unwrap();
+
+    // Sever every connection; a key never read before misses the in-memory
+    // tier, so the lookup has to go to Redis and fails.
+    proxy.stop();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let unseen = format!("reconnect:{}", uuid::Uuid::new_v4());
+    assert!(cache.get::<u32>(&unseen).await.is_err());
+
+    // Redis is back on the same address: operations recover without a new
+    // QueryCache, and a single failed operation does not trip the breaker.
+    let proxy = RedisProxy::start(&proxy.addr.to_string()).await;
+    assert_eq!(cache.get::<u32>(&unseen).await.unwrap(), None);
+    cache.set(&unseen, &2u32, ttl).await.unwrap();
+    assert_eq!(cache.circuit_state(), "closed");
+
+    cache.invalidate_exact(&key).await.unwrap();
+    cache.invalidate_exact(&unseen).await.unwrap();
+    proxy.stop();
+}
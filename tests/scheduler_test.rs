@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
-use synapse_core::services::scheduler::{Job, JobScheduler};
+use synapse_core::services::scheduler::{Job, JobOutcome, JobScheduler};
 use tokio::time::{sleep, Duration};
 
 // Test job that counts executions
@@ -180,6 +180,49 @@ async fn test_scheduler_job_status() {
     scheduler.stop().await.unwrap();
 }
 
+#[tokio::test]
+async fn test_scheduler_job_status_reports_last_runs() {
+    let scheduler = JobScheduler::new();
+    let ok_counter = Arc::new(AtomicU32::new(0));
+    let failing_counter = Arc::new(AtomicU32::new(0));
+
+    let ok_job = CounterJob::new("ok_job", "*/1 * * * * *", ok_counter.clone());
+    let failing_job = FailingJob::new("failing_job", "*/1 * * * * *", failing_counter.clone());
+    scheduler.register_job(Box::new(ok_job)).await.unwrap();
+    scheduler.register_job(Box::new(failing_job)).await.unwrap();
+
+    let status = scheduler.get_job_status().await;
+    assert!(status["ok_job"].last_run.is_none());
+    assert!(status["ok_job"].last_success.is_none());
+
+    scheduler.start().await.unwrap();
+    let started = chrono::Utc::now();
+    // Both jobs run once within about a second; allow for the history write.
+    for _ in 0..30 {
+        let status = scheduler.get_job_status().await;
+        if status.values().all(|s| s.last_run.is_some()) {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    let checked_at = chrono::Utc::now();
+    let status = scheduler.get_job_status().await;
+    let ok = &status["ok_job"];
+    assert!(ok_counter.load(Ordering::SeqCst) >= 1);
+    assert!(ok.last_run.unwrap() >= started);
+    assert!(ok.last_success.unwrap() >= ok.last_run.unwrap());
+    assert!(ok.last_error.is_none());
+    assert!(ok.next_run.unwrap() > checked_at);
+
+    let failing = &status["failing_job"];
+    assert!(failing.last_run.is_some());
+    assert!(failing.last_success.is_none());
+    assert_eq!(failing.last_error.as_deref(), Some("Intentional failure"));
+
+    scheduler.stop().await.unwrap();
+}
+
 #[tokio::test]
 async fn test_scheduler_shutdown() {
     let scheduler = JobScheduler::new();
@@ -276,3 +319,81 @@ async fn test_scheduler_multiple_jobs() {
         "Medium job should execute at least as many times as slow"
     );
 }
+
+// Test job that takes a while and reports when it starts and finishes
+struct SlowJob {
+    duration: Duration,
+    started: Arc<tokio::sync::Notify>,
+    finished: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl Job for SlowJob {
+    fn name(&self) -> &str {
+        "slow_job"
+    }
+
+    fn schedule(&self) -> &str {
+        "*/1 * * * * *"
+    }
+
+    async fn execute(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.started.notify_one();
+        sleep(self.duration).await;
+        self.finished.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+async fn start_slow_job(
+    scheduler: &JobScheduler,
+    duration: Duration,
+) -> (Arc<tokio::sync::Notify>, Arc<AtomicU32>) {
+    let started = Arc::new(tokio::sync::Notify::new());
+    let finished = Arc::new(AtomicU32::new(0));
+    let job = SlowJob {
+        duration,
+        started: started.clone(),
+        finished: finished.clone(),
+    };
+    scheduler.register_job(Box::new(job)).await.unwrap();
+    scheduler.start().await.unwrap();
+    (started, finished)
+}
+
+#[tokio::test]
+async fn test_scheduler_stop_waits_for_in_flight_job() {
+    let scheduler = JobScheduler::new().with_shutdown_timeout(Duration::from_secs(10));
+    let (started, finished) = start_slow_job(&scheduler, Duration::from_millis(1500)).await;
+
+    started.notified().await;
+    scheduler.stop().await.unwrap();
+
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+    let history = scheduler.job_history().await;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].name, "slow_job");
+    assert_eq!(history[0].outcome, JobOutcome::Succeeded);
+
+    // No new execution starts after stop.
+    sleep(Duration::from_secs(2)).await;
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_scheduler_stop_records_interrupted_job_after_timeout() {
+    let scheduler = JobScheduler::new().with_shutdown_timeout(Duration::from_millis(200));
+    let (started, finished) = start_slow_job(&scheduler, Duration::from_secs(30)).await;
+
+    started.notified().await;
+    let stopping = std::time::Instant::now();
+    scheduler.stop().await.unwrap();
+    assert!(stopping.elapsed() < Duration::from_secs(5));
+
+    assert_eq!(finished.load(Ordering::SeqCst), 0);
+    let history = scheduler.job_history().await;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].name, "slow_job");
+    assert_eq!(history[0].outcome, JobOutcome::Interrupted);
+    assert!(history[0].finished_at >= history[0].started_at);
+}
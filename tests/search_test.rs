@@ -28,6 +28,9 @@ async fn setup_test_app() -> (String, PgPool, impl std::any::Any) {
     .await
     .unwrap();
     migrator.run(&pool).await.unwrap();
+    synapse_core::db::cron::ensure_future_partitions(&pool, 1)
+        .await
+        .unwrap();
 
     let pool_manager = PoolManager::new(&database_url, None, 5).await.unwrap();
     let (tx_broadcast, _) = tokio::sync::broadcast::channel(100);
@@ -58,6 +61,21 @@ async fn setup_test_app() -> (String, PgPool, impl std::any::Any) {
         metrics_handle: synapse_core::metrics::init_metrics().unwrap(),
         secrets_store: None,
         ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        cors_allowed_origins: Vec::new(),
+        horizon_page_limit: 200,
+        health_latency_threshold_ms: 1000,
+        idempotency_failure_mode: Default::default(),
+        webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(64)),
+        app_env: Default::default(),
+        backup_checker: None,
+        request_timeout: std::time::Duration::from_secs(30),
+        search_default_window_days: 90,
+        export_jobs_dir: "/tmp/synapse_exports".into(),
+        config_info: Default::default(),
+        job_scheduler: Default::default(),
+        jwt_secret: Default::default(),
+        retry_policy: Default::default(),
+        batch_replay_limits: Default::default(),
     };
     let app = create_app(app_state);
 
@@ -463,12 +481,79 @@ async fn test_search_with_amount_range() {
     // Should return transactions with amounts 100, 250, and 500
     assert_eq!(response["total"], 3);
 
+    let min = BigDecimal::from_str("100").unwrap();
+    let max = BigDecimal::from_str("500").unwrap();
     for tx in response["results"].as_array().unwrap() {
-        let amount: f64 = tx["amount"].as_str().unwrap().parse().unwrap();
-        assert!((100.0..=500.0).contains(&amount));
+        let amount = BigDecimal::from_str(tx["amount"].as_str().unwrap()).unwrap();
+        assert!(amount >= min && amount <= max);
     }
 }
 
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_amount_range_is_exact_and_inclusive() {
+    let (base_url, pool, _container) = setup_test_app().await;
+
+    for amount in ["100.004", "100.005", "100.006"] {
+        sqlx::query(
+            "INSERT INTO transactions (id, stellar_account, amount, asset_code, status) \
+             VALUES ($1, $2, $3, 'USD', 'pending')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(format!("GPRECISION{}", amount))
+        .bind(BigDecimal::from_str(amount).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let client = reqwest::Client::new();
+    let search = |min: &'static str, max: &'static str| {
+        client
+            .get(format!("{}/transactions/search", base_url))
+            .query(&[("min_amount", min), ("max_amount", max)])
+            .send()
+    };
+
+    // A degenerate range matches exactly the one amount, at both bounds.
+    let response: serde_json::Value = search("100.005", "100.005")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["total"], 1);
+    assert_eq!(
+        BigDecimal::from_str(response["results"][0]["amount"].as_str().unwrap()).unwrap(),
+        BigDecimal::from_str("100.005").unwrap()
+    );
+
+    let response: serde_json::Value = search("100.0045", "100.006")
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["total"], 2);
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_rejects_inverted_amount_range() {
+    let (base_url, _pool, _container) = setup_test_app().await;
+
+    let res = reqwest::Client::new()
+        .get(format!("{}/transactions/search", base_url))
+        .query(&[("min_amount", "100.006"), ("max_amount", "100.005")])
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let error = res.text().await.unwrap();
+    assert!(error.contains("'min_amount' must not exceed 'max_amount'"));
+}
+
 #[tokio::test]
 #[ignore = "Requires Docker for testcontainers"]
 async fn test_search_limit_boundaries() {
@@ -557,3 +642,230 @@ async fn test_search_ordering() {
         );
     }
 }
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_sorted_by_amount_paginates() {
+    let (base_url, pool, _container) = setup_test_app().await;
+    seed_test_data(&pool).await;
+
+    let client = reqwest::Client::new();
+    let mut amounts = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = vec![("limit", "2".to_string()), ("sort", "amount".to_string())];
+        if let Some(c) = &cursor {
+            query.push(("cursor", c.clone()));
+        }
+        let res = client
+            .get(format!("{}/transactions/search", base_url))
+            .query(&query)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let page: serde_json::Value = res.json().await.unwrap();
+        for tx in page["results"].as_array().unwrap() {
+            amounts.push(BigDecimal::from_str(tx["amount"].as_str().unwrap()).unwrap());
+        }
+        match page["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+
+    let expected: Vec<BigDecimal> = ["1000", "500", "250", "100", "75"]
+        .iter()
+        .map(|a| BigDecimal::from_str(a).unwrap())
+        .collect();
+    assert_eq!(amounts, expected);
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_rejects_cursor_from_another_sort() {
+    let (base_url, pool, _container) = setup_test_app().await;
+    seed_test_data(&pool).await;
+
+    let client = reqwest::Client::new();
+    let page: serde_json::Value = client
+        .get(format!("{}/transactions/search", base_url))
+        .query(&[("limit", "2")])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let created_at_cursor = page["next_cursor"].as_str().unwrap();
+
+    let res = client
+        .get(format!("{}/transactions/search", base_url))
+        .query(&[
+            ("limit", "2"),
+            ("sort", "amount"),
+            ("cursor", created_at_cursor),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body = res.text().await.unwrap();
+    assert!(body.contains("Invalid cursor"), "{body}");
+    assert!(body.contains("created_at"), "{body}");
+
+    // The same cursor is still accepted under the sort it came from.
+    let res = client
+        .get(format!("{}/transactions/search", base_url))
+        .query(&[
+            ("limit", "2"),
+            ("sort", "created_at"),
+            ("cursor", created_at_cursor),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+async fn insert_with_metadata(pool: &PgPool, metadata: Option<serde_json::Value>) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO transactions (id, stellar_account, amount, asset_code, status, metadata) \
+         VALUES ($1, 'GMETA00000000', 10, 'USD', 'pending', $2)",
+    )
+    .bind(id)
+    .bind(metadata)
+    .execute(pool)
+    .await
+    .unwrap();
+    id
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_by_metadata_containment() {
+    let (base_url, pool, _container) = setup_test_app().await;
+    let order_x = insert_with_metadata(
+        &pool,
+        Some(serde_json::json!({ "order_id": "X", "channel": "web" })),
+    )
+    .await;
+    let order_x_nested = insert_with_metadata(
+        &pool,
+        Some(serde_json::json!({ "order_id": "X", "customer": { "tier": "gold" } })),
+    )
+    .await;
+    insert_with_metadata(&pool, Some(serde_json::json!({ "order_id": "Y" }))).await;
+    insert_with_metadata(&pool, None).await;
+
+    let client = reqwest::Client::new();
+    let search = |filter: &'static str| {
+        let client = client.clone();
+        let base_url = base_url.clone();
+        async move {
+            let res = client
+                .get(format!("{}/transactions/search", base_url))
+                .query(&[("metadata_contains", filter)])
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            let body: serde_json::Value = res.json().await.unwrap();
+            let mut ids: Vec<Uuid> = body["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tx| tx["id"].as_str().unwrap().parse().unwrap())
+                .collect();
+            ids.sort();
+            assert_eq!(body["total"], ids.len());
+            ids
+        }
+    };
+
+    let mut expected = vec![order_x, order_x_nested];
+    expected.sort();
+    assert_eq!(search(r#"{"order_id":"X"}"#).await, expected);
+    assert_eq!(
+        search(r#"{"order_id":"X","channel":"web"}"#).await,
+        [order_x]
+    );
+    assert_eq!(
+        search(r#"{"customer":{"tier":"gold"}}"#).await,
+        [order_x_nested]
+    );
+    assert!(search(r#"{"order_id":"Z"}"#).await.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_rejects_non_object_metadata_filter() {
+    let (base_url, _pool, _container) = setup_test_app().await;
+
+    for filter in [r#"["order_id"]"#, r#""X""#, "42", "{not json"] {
+        let res = reqwest::Client::new()
+            .get(format!("{}/transactions/search", base_url))
+            .query(&[("metadata_contains", filter)])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{filter}");
+        let error = res.text().await.unwrap();
+        assert!(error.contains("must be a JSON object"), "{error}");
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_search_without_dates_defaults_to_recent_window() {
+    use chrono::Datelike;
+
+    let (base_url, pool, _container) = setup_test_app().await;
+    seed_test_data(&pool).await;
+
+    // One transaction well outside the 90-day default window.
+    let old = Utc::now() - Duration::days(200);
+    synapse_core::db::cron::create_month_partition(&pool, old.year(), old.month())
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO transactions (stellar_account, amount, asset_code, status, created_at) \
+         VALUES ('GQRS6666666666', 42, 'USD', 'completed', $1)",
+    )
+    .bind(old)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let client = reqwest::Client::new();
+    let search = |query: &'static [(&'static str, &'static str)]| {
+        let request = client
+            .get(format!("{}/transactions/search", base_url))
+            .query(query);
+        async move {
+            let res = request.send().await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            res.json::<serde_json::Value>().await.unwrap()
+        }
+    };
+
+    let response = search(&[("asset_code", "USD")]).await;
+    assert_eq!(response["total"], 3);
+    let window = &response["effective_window"];
+    assert_eq!(window["default_applied"], true);
+    let from = chrono::DateTime::parse_from_rfc3339(window["from"].as_str().unwrap()).unwrap();
+    let age = Utc::now() - from.with_timezone(&Utc);
+    assert!((age - Duration::days(90)).num_minutes().abs() < 5, "{age}");
+    assert!(window["to"].is_null());
+
+    let response = search(&[("asset_code", "USD"), ("all_time", "true")]).await;
+    assert_eq!(response["total"], 4);
+    assert_eq!(response["effective_window"]["default_applied"], false);
+    assert!(response["effective_window"]["from"].is_null());
+
+    // An explicit range replaces the default window.
+    let response = search(&[("asset_code", "USD"), ("from", "2000-01-01")]).await;
+    assert_eq!(response["total"], 4);
+    assert_eq!(response["effective_window"]["default_applied"], false);
+}
@@ -196,6 +196,19 @@ async fn test_voided_settlement_releases_transactions() {
         released.0.is_none(),
         "voided settlement should release transactions"
     );
+
+    let (actor, new_val): (String, serde_json::Value) = sqlx::query_as(
+        "SELECT actor, new_val FROM audit_logs \
+         WHERE entity_type = 'settlement' AND entity_id = $1 AND action = 'settlement_reversed'",
+    )
+    .bind(settlement.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(actor, "admin");
+    assert_eq!(new_val["transaction_ids"], serde_json::json!([tx_id]));
+    assert_eq!(new_val["tx_count"], 1);
+    assert_eq!(new_val["reason"], "duplicate settlement");
 }
 
 #[tokio::test]
@@ -262,3 +275,62 @@ async fn test_settlement_status_change_is_audit_logged() {
         "at least one audit log entry should exist for the status change"
     );
 }
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_concurrent_updates_at_same_version_one_conflicts() {
+    let (pool, _c) = setup_db().await;
+    let svc = SettlementService::new(pool.clone());
+
+    let tx = TransactionFixture::new()
+        .with_status("completed")
+        .with_asset_code("USD")
+        .with_amount("75")
+        .build();
+    insert_tx(&pool, &tx).await;
+
+    let settlement = svc.settle_asset("USD").await.unwrap().remove(0);
+    assert_eq!(settlement.version, 1);
+    let in_review = svc
+        .update_status(settlement.id, "pending_review", None, None, "admin")
+        .await
+        .unwrap();
+    assert_eq!(in_review.version, 2);
+
+    // Completion-side and reversal-side writers both read version 2.
+    let (dispute, void) = tokio::join!(
+        svc.update_status_at_version(
+            settlement.id,
+            Some(2),
+            "disputed",
+            Some("amount query"),
+            None,
+            "reviewer-a",
+        ),
+        svc.update_status_at_version(
+            settlement.id,
+            Some(2),
+            "voided",
+            Some("duplicate"),
+            None,
+            "reviewer-b",
+        ),
+    );
+
+    let results = [dispute, void];
+    let winners: Vec<_> = results.iter().filter_map(|r| r.as_ref().ok()).collect();
+    assert_eq!(winners.len(), 1, "exactly one update must win: {results:?}");
+    assert_eq!(winners[0].version, 3);
+    assert!(
+        results
+            .iter()
+            .any(|r| matches!(r, Err(synapse_core::error::AppError::Conflict(_)))),
+        "the losing update must be a conflict: {results:?}"
+    );
+
+    let stored = synapse_core::db::queries::get_settlement(&pool, settlement.id)
+        .await
+        .unwrap();
+    assert_eq!(stored.version, 3);
+    assert_eq!(stored.status, winners[0].status);
+}
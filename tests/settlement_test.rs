@@ -67,8 +67,8 @@ async fn insert_tx(pool: &PgPool, tx: &Transaction) -> Transaction {
         INSERT INTO transactions (
             id, stellar_account, amount, asset_code, status,
             created_at, updated_at, anchor_transaction_id, callback_type, callback_status,
-            settlement_id, memo, memo_type, metadata
-        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14)
+            settlement_id, memo, memo_type, metadata, asset_issuer
+        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15)
         RETURNING *
         "#,
     )
@@ -86,6 +86,7 @@ async fn insert_tx(pool: &PgPool, tx: &Transaction) -> Transaction {
     .bind(&tx.memo)
     .bind(&tx.memo_type)
     .bind(&tx.metadata)
+    .bind(&tx.asset_issuer)
     .fetch_one(pool)
     .await
     .unwrap()
@@ -177,6 +178,98 @@ async fn test_settle_multiple_transactions() {
         .unwrap();
     assert_eq!(u1.settlement_id, Some(settlement.id));
     assert_eq!(u2.settlement_id, Some(settlement.id));
+
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM audit_logs \
+         WHERE action = 'settlement_id_update' AND new_val->>'settlement_id' = $1",
+    )
+    .bind(settlement.id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 2);
+
+    let new_val: serde_json::Value = sqlx::query_scalar(
+        "SELECT new_val FROM audit_logs \
+         WHERE entity_type = 'settlement' AND entity_id = $1 AND action = 'settlement_created'",
+    )
+    .bind(settlement.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let mut member_ids: Vec<String> = new_val["transaction_ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_str().unwrap().to_string())
+        .collect();
+    member_ids.sort();
+    let mut expected = vec![inserted1.id.to_string(), inserted2.id.to_string()];
+    expected.sort();
+    assert_eq!(member_ids, expected);
+    assert_eq!(new_val["tx_count"], 2);
+    assert_eq!(new_val["asset_code"], "EUR");
+    assert_eq!(
+        new_val["total_amount"]
+            .as_str()
+            .unwrap()
+            .parse::<BigDecimal>()
+            .unwrap(),
+        BigDecimal::from(100)
+    );
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_settle_separates_issuers_of_same_asset_code() {
+    let (pool, _container) = setup_test_db().await;
+    let service = SettlementService::new(pool.clone());
+
+    let issuer_a = "GAISSUERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+    let issuer_b = "GBISSUERBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB";
+
+    let mut a_ids = Vec::new();
+    for amount in ["10", "15"] {
+        let tx = TransactionFixture::new()
+            .with_amount(amount)
+            .with_asset_code("USDC")
+            .with_asset_issuer(issuer_a)
+            .with_status("completed")
+            .build();
+        a_ids.push(insert_tx(&pool, &tx).await.id);
+    }
+    let b = TransactionFixture::new()
+        .with_amount("40")
+        .with_asset_code("USDC")
+        .with_asset_issuer(issuer_b)
+        .with_status("completed")
+        .build();
+    let b_id = insert_tx(&pool, &b).await.id;
+
+    let settlements = service.settle_asset("USDC").await.unwrap();
+    assert_eq!(settlements.len(), 2);
+
+    let a = settlements
+        .iter()
+        .find(|s| s.asset_issuer.as_deref() == Some(issuer_a))
+        .expect("settlement for issuer A");
+    let b = settlements
+        .iter()
+        .find(|s| s.asset_issuer.as_deref() == Some(issuer_b))
+        .expect("settlement for issuer B");
+    assert_eq!(a.tx_count, 2);
+    assert_eq!(a.total_amount, BigDecimal::from(25));
+    assert_eq!(b.tx_count, 1);
+    assert_eq!(b.total_amount, BigDecimal::from(40));
+
+    for (id, expected) in a_ids.iter().map(|id| (*id, a.id)).chain([(b_id, b.id)]) {
+        let tx: Transaction = sqlx::query_as("SELECT * FROM transactions WHERE id=$1")
+            .bind(id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tx.settlement_id, Some(expected));
+    }
 }
 
 #[tokio::test]
@@ -244,3 +337,89 @@ async fn test_asset_grouping() {
     assert!(assets.contains(&"USD"));
     assert!(assets.contains(&"EUR"));
 }
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_mixed_case_asset_codes_settle_together() {
+    let (pool, _container) = setup_test_db().await;
+    let service = SettlementService::new(pool.clone());
+
+    for (i, asset_code) in ["usd", " Usd ", "USD"].into_iter().enumerate() {
+        let tx = Transaction::new(
+            format!("G{}", char::from(b'A' + i as u8).to_string().repeat(55)),
+            BigDecimal::from(10),
+            asset_code.to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let (inserted, _) = synapse_core::db::queries::insert_transaction(&pool, &tx)
+            .await
+            .unwrap();
+        assert_eq!(inserted.asset_code, "USD");
+    }
+    sqlx::query("UPDATE transactions SET status = 'completed'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let results = service.run_settlements().await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].asset_code, "USD");
+    assert_eq!(results[0].tx_count, 3);
+    assert_eq!(results[0].total_amount, BigDecimal::from(30));
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_concurrent_settle_asset_settles_each_transaction_once() {
+    let (pool, _container) = setup_test_db().await;
+    // Small batches so each run writes several settlements.
+    let first = SettlementService::with_config(pool.clone(), 3, 1);
+    let second = SettlementService::with_config(pool.clone(), 3, 1);
+
+    let mut ids = Vec::new();
+    for _ in 0..20 {
+        let tx = TransactionFixture::new()
+            .with_stellar_account("GA111111111111111111111111111111111111111111111111")
+            .with_amount("10")
+            .with_asset_code("USD")
+            .with_status("completed")
+            .build();
+        ids.push(insert_tx(&pool, &tx).await.id);
+    }
+
+    let (a, b) = tokio::join!(first.settle_asset("USD"), second.settle_asset("USD"));
+    let settlements: Vec<_> = a.unwrap().into_iter().chain(b.unwrap()).collect();
+
+    let settled: i32 = settlements.iter().map(|s| s.tx_count).sum();
+    assert_eq!(settled, 20, "every transaction is counted exactly once");
+    let total: BigDecimal = settlements.iter().map(|s| s.total_amount.clone()).sum();
+    assert_eq!(total, BigDecimal::from(200));
+
+    // Each transaction points at one of the settlements, and each
+    // settlement's count matches the rows pointing at it.
+    for settlement in &settlements {
+        let rows: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM transactions WHERE settlement_id = $1")
+                .bind(settlement.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(rows, settlement.tx_count as i64);
+    }
+    let unsettled: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM transactions WHERE id = ANY($1) AND settlement_id IS NULL",
+    )
+    .bind(&ids)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(unsettled, 0);
+
+    // A retry finds nothing left to settle.
+    assert!(first.settle_asset("USD").await.unwrap().is_empty());
+}
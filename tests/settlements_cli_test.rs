@@ -28,7 +28,7 @@ async fn test_settlements_list_table_format() {
         .and(path("/settlements"))
         .and(header("X-API-Key", "dev-key"))
         .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "settlements": [
+            "items": [
                 {
                     "id": "550e8400-e29b-41d4-a716-446655440000",
                     "asset_code": "USD",
@@ -45,7 +45,9 @@ async fn test_settlements_list_table_format() {
                     "reviewed_at": null
                 }
             ],
-            "next_cursor": null,
+            "total": 1,
+            "limit": 10,
+            "offset": 0,
             "has_more": false
         })))
         .mount(&server)
@@ -67,7 +69,7 @@ async fn test_settlements_list_json_format() {
     let server = MockServer::start().await;
 
     let settlement_json = json!({
-        "settlements": [
+        "items": [
             {
                 "id": "550e8400-e29b-41d4-a716-446655440000",
                 "asset_code": "USD",
@@ -84,7 +86,9 @@ async fn test_settlements_list_json_format() {
                 "reviewed_at": null
             }
         ],
-        "next_cursor": null,
+        "total": 1,
+        "limit": 10,
+        "offset": 0,
         "has_more": false
     });
 
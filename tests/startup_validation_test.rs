@@ -1,7 +1,8 @@
 use sqlx::{migrate::Migrator, ConnectOptions, PgPool};
 use std::path::Path;
 use synapse_core::config::{AllowedIps, Config, LogFormat};
-use synapse_core::startup::validate_environment;
+use synapse_core::startup::{ensure_horizon_network, validate_environment};
+use synapse_core::stellar::{network, HorizonClient, StellarNetwork};
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::postgres::Postgres;
 
@@ -9,11 +10,15 @@ use testcontainers_modules::postgres::Postgres;
 fn create_test_config(database_url: String, redis_url: String, horizon_url: String) -> Config {
     Config {
         app_env: synapse_core::config::AppEnv::Development,
+        profile_overrides: Vec::new(),
         server_port: 3000,
         database_url,
         database_replica_url: None,
         stellar_horizon_url: horizon_url,
+        stellar_horizon_fallback_urls: vec![],
+        stellar_network: synapse_core::stellar::StellarNetwork::Testnet,
         anchor_webhook_secret: "test-secret".to_string(),
+        jwt_secret: None,
         redis_url,
         default_rate_limit: 100,
         whitelist_rate_limit: 1000,
@@ -22,10 +27,29 @@ fn create_test_config(database_url: String, redis_url: String, horizon_url: Stri
         allowed_ips: AllowedIps::Any,
         backup_dir: "./backups".to_string(),
         backup_encryption_key: None,
+        backup_max_age_hours: 25,
+        migrations_dir: "./migrations".to_string(),
         db_timeouts: synapse_core::config::DbTimeoutConfig::default(),
         otlp_endpoint: None,
         cors_allowed_origins: vec![],
+        log_sensitive_keys: vec![],
         max_pending_queue: 10000,
+        webhook_max_concurrency: 64,
+        profiling_retention_hours: 168,
+        profiling_min_start_interval_secs: 60,
+        profiling_max_sample_rate_hz: 1000,
+        webhook_replay_max_batch: 500,
+        webhook_replay_concurrency: 8,
+        max_body_bytes: 1024 * 1024,
+        request_timeout_secs: 30,
+        search_default_window_days: 90,
+        export_jobs_dir: "/tmp/synapse_exports".to_string(),
+        broadcast_capacity: 100,
+        horizon_page_limit: 200,
+        health_latency_threshold_ms: 1000,
+        idempotency_failure_mode: Default::default(),
+        readiness_required_dependencies: Vec::new(),
+        feature_flag_defaults: Default::default(),
         db_min_connections: 5,
         db_max_connections: 50,
         db_statement_timeout_ms: 30000,
@@ -34,12 +58,22 @@ fn create_test_config(database_url: String, redis_url: String, horizon_url: Stri
         processor_workers: 4,
         processor_batch_size: 50,
         processor_poll_interval_ms: 1000,
+        processor_concurrency: 8,
         processor_min_batch: 10,
         processor_max_batch: 500,
         processor_scaling_factor: 0.5,
+        processor_max_attempts: 5,
+        processor_retry_backoff_ms: 1000,
+        processor_retry_max_backoff_ms: 300_000,
         slow_query_threshold_ms: 500,
         settlement_max_batch_size: 10000,
         settlement_min_tx_count: 1,
+        settlement_schedule: "0 0 * * * *".to_string(),
+        partition_strategy: synapse_core::db::partition::PartitionStrategy::Monthly,
+        partition_months_ahead: 3,
+        partition_days_ahead: 7,
+        partition_retention_months: 12,
+        partition_drop_after_archive: false,
     }
 }
 
@@ -312,3 +346,56 @@ async fn test_validation_multiple_failures() {
 
     report.print();
 }
+
+/// Horizon whose root info reports `passphrase`.
+async fn horizon_serving(passphrase: &str) -> (mockito::ServerGuard, HorizonClient) {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            serde_json::json!({
+                "horizon_version": "2.30.0",
+                "network_passphrase": passphrase,
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let client = HorizonClient::new(server.url());
+    (server, client)
+}
+
+#[tokio::test]
+async fn test_horizon_network_matching_config_starts() {
+    let (_server, client) = horizon_serving(network::PUBNET_PASSPHRASE).await;
+    ensure_horizon_network(&client, &StellarNetwork::Pubnet)
+        .await
+        .unwrap();
+
+    let custom = "Standalone Network ; February 2017";
+    let (_server, client) = horizon_serving(custom).await;
+    ensure_horizon_network(&client, &StellarNetwork::Custom(custom.to_string()))
+        .await
+        .unwrap();
+
+    // An unreachable Horizon can't be checked and doesn't block startup.
+    let unreachable = HorizonClient::new("http://127.0.0.1:9".to_string());
+    ensure_horizon_network(&unreachable, &StellarNetwork::Testnet)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_horizon_network_mismatch_is_a_startup_error() {
+    // A pubnet Horizon with a testnet config.
+    let (_server, client) = horizon_serving(network::PUBNET_PASSPHRASE).await;
+
+    let error = ensure_horizon_network(&client, &StellarNetwork::Testnet)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains(network::PUBNET_PASSPHRASE), "{error}");
+    assert!(error.contains(network::TESTNET_PASSPHRASE), "{error}");
+}
@@ -0,0 +1,26 @@
+//! `GET /version` reports what is deployed without requiring credentials.
+
+mod common;
+
+use common::TestApp;
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_version_endpoint_reports_build_and_profile() {
+    let app = TestApp::new().await;
+
+    let response = reqwest::get(format!("{}/version", app.base_url))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(body["profile"], "development");
+    for field in ["git_sha", "build_timestamp"] {
+        assert!(
+            body[field].as_str().is_some_and(|v| !v.is_empty()),
+            "{field} missing from {body}"
+        );
+    }
+}
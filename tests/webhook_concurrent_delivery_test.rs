@@ -417,3 +417,112 @@ async fn test_circuit_breaker_isolates_failing_endpoint() {
         assert_eq!(state["state"], "open", "Circuit breaker should be open");
     }
 }
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Test 4: Deliveries carry a signature made with the owning tenant's secret
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+#[ignore = "Requires Docker"]
+async fn test_delivery_is_signed_with_tenant_webhook_secret() {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use std::sync::{Arc, Mutex};
+    use synapse_core::services::webhook_dispatcher::{
+        SYNAPSE_SIGNATURE_HEADER, SYNAPSE_TIMESTAMP_HEADER,
+    };
+
+    let (pool, _pg) = setup_postgres().await;
+    let (redis_url, _redis) = setup_redis().await;
+
+    let tenant_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO tenants (tenant_id, name, api_key, webhook_secret) \
+         VALUES ($1, 'Signed', $2, 'tenant-webhook-secret')",
+    )
+    .bind(tenant_id)
+    .bind(format!("key-{tenant_id}"))
+    .execute(&pool)
+    .await
+    .unwrap();
+    let tx = synapse_core::db::models::Transaction::new(
+        "G".to_string() + &"A".repeat(55),
+        "10".parse().unwrap(),
+        "USDC".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .with_tenant_id(Some(tenant_id));
+    let tx = synapse_core::db::queries::insert_transaction(&pool, &tx)
+        .await
+        .unwrap()
+        .0;
+
+    // Capture the signature headers and body exactly as they arrive.
+    struct Received {
+        signature: String,
+        timestamp: String,
+        body: Vec<u8>,
+    }
+    let captured: Arc<Mutex<Option<Received>>> = Arc::default();
+    let sink = captured.clone();
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/signed")
+        .match_request(move |req| {
+            let header = |name: &str| {
+                req.header(name)
+                    .first()
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            *sink.lock().unwrap() = Some(Received {
+                signature: header(SYNAPSE_SIGNATURE_HEADER),
+                timestamp: header(SYNAPSE_TIMESTAMP_HEADER),
+                body: req.body().unwrap().clone(),
+            });
+            true
+        })
+        .with_status(200)
+        .expect(1)
+        .create();
+
+    let (endpoint_id, _) = insert_endpoint_and_delivery(
+        &pool,
+        &format!("{}/signed", server.url()),
+        100,
+        "test.signed",
+    )
+    .await;
+    sqlx::query("UPDATE webhook_deliveries SET transaction_id = $1 WHERE endpoint_id = $2")
+        .bind(tx.id)
+        .bind(endpoint_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let dispatcher = WebhookDispatcher::new(pool.clone(), &redis_url).expect("dispatcher");
+    dispatcher.process_pending().await.unwrap();
+    mock.assert_async().await;
+
+    let Received {
+        signature,
+        timestamp,
+        body,
+    } = captured.lock().unwrap().take().unwrap();
+    let sent_at: i64 = timestamp.parse().expect("timestamp is Unix seconds");
+    let age = chrono::Utc::now().timestamp() - sent_at;
+    assert!((0..=30).contains(&age), "timestamp {sent_at} is {age}s old");
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"tenant-webhook-secret").unwrap();
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(&body);
+    let expected = format!("v1={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(signature, expected);
+}
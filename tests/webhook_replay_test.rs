@@ -1,6 +1,62 @@
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use serde_json::Value;
 use sqlx::PgPool;
+use std::collections::HashSet;
+use synapse_core::db::cron::create_month_partition;
 use synapse_core::db::models::Transaction;
 use synapse_core::db::queries;
+use synapse_core::handlers::admin::webhook_replay::{
+    list_failed_webhooks, ListFailedWebhooksQuery,
+};
+
+/// `sqlx::test` databases only get the partitions the migrations create.
+async fn create_current_partition(pool: &PgPool) {
+    use chrono::Datelike;
+    let now = chrono::Utc::now();
+    create_month_partition(pool, now.year(), now.month())
+        .await
+        .unwrap();
+}
+
+async fn insert_failed(pool: &PgPool, asset_code: &str) -> sqlx::Result<Transaction> {
+    let tx = Transaction::new(
+        "GABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890ABCDEFGHIJKLMNOP".to_string(),
+        "10.00".parse().unwrap(),
+        asset_code.to_string(),
+        Some(format!("anchor-{}", uuid::Uuid::new_v4())),
+        Some("deposit".to_string()),
+        Some("failed".to_string()),
+        None,
+        None,
+        None,
+    );
+    let (inserted, _) = queries::insert_transaction(pool, &tx).await?;
+    sqlx::query("UPDATE transactions SET status = 'failed' WHERE id = $1")
+        .bind(inserted.id)
+        .execute(pool)
+        .await?;
+    Ok(inserted)
+}
+
+async fn list(pool: &PgPool, params: ListFailedWebhooksQuery) -> Value {
+    let response = list_failed_webhooks(State(pool.clone()), Query(params))
+        .await
+        .unwrap()
+        .into_response();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+fn query(asset_code: Option<&str>, limit: i64, offset: i64) -> ListFailedWebhooksQuery {
+    ListFailedWebhooksQuery {
+        limit,
+        offset,
+        asset_code: asset_code.map(str::to_string),
+        from_date: None,
+        to_date: None,
+    }
+}
 
 #[ignore = "Requires DATABASE_URL"]
 #[sqlx::test]
@@ -120,3 +176,72 @@ async fn test_replay_updates_status(pool: PgPool) -> sqlx::Result<()> {
 
     Ok(())
 }
+
+#[ignore = "Requires DATABASE_URL"]
+#[sqlx::test]
+async fn test_list_failed_webhooks_total_follows_filters(pool: PgPool) -> sqlx::Result<()> {
+    create_current_partition(&pool).await;
+    for _ in 0..3 {
+        insert_failed(&pool, "USDC").await?;
+    }
+    let cutoff = chrono::Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    for _ in 0..2 {
+        insert_failed(&pool, "USDC").await?;
+        insert_failed(&pool, "EURC").await?;
+    }
+
+    let all = list(&pool, query(None, 50, 0)).await;
+    assert_eq!(all["total"], 7);
+
+    let usdc = list(&pool, query(Some("USDC"), 50, 0)).await;
+    assert_eq!(usdc["total"], 5);
+    assert_eq!(usdc["items"].as_array().unwrap().len(), 5);
+    assert_eq!(usdc["has_more"], false);
+    assert!(usdc["next_offset"].is_null());
+
+    let mut recent_usdc = query(Some("USDC"), 50, 0);
+    recent_usdc.from_date = Some(cutoff);
+    let recent = list(&pool, recent_usdc).await;
+    assert_eq!(recent["total"], 2);
+    assert_eq!(recent["items"].as_array().unwrap().len(), 2);
+
+    Ok(())
+}
+
+#[ignore = "Requires DATABASE_URL"]
+#[sqlx::test]
+async fn test_list_failed_webhooks_pages_through_filtered_set(pool: PgPool) -> sqlx::Result<()> {
+    create_current_partition(&pool).await;
+    for _ in 0..5 {
+        insert_failed(&pool, "USDC").await?;
+    }
+    insert_failed(&pool, "EURC").await?;
+
+    let mut seen = HashSet::new();
+    let mut offset = 0;
+    let mut pages = Vec::new();
+    loop {
+        let page = list(&pool, query(Some("USDC"), 2, offset)).await;
+        assert_eq!(page["total"], 5);
+        assert_eq!(page["limit"], 2);
+        assert_eq!(page["offset"], offset);
+        for webhook in page["items"].as_array().unwrap() {
+            assert_eq!(webhook["asset_code"], "USDC");
+            assert!(seen.insert(webhook["transaction_id"].as_str().unwrap().to_string()));
+        }
+        pages.push(page["items"].as_array().unwrap().len());
+        if page["has_more"] == false {
+            assert!(page["next_offset"].is_null());
+            break;
+        }
+        let next = page["next_offset"].as_i64().unwrap();
+        assert_eq!(next, offset + 2);
+        offset = next;
+    }
+
+    assert_eq!(pages, vec![2, 2, 1]);
+    assert_eq!(seen.len(), 5);
+
+    Ok(())
+}
@@ -1,11 +1,16 @@
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
 use chrono::Utc;
 use futures::{SinkExt, StreamExt};
 use sqlx::{migrate::Migrator, PgPool};
 use std::path::Path;
 use synapse_core::db::pool_manager::PoolManager;
+use synapse_core::handlers::webhook::{callback, CallbackPayload, CallbackQuery};
 use synapse_core::handlers::ws::TransactionStatusUpdate;
+use synapse_core::middleware::auth::{encode_jwt, JwtClaims};
 use synapse_core::services::feature_flags::FeatureFlagService;
-use synapse_core::{create_app, AppState};
+use synapse_core::tenant::{OptionalTenant, TenantConfig, TenantContext};
+use synapse_core::{create_app, ApiState, AppState};
 use testcontainers::runners::AsyncRunner;
 use testcontainers_modules::postgres::Postgres;
 use tokio::net::TcpListener;
@@ -13,12 +18,43 @@ use tokio::sync::broadcast;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use uuid::Uuid;
 
+const JWT_SECRET: &str = "websocket-test-secret-of-32-bytes-or-more";
+
+/// Signed token for `tenant_id`, valid for an hour.
+fn ws_token(tenant_id: Uuid) -> String {
+    jwt_for(tenant_id, Utc::now().timestamp() + 3600)
+}
+
+fn jwt_for(tenant_id: Uuid, exp: i64) -> String {
+    encode_jwt(
+        &JwtClaims {
+            sub: "ws-test".to_string(),
+            tenant_id,
+            exp,
+            scopes: Vec::new(),
+        },
+        JWT_SECRET.as_bytes(),
+    )
+}
+
 async fn setup_test_app() -> (
     String,
     PgPool,
     broadcast::Sender<TransactionStatusUpdate>,
     impl std::any::Any,
 ) {
+    let (base_url, app_state, container) = setup_test_state().await;
+    (
+        base_url,
+        app_state.db.clone(),
+        app_state.tx_broadcast.clone(),
+        container,
+    )
+}
+
+/// Like [`setup_test_app`], returning the served app's state so handlers can
+/// be driven against it directly.
+async fn setup_test_state() -> (String, AppState, impl std::any::Any) {
     let container = Postgres::default().start().await.unwrap();
     let host_port = container.get_host_port_ipv4(5432).await.unwrap();
     let database_url = format!(
@@ -64,9 +100,24 @@ async fn setup_test_app() -> (
         secrets_store: None,
         metrics_handle: synapse_core::metrics::init_metrics().unwrap(),
         ws_connection_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+        cors_allowed_origins: Vec::new(),
+        horizon_page_limit: 200,
+        health_latency_threshold_ms: 1000,
+        idempotency_failure_mode: Default::default(),
+        webhook_permits: std::sync::Arc::new(tokio::sync::Semaphore::new(64)),
+        app_env: Default::default(),
+        backup_checker: None,
+        request_timeout: std::time::Duration::from_secs(30),
+        search_default_window_days: 90,
+        export_jobs_dir: "/tmp/synapse_exports".into(),
+        config_info: Default::default(),
+        job_scheduler: Default::default(),
+        jwt_secret: synapse_core::middleware::auth::JwtSecret::new(Some(JWT_SECRET)),
+        retry_policy: Default::default(),
+        batch_replay_limits: Default::default(),
     };
 
-    let app = create_app(app_state);
+    let app = create_app(app_state.clone());
 
     let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
@@ -81,7 +132,7 @@ async fn setup_test_app() -> (
     });
 
     let base_url = format!("ws://{}", addr);
-    (base_url, pool, tx_broadcast, container)
+    (base_url, app_state, container)
 }
 
 #[tokio::test]
@@ -90,7 +141,7 @@ async fn test_ws_connection_with_valid_token() {
     let (base_url, _pool, _tx, _container) = setup_test_app().await;
 
     // Connect with valid token
-    let ws_url = format!("{}/ws?token=valid-token-123", base_url);
+    let ws_url = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
     let result = connect_async(&ws_url).await;
 
     assert!(result.is_ok(), "Should connect with valid token");
@@ -137,7 +188,7 @@ async fn test_ws_receives_transaction_updates() {
     let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
 
     // Connect WebSocket client
-    let ws_url = format!("{}/ws?token=test-token", base_url);
+    let ws_url = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
     let (mut ws_stream, _) = connect_async(&ws_url).await.unwrap();
 
     // Give the connection time to establish
@@ -151,6 +202,7 @@ async fn test_ws_receives_transaction_updates() {
         status: "completed".to_string(),
         timestamp: Utc::now(),
         message: Some("Transaction processed successfully".to_string()),
+        stellar_account: None,
     };
 
     tx_broadcast.send(update.clone()).unwrap();
@@ -192,9 +244,9 @@ async fn test_ws_multiple_clients_receive_broadcast() {
     let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
 
     // Connect multiple WebSocket clients
-    let ws_url1 = format!("{}/ws?token=client1", base_url);
-    let ws_url2 = format!("{}/ws?token=client2", base_url);
-    let ws_url3 = format!("{}/ws?token=client3", base_url);
+    let ws_url1 = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
+    let ws_url2 = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
+    let ws_url3 = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
 
     let (mut ws_stream1, _) = connect_async(&ws_url1).await.unwrap();
     let (mut ws_stream2, _) = connect_async(&ws_url2).await.unwrap();
@@ -211,6 +263,7 @@ async fn test_ws_multiple_clients_receive_broadcast() {
         status: "pending".to_string(),
         timestamp: Utc::now(),
         message: None,
+        stellar_account: None,
     };
 
     let sent_count = tx_broadcast.send(update.clone()).unwrap();
@@ -256,7 +309,7 @@ async fn test_ws_connection_cleanup_on_disconnect() {
     let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
 
     // Connect a client
-    let ws_url = format!("{}/ws?token=test-client", base_url);
+    let ws_url = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
     let (ws_stream, _) = connect_async(&ws_url).await.unwrap();
 
     // Give connection time to establish
@@ -269,6 +322,7 @@ async fn test_ws_connection_cleanup_on_disconnect() {
         tenant_id: Uuid::default(),
         timestamp: Utc::now(),
         message: None,
+        stellar_account: None,
     };
 
     let sent_count = tx_broadcast.send(update.clone()).unwrap();
@@ -287,6 +341,7 @@ async fn test_ws_connection_cleanup_on_disconnect() {
         tenant_id: Uuid::default(),
         timestamp: Utc::now(),
         message: None,
+        stellar_account: None,
     };
 
     let sent_count2 = tx_broadcast.send(update2).unwrap_or(0);
@@ -302,7 +357,7 @@ async fn test_ws_heartbeat_keeps_connection_alive() {
     let (base_url, _pool, _tx, _container) = setup_test_app().await;
 
     // Connect WebSocket client
-    let ws_url = format!("{}/ws?token=heartbeat-test", base_url);
+    let ws_url = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
     let (mut ws_stream, _) = connect_async(&ws_url).await.unwrap();
 
     // Wait for heartbeat ping (server sends every 30 seconds, but we'll wait a bit)
@@ -329,7 +384,7 @@ async fn test_ws_client_can_send_messages() {
     let (base_url, _pool, _tx, _container) = setup_test_app().await;
 
     // Connect WebSocket client
-    let ws_url = format!("{}/ws?token=send-test", base_url);
+    let ws_url = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
     let (mut ws_stream, _) = connect_async(&ws_url).await.unwrap();
 
     // Send a text message to server
@@ -355,7 +410,7 @@ async fn test_ws_handles_rapid_broadcasts() {
     let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
 
     // Connect WebSocket client
-    let ws_url = format!("{}/ws?token=rapid-test", base_url);
+    let ws_url = format!("{}/ws?token={}", base_url, ws_token(Uuid::nil()));
     let (mut ws_stream, _) = connect_async(&ws_url).await.unwrap();
 
     // Give connection time to establish
@@ -373,6 +428,7 @@ async fn test_ws_handles_rapid_broadcasts() {
             status: format!("status_{}", i),
             timestamp: Utc::now(),
             message: Some(format!("Update {}", i)),
+            stellar_account: None,
         };
 
         tx_broadcast.send(update).unwrap();
@@ -417,3 +473,430 @@ async fn test_ws_connection_with_empty_token() {
         }
     }
 }
+
+type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Next text frame, skipping pings; `None` if nothing arrives within `wait`.
+async fn next_text_within(stream: &mut WsStream, wait: tokio::time::Duration) -> Option<String> {
+    loop {
+        match tokio::time::timeout(wait, stream.next()).await {
+            Ok(Some(Ok(Message::Text(t)))) => return Some(t),
+            Ok(Some(Ok(_))) => continue,
+            _ => return None,
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_subscription_filters() {
+    let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
+    let wait = tokio::time::Duration::from_secs(2);
+
+    let (mut by_status, _) =
+        connect_async(format!("{}/ws?token={}", base_url, ws_token(Uuid::nil())))
+            .await
+            .unwrap();
+    let (mut by_account, _) =
+        connect_async(format!("{}/ws?token={}", base_url, ws_token(Uuid::nil())))
+            .await
+            .unwrap();
+
+    by_status
+        .send(Message::Text(
+            r#"{"action":"subscribe","filters":{"status":"completed"}}"#.to_string(),
+        ))
+        .await
+        .unwrap();
+    by_account
+        .send(Message::Text(
+            r#"{"action":"subscribe","filters":{"stellar_account":"GACCOUNTA"}}"#.to_string(),
+        ))
+        .await
+        .unwrap();
+
+    // Both clients acknowledge their filters before any broadcast is sent.
+    for stream in [&mut by_status, &mut by_account] {
+        let ack = next_text_within(stream, wait).await.expect("subscribe ack");
+        assert!(ack.contains("\"type\":\"subscribed\""));
+    }
+
+    let make = |status: &str, account: &str| TransactionStatusUpdate {
+        transaction_id: Uuid::new_v4(),
+        tenant_id: Uuid::default(),
+        status: status.to_string(),
+        timestamp: Utc::now(),
+        message: None,
+        stellar_account: Some(account.to_string()),
+    };
+    let pending_a = make("pending", "GACCOUNTA");
+    let completed_b = make("completed", "GACCOUNTB");
+    tx_broadcast.send(pending_a.clone()).unwrap();
+    tx_broadcast.send(completed_b.clone()).unwrap();
+
+    let received: TransactionStatusUpdate =
+        serde_json::from_str(&next_text_within(&mut by_status, wait).await.unwrap()).unwrap();
+    assert_eq!(received.transaction_id, completed_b.transaction_id);
+    assert!(next_text_within(&mut by_status, wait).await.is_none());
+
+    let received: TransactionStatusUpdate =
+        serde_json::from_str(&next_text_within(&mut by_account, wait).await.unwrap()).unwrap();
+    assert_eq!(received.transaction_id, pending_a.transaction_id);
+    assert!(next_text_within(&mut by_account, wait).await.is_none());
+
+    by_status.close(None).await.unwrap();
+    by_account.close(None).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_transaction_snapshot_then_updates() {
+    let (base_url, pool, tx_broadcast, _container) = setup_test_app().await;
+    let wait = tokio::time::Duration::from_secs(2);
+
+    synapse_core::db::cron::ensure_future_partitions(&pool, 1)
+        .await
+        .unwrap();
+    let tx_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO transactions (id, stellar_account, amount, asset_code, status) \
+         VALUES ($1, $2, 100, 'USD', 'pending')",
+    )
+    .bind(tx_id)
+    .bind("GSNAPSHOT")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (mut ws, _) = connect_async(format!(
+        "{}/ws/transactions/{}?token={}",
+        base_url,
+        tx_id,
+        ws_token(Uuid::nil())
+    ))
+    .await
+    .unwrap();
+
+    // Broadcasts for this and another transaction are already queued; the
+    // snapshot must still be the first frame the client sees.
+    let update = |id: Uuid, status: &str| TransactionStatusUpdate {
+        transaction_id: id,
+        tenant_id: Uuid::default(),
+        status: status.to_string(),
+        timestamp: Utc::now(),
+        message: None,
+        stellar_account: Some("GSNAPSHOT".to_string()),
+    };
+    tx_broadcast
+        .send(update(Uuid::new_v4(), "completed"))
+        .unwrap();
+    tx_broadcast.send(update(tx_id, "completed")).unwrap();
+
+    let snapshot: serde_json::Value =
+        serde_json::from_str(&next_text_within(&mut ws, wait).await.expect("snapshot")).unwrap();
+    assert_eq!(snapshot["type"], "snapshot");
+    assert_eq!(snapshot["transaction_id"], tx_id.to_string());
+    assert_eq!(snapshot["status"], "pending");
+
+    let received: TransactionStatusUpdate =
+        serde_json::from_str(&next_text_within(&mut ws, wait).await.unwrap()).unwrap();
+    assert_eq!(received.transaction_id, tx_id);
+    assert_eq!(received.status, "completed");
+    assert!(next_text_within(&mut ws, wait).await.is_none());
+
+    ws.close(None).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_transaction_unknown_id_closes_with_reason() {
+    let (base_url, _pool, _tx, _container) = setup_test_app().await;
+
+    let (mut ws, _) = connect_async(format!(
+        "{}/ws/transactions/{}?token={}",
+        base_url,
+        Uuid::new_v4(),
+        ws_token(Uuid::nil())
+    ))
+    .await
+    .unwrap();
+
+    match tokio::time::timeout(tokio::time::Duration::from_secs(2), ws.next()).await {
+        Ok(Some(Ok(Message::Close(Some(frame))))) => {
+            assert_eq!(u16::from(frame.code), 4404);
+            assert_eq!(frame.reason, "transaction not found");
+        }
+        other => panic!("expected close frame, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_slow_consumer_receives_lag_notice() {
+    let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
+    let wait = tokio::time::Duration::from_secs(2);
+
+    let (mut ws, _) = connect_async(format!("{}/ws?token={}", base_url, ws_token(Uuid::nil())))
+        .await
+        .unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    // A burst well beyond the channel capacity (100) overruns the client's
+    // receiver before the server can drain it.
+    let update = || TransactionStatusUpdate {
+        transaction_id: Uuid::new_v4(),
+        tenant_id: Uuid::default(),
+        status: "pending".to_string(),
+        timestamp: Utc::now(),
+        message: None,
+        stellar_account: None,
+    };
+    for _ in 0..500 {
+        tx_broadcast.send(update()).unwrap();
+    }
+
+    // Updates delivered before the overrun are skipped until the notice.
+    let notice = loop {
+        let frame: serde_json::Value =
+            serde_json::from_str(&next_text_within(&mut ws, wait).await.expect("lag notice"))
+                .unwrap();
+        if frame["type"] == "lagged" {
+            break frame;
+        }
+    };
+    assert!(notice["missed"].as_u64().unwrap() > 0);
+
+    // The connection survives and resumes with the retained updates.
+    let resumed: TransactionStatusUpdate =
+        serde_json::from_str(&next_text_within(&mut ws, wait).await.unwrap()).unwrap();
+    assert_eq!(resumed.status, "pending");
+
+    ws.close(None).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_jwt_scopes_updates_to_tenant() {
+    let (base_url, _pool, tx_broadcast, _container) = setup_test_app().await;
+    let wait = tokio::time::Duration::from_secs(2);
+    let tenant_a = Uuid::new_v4();
+    let tenant_b = Uuid::new_v4();
+
+    let (mut ws, _) = connect_async(format!("{}/ws?token={}", base_url, ws_token(tenant_a)))
+        .await
+        .unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let update = |tenant_id: Uuid| TransactionStatusUpdate {
+        transaction_id: Uuid::new_v4(),
+        tenant_id,
+        status: "completed".to_string(),
+        timestamp: Utc::now(),
+        message: None,
+        stellar_account: None,
+    };
+    tx_broadcast.send(update(tenant_b)).unwrap();
+    let own = update(tenant_a);
+    tx_broadcast.send(own.clone()).unwrap();
+
+    let received: TransactionStatusUpdate =
+        serde_json::from_str(&next_text_within(&mut ws, wait).await.unwrap()).unwrap();
+    assert_eq!(received.transaction_id, own.transaction_id);
+    assert!(next_text_within(&mut ws, wait).await.is_none());
+
+    ws.close(None).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_expired_jwt_rejected_before_upgrade() {
+    let (base_url, _pool, _tx, _container) = setup_test_app().await;
+
+    let expired = jwt_for(Uuid::new_v4(), Utc::now().timestamp() - 60);
+    let result = connect_async(format!("{}/ws?token={}", base_url, expired)).await;
+    match result {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), 401);
+        }
+        other => panic!("expected 401 at upgrade, got {:?}", other.map(|_| ())),
+    }
+
+    let forged = encode_jwt(
+        &JwtClaims {
+            sub: "ws-test".to_string(),
+            tenant_id: Uuid::new_v4(),
+            exp: Utc::now().timestamp() + 3600,
+            scopes: Vec::new(),
+        },
+        b"wrong-secret",
+    );
+    let result = connect_async(format!("{}/ws?token={}", base_url, forged)).await;
+    assert!(matches!(
+        result,
+        Err(tokio_tungstenite::tungstenite::Error::Http(ref r)) if r.status() == 401
+    ));
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_jwt_socket_receives_callback_for_its_tenant() {
+    let (base_url, app_state, _container) = setup_test_state().await;
+    synapse_core::db::cron::ensure_future_partitions(&app_state.db, 1)
+        .await
+        .unwrap();
+    let wait = tokio::time::Duration::from_secs(2);
+
+    let tenant_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO tenants (tenant_id, name, api_key) VALUES ($1, 'acme', $2)")
+        .bind(tenant_id)
+        .bind(format!("key-{tenant_id}"))
+        .execute(&app_state.db)
+        .await
+        .unwrap();
+    let config: TenantConfig = sqlx::query_as("SELECT * FROM tenants WHERE tenant_id = $1")
+        .bind(tenant_id)
+        .fetch_one(&app_state.db)
+        .await
+        .unwrap();
+
+    let (mut own, _) = connect_async(format!("{}/ws?token={}", base_url, ws_token(tenant_id)))
+        .await
+        .unwrap();
+    let (mut other, _) = connect_async(format!(
+        "{}/ws?token={}",
+        base_url,
+        ws_token(Uuid::new_v4())
+    ))
+    .await
+    .unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let api_state = ApiState {
+        graphql_schema: synapse_core::graphql::schema::build_schema(app_state.clone()),
+        app_state,
+    };
+    let payload = CallbackPayload {
+        stellar_account: "G".to_string() + &"A".repeat(55),
+        amount: "42.00".to_string(),
+        asset_code: "USDC".to_string(),
+        asset_issuer: None,
+        callback_type: None,
+        callback_status: None,
+        anchor_transaction_id: None,
+        memo: None,
+        memo_type: None,
+        metadata: None,
+    };
+    let response = callback(
+        State(api_state),
+        OptionalTenant(Some(TenantContext::new(tenant_id, config))),
+        Query(CallbackQuery::default()),
+        axum::Json(payload).into(),
+    )
+    .await
+    .unwrap()
+    .into_response();
+    assert_eq!(response.status(), axum::http::StatusCode::ACCEPTED);
+
+    let received: TransactionStatusUpdate =
+        serde_json::from_str(&next_text_within(&mut own, wait).await.unwrap()).unwrap();
+    assert_eq!(received.tenant_id, tenant_id);
+    assert_eq!(received.status, "pending");
+    assert!(next_text_within(&mut other, wait).await.is_none());
+
+    own.close(None).await.unwrap();
+    other.close(None).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker for testcontainers"]
+async fn test_ws_resync_is_scoped_to_tenant_and_filters() {
+    let (base_url, app_state, _container) = setup_test_state().await;
+    let pool = app_state.db.clone();
+    synapse_core::db::cron::ensure_future_partitions(&pool, 1)
+        .await
+        .unwrap();
+    let wait = tokio::time::Duration::from_secs(2);
+
+    let (tenant_a, tenant_b) = (Uuid::new_v4(), Uuid::new_v4());
+    for tenant_id in [tenant_a, tenant_b] {
+        sqlx::query("INSERT INTO tenants (tenant_id, name, api_key) VALUES ($1, 'resync', $2)")
+            .bind(tenant_id)
+            .bind(format!("key-{tenant_id}"))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let insert = |tenant_id: Option<Uuid>, status: &'static str| {
+        let pool = pool.clone();
+        async move {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO transactions (id, stellar_account, amount, asset_code, status, tenant_id) \
+                 VALUES ($1, 'GRESYNC', 100, 'USD', $2, $3)",
+            )
+            .bind(id)
+            .bind(status)
+            .bind(tenant_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+            id
+        }
+    };
+    let a_pending = insert(Some(tenant_a), "pending").await;
+    let a_completed = insert(Some(tenant_a), "completed").await;
+    let b_completed = insert(Some(tenant_b), "completed").await;
+    let untenanted = insert(None, "completed").await;
+
+    async fn resync(ws: &mut WsStream, wait: tokio::time::Duration) -> Vec<Uuid> {
+        ws.send(Message::Text(r#"{"type":"resync"}"#.to_string()))
+            .await
+            .unwrap();
+        let frame: serde_json::Value =
+            serde_json::from_str(&next_text_within(ws, wait).await.expect("resync")).unwrap();
+        assert_eq!(frame["type"], "resync");
+        let mut ids: Vec<Uuid> = frame["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["id"].as_str().unwrap().parse().unwrap())
+            .collect();
+        ids.sort();
+        ids
+    }
+    let sorted = |mut ids: Vec<Uuid>| {
+        ids.sort();
+        ids
+    };
+
+    let (mut ws_a, _) = connect_async(format!("{}/ws?token={}", base_url, ws_token(tenant_a)))
+        .await
+        .unwrap();
+    assert_eq!(
+        resync(&mut ws_a, wait).await,
+        sorted(vec![a_pending, a_completed])
+    );
+
+    ws_a.send(Message::Text(
+        r#"{"action":"subscribe","filters":{"status":"completed"}}"#.to_string(),
+    ))
+    .await
+    .unwrap();
+    let ack = next_text_within(&mut ws_a, wait)
+        .await
+        .expect("subscribe ack");
+    assert!(ack.contains("\"type\":\"subscribed\""));
+    assert_eq!(resync(&mut ws_a, wait).await, vec![a_completed]);
+    assert!(!resync(&mut ws_a, wait).await.contains(&b_completed));
+
+    // A tenantless connection only sees transactions without a tenant.
+    let (mut ws_nil, _) = connect_async(format!("{}/ws?token={}", base_url, ws_token(Uuid::nil())))
+        .await
+        .unwrap();
+    assert_eq!(resync(&mut ws_nil, wait).await, vec![untenanted]);
+
+    ws_a.close(None).await.unwrap();
+    ws_nil.close(None).await.unwrap();
+}